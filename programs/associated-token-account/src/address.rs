@@ -0,0 +1,259 @@
+use {
+    pinocchio_token::TokenInterface,
+    solana_account_view::AccountView,
+    solana_address::Address,
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// Returns the associated token account address for the given wallet address,
+/// token mint and token program, together with its bump seed.
+///
+/// The address is derived from the seeds `[wallet, token_program, mint]` with
+/// the Associated Token Account program id.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+#[inline(always)]
+pub fn get_associated_token_address(
+    wallet: &Address,
+    mint: &Address,
+    token_program: &Address,
+) -> (Address, u8) {
+    Address::find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Verifies that `account` is the associated token account for the given
+/// wallet address, token mint and token program.
+///
+/// The token program is verified with [`TokenInterface::verify`], so
+/// `Program` determines which token programs are accepted.
+///
+/// The address is derived by searching for the bump seed, as in
+/// [`get_associated_token_address`]. When the canonical bump is known, e.g.,
+/// stored by the program, [`verify_associated_token_account_with_bump`] is
+/// cheaper.
+///
+/// # Errors
+///
+/// Returns [`ProgramError::IncorrectProgramId`] if `token_program` is not
+/// accepted by `Program`, or [`ProgramError::InvalidSeeds`] if the account
+/// address is not the associated token account address.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+#[inline]
+pub fn verify_associated_token_account<Program: TokenInterface>(
+    account: &AccountView,
+    wallet: &Address,
+    mint: &Address,
+    token_program: &Address,
+) -> ProgramResult {
+    Program::verify(token_program)?;
+
+    let (address, _) = get_associated_token_address(wallet, mint, token_program);
+
+    if account.address() != &address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(())
+}
+
+/// Verifies that `account` is the associated token account for the given
+/// wallet address, token mint and token program, derived with `bump`.
+///
+/// The token program is verified with [`TokenInterface::verify`], so
+/// `Program` determines which token programs are accepted.
+///
+/// The address is derived with the given `bump` instead of searching for it,
+/// so `bump` must be the canonical bump of the associated token account, e.g.,
+/// one stored by the program. Since the derivation does not check that the
+/// address is off-curve, it is only valid to compare it with the address of an
+/// existing account.
+///
+/// # Errors
+///
+/// Returns [`ProgramError::IncorrectProgramId`] if `token_program` is not
+/// accepted by `Program`, or [`ProgramError::InvalidSeeds`] if the account
+/// address is not the associated token account address.
+#[inline]
+pub fn verify_associated_token_account_with_bump<Program: TokenInterface>(
+    account: &AccountView,
+    wallet: &Address,
+    mint: &Address,
+    token_program: &Address,
+    bump: u8,
+) -> ProgramResult {
+    Program::verify(token_program)?;
+
+    let address = Address::derive_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        Some(bump),
+        &crate::ID,
+    );
+
+    if account.address() != &address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        pinocchio::{
+            test_utils::{ProgramInput, TestAccount},
+            SUCCESS,
+        },
+        pinocchio_token::TokenProgram,
+    };
+
+    const WALLET: Address = Address::from_str_const("7EcDhSYGxXyscszYEp35KHN8vvw3svAuLKTzXwCFLtV");
+
+    const MINT: Address = Address::from_str_const("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+    /// The associated token account of `WALLET` for `MINT` and the Token
+    /// program.
+    const ASSOCIATED_TOKEN_ACCOUNT: Address =
+        Address::from_str_const("42JrewdefrETKYFymk8F8FCtJSxLpQQjSyLb4GwueWHG");
+
+    const BUMP: u8 = 254;
+
+    #[cfg(feature = "curve25519")]
+    #[test]
+    fn test_associated_token_address() {
+        let (address, bump) = get_associated_token_address(&WALLET, &MINT, &pinocchio_token::ID);
+
+        assert_eq!(address, ASSOCIATED_TOKEN_ACCOUNT);
+        assert_eq!(bump, BUMP);
+
+        // The address depends on the token program.
+        let (address, _) = get_associated_token_address(&WALLET, &MINT, &pinocchio_token_2022::ID);
+        assert_ne!(address, ASSOCIATED_TOKEN_ACCOUNT);
+    }
+
+    #[cfg(feature = "curve25519")]
+    #[test]
+    fn test_verify_associated_token_account() {
+        let mut input = ProgramInput::new(
+            &Address::default(),
+            &[TestAccount::new(ASSOCIATED_TOKEN_ACCOUNT, pinocchio_token::ID, 1, &[]).into()],
+            &[],
+        );
+
+        let result = input.process(|_, accounts, _| {
+            let account = &accounts[0];
+            let token_program = pinocchio_token::ID;
+
+            assert_eq!(
+                verify_associated_token_account::<TokenProgram>(
+                    account,
+                    &WALLET,
+                    &MINT,
+                    &token_program
+                ),
+                Ok(())
+            );
+            // Different mint.
+            assert_eq!(
+                verify_associated_token_account::<TokenProgram>(
+                    account,
+                    &WALLET,
+                    &WALLET,
+                    &token_program
+                ),
+                Err(ProgramError::InvalidSeeds)
+            );
+            // Token program not accepted by `TokenProgram`.
+            assert_eq!(
+                verify_associated_token_account::<TokenProgram>(
+                    account,
+                    &WALLET,
+                    &MINT,
+                    &crate::ID
+                ),
+                Err(ProgramError::IncorrectProgramId)
+            );
+
+            Ok(())
+        });
+
+        assert_eq!(result, SUCCESS);
+    }
+
+    #[test]
+    fn test_verify_associated_token_account_with_bump() {
+        let token_2022_program = pinocchio_token_2022::ID;
+        let mut input = ProgramInput::new(
+            &Address::default(),
+            &[TestAccount::new(ASSOCIATED_TOKEN_ACCOUNT, pinocchio_token::ID, 1, &[]).into()],
+            &[],
+        );
+
+        let result = input.process(|_, accounts, _| {
+            let account = &accounts[0];
+            let token_program = pinocchio_token::ID;
+
+            assert_eq!(
+                verify_associated_token_account_with_bump::<TokenProgram>(
+                    account,
+                    &WALLET,
+                    &MINT,
+                    &token_program,
+                    BUMP
+                ),
+                Ok(())
+            );
+            // Non-canonical bump.
+            assert_eq!(
+                verify_associated_token_account_with_bump::<TokenProgram>(
+                    account,
+                    &WALLET,
+                    &MINT,
+                    &token_program,
+                    BUMP - 1
+                ),
+                Err(ProgramError::InvalidSeeds)
+            );
+            // Different mint.
+            assert_eq!(
+                verify_associated_token_account_with_bump::<TokenProgram>(
+                    account,
+                    &WALLET,
+                    &WALLET,
+                    &token_program,
+                    BUMP
+                ),
+                Err(ProgramError::InvalidSeeds)
+            );
+            // Token-2022 is accepted by `TokenProgram`, but it derives a
+            // different address.
+            assert_eq!(
+                verify_associated_token_account_with_bump::<TokenProgram>(
+                    account,
+                    &WALLET,
+                    &MINT,
+                    &token_2022_program,
+                    BUMP
+                ),
+                Err(ProgramError::InvalidSeeds)
+            );
+            // Token program not accepted by `TokenProgram`.
+            assert_eq!(
+                verify_associated_token_account_with_bump::<TokenProgram>(
+                    account,
+                    &WALLET,
+                    &MINT,
+                    &crate::ID,
+                    BUMP
+                ),
+                Err(ProgramError::IncorrectProgramId)
+            );
+
+            Ok(())
+        });
+
+        assert_eq!(result, SUCCESS);
+    }
+}
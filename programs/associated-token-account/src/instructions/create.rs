@@ -1,9 +1,7 @@
 use {
+    crate::cpi::{invoke_signed, Signer},
     solana_account_view::AccountView,
-    solana_instruction_view::{
-        cpi::{invoke_signed, Signer},
-        InstructionAccount, InstructionView,
-    },
+    solana_instruction_view::{InstructionAccount, InstructionView},
     solana_program_error::ProgramResult,
 };
 
@@ -1,10 +1,9 @@
 use {
+    crate::cpi::{invoke_signed, Signer},
     solana_account_view::AccountView,
-    solana_instruction_view::{
-        cpi::{invoke_signed, Signer},
-        InstructionAccount, InstructionView,
-    },
-    solana_program_error::ProgramResult,
+    solana_address::Address,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
 };
 
 /// Creates an associated token account for the given wallet address and
@@ -75,4 +74,266 @@ impl CreateIdempotent<'_> {
             signers,
         )
     }
+
+    /// Creates the associated token account only if it does not exist.
+    ///
+    /// See [`Self::invoke_signed_if_needed`].
+    #[inline(always)]
+    pub fn invoke_if_needed(&self) -> ProgramResult {
+        self.invoke_signed_if_needed(&[])
+    }
+
+    /// Creates the associated token account only if it does not exist.
+    ///
+    /// When the account is already owned by the token program, its state is
+    /// read to check the mint and owner, and the cross-program invocation is
+    /// skipped. Otherwise, the account is created with `CreateIdempotent`.
+    ///
+    /// # Important
+    ///
+    /// When the cross-program invocation is skipped, the account address is not
+    /// verified to be the associated token account address of the wallet and
+    /// mint. Use `verify_associated_token_account` when the address
+    /// must be checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::IncorrectProgramId`] if the token program is not
+    /// the Token or Token-2022 program, [`ProgramError::InvalidAccountData`] if
+    /// the account is not a token account or its mint does not match, or
+    /// [`ProgramError::IllegalOwner`] if the token account owner is not the
+    /// wallet.
+    #[inline]
+    pub fn invoke_signed_if_needed(&self, signers: &[Signer]) -> ProgramResult {
+        let token_program = self.token_program.address();
+
+        if !self.account.owned_by(token_program) {
+            return self.invoke_signed(signers);
+        }
+
+        if token_program == &pinocchio_token::ID {
+            let account = pinocchio_token::state::Account::from_account_view(self.account)?;
+            self.check_token_account(account.mint(), account.owner())
+        } else if token_program == &pinocchio_token_2022::ID {
+            let account = pinocchio_token_2022::state::Account::from_account_view(self.account)?;
+            self.check_token_account(account.mint(), account.owner())
+        } else {
+            Err(ProgramError::IncorrectProgramId)
+        }
+    }
+
+    /// Checks the mint and owner of an existing token account.
+    #[inline(always)]
+    fn check_token_account(&self, mint: &Address, owner: &Address) -> ProgramResult {
+        if mint != self.mint.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if owner != self.wallet.address() {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "cpi-mock"))]
+mod tests {
+    use {
+        super::*,
+        crate::cpi::{
+            mock::{self, InvokedAccount},
+            Seed,
+        },
+        pinocchio::{
+            test_utils::{ProgramInput, TestAccount},
+            SUCCESS,
+        },
+    };
+
+    const FUNDING_ACCOUNT: Address = Address::new_from_array([1; 32]);
+
+    const ACCOUNT: Address = Address::new_from_array([2; 32]);
+
+    const WALLET: Address = Address::new_from_array([3; 32]);
+
+    const MINT: Address = Address::new_from_array([4; 32]);
+
+    const OTHER_PROGRAM: Address = Address::new_from_array([5; 32]);
+
+    const SYSTEM_PROGRAM: Address = Address::new_from_array([0; 32]);
+
+    fn to_owned(address: &Address) -> Address {
+        Address::new_from_array(address.to_bytes())
+    }
+
+    fn invoked_account(address: Address, is_writable: bool, is_signer: bool) -> InvokedAccount {
+        InvokedAccount {
+            address,
+            is_writable,
+            is_signer,
+        }
+    }
+
+    /// Token account data with the given mint and owner.
+    fn token_account(mint: &Address, owner: &Address) -> [u8; 165] {
+        let mut data = [0; 165];
+        data[..32].copy_from_slice(mint.as_ref());
+        data[32..64].copy_from_slice(owner.as_ref());
+        // Initialized state.
+        data[108] = 1;
+        data
+    }
+
+    /// Runs `f` with a `CreateIdempotent` instruction, where the associated
+    /// token account is owned by `account_owner` and has `account_data`.
+    fn with_create_idempotent(
+        token_program: &Address,
+        account_owner: &Address,
+        account_data: &[u8],
+        f: impl FnOnce(&CreateIdempotent) -> ProgramResult,
+    ) -> ProgramResult {
+        mock::reset();
+
+        let mut input = ProgramInput::new(
+            &OTHER_PROGRAM,
+            &[
+                TestAccount::new(FUNDING_ACCOUNT, SYSTEM_PROGRAM, 1_000_000, &[])
+                    .signer()
+                    .writable()
+                    .into(),
+                TestAccount::new(ACCOUNT, to_owned(account_owner), 0, account_data)
+                    .writable()
+                    .into(),
+                TestAccount::new(WALLET, SYSTEM_PROGRAM, 0, &[]).into(),
+                TestAccount::new(MINT, to_owned(token_program), 0, &[0; 82]).into(),
+                TestAccount::new(SYSTEM_PROGRAM, Address::default(), 1, &[]).into(),
+                TestAccount::new(to_owned(token_program), Address::default(), 1, &[]).into(),
+            ],
+            &[],
+        );
+
+        let mut result = Ok(());
+        let status = input.process(|_, accounts, _| {
+            let [funding_account, account, wallet, mint, system_program, token_program] = accounts
+            else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            result = f(&CreateIdempotent {
+                funding_account,
+                account,
+                wallet,
+                mint,
+                system_program,
+                token_program,
+            });
+
+            Ok(())
+        });
+        assert_eq!(status, SUCCESS);
+
+        result
+    }
+
+    #[test]
+    fn test_invoke_if_needed_creates_account() {
+        let result =
+            with_create_idempotent(&pinocchio_token::ID, &SYSTEM_PROGRAM, &[], |instruction| {
+                instruction.invoke_if_needed()
+            });
+        assert_eq!(result, Ok(()));
+
+        let invocations = mock::take_invocations();
+        assert_eq!(invocations.len(), 1);
+        assert_eq!(invocations[0].program_id, crate::ID);
+        assert_eq!(invocations[0].data, [1]);
+        assert_eq!(
+            invocations[0].accounts,
+            [
+                invoked_account(FUNDING_ACCOUNT, true, true),
+                invoked_account(ACCOUNT, true, false),
+                invoked_account(WALLET, false, false),
+                invoked_account(MINT, false, false),
+                invoked_account(SYSTEM_PROGRAM, false, false),
+                invoked_account(pinocchio_token::ID, false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invoke_signed_if_needed_creates_account() {
+        let result =
+            with_create_idempotent(&pinocchio_token::ID, &SYSTEM_PROGRAM, &[], |instruction| {
+                let seeds = [Seed::from(b"funding"), Seed::from(&[255])];
+                instruction.invoke_signed_if_needed(&[Signer::from(&seeds)])
+            });
+        assert_eq!(result, Ok(()));
+
+        let invocations = mock::take_invocations();
+        assert_eq!(invocations.len(), 1);
+        assert_eq!(invocations[0].program_id, crate::ID);
+        assert_eq!(invocations[0].signers, [[b"funding".as_slice(), &[255]]]);
+    }
+
+    #[test]
+    fn test_invoke_if_needed_skips_existing_account() {
+        for token_program in [pinocchio_token::ID, pinocchio_token_2022::ID] {
+            let data = token_account(&MINT, &WALLET);
+            let result =
+                with_create_idempotent(&token_program, &token_program, &data, |instruction| {
+                    instruction.invoke_if_needed()
+                });
+            assert_eq!(result, Ok(()));
+            assert!(mock::take_invocations().is_empty());
+
+            let result =
+                with_create_idempotent(&token_program, &token_program, &data, |instruction| {
+                    instruction.invoke_signed_if_needed(&[])
+                });
+            assert_eq!(result, Ok(()));
+            assert!(mock::take_invocations().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_invoke_if_needed_mint_mismatch() {
+        let data = token_account(&OTHER_PROGRAM, &WALLET);
+        let result = with_create_idempotent(
+            &pinocchio_token::ID,
+            &pinocchio_token::ID,
+            &data,
+            |instruction| instruction.invoke_if_needed(),
+        );
+
+        assert_eq!(result, Err(ProgramError::InvalidAccountData));
+        assert!(mock::take_invocations().is_empty());
+    }
+
+    #[test]
+    fn test_invoke_if_needed_owner_mismatch() {
+        let data = token_account(&MINT, &OTHER_PROGRAM);
+        let result = with_create_idempotent(
+            &pinocchio_token::ID,
+            &pinocchio_token::ID,
+            &data,
+            |instruction| instruction.invoke_if_needed(),
+        );
+
+        assert_eq!(result, Err(ProgramError::IllegalOwner));
+        assert!(mock::take_invocations().is_empty());
+    }
+
+    #[test]
+    fn test_invoke_if_needed_different_program() {
+        // The account is owned by the given token program, which is not the
+        // Token or Token-2022 program.
+        let data = token_account(&MINT, &WALLET);
+        let result = with_create_idempotent(&OTHER_PROGRAM, &OTHER_PROGRAM, &data, |instruction| {
+            instruction.invoke_if_needed()
+        });
+
+        assert_eq!(result, Err(ProgramError::IncorrectProgramId));
+        assert!(mock::take_invocations().is_empty());
+    }
 }
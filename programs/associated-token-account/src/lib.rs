@@ -1,5 +1,14 @@
 #![no_std]
 
+mod address;
 pub mod instructions;
 
+pub use address::*;
+// Cross-program invocation helpers. With the `cpi-mock` feature, invocations
+// are routed through the host-side mock runtime of `pinocchio`.
+#[cfg(feature = "cpi-mock")]
+use pinocchio::cpi;
+#[cfg(not(feature = "cpi-mock"))]
+use solana_instruction_view::cpi;
+
 solana_address::declare_id!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
@@ -0,0 +1,590 @@
+use {
+    super::{
+        unpack_address, unpack_bytes, unpack_option_address, unpack_optional_nonzero_address,
+        unpack_u64, unpack_u8,
+    },
+    crate::{
+        instructions::ExtensionDiscriminator,
+        state::{AccountState, AeCiphertext, ElGamalCiphertext},
+    },
+    solana_address::Address,
+    solana_program_error::ProgramError,
+};
+
+/// A decoded transfer fee extension instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransferFeeInstruction<'a> {
+    /// Initializes the transfer fee configuration of a mint (`0`).
+    InitializeTransferFeeConfig {
+        transfer_fee_config_authority: Option<&'a Address>,
+        withdraw_withheld_authority: Option<&'a Address>,
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+    },
+
+    /// Transfers tokens, asserting the mint decimals and the fee (`1`).
+    TransferCheckedWithFee { amount: u64, decimals: u8, fee: u64 },
+
+    /// Withdraws the withheld tokens of a mint (`2`).
+    WithdrawWithheldTokensFromMint,
+
+    /// Withdraws the withheld tokens of `num_token_accounts` accounts (`3`).
+    WithdrawWithheldTokensFromAccounts { num_token_accounts: u8 },
+
+    /// Harvests the withheld tokens of accounts to their mint (`4`).
+    HarvestWithheldTokensToMint,
+
+    /// Sets the transfer fee of a mint (`5`).
+    SetTransferFee {
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+    },
+}
+
+impl<'a> TransferFeeInstruction<'a> {
+    /// Unpack a transfer fee extension instruction from its instruction data,
+    /// starting with the extension discriminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the instruction is
+    /// unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        Ok(
+            match sub_discriminator(data, ExtensionDiscriminator::TransferFee)? {
+                0 => {
+                    let transfer_fee_config_authority = unpack_option_address(data, 2)?;
+                    // The optional authority is 1 or 33 bytes long.
+                    let offset = 3 + transfer_fee_config_authority.map_or(0, |_| 32);
+                    let withdraw_withheld_authority = unpack_option_address(data, offset)?;
+                    let offset = offset + 1 + withdraw_withheld_authority.map_or(0, |_| 32);
+
+                    TransferFeeInstruction::InitializeTransferFeeConfig {
+                        transfer_fee_config_authority,
+                        withdraw_withheld_authority,
+                        transfer_fee_basis_points: u16::from_le_bytes(*unpack_bytes(data, offset)?),
+                        maximum_fee: unpack_u64(data, offset + 2)?,
+                    }
+                }
+                1 => TransferFeeInstruction::TransferCheckedWithFee {
+                    amount: unpack_u64(data, 2)?,
+                    decimals: unpack_u8(data, 10)?,
+                    fee: unpack_u64(data, 11)?,
+                },
+                2 => TransferFeeInstruction::WithdrawWithheldTokensFromMint,
+                3 => TransferFeeInstruction::WithdrawWithheldTokensFromAccounts {
+                    num_token_accounts: unpack_u8(data, 2)?,
+                },
+                4 => TransferFeeInstruction::HarvestWithheldTokensToMint,
+                5 => TransferFeeInstruction::SetTransferFee {
+                    transfer_fee_basis_points: u16::from_le_bytes(*unpack_bytes(data, 2)?),
+                    maximum_fee: unpack_u64(data, 4)?,
+                },
+                _ => return Err(ProgramError::InvalidInstructionData),
+            },
+        )
+    }
+}
+
+/// A decoded confidential transfer extension instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConfidentialTransferInstruction<'a> {
+    /// Configures an account for confidential transfers (`2`).
+    ConfigureAccount {
+        decryptable_zero_balance: &'a AeCiphertext,
+        maximum_pending_balance_credit_counter: u64,
+        proof_instruction_offset: i8,
+    },
+
+    /// Approves an account for confidential transfers (`3`).
+    ApproveAccount,
+
+    /// Deposits tokens into the pending balance of an account (`5`).
+    Deposit { amount: u64, decimals: u8 },
+
+    /// Withdraws tokens from the available balance of an account (`6`).
+    Withdraw {
+        amount: u64,
+        decimals: u8,
+        new_decryptable_available_balance: &'a AeCiphertext,
+        equality_proof_instruction_offset: i8,
+        range_proof_instruction_offset: i8,
+    },
+
+    /// Transfers tokens confidentially (`7`).
+    Transfer {
+        new_source_decryptable_available_balance: &'a AeCiphertext,
+        transfer_amount_auditor_ciphertext_lo: &'a ElGamalCiphertext,
+        transfer_amount_auditor_ciphertext_hi: &'a ElGamalCiphertext,
+        equality_proof_instruction_offset: i8,
+        ciphertext_validity_proof_instruction_offset: i8,
+        range_proof_instruction_offset: i8,
+    },
+
+    /// Applies the pending balance of an account to its available balance
+    /// (`8`).
+    ApplyPendingBalance {
+        expected_pending_balance_credit_counter: u64,
+        new_decryptable_available_balance: &'a AeCiphertext,
+    },
+
+    /// Enables confidential transfers to an account (`9`).
+    EnableConfidentialCredits,
+
+    /// Disables confidential transfers to an account (`10`).
+    DisableConfidentialCredits,
+
+    /// Enables non-confidential transfers to an account (`11`).
+    EnableNonConfidentialCredits,
+
+    /// Disables non-confidential transfers to an account (`12`).
+    DisableNonConfidentialCredits,
+}
+
+impl<'a> ConfidentialTransferInstruction<'a> {
+    /// Unpack a confidential transfer extension instruction from its
+    /// instruction data, starting with the extension discriminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the instruction is
+    /// unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        Ok(
+            match sub_discriminator(data, ExtensionDiscriminator::ConfidentialTransfer)? {
+                2 => ConfidentialTransferInstruction::ConfigureAccount {
+                    decryptable_zero_balance: unpack_bytes(data, 2)?,
+                    maximum_pending_balance_credit_counter: unpack_u64(data, 38)?,
+                    proof_instruction_offset: unpack_u8(data, 46)? as i8,
+                },
+                3 => ConfidentialTransferInstruction::ApproveAccount,
+                5 => ConfidentialTransferInstruction::Deposit {
+                    amount: unpack_u64(data, 2)?,
+                    decimals: unpack_u8(data, 10)?,
+                },
+                6 => ConfidentialTransferInstruction::Withdraw {
+                    amount: unpack_u64(data, 2)?,
+                    decimals: unpack_u8(data, 10)?,
+                    new_decryptable_available_balance: unpack_bytes(data, 11)?,
+                    equality_proof_instruction_offset: unpack_u8(data, 47)? as i8,
+                    range_proof_instruction_offset: unpack_u8(data, 48)? as i8,
+                },
+                7 => ConfidentialTransferInstruction::Transfer {
+                    new_source_decryptable_available_balance: unpack_bytes(data, 2)?,
+                    transfer_amount_auditor_ciphertext_lo: unpack_bytes(data, 38)?,
+                    transfer_amount_auditor_ciphertext_hi: unpack_bytes(data, 102)?,
+                    equality_proof_instruction_offset: unpack_u8(data, 166)? as i8,
+                    ciphertext_validity_proof_instruction_offset: unpack_u8(data, 167)? as i8,
+                    range_proof_instruction_offset: unpack_u8(data, 168)? as i8,
+                },
+                8 => ConfidentialTransferInstruction::ApplyPendingBalance {
+                    expected_pending_balance_credit_counter: unpack_u64(data, 2)?,
+                    new_decryptable_available_balance: unpack_bytes(data, 10)?,
+                },
+                9 => ConfidentialTransferInstruction::EnableConfidentialCredits,
+                10 => ConfidentialTransferInstruction::DisableConfidentialCredits,
+                11 => ConfidentialTransferInstruction::EnableNonConfidentialCredits,
+                12 => ConfidentialTransferInstruction::DisableNonConfidentialCredits,
+                _ => return Err(ProgramError::InvalidInstructionData),
+            },
+        )
+    }
+}
+
+/// A decoded default account state extension instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DefaultAccountStateInstruction {
+    /// Initializes the default state of new accounts of a mint (`0`).
+    Initialize { state: AccountState },
+
+    /// Updates the default state of new accounts of a mint (`1`).
+    Update { state: AccountState },
+}
+
+impl DefaultAccountStateInstruction {
+    /// Unpack a default account state extension instruction from its
+    /// instruction data, starting with the extension discriminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the instruction is
+    /// unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let discriminator = sub_discriminator(data, ExtensionDiscriminator::DefaultAccountState)?;
+        let state = AccountState::try_from(unpack_u8(data, 2)?)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        match discriminator {
+            0 => Ok(DefaultAccountStateInstruction::Initialize { state }),
+            1 => Ok(DefaultAccountStateInstruction::Update { state }),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// A decoded memo transfer extension instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MemoTransferInstruction {
+    /// Requires memos for transfers into an account (`0`).
+    Enable,
+
+    /// Stops requiring memos for transfers into an account (`1`).
+    Disable,
+}
+
+impl MemoTransferInstruction {
+    /// Unpack a memo transfer extension instruction from its instruction
+    /// data, starting with the extension discriminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the instruction is
+    /// unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        match sub_discriminator(data, ExtensionDiscriminator::MemoTransfer)? {
+            0 => Ok(MemoTransferInstruction::Enable),
+            1 => Ok(MemoTransferInstruction::Disable),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// A decoded interest-bearing mint extension instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InterestBearingMintInstruction<'a> {
+    /// Initializes the interest rate of a mint (`0`).
+    Initialize {
+        rate_authority: Option<&'a Address>,
+        rate: i16,
+    },
+
+    /// Updates the interest rate of a mint (`1`).
+    UpdateRate { rate: i16 },
+}
+
+impl<'a> InterestBearingMintInstruction<'a> {
+    /// Unpack an interest-bearing mint extension instruction from its
+    /// instruction data, starting with the extension discriminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the instruction is
+    /// unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        match sub_discriminator(data, ExtensionDiscriminator::InterestBearingMint)? {
+            0 => Ok(InterestBearingMintInstruction::Initialize {
+                rate_authority: unpack_optional_nonzero_address(data, 2)?,
+                rate: i16::from_le_bytes(*unpack_bytes(data, 34)?),
+            }),
+            1 => Ok(InterestBearingMintInstruction::UpdateRate {
+                rate: i16::from_le_bytes(*unpack_bytes(data, 2)?),
+            }),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// A decoded CPI guard extension instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CpiGuardInstruction {
+    /// Enables the CPI guard of an account (`0`).
+    Enable,
+
+    /// Disables the CPI guard of an account (`1`).
+    Disable,
+}
+
+impl CpiGuardInstruction {
+    /// Unpack a CPI guard extension instruction from its instruction data,
+    /// starting with the extension discriminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the instruction is
+    /// unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        match sub_discriminator(data, ExtensionDiscriminator::CpiGuard)? {
+            0 => Ok(CpiGuardInstruction::Enable),
+            1 => Ok(CpiGuardInstruction::Disable),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// A decoded transfer hook extension instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransferHookInstruction<'a> {
+    /// Initializes the transfer hook program of a mint (`0`).
+    Initialize {
+        authority: Option<&'a Address>,
+        program_id: Option<&'a Address>,
+    },
+
+    /// Updates the transfer hook program of a mint (`1`).
+    Update { program_id: Option<&'a Address> },
+}
+
+impl<'a> TransferHookInstruction<'a> {
+    /// Unpack a transfer hook extension instruction from its instruction
+    /// data, starting with the extension discriminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the instruction is
+    /// unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        match sub_discriminator(data, ExtensionDiscriminator::TransferHook)? {
+            0 => Ok(TransferHookInstruction::Initialize {
+                authority: unpack_optional_nonzero_address(data, 2)?,
+                program_id: unpack_optional_nonzero_address(data, 34)?,
+            }),
+            1 => Ok(TransferHookInstruction::Update {
+                program_id: unpack_optional_nonzero_address(data, 2)?,
+            }),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// A decoded metadata pointer extension instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MetadataPointerInstruction<'a> {
+    /// Initializes the metadata pointer of a mint (`0`).
+    Initialize {
+        authority: Option<&'a Address>,
+        metadata_address: Option<&'a Address>,
+    },
+
+    /// Updates the metadata pointer of a mint (`1`).
+    Update {
+        metadata_address: Option<&'a Address>,
+    },
+}
+
+impl<'a> MetadataPointerInstruction<'a> {
+    /// Unpack a metadata pointer extension instruction from its instruction
+    /// data, starting with the extension discriminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the instruction is
+    /// unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        match sub_discriminator(data, ExtensionDiscriminator::MetadataPointer)? {
+            0 => Ok(MetadataPointerInstruction::Initialize {
+                authority: unpack_optional_nonzero_address(data, 2)?,
+                metadata_address: unpack_optional_nonzero_address(data, 34)?,
+            }),
+            1 => Ok(MetadataPointerInstruction::Update {
+                metadata_address: unpack_optional_nonzero_address(data, 2)?,
+            }),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// A decoded group pointer extension instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GroupPointerInstruction<'a> {
+    /// Initializes the group pointer of a mint (`0`).
+    Initialize {
+        authority: Option<&'a Address>,
+        group_address: Option<&'a Address>,
+    },
+
+    /// Updates the group pointer of a mint (`1`).
+    Update { group_address: Option<&'a Address> },
+}
+
+impl<'a> GroupPointerInstruction<'a> {
+    /// Unpack a group pointer extension instruction from its instruction
+    /// data, starting with the extension discriminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the instruction is
+    /// unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        match sub_discriminator(data, ExtensionDiscriminator::GroupPointer)? {
+            0 => Ok(GroupPointerInstruction::Initialize {
+                authority: unpack_optional_nonzero_address(data, 2)?,
+                group_address: unpack_optional_nonzero_address(data, 34)?,
+            }),
+            1 => Ok(GroupPointerInstruction::Update {
+                group_address: unpack_optional_nonzero_address(data, 2)?,
+            }),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// A decoded group member pointer extension instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GroupMemberPointerInstruction<'a> {
+    /// Initializes the group member pointer of a mint (`0`).
+    Initialize {
+        authority: Option<&'a Address>,
+        member_address: Option<&'a Address>,
+    },
+
+    /// Updates the group member pointer of a mint (`1`).
+    Update { member_address: Option<&'a Address> },
+}
+
+impl<'a> GroupMemberPointerInstruction<'a> {
+    /// Unpack a group member pointer extension instruction from its
+    /// instruction data, starting with the extension discriminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the instruction is
+    /// unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        match sub_discriminator(data, ExtensionDiscriminator::GroupMemberPointer)? {
+            0 => Ok(GroupMemberPointerInstruction::Initialize {
+                authority: unpack_optional_nonzero_address(data, 2)?,
+                member_address: unpack_optional_nonzero_address(data, 34)?,
+            }),
+            1 => Ok(GroupMemberPointerInstruction::Update {
+                member_address: unpack_optional_nonzero_address(data, 2)?,
+            }),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// A decoded scaled UI amount extension instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScaledUiAmountInstruction<'a> {
+    /// Initializes the UI amount multiplier of a mint (`0`).
+    Initialize {
+        authority: Option<&'a Address>,
+        multiplier: f64,
+    },
+
+    /// Updates the UI amount multiplier of a mint from
+    /// `effective_timestamp` (`1`).
+    UpdateMultiplier {
+        multiplier: f64,
+        effective_timestamp: i64,
+    },
+}
+
+impl<'a> ScaledUiAmountInstruction<'a> {
+    /// Unpack a scaled UI amount extension instruction from its instruction
+    /// data, starting with the extension discriminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the instruction is
+    /// unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        match sub_discriminator(data, ExtensionDiscriminator::ScaledUiAmount)? {
+            0 => Ok(ScaledUiAmountInstruction::Initialize {
+                authority: unpack_optional_nonzero_address(data, 2)?,
+                multiplier: f64::from_le_bytes(*unpack_bytes(data, 34)?),
+            }),
+            1 => Ok(ScaledUiAmountInstruction::UpdateMultiplier {
+                multiplier: f64::from_le_bytes(*unpack_bytes(data, 2)?),
+                effective_timestamp: i64::from_le_bytes(*unpack_bytes(data, 10)?),
+            }),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// A decoded pausable extension instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PausableInstruction<'a> {
+    /// Initializes the pause authority of a mint (`0`).
+    Initialize { authority: &'a Address },
+
+    /// Pauses transfers, mints and burns of a mint (`1`).
+    Pause,
+
+    /// Resumes transfers, mints and burns of a mint (`2`).
+    Resume,
+}
+
+impl<'a> PausableInstruction<'a> {
+    /// Unpack a pausable extension instruction from its instruction data,
+    /// starting with the extension discriminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the instruction is
+    /// unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        match sub_discriminator(data, ExtensionDiscriminator::Pausable)? {
+            0 => Ok(PausableInstruction::Initialize {
+                authority: unpack_address(data, 2)?,
+            }),
+            1 => Ok(PausableInstruction::Pause),
+            2 => Ok(PausableInstruction::Resume),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// A decoded permissioned burn extension instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PermissionedBurnInstruction<'a> {
+    /// Initializes the permissioned burn authority of a mint (`0`).
+    Initialize { authority: &'a Address },
+
+    /// Burns tokens with the permissioned burn authority (`1`).
+    Burn { amount: u64 },
+
+    /// Burns tokens with the permissioned burn authority, asserting the mint
+    /// decimals (`2`).
+    BurnChecked { amount: u64, decimals: u8 },
+}
+
+impl<'a> PermissionedBurnInstruction<'a> {
+    /// Unpack a permissioned burn extension instruction from its instruction
+    /// data, starting with the extension discriminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the instruction is
+    /// unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        match sub_discriminator(data, ExtensionDiscriminator::PermissionedBurn)? {
+            0 => Ok(PermissionedBurnInstruction::Initialize {
+                authority: unpack_address(data, 2)?,
+            }),
+            1 => Ok(PermissionedBurnInstruction::Burn {
+                amount: unpack_u64(data, 2)?,
+            }),
+            2 => Ok(PermissionedBurnInstruction::BurnChecked {
+                amount: unpack_u64(data, 2)?,
+                decimals: unpack_u8(data, 10)?,
+            }),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// Return the sub-instruction discriminator of an `extension` instruction.
+#[inline(always)]
+fn sub_discriminator(data: &[u8], extension: ExtensionDiscriminator) -> Result<u8, ProgramError> {
+    match data {
+        [discriminator, sub_discriminator, ..] if *discriminator == extension as u8 => {
+            Ok(*sub_discriminator)
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
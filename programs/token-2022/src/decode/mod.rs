@@ -0,0 +1,501 @@
+//! Decoders of Token-2022 instruction data.
+//!
+//! [`TokenInstruction::unpack`] parses the data of the instructions built by
+//! this crate back into a typed value: the instructions shared with SPL Token
+//! are decoded by [`pinocchio_token::decode`], and the extension instructions
+//! into one enum per [`ExtensionDiscriminator`]. Addresses and ciphertexts
+//! are borrowed from the instruction data without copying.
+//!
+//! [`ExtensionDiscriminator`]: crate::instructions::ExtensionDiscriminator
+
+mod extensions;
+
+use {crate::state::ExtensionType, solana_address::Address, solana_program_error::ProgramError};
+pub use {
+    extensions::*,
+    pinocchio_token::decode::{BatchInstruction, BatchInstructions},
+};
+
+/// A decoded Token-2022 instruction.
+///
+/// Each variant holds the parameters of the corresponding builder in
+/// [`instructions`](crate::instructions); see the builder for the accounts
+/// expected by the instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TokenInstruction<'a> {
+    /// An instruction shared with SPL Token.
+    ///
+    /// `GetAccountDataSize` is decoded as
+    /// [`TokenInstruction::GetAccountDataSize`] instead, with its extension
+    /// types.
+    Token(pinocchio_token::decode::TokenInstruction<'a>),
+
+    /// Gets the required size of an account for the given mint and
+    /// extension types (`21`).
+    GetAccountDataSize { extension_types: ExtensionTypes<'a> },
+
+    /// Initializes the close account authority of a mint (`25`).
+    InitializeMintCloseAuthority {
+        close_authority: Option<&'a Address>,
+    },
+
+    /// A transfer fee extension instruction (`26`).
+    TransferFee(TransferFeeInstruction<'a>),
+
+    /// A confidential transfer extension instruction (`27`).
+    ConfidentialTransfer(ConfidentialTransferInstruction<'a>),
+
+    /// A default account state extension instruction (`28`).
+    DefaultAccountState(DefaultAccountStateInstruction),
+
+    /// Reallocates an account to hold the given extension types (`29`).
+    Reallocate { extension_types: ExtensionTypes<'a> },
+
+    /// A memo transfer extension instruction (`30`).
+    MemoTransfer(MemoTransferInstruction),
+
+    /// Creates the native mint (`31`).
+    CreateNativeMint,
+
+    /// Initializes the non-transferable extension of a mint (`32`).
+    InitializeNonTransferableMint,
+
+    /// An interest-bearing mint extension instruction (`33`).
+    InterestBearingMint(InterestBearingMintInstruction<'a>),
+
+    /// A CPI guard extension instruction (`34`).
+    CpiGuard(CpiGuardInstruction),
+
+    /// Initializes the permanent delegate of a mint (`35`).
+    InitializePermanentDelegate { delegate: &'a Address },
+
+    /// A transfer hook extension instruction (`36`).
+    TransferHook(TransferHookInstruction<'a>),
+
+    /// A metadata pointer extension instruction (`39`).
+    MetadataPointer(MetadataPointerInstruction<'a>),
+
+    /// A group pointer extension instruction (`40`).
+    GroupPointer(GroupPointerInstruction<'a>),
+
+    /// A group member pointer extension instruction (`41`).
+    GroupMemberPointer(GroupMemberPointerInstruction<'a>),
+
+    /// A scaled UI amount extension instruction (`43`).
+    ScaledUiAmount(ScaledUiAmountInstruction<'a>),
+
+    /// A pausable extension instruction (`44`).
+    Pausable(PausableInstruction<'a>),
+
+    /// A permissioned burn extension instruction (`46`).
+    PermissionedBurn(PermissionedBurnInstruction<'a>),
+}
+
+impl<'a> TokenInstruction<'a> {
+    /// Unpack a Token-2022 instruction from its instruction data.
+    ///
+    /// As the token programs do, bytes following the instruction parameters
+    /// are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the discriminator
+    /// is unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        let discriminator = *data.first().ok_or(ProgramError::InvalidInstructionData)?;
+
+        Ok(match discriminator {
+            21 => TokenInstruction::GetAccountDataSize {
+                extension_types: ExtensionTypes::new(&data[1..])?,
+            },
+            25 => TokenInstruction::InitializeMintCloseAuthority {
+                close_authority: unpack_option_address(data, 1)?,
+            },
+            26 => TokenInstruction::TransferFee(TransferFeeInstruction::unpack(data)?),
+            27 => TokenInstruction::ConfidentialTransfer(ConfidentialTransferInstruction::unpack(
+                data,
+            )?),
+            28 => {
+                TokenInstruction::DefaultAccountState(DefaultAccountStateInstruction::unpack(data)?)
+            }
+            29 => TokenInstruction::Reallocate {
+                extension_types: ExtensionTypes::new(&data[1..])?,
+            },
+            30 => TokenInstruction::MemoTransfer(MemoTransferInstruction::unpack(data)?),
+            31 => TokenInstruction::CreateNativeMint,
+            32 => TokenInstruction::InitializeNonTransferableMint,
+            33 => {
+                TokenInstruction::InterestBearingMint(InterestBearingMintInstruction::unpack(data)?)
+            }
+            34 => TokenInstruction::CpiGuard(CpiGuardInstruction::unpack(data)?),
+            35 => TokenInstruction::InitializePermanentDelegate {
+                delegate: unpack_address(data, 1)?,
+            },
+            36 => TokenInstruction::TransferHook(TransferHookInstruction::unpack(data)?),
+            39 => TokenInstruction::MetadataPointer(MetadataPointerInstruction::unpack(data)?),
+            40 => TokenInstruction::GroupPointer(GroupPointerInstruction::unpack(data)?),
+            41 => {
+                TokenInstruction::GroupMemberPointer(GroupMemberPointerInstruction::unpack(data)?)
+            }
+            43 => TokenInstruction::ScaledUiAmount(ScaledUiAmountInstruction::unpack(data)?),
+            44 => TokenInstruction::Pausable(PausableInstruction::unpack(data)?),
+            46 => TokenInstruction::PermissionedBurn(PermissionedBurnInstruction::unpack(data)?),
+            _ => TokenInstruction::Token(pinocchio_token::decode::TokenInstruction::unpack(data)?),
+        })
+    }
+}
+
+/// Iterator over the extension types of a `GetAccountDataSize` or
+/// `Reallocate` instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExtensionTypes<'a> {
+    /// The remaining (validated) little-endian `u16` extension types.
+    data: &'a [u8],
+}
+
+impl<'a> ExtensionTypes<'a> {
+    /// Validate the extension types following the instruction discriminator.
+    #[inline]
+    fn new(data: &'a [u8]) -> Result<Self, ProgramError> {
+        if !data.len().is_multiple_of(2) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        for extension_type in data.chunks_exact(2) {
+            ExtensionType::try_from(u16::from_le_bytes([extension_type[0], extension_type[1]]))
+                .map_err(|_| ProgramError::InvalidInstructionData)?;
+        }
+
+        Ok(Self { data })
+    }
+}
+
+impl Iterator for ExtensionTypes<'_> {
+    type Item = ExtensionType;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let [low, high, rest @ ..] = self.data else {
+            return None;
+        };
+
+        self.data = rest;
+
+        // The extension types were validated when unpacking the instruction.
+        ExtensionType::try_from(u16::from_le_bytes([*low, *high])).ok()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.data.len() / 2, Some(self.data.len() / 2))
+    }
+}
+
+impl ExactSizeIterator for ExtensionTypes<'_> {}
+
+/// Unpack the `N` bytes of `data` at `offset`.
+#[inline(always)]
+fn unpack_bytes<const N: usize>(data: &[u8], offset: usize) -> Result<&[u8; N], ProgramError> {
+    data.get(offset..offset + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+/// Unpack the `u8` of `data` at `offset`.
+#[inline(always)]
+fn unpack_u8(data: &[u8], offset: usize) -> Result<u8, ProgramError> {
+    data.get(offset)
+        .copied()
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+/// Unpack the little-endian `u64` of `data` at `offset`.
+#[inline(always)]
+fn unpack_u64(data: &[u8], offset: usize) -> Result<u64, ProgramError> {
+    unpack_bytes(data, offset).map(|bytes| u64::from_le_bytes(*bytes))
+}
+
+/// Unpack the address of `data` at `offset`.
+#[inline(always)]
+fn unpack_address(data: &[u8], offset: usize) -> Result<&Address, ProgramError> {
+    let bytes = unpack_bytes::<32>(data, offset)?;
+    // SAFETY: `bytes` is 32 bytes long and `Address` has alignment 1.
+    Ok(unsafe { &*(bytes.as_ptr() as *const Address) })
+}
+
+/// Unpack the optional address of `data` at `offset`, encoded as a `0` byte
+/// or a `1` byte followed by the address.
+#[inline(always)]
+fn unpack_option_address(data: &[u8], offset: usize) -> Result<Option<&Address>, ProgramError> {
+    match unpack_u8(data, offset)? {
+        0 => Ok(None),
+        1 => unpack_address(data, offset + 1).map(Some),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// Unpack the optional address of `data` at `offset`, encoded as the
+/// address or 32 zero bytes.
+#[inline(always)]
+fn unpack_optional_nonzero_address(
+    data: &[u8],
+    offset: usize,
+) -> Result<Option<&Address>, ProgramError> {
+    let address = unpack_address(data, offset)?;
+    Ok((address.as_array() != &[0; 32]).then_some(address))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use {
+        super::*,
+        crate::state::{AccountState, AeCiphertext},
+        pinocchio_token::instructions::Amount,
+        std::{vec, vec::Vec},
+    };
+
+    #[test]
+    fn unpack_token_instructions() {
+        let mut data = vec![12];
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.push(6);
+
+        assert_eq!(
+            TokenInstruction::unpack(&data),
+            Ok(TokenInstruction::Token(
+                pinocchio_token::decode::TokenInstruction::TransferChecked {
+                    amount: 42,
+                    decimals: 6,
+                }
+            ))
+        );
+
+        let owner = Address::new_from_array([7; 32]);
+        let mut data = vec![0, 9];
+        data.extend_from_slice(owner.as_ref());
+        data.push(0);
+
+        assert_eq!(
+            TokenInstruction::unpack(&data),
+            Ok(TokenInstruction::Token(
+                pinocchio_token::decode::TokenInstruction::InitializeMint {
+                    decimals: 9,
+                    mint_authority: &owner,
+                    freeze_authority: None,
+                }
+            ))
+        );
+
+        // A truncated optional freeze authority.
+        data.pop();
+        data.push(1);
+        assert_eq!(
+            TokenInstruction::unpack(&data),
+            Err(ProgramError::InvalidInstructionData)
+        );
+
+        assert_eq!(
+            TokenInstruction::unpack(&[45, 0]),
+            Ok(TokenInstruction::Token(
+                pinocchio_token::decode::TokenInstruction::UnwrapLamports {
+                    amount: Amount::All,
+                }
+            ))
+        );
+        assert_eq!(
+            TokenInstruction::unpack(&[24, b'1', b'.', b'5']),
+            Ok(TokenInstruction::Token(
+                pinocchio_token::decode::TokenInstruction::UiAmountToAmount { ui_amount: "1.5" }
+            ))
+        );
+
+        assert_eq!(
+            TokenInstruction::unpack(&[]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            TokenInstruction::unpack(&[100]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn unpack_batch_instruction() {
+        let mut data = vec![255, 3, 9, 3];
+        data.extend_from_slice(&5u64.to_le_bytes());
+        data.extend_from_slice(&[2, 1, 17]);
+
+        let Ok(TokenInstruction::Token(pinocchio_token::decode::TokenInstruction::Batch {
+            instructions,
+        })) = TokenInstruction::unpack(&data)
+        else {
+            panic!("expected a batch instruction");
+        };
+
+        let instructions: Vec<_> = instructions
+            .map(|instruction| {
+                (
+                    instruction.accounts_len,
+                    TokenInstruction::unpack(instruction.data).unwrap(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            instructions,
+            [
+                (
+                    3,
+                    TokenInstruction::Token(pinocchio_token::decode::TokenInstruction::Transfer {
+                        amount: 5
+                    })
+                ),
+                (
+                    2,
+                    TokenInstruction::Token(pinocchio_token::decode::TokenInstruction::SyncNative)
+                ),
+            ]
+        );
+
+        // An instruction longer than the batch data.
+        data.push(1);
+        assert_eq!(
+            TokenInstruction::unpack(&data),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn unpack_extension_types_instructions() {
+        let data = [29, 14, 0, 8, 0];
+
+        let Ok(TokenInstruction::Reallocate { extension_types }) = TokenInstruction::unpack(&data)
+        else {
+            panic!("expected a reallocate instruction");
+        };
+
+        assert_eq!(extension_types.len(), 2);
+        assert_eq!(
+            extension_types.collect::<Vec<_>>(),
+            [ExtensionType::TransferHook, ExtensionType::MemoTransfer]
+        );
+
+        let Ok(TokenInstruction::GetAccountDataSize { extension_types }) =
+            TokenInstruction::unpack(&[21])
+        else {
+            panic!("expected a get account data size instruction");
+        };
+        assert_eq!(extension_types.len(), 0);
+
+        assert_eq!(
+            TokenInstruction::unpack(&[29, 14]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            TokenInstruction::unpack(&[29, 255, 0]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn unpack_extension_instructions() {
+        let authority = Address::new_from_array([7; 32]);
+
+        let mut data = vec![25, 1];
+        data.extend_from_slice(authority.as_ref());
+        assert_eq!(
+            TokenInstruction::unpack(&data),
+            Ok(TokenInstruction::InitializeMintCloseAuthority {
+                close_authority: Some(&authority),
+            })
+        );
+
+        let mut data = vec![26, 0, 0, 1];
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(&50u16.to_le_bytes());
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        assert_eq!(
+            TokenInstruction::unpack(&data),
+            Ok(TokenInstruction::TransferFee(
+                TransferFeeInstruction::InitializeTransferFeeConfig {
+                    transfer_fee_config_authority: None,
+                    withdraw_withheld_authority: Some(&authority),
+                    transfer_fee_basis_points: 50,
+                    maximum_fee: 1_000,
+                }
+            ))
+        );
+
+        let balance: AeCiphertext = [3; 36];
+        let mut data = vec![27, 8];
+        data.extend_from_slice(&4u64.to_le_bytes());
+        data.extend_from_slice(&balance);
+        assert_eq!(
+            TokenInstruction::unpack(&data),
+            Ok(TokenInstruction::ConfidentialTransfer(
+                ConfidentialTransferInstruction::ApplyPendingBalance {
+                    expected_pending_balance_credit_counter: 4,
+                    new_decryptable_available_balance: &balance,
+                }
+            ))
+        );
+
+        assert_eq!(
+            TokenInstruction::unpack(&[28, 1, 2]),
+            Ok(TokenInstruction::DefaultAccountState(
+                DefaultAccountStateInstruction::Update {
+                    state: AccountState::Frozen,
+                }
+            ))
+        );
+
+        // The metadata pointer authority is unset (all zeros).
+        let mut data = vec![39, 0];
+        data.extend_from_slice(&[0; 32]);
+        data.extend_from_slice(authority.as_ref());
+        assert_eq!(
+            TokenInstruction::unpack(&data),
+            Ok(TokenInstruction::MetadataPointer(
+                MetadataPointerInstruction::Initialize {
+                    authority: None,
+                    metadata_address: Some(&authority),
+                }
+            ))
+        );
+
+        let mut data = vec![43, 1];
+        data.extend_from_slice(&1.5f64.to_le_bytes());
+        data.extend_from_slice(&(-1i64).to_le_bytes());
+        assert_eq!(
+            TokenInstruction::unpack(&data),
+            Ok(TokenInstruction::ScaledUiAmount(
+                ScaledUiAmountInstruction::UpdateMultiplier {
+                    multiplier: 1.5,
+                    effective_timestamp: -1,
+                }
+            ))
+        );
+
+        assert_eq!(
+            TokenInstruction::unpack(&[34, 1]),
+            Ok(TokenInstruction::CpiGuard(CpiGuardInstruction::Disable))
+        );
+
+        // Unknown and truncated extension instructions.
+        assert_eq!(
+            TokenInstruction::unpack(&[34, 2]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            TokenInstruction::unpack(&[44]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            TokenInstruction::unpack(&[46, 2, 1]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! Error types of the SPL Token and Token-2022 programs.
+
+use {core::fmt, solana_program_error::ProgramError};
+
+/// Errors returned by the SPL Token and Token-2022 programs.
+///
+/// The SPL Token program uses the first 20 error codes; the remaining ones are
+/// specific to Token-2022. The token programs return these errors as
+/// [`ProgramError::Custom`] with the enum discriminant as the error code.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TokenError {
+    /// Lamport balance below rent-exempt threshold.
+    NotRentExempt = 0,
+    /// Insufficient funds.
+    InsufficientFunds = 1,
+    /// Invalid Mint.
+    InvalidMint = 2,
+    /// Account not associated with this Mint.
+    MintMismatch = 3,
+    /// Owner does not match.
+    OwnerMismatch = 4,
+    /// Fixed supply.
+    FixedSupply = 5,
+    /// Already in use.
+    AlreadyInUse = 6,
+    /// Invalid number of provided signers.
+    InvalidNumberOfProvidedSigners = 7,
+    /// Invalid number of required signers.
+    InvalidNumberOfRequiredSigners = 8,
+    /// State is uninitialized.
+    UninitializedState = 9,
+    /// Instruction does not support native tokens.
+    NativeNotSupported = 10,
+    /// Non-native account can only be closed if its balance is zero.
+    NonNativeHasBalance = 11,
+    /// Invalid instruction.
+    InvalidInstruction = 12,
+    /// State is invalid for requested operation.
+    InvalidState = 13,
+    /// Operation overflowed.
+    Overflow = 14,
+    /// Account does not support specified authority type.
+    AuthorityTypeNotSupported = 15,
+    /// This token mint cannot freeze accounts.
+    MintCannotFreeze = 16,
+    /// Account is frozen.
+    AccountFrozen = 17,
+    /// The provided decimals value different from the Mint decimals.
+    MintDecimalsMismatch = 18,
+    /// Instruction does not support non-native tokens.
+    NonNativeNotSupported = 19,
+    /// Extension type does not match already existing extensions.
+    ExtensionTypeMismatch = 20,
+    /// Extension does not match the base type provided.
+    ExtensionBaseMismatch = 21,
+    /// Extension already initialized on this account.
+    ExtensionAlreadyInitialized = 22,
+    /// An account can only be closed if its confidential balance is zero.
+    ConfidentialTransferAccountHasBalance = 23,
+    /// Account not approved for confidential transfers.
+    ConfidentialTransferAccountNotApproved = 24,
+    /// Account not accepting deposits or transfers.
+    ConfidentialTransferDepositsAndTransfersDisabled = 25,
+    /// ElGamal public key mismatch.
+    ConfidentialTransferElGamalPubkeyMismatch = 26,
+    /// Balance mismatch.
+    ConfidentialTransferBalanceMismatch = 27,
+    /// Mint has non-zero supply. Burn all tokens before closing the mint.
+    MintHasSupply = 28,
+    /// No authority exists to perform the desired operation.
+    NoAuthorityExists = 29,
+    /// Transfer fee exceeds maximum of 10,000 basis points.
+    TransferFeeExceedsMaximum = 30,
+    /// Mint required for this account to transfer tokens, use
+    /// `transfer_checked` or `transfer_checked_with_fee`.
+    MintRequiredForTransfer = 31,
+    /// Calculated fee does not match expected fee.
+    FeeMismatch = 32,
+    /// Fee parameters associated with zero-knowledge proofs do not match fee
+    /// parameters in mint.
+    FeeParametersMismatch = 33,
+    /// The owner authority cannot be changed.
+    ImmutableOwner = 34,
+    /// An account can only be closed if its withheld fee balance is zero,
+    /// harvest fees to the mint and try again.
+    AccountHasWithheldTransferFees = 35,
+    /// No memo in previous instruction; required for recipient to receive a
+    /// transfer.
+    NoMemo = 36,
+    /// Transfer is disabled for this mint.
+    NonTransferable = 37,
+    /// Non-transferable tokens can't be minted to an account without immutable
+    /// ownership.
+    NonTransferableNeedsImmutableOwnership = 38,
+    /// The total number of `Deposit` and `Transfer` instructions to an account
+    /// cannot exceed the associated
+    /// `maximum_pending_balance_credit_counter`.
+    MaximumPendingBalanceCreditCounterExceeded = 39,
+    /// Deposit amount exceeds maximum limit.
+    MaximumDepositAmountExceeded = 40,
+    /// CPI Guard cannot be enabled or disabled in CPI.
+    CpiGuardSettingsLocked = 41,
+    /// CPI Guard is enabled, and a program attempted to transfer user funds via
+    /// CPI without using a delegate.
+    CpiGuardTransferBlocked = 42,
+    /// CPI Guard is enabled, and a program attempted to burn user funds via CPI
+    /// without using a delegate.
+    CpiGuardBurnBlocked = 43,
+    /// CPI Guard is enabled, and a program attempted to close an account via
+    /// CPI without returning lamports to owner.
+    CpiGuardCloseAccountBlocked = 44,
+    /// CPI Guard is enabled, and a program attempted to approve a delegate via
+    /// CPI.
+    CpiGuardApproveBlocked = 45,
+    /// CPI Guard is enabled, and a program attempted to add or replace an
+    /// authority via CPI.
+    CpiGuardSetAuthorityBlocked = 46,
+    /// Account ownership cannot be changed while CPI Guard is enabled.
+    CpiGuardOwnerChangeBlocked = 47,
+    /// Extension not found in account data.
+    ExtensionNotFound = 48,
+    /// Non-confidential transfers disabled.
+    NonConfidentialTransfersDisabled = 49,
+    /// An account can only be closed if the confidential withheld fee is zero.
+    ConfidentialTransferFeeAccountHasWithheldFee = 50,
+    /// A mint or an account is initialized to an invalid combination of
+    /// extensions.
+    InvalidExtensionCombination = 51,
+    /// Extension allocation with overwrite must use the same length.
+    InvalidLengthForAlloc = 52,
+    /// Failed to decrypt a confidential transfer account.
+    AccountDecryption = 53,
+    /// Failed to generate proof.
+    ProofGeneration = 54,
+    /// An invalid proof instruction offset was provided.
+    InvalidProofInstructionOffset = 55,
+    /// Harvest of withheld tokens to mint is disabled.
+    HarvestToMintDisabled = 56,
+    /// Split proof context state accounts not supported for instruction.
+    SplitProofContextStateAccountsNotSupported = 57,
+    /// Not enough proof context state accounts provided.
+    NotEnoughProofContextStateAccounts = 58,
+    /// Ciphertext is malformed.
+    MalformedCiphertext = 59,
+    /// Ciphertext arithmetic failed.
+    CiphertextArithmeticFailed = 60,
+    /// Pedersen commitments did not match.
+    PedersenCommitmentMismatch = 61,
+    /// Range proof lengths did not match.
+    RangeProofLengthMismatch = 62,
+    /// Illegal transfer amount bit length.
+    IllegalBitLength = 63,
+    /// Fee calculation failed.
+    FeeCalculation = 64,
+    /// Conversions from normal to confidential token balance and vice versa are
+    /// illegal if the confidential-mint-burn extension is enabled.
+    IllegalMintBurnConversion = 65,
+    /// Invalid scale for scaled ui amount.
+    InvalidScale = 66,
+    /// Transferring, minting, and burning is paused on this mint.
+    MintPaused = 67,
+    /// Key rotation attempted while pending balance is not zero.
+    PendingBalanceNonZero = 68,
+}
+
+impl TokenError {
+    /// Return the description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            TokenError::NotRentExempt => "Lamport balance below rent-exempt threshold",
+            TokenError::InsufficientFunds => "Insufficient funds",
+            TokenError::InvalidMint => "Invalid Mint",
+            TokenError::MintMismatch => "Account not associated with this Mint",
+            TokenError::OwnerMismatch => "Owner does not match",
+            TokenError::FixedSupply => "Fixed supply",
+            TokenError::AlreadyInUse => "Already in use",
+            TokenError::InvalidNumberOfProvidedSigners => "Invalid number of provided signers",
+            TokenError::InvalidNumberOfRequiredSigners => "Invalid number of required signers",
+            TokenError::UninitializedState => "State is uninitialized",
+            TokenError::NativeNotSupported => "Instruction does not support native tokens",
+            TokenError::NonNativeHasBalance => {
+                "Non-native account can only be closed if its balance is zero"
+            }
+            TokenError::InvalidInstruction => "Invalid instruction",
+            TokenError::InvalidState => "State is invalid for requested operation",
+            TokenError::Overflow => "Operation overflowed",
+            TokenError::AuthorityTypeNotSupported => {
+                "Account does not support specified authority type"
+            }
+            TokenError::MintCannotFreeze => "This token mint cannot freeze accounts",
+            TokenError::AccountFrozen => "Account is frozen",
+            TokenError::MintDecimalsMismatch => {
+                "The provided decimals value different from the Mint decimals"
+            }
+            TokenError::NonNativeNotSupported => "Instruction does not support non-native tokens",
+            TokenError::ExtensionTypeMismatch => {
+                "Extension type does not match already existing extensions"
+            }
+            TokenError::ExtensionBaseMismatch => "Extension does not match the base type provided",
+            TokenError::ExtensionAlreadyInitialized => {
+                "Extension already initialized on this account"
+            }
+            TokenError::ConfidentialTransferAccountHasBalance => {
+                "An account can only be closed if its confidential balance is zero"
+            }
+            TokenError::ConfidentialTransferAccountNotApproved => {
+                "Account not approved for confidential transfers"
+            }
+            TokenError::ConfidentialTransferDepositsAndTransfersDisabled => {
+                "Account not accepting deposits or transfers"
+            }
+            TokenError::ConfidentialTransferElGamalPubkeyMismatch => "ElGamal public key mismatch",
+            TokenError::ConfidentialTransferBalanceMismatch => "Balance mismatch",
+            TokenError::MintHasSupply => {
+                "Mint has non-zero supply. Burn all tokens before closing the mint"
+            }
+            TokenError::NoAuthorityExists => "No authority exists to perform the desired operation",
+            TokenError::TransferFeeExceedsMaximum => {
+                "Transfer fee exceeds maximum of 10,000 basis points"
+            }
+            TokenError::MintRequiredForTransfer => {
+                "Mint required for this account to transfer tokens, use `transfer_checked` or \
+                 `transfer_checked_with_fee`"
+            }
+            TokenError::FeeMismatch => "Calculated fee does not match expected fee",
+            TokenError::FeeParametersMismatch => {
+                "Fee parameters associated with zero-knowledge proofs do not match fee parameters \
+                 in mint"
+            }
+            TokenError::ImmutableOwner => "The owner authority cannot be changed",
+            TokenError::AccountHasWithheldTransferFees => {
+                "An account can only be closed if its withheld fee balance is zero, harvest fees \
+                 to the mint and try again"
+            }
+            TokenError::NoMemo => {
+                "No memo in previous instruction; required for recipient to receive a transfer"
+            }
+            TokenError::NonTransferable => "Transfer is disabled for this mint",
+            TokenError::NonTransferableNeedsImmutableOwnership => {
+                "Non-transferable tokens can't be minted to an account without immutable ownership"
+            }
+            TokenError::MaximumPendingBalanceCreditCounterExceeded => {
+                "The total number of `Deposit` and `Transfer` instructions to an account cannot \
+                 exceed the associated `maximum_pending_balance_credit_counter`"
+            }
+            TokenError::MaximumDepositAmountExceeded => "Deposit amount exceeds maximum limit",
+            TokenError::CpiGuardSettingsLocked => "CPI Guard cannot be enabled or disabled in CPI",
+            TokenError::CpiGuardTransferBlocked => {
+                "CPI Guard is enabled, and a program attempted to transfer user funds via CPI \
+                 without using a delegate"
+            }
+            TokenError::CpiGuardBurnBlocked => {
+                "CPI Guard is enabled, and a program attempted to burn user funds via CPI without \
+                 using a delegate"
+            }
+            TokenError::CpiGuardCloseAccountBlocked => {
+                "CPI Guard is enabled, and a program attempted to close an account via CPI without \
+                 returning lamports to owner"
+            }
+            TokenError::CpiGuardApproveBlocked => {
+                "CPI Guard is enabled, and a program attempted to approve a delegate via CPI"
+            }
+            TokenError::CpiGuardSetAuthorityBlocked => {
+                "CPI Guard is enabled, and a program attempted to add or replace an authority via \
+                 CPI"
+            }
+            TokenError::CpiGuardOwnerChangeBlocked => {
+                "Account ownership cannot be changed while CPI Guard is enabled"
+            }
+            TokenError::ExtensionNotFound => "Extension not found in account data",
+            TokenError::NonConfidentialTransfersDisabled => "Non-confidential transfers disabled",
+            TokenError::ConfidentialTransferFeeAccountHasWithheldFee => {
+                "An account can only be closed if the confidential withheld fee is zero"
+            }
+            TokenError::InvalidExtensionCombination => {
+                "A mint or an account is initialized to an invalid combination of extensions"
+            }
+            TokenError::InvalidLengthForAlloc => {
+                "Extension allocation with overwrite must use the same length"
+            }
+            TokenError::AccountDecryption => "Failed to decrypt a confidential transfer account",
+            TokenError::ProofGeneration => "Failed to generate proof",
+            TokenError::InvalidProofInstructionOffset => {
+                "An invalid proof instruction offset was provided"
+            }
+            TokenError::HarvestToMintDisabled => "Harvest of withheld tokens to mint is disabled",
+            TokenError::SplitProofContextStateAccountsNotSupported => {
+                "Split proof context state accounts not supported for instruction"
+            }
+            TokenError::NotEnoughProofContextStateAccounts => {
+                "Not enough proof context state accounts provided"
+            }
+            TokenError::MalformedCiphertext => "Ciphertext is malformed",
+            TokenError::CiphertextArithmeticFailed => "Ciphertext arithmetic failed",
+            TokenError::PedersenCommitmentMismatch => "Pedersen commitments did not match",
+            TokenError::RangeProofLengthMismatch => "Range proof lengths did not match",
+            TokenError::IllegalBitLength => "Illegal transfer amount bit length",
+            TokenError::FeeCalculation => "Fee calculation failed",
+            TokenError::IllegalMintBurnConversion => {
+                "Conversions from normal to confidential token balance and vice versa are illegal \
+                 if the confidential-mint-burn extension is enabled"
+            }
+            TokenError::InvalidScale => "Invalid scale for scaled ui amount",
+            TokenError::MintPaused => "Transferring, minting, and burning is paused on this mint",
+            TokenError::PendingBalanceNonZero => {
+                "Key rotation attempted while pending balance is not zero"
+            }
+        }
+    }
+}
+
+impl fmt::Display for TokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<TokenError> for ProgramError {
+    #[inline(always)]
+    fn from(error: TokenError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl TryFrom<u32> for TokenError {
+    type Error = ProgramError;
+
+    /// Decode a token error from its error code.
+    ///
+    /// Returns the code as a [`ProgramError::Custom`] if it is not a token
+    /// error code.
+    #[inline(always)]
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0..=68 => {
+                // SAFETY: `value` is guaranteed to be in the range of the enum variants.
+                Ok(unsafe { core::mem::transmute::<u32, TokenError>(value) })
+            }
+            _ => Err(ProgramError::Custom(value)),
+        }
+    }
+}
+
+impl TryFrom<ProgramError> for TokenError {
+    type Error = ProgramError;
+
+    /// Decode a token error from a [`ProgramError::Custom`] error, e.g., the
+    /// result of a failed cross-program invocation.
+    ///
+    /// Returns the original error if it is not a token error.
+    #[inline(always)]
+    fn try_from(error: ProgramError) -> Result<Self, Self::Error> {
+        match error {
+            ProgramError::Custom(code) => TokenError::try_from(code),
+            error => Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(TokenError::NotRentExempt as u32, 0);
+        assert_eq!(TokenError::NonNativeNotSupported as u32, 19);
+        assert_eq!(TokenError::ExtensionNotFound as u32, 48);
+        assert_eq!(TokenError::PendingBalanceNonZero as u32, 68);
+
+        for code in 0..=68 {
+            let error = TokenError::try_from(code).unwrap();
+            assert_eq!(error as u32, code);
+            assert!(!error.as_str().is_empty());
+            assert_eq!(TokenError::try_from(ProgramError::from(error)), Ok(error));
+        }
+
+        assert_eq!(TokenError::try_from(69), Err(ProgramError::Custom(69)));
+        assert_eq!(
+            TokenError::try_from(ProgramError::InvalidArgument),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            TokenError::MintMismatch.as_str(),
+            "Account not associated with this Mint"
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use {
+    crate::{
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer},
+        instructions::{
+            account_borrow_failed_error, extensions::ExtensionDiscriminator,
+            invalid_argument_error, Batch, CpiWriter, IntoBatch, MAX_MULTISIG_SIGNERS,
+        },
+        state::AeCiphertext,
+        write_bytes, Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
+    solana_account_view::AccountView,
+    solana_address::Address,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// The instruction discriminator.
+const DISCRIMINATOR: u8 = 8;
+
+/// Maximum number of accounts expected by this instruction.
+const MAX_ACCOUNTS_LEN: usize = 2 + MAX_MULTISIG_SIGNERS;
+
+/// Instruction data length:
+///   - extension discriminator (1 byte)
+///   - instruction discriminator (1 byte)
+///   - expected pending balance credit counter (8 bytes, u64)
+///   - new decryptable available balance (36 bytes, AeCiphertext)
+const DATA_LEN: usize = 46;
+
+/// Apply the pending balance of a confidential transfer account to its
+/// available balance.
+///
+/// Accounts expected by this instruction:
+///
+///   * Single authority
+///   0. `[writable]` The token account to update.
+///   1. `[signer]` The account's owner.
+///
+///   * Multisignature authority
+///   0. `[writable]` The token account to update.
+///   1. `[]` The account's multisignature owner.
+///   2. `..2+M` `[signer]` M signer accounts.
+pub struct ApplyPendingBalance<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> {
+    /// The token account to update.
+    pub account: &'a AccountView,
+
+    /// The account's owner.
+    pub authority: &'a AccountView,
+
+    /// The signer accounts if the authority is a multisig.
+    pub multisig_signers: &'c [MultisigSigner],
+
+    /// The expected number of pending balance credits since the last
+    /// successful `ApplyPendingBalance` instruction.
+    pub expected_pending_balance_credit_counter: u64,
+
+    /// The new decryptable balance if the pending balance is applied
+    /// successfully.
+    pub new_decryptable_available_balance: &'b AeCiphertext,
+
+    /// The token program.
+    pub token_program: &'b Address,
+}
+
+impl<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>>
+    ApplyPendingBalance<'a, 'b, 'c, MultisigSigner>
+{
+    pub const DISCRIMINATOR: u8 = DISCRIMINATOR;
+
+    /// Creates a new `ApplyPendingBalance` instruction with a single
+    /// owner/delegate authority.
+    #[inline(always)]
+    pub fn new(
+        token_program: &'b Address,
+        account: &'a AccountView,
+        authority: &'a AccountView,
+        expected_pending_balance_credit_counter: u64,
+        new_decryptable_available_balance: &'b AeCiphertext,
+    ) -> Self {
+        Self::with_multisig_signers(
+            token_program,
+            account,
+            authority,
+            expected_pending_balance_credit_counter,
+            new_decryptable_available_balance,
+            &[],
+        )
+    }
+
+    /// Creates a new `ApplyPendingBalance` instruction with a multisignature
+    /// owner/delegate authority and signer accounts.
+    #[inline(always)]
+    pub fn with_multisig_signers(
+        token_program: &'b Address,
+        account: &'a AccountView,
+        authority: &'a AccountView,
+        expected_pending_balance_credit_counter: u64,
+        new_decryptable_available_balance: &'b AeCiphertext,
+        multisig_signers: &'c [MultisigSigner],
+    ) -> Self {
+        Self {
+            account,
+            authority,
+            multisig_signers,
+            expected_pending_balance_credit_counter,
+            new_decryptable_available_balance,
+            token_program,
+        }
+    }
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.multisig_signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_signed_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+                signers,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl<MultisigSigner: AsRef<AccountView>> CpiWriter
+    for ApplyPendingBalance<'_, '_, '_, MultisigSigner>
+{
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(
+            self.account,
+            self.authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(
+            self.account,
+            self.authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(
+            self.expected_pending_balance_credit_counter,
+            self.new_decryptable_available_balance,
+            data,
+        )
+    }
+}
+
+impl<MultisigSigner: AsRef<AccountView>> IntoBatch<Token2022Program>
+    for ApplyPendingBalance<'_, '_, '_, MultisigSigner>
+{
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| {
+                write_accounts(
+                    self.account,
+                    self.authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            |accounts| {
+                write_instruction_accounts(
+                    self.account,
+                    self.authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            |data| {
+                write_instruction_data(
+                    self.expected_pending_balance_credit_counter,
+                    self.new_decryptable_available_balance,
+                    data,
+                )
+            },
+        )
+    }
+}
+
+#[inline(always)]
+fn write_accounts<'account, 'multisig, 'out, MultisigSigner: AsRef<AccountView>>(
+    account: &'account AccountView,
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 2 + multisig_signers.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if account.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
+
+    CpiAccount::init_from_account_view(account, &mut accounts[0]);
+
+    CpiAccount::init_from_account_view(authority, &mut accounts[1]);
+
+    for (account, signer) in accounts[2..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        CpiAccount::init_from_account_view(signer.as_ref(), account);
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<'account, 'multisig, 'out, MultisigSigner: AsRef<AccountView>>(
+    account: &'account AccountView,
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 2 + multisig_signers.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::writable(account.address()));
+
+    accounts[1].write(InstructionAccount::new(
+        authority.address(),
+        false,
+        multisig_signers.is_empty(),
+    ));
+
+    for (account, signer) in accounts[2..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        account.write(InstructionAccount::readonly_signer(
+            signer.as_ref().address(),
+        ));
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    expected_pending_balance_credit_counter: u64,
+    new_decryptable_available_balance: &AeCiphertext,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    data[0].write(ExtensionDiscriminator::ConfidentialTransfer as u8);
+
+    data[1].write(DISCRIMINATOR);
+
+    write_bytes(
+        &mut data[2..10],
+        &expected_pending_balance_credit_counter.to_le_bytes(),
+    );
+
+    write_bytes(&mut data[10..46], new_decryptable_available_balance);
+
+    Ok(DATA_LEN)
+}
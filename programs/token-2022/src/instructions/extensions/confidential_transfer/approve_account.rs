@@ -0,0 +1,299 @@
+use {
+    crate::{
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer},
+        instructions::{
+            account_borrow_failed_error, extensions::ExtensionDiscriminator,
+            invalid_argument_error, Batch, CpiWriter, IntoBatch, MAX_MULTISIG_SIGNERS,
+        },
+        Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
+    solana_account_view::AccountView,
+    solana_address::Address,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// The instruction discriminator.
+const DISCRIMINATOR: u8 = 3;
+
+/// Maximum number of accounts expected by this instruction.
+const MAX_ACCOUNTS_LEN: usize = 3 + MAX_MULTISIG_SIGNERS;
+
+/// Instruction data length:
+///   - extension discriminator (1 byte)
+///   - instruction discriminator (1 byte)
+const DATA_LEN: usize = 2;
+
+/// Approve a token account for confidential transfers.
+///
+/// Only required when the `auto_approve_new_accounts` field of the mint's
+/// `ConfidentialTransferMint` extension is `false`.
+///
+/// Accounts expected by this instruction:
+///
+///   * Single authority
+///   0. `[writable]` The token account.
+///   1. `[]` The corresponding token mint.
+///   2. `[signer]` The confidential transfer mint authority.
+///
+///   * Multisignature authority
+///   0. `[writable]` The token account.
+///   1. `[]` The corresponding token mint.
+///   2. `[]` The multisignature confidential transfer mint authority.
+///   3. `..3+M` `[signer]` M signer accounts.
+pub struct ApproveAccount<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> {
+    /// The token account.
+    pub account: &'a AccountView,
+
+    /// The corresponding token mint.
+    pub mint: &'a AccountView,
+
+    /// The confidential transfer mint authority.
+    pub authority: &'a AccountView,
+
+    /// The signer accounts if the authority is a multisig.
+    pub multisig_signers: &'c [MultisigSigner],
+
+    /// The token program.
+    pub token_program: &'b Address,
+}
+
+impl<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> ApproveAccount<'a, 'b, 'c, MultisigSigner> {
+    pub const DISCRIMINATOR: u8 = DISCRIMINATOR;
+
+    /// Creates a new `ApproveAccount` instruction with a single authority.
+    #[inline(always)]
+    pub fn new(
+        token_program: &'b Address,
+        account: &'a AccountView,
+        mint: &'a AccountView,
+        authority: &'a AccountView,
+    ) -> Self {
+        Self::with_multisig_signers(token_program, account, mint, authority, &[])
+    }
+
+    /// Creates a new `ApproveAccount` instruction with a multisignature
+    /// authority and signer accounts.
+    #[inline(always)]
+    pub fn with_multisig_signers(
+        token_program: &'b Address,
+        account: &'a AccountView,
+        mint: &'a AccountView,
+        authority: &'a AccountView,
+        multisig_signers: &'c [MultisigSigner],
+    ) -> Self {
+        Self {
+            account,
+            mint,
+            authority,
+            multisig_signers,
+            token_program,
+        }
+    }
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.multisig_signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_signed_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+                signers,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl<MultisigSigner: AsRef<AccountView>> CpiWriter for ApproveAccount<'_, '_, '_, MultisigSigner> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(
+            self.account,
+            self.mint,
+            self.authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(
+            self.account,
+            self.mint,
+            self.authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(data)
+    }
+}
+
+impl<MultisigSigner: AsRef<AccountView>> IntoBatch<Token2022Program>
+    for ApproveAccount<'_, '_, '_, MultisigSigner>
+{
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| {
+                write_accounts(
+                    self.account,
+                    self.mint,
+                    self.authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            |accounts| {
+                write_instruction_accounts(
+                    self.account,
+                    self.mint,
+                    self.authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            write_instruction_data,
+        )
+    }
+}
+
+#[inline(always)]
+fn write_accounts<'account, 'multisig, 'out, MultisigSigner: AsRef<AccountView>>(
+    account: &'account AccountView,
+    mint: &'account AccountView,
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 3 + multisig_signers.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if account.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
+
+    CpiAccount::init_from_account_view(account, &mut accounts[0]);
+
+    CpiAccount::init_from_account_view(mint, &mut accounts[1]);
+
+    CpiAccount::init_from_account_view(authority, &mut accounts[2]);
+
+    for (account, signer) in accounts[3..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        CpiAccount::init_from_account_view(signer.as_ref(), account);
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<'account, 'multisig, 'out, MultisigSigner: AsRef<AccountView>>(
+    account: &'account AccountView,
+    mint: &'account AccountView,
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 3 + multisig_signers.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::writable(account.address()));
+
+    accounts[1].write(InstructionAccount::readonly(mint.address()));
+
+    accounts[2].write(InstructionAccount::new(
+        authority.address(),
+        false,
+        multisig_signers.is_empty(),
+    ));
+
+    for (account, signer) in accounts[3..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        account.write(InstructionAccount::readonly_signer(
+            signer.as_ref().address(),
+        ));
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    data[0].write(ExtensionDiscriminator::ConfidentialTransfer as u8);
+
+    data[1].write(DISCRIMINATOR);
+
+    Ok(DATA_LEN)
+}
@@ -0,0 +1,408 @@
+use {
+    crate::{
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer, MAX_STATIC_CPI_ACCOUNTS},
+        instructions::{
+            account_borrow_failed_error, extensions::ExtensionDiscriminator,
+            invalid_argument_error, Batch, CpiWriter, IntoBatch, MAX_MULTISIG_SIGNERS,
+        },
+        state::AeCiphertext,
+        write_bytes, Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
+    solana_account_view::AccountView,
+    solana_address::Address,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// The instruction discriminator.
+const DISCRIMINATOR: u8 = 2;
+
+/// Maximum number of accounts expected by this instruction.
+const MAX_ACCOUNTS_LEN: usize = MAX_STATIC_CPI_ACCOUNTS;
+
+/// Instruction data length:
+///   - extension discriminator (1 byte)
+///   - instruction discriminator (1 byte)
+///   - decryptable zero balance (36 bytes, AeCiphertext)
+///   - maximum pending balance credit counter (8 bytes, u64)
+///   - proof instruction offset (1 byte, i8)
+const DATA_LEN: usize = 47;
+
+/// Configure a token account for confidential transfers.
+///
+/// The token account must already include the `ConfidentialTransferAccount`
+/// extension space, and the ElGamal public key validity proof must be
+/// provided either by a `VerifyPubkeyValidity` instruction in the same
+/// transaction or by a pre-verified context state account.
+///
+/// Accounts expected by this instruction:
+///
+///   * Single owner/delegate
+///   0. `[writable]` The token account.
+///   1. `[]` The corresponding token mint.
+///   2. `..2+P` `[]` The proof accounts: the instructions sysvar or the context
+///      state account, and optionally the record account.
+///   3. `2+P` `[signer]` The account's owner.
+///
+///   * Multisignature owner/delegate
+///   0. `[writable]` The token account.
+///   1. `[]` The corresponding token mint.
+///   2. `..2+P` `[]` The proof accounts.
+///   3. `2+P` `[]` The account's multisignature owner.
+///   4. `3+P..3+P+M` `[signer]` M signer accounts.
+pub struct ConfigureAccount<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> {
+    /// The token account.
+    pub account: &'a AccountView,
+
+    /// The corresponding token mint.
+    pub mint: &'a AccountView,
+
+    /// The proof accounts, passed through to the token program.
+    pub proof_accounts: &'c [&'a AccountView],
+
+    /// The account's owner.
+    pub authority: &'a AccountView,
+
+    /// The signer accounts if the authority is a multisig.
+    pub multisig_signers: &'c [MultisigSigner],
+
+    /// The decryptable balance (always 0) once the configure account
+    /// succeeds.
+    pub decryptable_zero_balance: &'b AeCiphertext,
+
+    /// The maximum number of pending balance credits before
+    /// `ApplyPendingBalance` must be called.
+    pub maximum_pending_balance_credit_counter: u64,
+
+    /// Relative location of the `VerifyPubkeyValidity` proof instruction to
+    /// this instruction, or 0 if the proof is read from an account.
+    pub proof_instruction_offset: i8,
+
+    /// The token program.
+    pub token_program: &'b Address,
+}
+
+impl<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> ConfigureAccount<'a, 'b, 'c, MultisigSigner> {
+    pub const DISCRIMINATOR: u8 = DISCRIMINATOR;
+
+    /// Creates a new `ConfigureAccount` instruction with a single
+    /// owner/delegate authority.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(always)]
+    pub fn new(
+        token_program: &'b Address,
+        account: &'a AccountView,
+        mint: &'a AccountView,
+        proof_accounts: &'c [&'a AccountView],
+        authority: &'a AccountView,
+        decryptable_zero_balance: &'b AeCiphertext,
+        maximum_pending_balance_credit_counter: u64,
+        proof_instruction_offset: i8,
+    ) -> Self {
+        Self::with_multisig_signers(
+            token_program,
+            account,
+            mint,
+            proof_accounts,
+            authority,
+            decryptable_zero_balance,
+            maximum_pending_balance_credit_counter,
+            proof_instruction_offset,
+            &[],
+        )
+    }
+
+    /// Creates a new `ConfigureAccount` instruction with a multisignature
+    /// owner/delegate authority and signer accounts.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(always)]
+    pub fn with_multisig_signers(
+        token_program: &'b Address,
+        account: &'a AccountView,
+        mint: &'a AccountView,
+        proof_accounts: &'c [&'a AccountView],
+        authority: &'a AccountView,
+        decryptable_zero_balance: &'b AeCiphertext,
+        maximum_pending_balance_credit_counter: u64,
+        proof_instruction_offset: i8,
+        multisig_signers: &'c [MultisigSigner],
+    ) -> Self {
+        Self {
+            account,
+            mint,
+            proof_accounts,
+            authority,
+            multisig_signers,
+            decryptable_zero_balance,
+            maximum_pending_balance_credit_counter,
+            proof_instruction_offset,
+            token_program,
+        }
+    }
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.multisig_signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_signed_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+                signers,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl<MultisigSigner: AsRef<AccountView>> CpiWriter
+    for ConfigureAccount<'_, '_, '_, MultisigSigner>
+{
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(
+            self.account,
+            self.mint,
+            self.proof_accounts,
+            self.authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(
+            self.account,
+            self.mint,
+            self.proof_accounts,
+            self.authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(
+            self.decryptable_zero_balance,
+            self.maximum_pending_balance_credit_counter,
+            self.proof_instruction_offset,
+            data,
+        )
+    }
+}
+
+impl<MultisigSigner: AsRef<AccountView>> IntoBatch<Token2022Program>
+    for ConfigureAccount<'_, '_, '_, MultisigSigner>
+{
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| {
+                write_accounts(
+                    self.account,
+                    self.mint,
+                    self.proof_accounts,
+                    self.authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            |accounts| {
+                write_instruction_accounts(
+                    self.account,
+                    self.mint,
+                    self.proof_accounts,
+                    self.authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            |data| {
+                write_instruction_data(
+                    self.decryptable_zero_balance,
+                    self.maximum_pending_balance_credit_counter,
+                    self.proof_instruction_offset,
+                    data,
+                )
+            },
+        )
+    }
+}
+
+#[inline(always)]
+fn write_accounts<'account, 'proof, 'multisig, 'out, MultisigSigner: AsRef<AccountView>>(
+    account: &'account AccountView,
+    mint: &'account AccountView,
+    proof_accounts: &'proof [&'account AccountView],
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'proof: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 3 + proof_accounts.len() + multisig_signers.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if account.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
+
+    CpiAccount::init_from_account_view(account, &mut accounts[0]);
+
+    CpiAccount::init_from_account_view(mint, &mut accounts[1]);
+
+    let authority_index = 2 + proof_accounts.len();
+
+    for (account, proof_account) in accounts[2..authority_index]
+        .iter_mut()
+        .zip(proof_accounts.iter())
+    {
+        CpiAccount::init_from_account_view(proof_account, account);
+    }
+
+    CpiAccount::init_from_account_view(authority, &mut accounts[authority_index]);
+
+    for (account, signer) in accounts[authority_index + 1..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        CpiAccount::init_from_account_view(signer.as_ref(), account);
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<
+    'account,
+    'proof,
+    'multisig,
+    'out,
+    MultisigSigner: AsRef<AccountView>,
+>(
+    account: &'account AccountView,
+    mint: &'account AccountView,
+    proof_accounts: &'proof [&'account AccountView],
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'proof: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 3 + proof_accounts.len() + multisig_signers.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::writable(account.address()));
+
+    accounts[1].write(InstructionAccount::readonly(mint.address()));
+
+    let authority_index = 2 + proof_accounts.len();
+
+    for (account, proof_account) in accounts[2..authority_index]
+        .iter_mut()
+        .zip(proof_accounts.iter())
+    {
+        account.write(InstructionAccount::readonly(proof_account.address()));
+    }
+
+    accounts[authority_index].write(InstructionAccount::new(
+        authority.address(),
+        false,
+        multisig_signers.is_empty(),
+    ));
+
+    for (account, signer) in accounts[authority_index + 1..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        account.write(InstructionAccount::readonly_signer(
+            signer.as_ref().address(),
+        ));
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    decryptable_zero_balance: &AeCiphertext,
+    maximum_pending_balance_credit_counter: u64,
+    proof_instruction_offset: i8,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    data[0].write(ExtensionDiscriminator::ConfidentialTransfer as u8);
+
+    data[1].write(DISCRIMINATOR);
+
+    write_bytes(&mut data[2..38], decryptable_zero_balance);
+
+    write_bytes(
+        &mut data[38..46],
+        &maximum_pending_balance_credit_counter.to_le_bytes(),
+    );
+
+    data[46].write(proof_instruction_offset as u8);
+
+    Ok(DATA_LEN)
+}
@@ -0,0 +1,16 @@
+mod apply_pending_balance;
+mod approve_account;
+mod configure_account;
+mod deposit;
+mod disable_confidential_credits;
+mod disable_non_confidential_credits;
+mod enable_confidential_credits;
+mod enable_non_confidential_credits;
+mod transfer;
+mod withdraw;
+
+pub use {
+    apply_pending_balance::*, approve_account::*, configure_account::*, deposit::*,
+    disable_confidential_credits::*, disable_non_confidential_credits::*,
+    enable_confidential_credits::*, enable_non_confidential_credits::*, transfer::*, withdraw::*,
+};
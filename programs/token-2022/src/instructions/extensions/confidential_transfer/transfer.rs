@@ -0,0 +1,468 @@
+use {
+    crate::{
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer, MAX_STATIC_CPI_ACCOUNTS},
+        instructions::{
+            account_borrow_failed_error, extensions::ExtensionDiscriminator,
+            invalid_argument_error, Batch, CpiWriter, IntoBatch, MAX_MULTISIG_SIGNERS,
+        },
+        state::{AeCiphertext, ElGamalCiphertext},
+        write_bytes, Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
+    solana_account_view::AccountView,
+    solana_address::Address,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// The instruction discriminator.
+const DISCRIMINATOR: u8 = 7;
+
+/// Maximum number of accounts expected by this instruction.
+const MAX_ACCOUNTS_LEN: usize = MAX_STATIC_CPI_ACCOUNTS;
+
+/// Instruction data length:
+///   - extension discriminator (1 byte)
+///   - instruction discriminator (1 byte)
+///   - new source decryptable available balance (36 bytes, AeCiphertext)
+///   - transfer amount auditor ciphertext lo (64 bytes, ElGamalCiphertext)
+///   - transfer amount auditor ciphertext hi (64 bytes, ElGamalCiphertext)
+///   - equality proof instruction offset (1 byte, i8)
+///   - ciphertext validity proof instruction offset (1 byte, i8)
+///   - range proof instruction offset (1 byte, i8)
+const DATA_LEN: usize = 169;
+
+/// Transfer tokens confidentially from the available balance of a source
+/// account to the pending balance of a destination account.
+///
+/// The ciphertext-commitment equality, ciphertext validity and range proofs
+/// must be provided either by proof instructions in the same transaction or
+/// by pre-verified context state or record accounts.
+///
+/// Accounts expected by this instruction:
+///
+///   * Single owner/delegate
+///   0. `[writable]` The source token account.
+///   1. `[]` The token mint.
+///   2. `[writable]` The destination token account.
+///   3. `..3+P` `[]` The proof accounts: the instructions sysvar if at least
+///      one proof instruction is included in the same transaction, followed by
+///      the equality, ciphertext validity and range proof context state or
+///      record accounts.
+///   4. `3+P` `[signer]` The source account's owner.
+///
+///   * Multisignature owner/delegate
+///   0. `[writable]` The source token account.
+///   1. `[]` The token mint.
+///   2. `[writable]` The destination token account.
+///   3. `..3+P` `[]` The proof accounts.
+///   4. `3+P` `[]` The source account's multisignature owner.
+///   5. `4+P..4+P+M` `[signer]` M signer accounts.
+pub struct Transfer<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> {
+    /// The source token account.
+    pub source: &'a AccountView,
+
+    /// The token mint.
+    pub mint: &'a AccountView,
+
+    /// The destination token account.
+    pub destination: &'a AccountView,
+
+    /// The proof accounts, passed through to the token program.
+    pub proof_accounts: &'c [&'a AccountView],
+
+    /// The source account's owner.
+    pub authority: &'a AccountView,
+
+    /// The signer accounts if the authority is a multisig.
+    pub multisig_signers: &'c [MultisigSigner],
+
+    /// The new source decryptable balance if the transfer succeeds.
+    pub new_source_decryptable_available_balance: &'b AeCiphertext,
+
+    /// The low 16 bits of the transfer amount encrypted under the auditor
+    /// ElGamal public key.
+    pub transfer_amount_auditor_ciphertext_lo: &'b ElGamalCiphertext,
+
+    /// The high 32 bits of the transfer amount encrypted under the auditor
+    /// ElGamal public key.
+    pub transfer_amount_auditor_ciphertext_hi: &'b ElGamalCiphertext,
+
+    /// Relative location of the `VerifyCiphertextCommitmentEquality` proof
+    /// instruction to this instruction, or 0 if the proof is read from an
+    /// account.
+    pub equality_proof_instruction_offset: i8,
+
+    /// Relative location of the
+    /// `VerifyBatchedGroupedCiphertext3HandlesValidity` proof instruction to
+    /// this instruction, or 0 if the proof is read from an account.
+    pub ciphertext_validity_proof_instruction_offset: i8,
+
+    /// Relative location of the `VerifyBatchedRangeProofU128` proof
+    /// instruction to this instruction, or 0 if the proof is read from an
+    /// account.
+    pub range_proof_instruction_offset: i8,
+
+    /// The token program.
+    pub token_program: &'b Address,
+}
+
+impl<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> Transfer<'a, 'b, 'c, MultisigSigner> {
+    pub const DISCRIMINATOR: u8 = DISCRIMINATOR;
+
+    /// Creates a new `Transfer` instruction with a single
+    /// owner/delegate authority.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(always)]
+    pub fn new(
+        token_program: &'b Address,
+        source: &'a AccountView,
+        mint: &'a AccountView,
+        destination: &'a AccountView,
+        proof_accounts: &'c [&'a AccountView],
+        authority: &'a AccountView,
+        new_source_decryptable_available_balance: &'b AeCiphertext,
+        transfer_amount_auditor_ciphertext_lo: &'b ElGamalCiphertext,
+        transfer_amount_auditor_ciphertext_hi: &'b ElGamalCiphertext,
+        equality_proof_instruction_offset: i8,
+        ciphertext_validity_proof_instruction_offset: i8,
+        range_proof_instruction_offset: i8,
+    ) -> Self {
+        Self::with_multisig_signers(
+            token_program,
+            source,
+            mint,
+            destination,
+            proof_accounts,
+            authority,
+            new_source_decryptable_available_balance,
+            transfer_amount_auditor_ciphertext_lo,
+            transfer_amount_auditor_ciphertext_hi,
+            equality_proof_instruction_offset,
+            ciphertext_validity_proof_instruction_offset,
+            range_proof_instruction_offset,
+            &[],
+        )
+    }
+
+    /// Creates a new `Transfer` instruction with a multisignature
+    /// owner/delegate authority and signer accounts.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(always)]
+    pub fn with_multisig_signers(
+        token_program: &'b Address,
+        source: &'a AccountView,
+        mint: &'a AccountView,
+        destination: &'a AccountView,
+        proof_accounts: &'c [&'a AccountView],
+        authority: &'a AccountView,
+        new_source_decryptable_available_balance: &'b AeCiphertext,
+        transfer_amount_auditor_ciphertext_lo: &'b ElGamalCiphertext,
+        transfer_amount_auditor_ciphertext_hi: &'b ElGamalCiphertext,
+        equality_proof_instruction_offset: i8,
+        ciphertext_validity_proof_instruction_offset: i8,
+        range_proof_instruction_offset: i8,
+        multisig_signers: &'c [MultisigSigner],
+    ) -> Self {
+        Self {
+            source,
+            mint,
+            destination,
+            proof_accounts,
+            authority,
+            multisig_signers,
+            new_source_decryptable_available_balance,
+            transfer_amount_auditor_ciphertext_lo,
+            transfer_amount_auditor_ciphertext_hi,
+            equality_proof_instruction_offset,
+            ciphertext_validity_proof_instruction_offset,
+            range_proof_instruction_offset,
+            token_program,
+        }
+    }
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.multisig_signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_signed_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+                signers,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl<MultisigSigner: AsRef<AccountView>> CpiWriter for Transfer<'_, '_, '_, MultisigSigner> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(
+            self.source,
+            self.mint,
+            self.destination,
+            self.proof_accounts,
+            self.authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(
+            self.source,
+            self.mint,
+            self.destination,
+            self.proof_accounts,
+            self.authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(
+            self.new_source_decryptable_available_balance,
+            self.transfer_amount_auditor_ciphertext_lo,
+            self.transfer_amount_auditor_ciphertext_hi,
+            self.equality_proof_instruction_offset,
+            self.ciphertext_validity_proof_instruction_offset,
+            self.range_proof_instruction_offset,
+            data,
+        )
+    }
+}
+
+impl<MultisigSigner: AsRef<AccountView>> IntoBatch<Token2022Program>
+    for Transfer<'_, '_, '_, MultisigSigner>
+{
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| {
+                write_accounts(
+                    self.source,
+                    self.mint,
+                    self.destination,
+                    self.proof_accounts,
+                    self.authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            |accounts| {
+                write_instruction_accounts(
+                    self.source,
+                    self.mint,
+                    self.destination,
+                    self.proof_accounts,
+                    self.authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            |data| {
+                write_instruction_data(
+                    self.new_source_decryptable_available_balance,
+                    self.transfer_amount_auditor_ciphertext_lo,
+                    self.transfer_amount_auditor_ciphertext_hi,
+                    self.equality_proof_instruction_offset,
+                    self.ciphertext_validity_proof_instruction_offset,
+                    self.range_proof_instruction_offset,
+                    data,
+                )
+            },
+        )
+    }
+}
+
+#[inline(always)]
+fn write_accounts<'account, 'proof, 'multisig, 'out, MultisigSigner: AsRef<AccountView>>(
+    source: &'account AccountView,
+    mint: &'account AccountView,
+    destination: &'account AccountView,
+    proof_accounts: &'proof [&'account AccountView],
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'proof: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 4 + proof_accounts.len() + multisig_signers.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if source.is_borrowed() | destination.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
+
+    CpiAccount::init_from_account_view(source, &mut accounts[0]);
+
+    CpiAccount::init_from_account_view(mint, &mut accounts[1]);
+
+    CpiAccount::init_from_account_view(destination, &mut accounts[2]);
+
+    let authority_index = 3 + proof_accounts.len();
+
+    for (account, proof_account) in accounts[3..authority_index]
+        .iter_mut()
+        .zip(proof_accounts.iter())
+    {
+        CpiAccount::init_from_account_view(proof_account, account);
+    }
+
+    CpiAccount::init_from_account_view(authority, &mut accounts[authority_index]);
+
+    for (account, signer) in accounts[authority_index + 1..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        CpiAccount::init_from_account_view(signer.as_ref(), account);
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<
+    'account,
+    'proof,
+    'multisig,
+    'out,
+    MultisigSigner: AsRef<AccountView>,
+>(
+    source: &'account AccountView,
+    mint: &'account AccountView,
+    destination: &'account AccountView,
+    proof_accounts: &'proof [&'account AccountView],
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'proof: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 4 + proof_accounts.len() + multisig_signers.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::writable(source.address()));
+
+    accounts[1].write(InstructionAccount::readonly(mint.address()));
+
+    accounts[2].write(InstructionAccount::writable(destination.address()));
+
+    let authority_index = 3 + proof_accounts.len();
+
+    for (account, proof_account) in accounts[3..authority_index]
+        .iter_mut()
+        .zip(proof_accounts.iter())
+    {
+        account.write(InstructionAccount::readonly(proof_account.address()));
+    }
+
+    accounts[authority_index].write(InstructionAccount::new(
+        authority.address(),
+        false,
+        multisig_signers.is_empty(),
+    ));
+
+    for (account, signer) in accounts[authority_index + 1..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        account.write(InstructionAccount::readonly_signer(
+            signer.as_ref().address(),
+        ));
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    new_source_decryptable_available_balance: &AeCiphertext,
+    transfer_amount_auditor_ciphertext_lo: &ElGamalCiphertext,
+    transfer_amount_auditor_ciphertext_hi: &ElGamalCiphertext,
+    equality_proof_instruction_offset: i8,
+    ciphertext_validity_proof_instruction_offset: i8,
+    range_proof_instruction_offset: i8,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    data[0].write(ExtensionDiscriminator::ConfidentialTransfer as u8);
+
+    data[1].write(DISCRIMINATOR);
+
+    write_bytes(&mut data[2..38], new_source_decryptable_available_balance);
+
+    write_bytes(&mut data[38..102], transfer_amount_auditor_ciphertext_lo);
+
+    write_bytes(&mut data[102..166], transfer_amount_auditor_ciphertext_hi);
+
+    data[166].write(equality_proof_instruction_offset as u8);
+
+    data[167].write(ciphertext_validity_proof_instruction_offset as u8);
+
+    data[168].write(range_proof_instruction_offset as u8);
+
+    Ok(DATA_LEN)
+}
@@ -0,0 +1,431 @@
+use {
+    crate::{
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer, MAX_STATIC_CPI_ACCOUNTS},
+        instructions::{
+            account_borrow_failed_error, extensions::ExtensionDiscriminator,
+            invalid_argument_error, Batch, CpiWriter, IntoBatch, MAX_MULTISIG_SIGNERS,
+        },
+        state::AeCiphertext,
+        write_bytes, Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
+    solana_account_view::AccountView,
+    solana_address::Address,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// The instruction discriminator.
+const DISCRIMINATOR: u8 = 6;
+
+/// Maximum number of accounts expected by this instruction.
+const MAX_ACCOUNTS_LEN: usize = MAX_STATIC_CPI_ACCOUNTS;
+
+/// Instruction data length:
+///   - extension discriminator (1 byte)
+///   - instruction discriminator (1 byte)
+///   - amount (8 bytes, u64)
+///   - decimals (1 byte, u8)
+///   - new decryptable available balance (36 bytes, AeCiphertext)
+///   - equality proof instruction offset (1 byte, i8)
+///   - range proof instruction offset (1 byte, i8)
+const DATA_LEN: usize = 49;
+
+/// Withdraw tokens from the available balance of a confidential transfer
+/// account into its non-confidential balance.
+///
+/// The ciphertext-commitment equality and range proofs must be provided
+/// either by proof instructions in the same transaction or by pre-verified
+/// context state or record accounts.
+///
+/// Accounts expected by this instruction:
+///
+///   * Single owner/delegate
+///   0. `[writable]` The token account.
+///   1. `[]` The corresponding token mint.
+///   2. `..2+P` `[]` The proof accounts: the instructions sysvar if at least
+///      one proof instruction is included in the same transaction, followed by
+///      the equality and range proof context state or record accounts.
+///   3. `2+P` `[signer]` The account's owner.
+///
+///   * Multisignature owner/delegate
+///   0. `[writable]` The token account.
+///   1. `[]` The corresponding token mint.
+///   2. `..2+P` `[]` The proof accounts.
+///   3. `2+P` `[]` The account's multisignature owner.
+///   4. `3+P..3+P+M` `[signer]` M signer accounts.
+pub struct Withdraw<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> {
+    /// The token account.
+    pub account: &'a AccountView,
+
+    /// The corresponding token mint.
+    pub mint: &'a AccountView,
+
+    /// The proof accounts, passed through to the token program.
+    pub proof_accounts: &'c [&'a AccountView],
+
+    /// The account's owner.
+    pub authority: &'a AccountView,
+
+    /// The signer accounts if the authority is a multisig.
+    pub multisig_signers: &'c [MultisigSigner],
+
+    /// The amount of tokens to withdraw.
+    pub amount: u64,
+
+    /// Expected number of base 10 digits to the right of the decimal place.
+    pub decimals: u8,
+
+    /// The new decryptable balance if the withdrawal succeeds.
+    pub new_decryptable_available_balance: &'b AeCiphertext,
+
+    /// Relative location of the `VerifyCiphertextCommitmentEquality` proof
+    /// instruction to this instruction, or 0 if the proof is read from an
+    /// account.
+    pub equality_proof_instruction_offset: i8,
+
+    /// Relative location of the `VerifyBatchedRangeProofU64` proof
+    /// instruction to this instruction, or 0 if the proof is read from an
+    /// account.
+    pub range_proof_instruction_offset: i8,
+
+    /// The token program.
+    pub token_program: &'b Address,
+}
+
+impl<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> Withdraw<'a, 'b, 'c, MultisigSigner> {
+    pub const DISCRIMINATOR: u8 = DISCRIMINATOR;
+
+    /// Creates a new `Withdraw` instruction with a single
+    /// owner/delegate authority.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(always)]
+    pub fn new(
+        token_program: &'b Address,
+        account: &'a AccountView,
+        mint: &'a AccountView,
+        proof_accounts: &'c [&'a AccountView],
+        authority: &'a AccountView,
+        amount: u64,
+        decimals: u8,
+        new_decryptable_available_balance: &'b AeCiphertext,
+        equality_proof_instruction_offset: i8,
+        range_proof_instruction_offset: i8,
+    ) -> Self {
+        Self::with_multisig_signers(
+            token_program,
+            account,
+            mint,
+            proof_accounts,
+            authority,
+            amount,
+            decimals,
+            new_decryptable_available_balance,
+            equality_proof_instruction_offset,
+            range_proof_instruction_offset,
+            &[],
+        )
+    }
+
+    /// Creates a new `Withdraw` instruction with a multisignature
+    /// owner/delegate authority and signer accounts.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(always)]
+    pub fn with_multisig_signers(
+        token_program: &'b Address,
+        account: &'a AccountView,
+        mint: &'a AccountView,
+        proof_accounts: &'c [&'a AccountView],
+        authority: &'a AccountView,
+        amount: u64,
+        decimals: u8,
+        new_decryptable_available_balance: &'b AeCiphertext,
+        equality_proof_instruction_offset: i8,
+        range_proof_instruction_offset: i8,
+        multisig_signers: &'c [MultisigSigner],
+    ) -> Self {
+        Self {
+            account,
+            mint,
+            proof_accounts,
+            authority,
+            multisig_signers,
+            amount,
+            decimals,
+            new_decryptable_available_balance,
+            equality_proof_instruction_offset,
+            range_proof_instruction_offset,
+            token_program,
+        }
+    }
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.multisig_signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_signed_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+                signers,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl<MultisigSigner: AsRef<AccountView>> CpiWriter for Withdraw<'_, '_, '_, MultisigSigner> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(
+            self.account,
+            self.mint,
+            self.proof_accounts,
+            self.authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(
+            self.account,
+            self.mint,
+            self.proof_accounts,
+            self.authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(
+            self.amount,
+            self.decimals,
+            self.new_decryptable_available_balance,
+            self.equality_proof_instruction_offset,
+            self.range_proof_instruction_offset,
+            data,
+        )
+    }
+}
+
+impl<MultisigSigner: AsRef<AccountView>> IntoBatch<Token2022Program>
+    for Withdraw<'_, '_, '_, MultisigSigner>
+{
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| {
+                write_accounts(
+                    self.account,
+                    self.mint,
+                    self.proof_accounts,
+                    self.authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            |accounts| {
+                write_instruction_accounts(
+                    self.account,
+                    self.mint,
+                    self.proof_accounts,
+                    self.authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            |data| {
+                write_instruction_data(
+                    self.amount,
+                    self.decimals,
+                    self.new_decryptable_available_balance,
+                    self.equality_proof_instruction_offset,
+                    self.range_proof_instruction_offset,
+                    data,
+                )
+            },
+        )
+    }
+}
+
+#[inline(always)]
+fn write_accounts<'account, 'proof, 'multisig, 'out, MultisigSigner: AsRef<AccountView>>(
+    account: &'account AccountView,
+    mint: &'account AccountView,
+    proof_accounts: &'proof [&'account AccountView],
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'proof: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 3 + proof_accounts.len() + multisig_signers.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if account.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
+
+    CpiAccount::init_from_account_view(account, &mut accounts[0]);
+
+    CpiAccount::init_from_account_view(mint, &mut accounts[1]);
+
+    let authority_index = 2 + proof_accounts.len();
+
+    for (account, proof_account) in accounts[2..authority_index]
+        .iter_mut()
+        .zip(proof_accounts.iter())
+    {
+        CpiAccount::init_from_account_view(proof_account, account);
+    }
+
+    CpiAccount::init_from_account_view(authority, &mut accounts[authority_index]);
+
+    for (account, signer) in accounts[authority_index + 1..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        CpiAccount::init_from_account_view(signer.as_ref(), account);
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<
+    'account,
+    'proof,
+    'multisig,
+    'out,
+    MultisigSigner: AsRef<AccountView>,
+>(
+    account: &'account AccountView,
+    mint: &'account AccountView,
+    proof_accounts: &'proof [&'account AccountView],
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'proof: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 3 + proof_accounts.len() + multisig_signers.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::writable(account.address()));
+
+    accounts[1].write(InstructionAccount::readonly(mint.address()));
+
+    let authority_index = 2 + proof_accounts.len();
+
+    for (account, proof_account) in accounts[2..authority_index]
+        .iter_mut()
+        .zip(proof_accounts.iter())
+    {
+        account.write(InstructionAccount::readonly(proof_account.address()));
+    }
+
+    accounts[authority_index].write(InstructionAccount::new(
+        authority.address(),
+        false,
+        multisig_signers.is_empty(),
+    ));
+
+    for (account, signer) in accounts[authority_index + 1..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        account.write(InstructionAccount::readonly_signer(
+            signer.as_ref().address(),
+        ));
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    amount: u64,
+    decimals: u8,
+    new_decryptable_available_balance: &AeCiphertext,
+    equality_proof_instruction_offset: i8,
+    range_proof_instruction_offset: i8,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    data[0].write(ExtensionDiscriminator::ConfidentialTransfer as u8);
+
+    data[1].write(DISCRIMINATOR);
+
+    write_bytes(&mut data[2..10], &amount.to_le_bytes());
+
+    data[10].write(decimals);
+
+    write_bytes(&mut data[11..47], new_decryptable_available_balance);
+
+    data[47].write(equality_proof_instruction_offset as u8);
+
+    data[48].write(range_proof_instruction_offset as u8);
+
+    Ok(DATA_LEN)
+}
@@ -1,18 +1,32 @@
 use {
     crate::{
-        instructions::{extensions::ExtensionDiscriminator, MAX_MULTISIG_SIGNERS},
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer},
+        instructions::{
+            account_borrow_failed_error, extensions::ExtensionDiscriminator,
+            invalid_argument_error, Batch, CpiWriter, IntoBatch, MAX_MULTISIG_SIGNERS,
+        },
         state::AccountState,
+        Token2022Program, UNINIT_BYTE,
     },
     core::{mem::MaybeUninit, slice::from_raw_parts},
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{
-        cpi::{invoke_signed_with_bounds, Signer},
-        InstructionAccount, InstructionView,
-    },
+    solana_instruction_view::{InstructionAccount, InstructionView},
     solana_program_error::{ProgramError, ProgramResult},
 };
 
+/// The instruction discriminator.
+const DISCRIMINATOR: u8 = 1;
+
+/// Maximum number of accounts expected by this instruction.
+const MAX_ACCOUNTS_LEN: usize = 2 + MAX_MULTISIG_SIGNERS;
+
+/// Instruction data length:
+///   - extension discriminator (1 byte)
+///   - instruction discriminator (1 byte)
+///   - account state (1 byte, u8)
+const DATA_LEN: usize = 3;
+
 /// Update the default state for new Accounts. Only supported for mints that
 /// include the `DefaultAccountState` extension.
 ///
@@ -45,7 +59,7 @@ pub struct Update<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> {
 }
 
 impl<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> Update<'a, 'b, 'c, MultisigSigner> {
-    pub const DISCRIMINATOR: u8 = 1;
+    pub const DISCRIMINATOR: u8 = DISCRIMINATOR;
 
     /// Creates a new `Update` instruction with a single owner/delegate
     /// authority.
@@ -89,59 +103,192 @@ impl<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> Update<'a, 'b, 'c, Multisig
             return Err(ProgramError::InvalidArgument);
         }
 
-        let expected_accounts = 2 + self.multisig_signers.len();
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
 
-        // Instruction accounts.
+        let mut accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
 
-        let mut instruction_accounts =
-            [const { MaybeUninit::<InstructionAccount>::uninit() }; 2 + MAX_MULTISIG_SIGNERS];
-
-        instruction_accounts[0].write(InstructionAccount::writable(self.mint.address()));
-
-        instruction_accounts[1].write(InstructionAccount::new(
-            self.freeze_authority.address(),
-            false,
-            self.multisig_signers.is_empty(),
-        ));
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
 
-        for (account, signer) in instruction_accounts[2..]
-            .iter_mut()
-            .zip(self.multisig_signers.iter())
-        {
-            account.write(InstructionAccount::readonly_signer(
-                signer.as_ref().address(),
-            ));
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_signed_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+                signers,
+            );
         }
 
-        // Accounts.
-
-        let mut accounts =
-            [const { MaybeUninit::<&AccountView>::uninit() }; 2 + MAX_MULTISIG_SIGNERS];
+        Ok(())
+    }
+}
 
-        accounts[0].write(self.mint);
+impl<MultisigSigner: AsRef<AccountView>> CpiWriter for Update<'_, '_, '_, MultisigSigner> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(
+            self.mint,
+            self.freeze_authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
 
-        accounts[1].write(self.freeze_authority);
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(
+            self.mint,
+            self.freeze_authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
 
-        for (account, signer) in accounts[2..].iter_mut().zip(self.multisig_signers.iter()) {
-            account.write(signer.as_ref());
-        }
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(self.state, data)
+    }
+}
 
-        invoke_signed_with_bounds::<{ 2 + MAX_MULTISIG_SIGNERS }, _>(
-            &InstructionView {
-                program_id: self.token_program,
-                // SAFETY: instruction accounts has `expected_accounts` initialized.
-                accounts: unsafe {
-                    from_raw_parts(instruction_accounts.as_ptr() as _, expected_accounts)
-                },
-                data: &[
-                    ExtensionDiscriminator::DefaultAccountState as u8,
-                    Self::DISCRIMINATOR,
-                    self.state as u8,
-                ],
+impl<MultisigSigner: AsRef<AccountView>> IntoBatch<Token2022Program>
+    for Update<'_, '_, '_, MultisigSigner>
+{
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| {
+                write_accounts(
+                    self.mint,
+                    self.freeze_authority,
+                    self.multisig_signers,
+                    accounts,
+                )
             },
-            // SAFETY: accounts has `expected_accounts` initialized.
-            unsafe { from_raw_parts(accounts.as_ptr() as *const &AccountView, expected_accounts) },
-            signers,
+            |accounts| {
+                write_instruction_accounts(
+                    self.mint,
+                    self.freeze_authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            |data| write_instruction_data(self.state, data),
         )
     }
 }
+
+#[inline(always)]
+fn write_accounts<'account, 'multisig, 'out, MultisigSigner: AsRef<AccountView>>(
+    mint: &'account AccountView,
+    freeze_authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 2 + multisig_signers.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if mint.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
+
+    CpiAccount::init_from_account_view(mint, &mut accounts[0]);
+
+    CpiAccount::init_from_account_view(freeze_authority, &mut accounts[1]);
+
+    for (account, signer) in accounts[2..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        CpiAccount::init_from_account_view(signer.as_ref(), account);
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<'account, 'multisig, 'out, MultisigSigner: AsRef<AccountView>>(
+    mint: &'account AccountView,
+    freeze_authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 2 + multisig_signers.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::writable(mint.address()));
+
+    accounts[1].write(InstructionAccount::new(
+        freeze_authority.address(),
+        false,
+        multisig_signers.is_empty(),
+    ));
+
+    for (account, signer) in accounts[2..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        account.write(InstructionAccount::readonly_signer(
+            signer.as_ref().address(),
+        ));
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    state: AccountState,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    data[0].write(ExtensionDiscriminator::DefaultAccountState as u8);
+
+    data[1].write(DISCRIMINATOR);
+
+    data[2].write(state as u8);
+
+    Ok(DATA_LEN)
+}
@@ -1,12 +1,32 @@
 use {
-    crate::{instructions::extensions::ExtensionDiscriminator, write_bytes, UNINIT_BYTE},
-    core::slice::from_raw_parts,
+    crate::{
+        cpi::{invoke_unchecked, CpiAccount},
+        instructions::{
+            account_borrow_failed_error, extensions::ExtensionDiscriminator,
+            invalid_argument_error, Batch, CpiWriter, IntoBatch,
+        },
+        write_bytes, Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{cpi::invoke, InstructionAccount, InstructionView},
-    solana_program_error::ProgramResult,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
 };
 
+/// The instruction discriminator.
+const DISCRIMINATOR: u8 = 0;
+
+/// Expected number of accounts.
+const ACCOUNTS_LEN: usize = 1;
+
+/// Instruction data length:
+///   - extension discriminator (1 byte)
+///   - instruction discriminator (1 byte)
+///   - authority (32 bytes, zeroed if `None`)
+///   - member address (32 bytes, zeroed if `None`)
+const DATA_LEN: usize = 66;
+
 /// Initialize a new mint with a group member pointer
 ///
 /// Fails if the mint has already been initialized, so must be called before
@@ -34,46 +54,156 @@ pub struct Initialize<'a, 'b> {
 }
 
 impl Initialize<'_, '_> {
-    pub const DISCRIMINATOR: u8 = 0;
+    pub const DISCRIMINATOR: u8 = DISCRIMINATOR;
 
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
-        // Instruction data.
-
-        let mut instruction_data = [UNINIT_BYTE; 66];
-
-        instruction_data[0].write(ExtensionDiscriminator::GroupMemberPointer as u8);
-
-        instruction_data[1].write(Self::DISCRIMINATOR);
-
-        write_bytes(
-            &mut instruction_data[2..34],
-            if let Some(authority) = self.authority {
-                authority.as_ref()
-            } else {
-                &[0u8; 32]
-            },
-        );
-
-        write_bytes(
-            &mut instruction_data[34..66],
-            if let Some(member_address) = self.member_address {
-                member_address.as_ref()
-            } else {
-                &[0u8; 32]
-            },
-        );
-
-        invoke(
-            &InstructionView {
-                program_id: self.token_program,
-                accounts: &[InstructionAccount::writable(self.mint.address())],
-                // SAFETY: `instruction_data` is initialized.
-                data: unsafe {
-                    from_raw_parts(instruction_data.as_ptr() as _, instruction_data.len())
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
                 },
-            },
-            &[self.mint],
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl CpiWriter for Initialize<'_, '_> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(self.mint, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(self.mint, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(self.authority, self.member_address, data)
+    }
+}
+
+impl IntoBatch<Token2022Program> for Initialize<'_, '_> {
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| write_accounts(self.mint, accounts),
+            |accounts| write_instruction_accounts(self.mint, accounts),
+            |data| write_instruction_data(self.authority, self.member_address, data),
         )
     }
 }
+
+#[inline(always)]
+fn write_accounts<'account, 'out>(
+    mint: &'account AccountView,
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 1;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if mint.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
+
+    CpiAccount::init_from_account_view(mint, &mut accounts[0]);
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<'account, 'out>(
+    mint: &'account AccountView,
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 1;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::writable(mint.address()));
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    authority: Option<&Address>,
+    member_address: Option<&Address>,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    data[0].write(ExtensionDiscriminator::GroupMemberPointer as u8);
+
+    data[1].write(DISCRIMINATOR);
+
+    write_bytes(
+        &mut data[2..34],
+        if let Some(authority) = authority {
+            authority.as_ref()
+        } else {
+            &[0; 32]
+        },
+    );
+
+    write_bytes(
+        &mut data[34..66],
+        if let Some(member_address) = member_address {
+            member_address.as_ref()
+        } else {
+            &[0; 32]
+        },
+    );
+
+    Ok(DATA_LEN)
+}
@@ -1,12 +1,27 @@
 use {
-    crate::{instructions::ExtensionDiscriminator, write_bytes, UNINIT_BYTE},
-    core::slice::from_raw_parts,
+    crate::{
+        cpi::{invoke_unchecked, CpiAccount},
+        instructions::{
+            account_borrow_failed_error, extensions::ExtensionDiscriminator,
+            invalid_argument_error, Batch, CpiWriter, IntoBatch,
+        },
+        write_bytes, Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{cpi::invoke, InstructionAccount, InstructionView},
-    solana_program_error::ProgramResult,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
 };
 
+/// Expected number of accounts.
+const ACCOUNTS_LEN: usize = 1;
+
+/// Maximum instruction data length:
+///   - extension discriminator (1 byte)
+///   - close authority (1 or 33 bytes, optional Address)
+const MAX_DATA_LEN: usize = 34;
+
 /// Initialize the close account authority on a new mint.
 ///
 /// Fails if the mint has already been initialized, so must be called before
@@ -33,29 +48,140 @@ pub struct InitializeMintCloseAuthority<'a, 'b> {
 impl InitializeMintCloseAuthority<'_, '_> {
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
-        // Instruction data.
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
 
-        let mut instruction_data = [UNINIT_BYTE; 34];
-        let mut expected_data = 2;
+        let mut accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
 
-        instruction_data[0].write(ExtensionDiscriminator::MintCloseAuthority as u8);
+        let mut instruction_data = [UNINIT_BYTE; MAX_DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
 
-        if let Some(authority) = self.close_authority {
-            instruction_data[1].write(1);
-            write_bytes(&mut instruction_data[2..34], authority.as_ref());
-            expected_data += 32;
-        } else {
-            instruction_data[1].write(0);
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+            );
         }
 
-        invoke(
-            &InstructionView {
-                program_id: self.token_program,
-                accounts: &[InstructionAccount::writable(self.mint.address())],
-                // SAFETY: `instruction_data` was initialized for `expected_data` bytes.
-                data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, expected_data) },
-            },
-            &[self.mint],
+        Ok(())
+    }
+}
+
+impl CpiWriter for InitializeMintCloseAuthority<'_, '_> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(self.mint, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(self.mint, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(self.close_authority, data)
+    }
+}
+
+impl IntoBatch<Token2022Program> for InitializeMintCloseAuthority<'_, '_> {
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| write_accounts(self.mint, accounts),
+            |accounts| write_instruction_accounts(self.mint, accounts),
+            |data| write_instruction_data(self.close_authority, data),
         )
     }
 }
+
+#[inline(always)]
+fn write_accounts<'account, 'out>(
+    mint: &'account AccountView,
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 1;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if mint.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
+
+    CpiAccount::init_from_account_view(mint, &mut accounts[0]);
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<'account, 'out>(
+    mint: &'account AccountView,
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 1;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::writable(mint.address()));
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    close_authority: Option<&Address>,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < MAX_DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    data[0].write(ExtensionDiscriminator::MintCloseAuthority as u8);
+
+    if let Some(close_authority) = close_authority {
+        data[1].write(1);
+        write_bytes(&mut data[2..34], close_authority.as_ref());
+
+        Ok(34)
+    } else {
+        data[1].write(0);
+
+        Ok(2)
+    }
+}
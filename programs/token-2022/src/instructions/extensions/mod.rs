@@ -1,3 +1,4 @@
+pub mod confidential_transfer;
 pub mod cpi_guard;
 pub mod default_account_state;
 pub mod group_member_pointer;
@@ -10,6 +11,8 @@ pub mod pausable;
 pub mod permanent_delegate;
 pub mod permissioned_burn;
 pub mod scaled_ui_amount;
+pub mod token_group;
+pub mod token_metadata;
 pub mod transfer_fee;
 pub mod transfer_hook;
 
@@ -19,6 +22,7 @@ pub mod transfer_hook;
 pub enum ExtensionDiscriminator {
     MintCloseAuthority = 25,
     TransferFee = 26,
+    ConfidentialTransfer = 27,
     DefaultAccountState = 28,
     MemoTransfer = 30,
     InterestBearingMint = 33,
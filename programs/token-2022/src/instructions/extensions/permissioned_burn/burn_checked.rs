@@ -1,18 +1,32 @@
 use {
     crate::{
-        instructions::{extensions::ExtensionDiscriminator, MAX_MULTISIG_SIGNERS},
-        write_bytes, UNINIT_BYTE,
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer},
+        instructions::{
+            account_borrow_failed_error, extensions::ExtensionDiscriminator,
+            invalid_argument_error, Batch, CpiWriter, IntoBatch, MAX_MULTISIG_SIGNERS,
+        },
+        write_bytes, Token2022Program, UNINIT_BYTE,
     },
     core::{mem::MaybeUninit, slice::from_raw_parts},
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{
-        cpi::{invoke_signed_with_bounds, Signer},
-        InstructionAccount, InstructionView,
-    },
+    solana_instruction_view::{InstructionAccount, InstructionView},
     solana_program_error::{ProgramError, ProgramResult},
 };
 
+/// The instruction discriminator.
+const DISCRIMINATOR: u8 = 2;
+
+/// Maximum number of accounts expected by this instruction.
+const MAX_ACCOUNTS_LEN: usize = 4 + MAX_MULTISIG_SIGNERS;
+
+/// Instruction data length:
+///   - extension discriminator (1 byte)
+///   - instruction discriminator (1 byte)
+///   - amount (8 bytes, u64)
+///   - decimals (1 byte, u8)
+const DATA_LEN: usize = 11;
+
 /// Burn tokens with expected decimals when the mint has the permissioned
 /// burn extension enabled.
 ///
@@ -59,7 +73,7 @@ pub struct BurnChecked<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> {
 }
 
 impl<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> BurnChecked<'a, 'b, 'c, MultisigSigner> {
-    pub const DISCRIMINATOR: u8 = 2;
+    pub const DISCRIMINATOR: u8 = DISCRIMINATOR;
 
     /// Creates a new `BurnChecked` instruction with a single owner/delegate
     /// authority.
@@ -119,83 +133,220 @@ impl<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> BurnChecked<'a, 'b, 'c, Mul
     #[inline(always)]
     pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
         if self.multisig_signers.len() > MAX_MULTISIG_SIGNERS {
-            Err(ProgramError::InvalidArgument)?;
+            return Err(ProgramError::InvalidArgument);
         }
 
-        let expected_accounts = 4 + self.multisig_signers.len();
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_signed_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+                signers,
+            );
+        }
 
-        // Instruction accounts.
+        Ok(())
+    }
+}
 
-        let mut instruction_accounts =
-            [const { MaybeUninit::<InstructionAccount>::uninit() }; 4 + MAX_MULTISIG_SIGNERS];
+impl<MultisigSigner: AsRef<AccountView>> CpiWriter for BurnChecked<'_, '_, '_, MultisigSigner> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(
+            self.account,
+            self.mint,
+            self.permissioned_burn_authority,
+            self.authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
 
-        instruction_accounts[0].write(InstructionAccount::writable(self.account.address()));
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(
+            self.account,
+            self.mint,
+            self.permissioned_burn_authority,
+            self.authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
 
-        instruction_accounts[1].write(InstructionAccount::writable(self.mint.address()));
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(self.amount, self.decimals, data)
+    }
+}
 
-        instruction_accounts[2].write(InstructionAccount::readonly_signer(
-            self.permissioned_burn_authority.address(),
-        ));
+impl<MultisigSigner: AsRef<AccountView>> IntoBatch<Token2022Program>
+    for BurnChecked<'_, '_, '_, MultisigSigner>
+{
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| {
+                write_accounts(
+                    self.account,
+                    self.mint,
+                    self.permissioned_burn_authority,
+                    self.authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            |accounts| {
+                write_instruction_accounts(
+                    self.account,
+                    self.mint,
+                    self.permissioned_burn_authority,
+                    self.authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            |data| write_instruction_data(self.amount, self.decimals, data),
+        )
+    }
+}
 
-        instruction_accounts[3].write(InstructionAccount::new(
-            self.authority.address(),
-            false,
-            self.multisig_signers.is_empty(),
-        ));
+#[inline(always)]
+fn write_accounts<'account, 'multisig, 'out, MultisigSigner: AsRef<AccountView>>(
+    account: &'account AccountView,
+    mint: &'account AccountView,
+    permissioned_burn_authority: &'account AccountView,
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 4 + multisig_signers.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
 
-        for (account, signer) in instruction_accounts[4..]
-            .iter_mut()
-            .zip(self.multisig_signers.iter())
-        {
-            account.write(InstructionAccount::readonly_signer(
-                signer.as_ref().address(),
-            ));
-        }
+    if account.is_borrowed() | mint.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
 
-        // Accounts.
+    CpiAccount::init_from_account_view(account, &mut accounts[0]);
 
-        let mut accounts =
-            [const { MaybeUninit::<&AccountView>::uninit() }; 4 + MAX_MULTISIG_SIGNERS];
+    CpiAccount::init_from_account_view(mint, &mut accounts[1]);
 
-        accounts[0].write(self.account);
+    CpiAccount::init_from_account_view(permissioned_burn_authority, &mut accounts[2]);
 
-        accounts[1].write(self.mint);
+    CpiAccount::init_from_account_view(authority, &mut accounts[3]);
 
-        accounts[2].write(self.permissioned_burn_authority);
+    for (account, signer) in accounts[4..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        CpiAccount::init_from_account_view(signer.as_ref(), account);
+    }
 
-        accounts[3].write(self.authority);
+    Ok(expected_accounts)
+}
 
-        for (account, signer) in accounts[4..].iter_mut().zip(self.multisig_signers.iter()) {
-            account.write(signer.as_ref());
-        }
+#[inline(always)]
+fn write_instruction_accounts<'account, 'multisig, 'out, MultisigSigner: AsRef<AccountView>>(
+    account: &'account AccountView,
+    mint: &'account AccountView,
+    permissioned_burn_authority: &'account AccountView,
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 4 + multisig_signers.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
 
-        // Instruction data.
+    accounts[0].write(InstructionAccount::writable(account.address()));
 
-        let mut instruction_data = [UNINIT_BYTE; 11];
+    accounts[1].write(InstructionAccount::writable(mint.address()));
 
-        // discriminators
-        instruction_data[0].write(ExtensionDiscriminator::PermissionedBurn as u8);
-        instruction_data[1].write(Self::DISCRIMINATOR);
-        // amount
-        write_bytes(&mut instruction_data[2..10], &self.amount.to_le_bytes());
-        // decimals
-        instruction_data[10].write(self.decimals);
+    accounts[2].write(InstructionAccount::readonly_signer(
+        permissioned_burn_authority.address(),
+    ));
 
-        invoke_signed_with_bounds::<{ 4 + MAX_MULTISIG_SIGNERS }, _>(
-            &InstructionView {
-                program_id: self.token_program,
-                // SAFETY: instruction accounts has `expected_accounts` initialized.
-                accounts: unsafe {
-                    from_raw_parts(instruction_accounts.as_ptr() as _, expected_accounts)
-                },
-                // SAFETY: `instruction_data` is initialized.
-                data: unsafe {
-                    from_raw_parts(instruction_data.as_ptr() as _, instruction_data.len())
-                },
-            },
-            // SAFETY: accounts has `expected_accounts` initialized.
-            unsafe { from_raw_parts(accounts.as_ptr() as *const &AccountView, expected_accounts) },
-            signers,
-        )
+    accounts[3].write(InstructionAccount::new(
+        authority.address(),
+        false,
+        multisig_signers.is_empty(),
+    ));
+
+    for (account, signer) in accounts[4..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        account.write(InstructionAccount::readonly_signer(
+            signer.as_ref().address(),
+        ));
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    amount: u64,
+    decimals: u8,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
     }
+
+    data[0].write(ExtensionDiscriminator::PermissionedBurn as u8);
+
+    data[1].write(DISCRIMINATOR);
+
+    write_bytes(&mut data[2..10], &amount.to_le_bytes());
+
+    data[10].write(decimals);
+
+    Ok(DATA_LEN)
 }
@@ -0,0 +1,230 @@
+use {
+    super::DISCRIMINATOR_LEN,
+    crate::{
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer},
+        instructions::{
+            account_borrow_failed_error, invalid_argument_error, Batch, CpiWriter, IntoBatch,
+        },
+        write_bytes, Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
+    solana_account_view::AccountView,
+    solana_address::Address,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// The instruction discriminator.
+const DISCRIMINATOR: [u8; 8] = [121, 113, 108, 39, 54, 51, 0, 4];
+
+/// Expected number of accounts.
+const ACCOUNTS_LEN: usize = 3;
+
+/// Instruction data length:
+///   - discriminator (8 bytes)
+///   - update authority (32 bytes, zeroed if `None`)
+///   - max size (8 bytes, u64)
+const DATA_LEN: usize = DISCRIMINATOR_LEN + 40;
+
+/// Initialize a new token group.
+///
+/// For groups stored in the mint, the mint must include the `GroupPointer`
+/// extension pointing to itself and have enough space allocated for the
+/// `TokenGroup` extension.
+///
+/// Accounts expected by this instruction:
+///
+///   0. `[writable]` The group.
+///   1. `[]` The mint.
+///   2. `[signer]` The mint authority.
+pub struct InitializeGroup<'a, 'b> {
+    /// The group account.
+    pub group: &'a AccountView,
+
+    /// The mint.
+    pub mint: &'a AccountView,
+
+    /// The mint authority.
+    pub mint_authority: &'a AccountView,
+
+    /// The authority that can sign to update the group.
+    pub update_authority: Option<&'b Address>,
+
+    /// The maximum number of group members.
+    pub max_size: u64,
+
+    /// The token group program, i.e., the token program for groups stored in
+    /// the mint.
+    pub token_program: &'b Address,
+}
+
+impl InitializeGroup<'_, '_> {
+    pub const DISCRIMINATOR: [u8; 8] = DISCRIMINATOR;
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_signed_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+                signers,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl CpiWriter for InitializeGroup<'_, '_> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(self.group, self.mint, self.mint_authority, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(self.group, self.mint, self.mint_authority, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(self.update_authority, self.max_size, data)
+    }
+}
+
+impl IntoBatch<Token2022Program> for InitializeGroup<'_, '_> {
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| write_accounts(self.group, self.mint, self.mint_authority, accounts),
+            |accounts| {
+                write_instruction_accounts(self.group, self.mint, self.mint_authority, accounts)
+            },
+            |data| write_instruction_data(self.update_authority, self.max_size, data),
+        )
+    }
+}
+
+#[inline(always)]
+fn write_accounts<'account, 'out>(
+    group: &'account AccountView,
+    mint: &'account AccountView,
+    mint_authority: &'account AccountView,
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 3;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if group.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
+
+    CpiAccount::init_from_account_view(group, &mut accounts[0]);
+
+    CpiAccount::init_from_account_view(mint, &mut accounts[1]);
+
+    CpiAccount::init_from_account_view(mint_authority, &mut accounts[2]);
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<'account, 'out>(
+    group: &'account AccountView,
+    mint: &'account AccountView,
+    mint_authority: &'account AccountView,
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 3;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::writable(group.address()));
+
+    accounts[1].write(InstructionAccount::readonly(mint.address()));
+
+    accounts[2].write(InstructionAccount::readonly_signer(
+        mint_authority.address(),
+    ));
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    update_authority: Option<&Address>,
+    max_size: u64,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    write_bytes(&mut data[..DISCRIMINATOR_LEN], &DISCRIMINATOR);
+
+    write_bytes(
+        &mut data[DISCRIMINATOR_LEN..DISCRIMINATOR_LEN + 32],
+        if let Some(update_authority) = update_authority {
+            update_authority.as_ref()
+        } else {
+            &[0; 32]
+        },
+    );
+
+    write_bytes(
+        &mut data[DISCRIMINATOR_LEN + 32..DATA_LEN],
+        &max_size.to_le_bytes(),
+    );
+
+    Ok(DATA_LEN)
+}
@@ -0,0 +1,256 @@
+use {
+    super::DISCRIMINATOR_LEN,
+    crate::{
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer},
+        instructions::{
+            account_borrow_failed_error, invalid_argument_error, Batch, CpiWriter, IntoBatch,
+        },
+        write_bytes, Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
+    solana_account_view::AccountView,
+    solana_address::Address,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// The instruction discriminator.
+const DISCRIMINATOR: [u8; 8] = [152, 32, 222, 176, 223, 237, 116, 134];
+
+/// Expected number of accounts.
+const ACCOUNTS_LEN: usize = 5;
+
+/// Instruction data length:
+///   - discriminator (8 bytes)
+const DATA_LEN: usize = DISCRIMINATOR_LEN;
+
+/// Initialize a new member of a token group.
+///
+/// For members stored in the mint, the member mint must include the
+/// `GroupMemberPointer` extension pointing to itself and have enough space
+/// allocated for the `TokenGroupMember` extension.
+///
+/// Accounts expected by this instruction:
+///
+///   0. `[writable]` The member.
+///   1. `[]` The member mint.
+///   2. `[signer]` The member mint authority.
+///   3. `[writable]` The group.
+///   4. `[signer]` The group update authority.
+pub struct InitializeMember<'a, 'b> {
+    /// The member account.
+    pub member: &'a AccountView,
+
+    /// The member mint.
+    pub member_mint: &'a AccountView,
+
+    /// The member mint authority.
+    pub member_mint_authority: &'a AccountView,
+
+    /// The group account.
+    pub group: &'a AccountView,
+
+    /// The group update authority.
+    pub group_update_authority: &'a AccountView,
+
+    /// The token group program, i.e., the token program for members stored in
+    /// the mint.
+    pub token_program: &'b Address,
+}
+
+impl InitializeMember<'_, '_> {
+    pub const DISCRIMINATOR: [u8; 8] = DISCRIMINATOR;
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_signed_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+                signers,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl CpiWriter for InitializeMember<'_, '_> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(
+            self.member,
+            self.member_mint,
+            self.member_mint_authority,
+            self.group,
+            self.group_update_authority,
+            accounts,
+        )
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(
+            self.member,
+            self.member_mint,
+            self.member_mint_authority,
+            self.group,
+            self.group_update_authority,
+            accounts,
+        )
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(data)
+    }
+}
+
+impl IntoBatch<Token2022Program> for InitializeMember<'_, '_> {
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| {
+                write_accounts(
+                    self.member,
+                    self.member_mint,
+                    self.member_mint_authority,
+                    self.group,
+                    self.group_update_authority,
+                    accounts,
+                )
+            },
+            |accounts| {
+                write_instruction_accounts(
+                    self.member,
+                    self.member_mint,
+                    self.member_mint_authority,
+                    self.group,
+                    self.group_update_authority,
+                    accounts,
+                )
+            },
+            write_instruction_data,
+        )
+    }
+}
+
+#[inline(always)]
+fn write_accounts<'account, 'out>(
+    member: &'account AccountView,
+    member_mint: &'account AccountView,
+    member_mint_authority: &'account AccountView,
+    group: &'account AccountView,
+    group_update_authority: &'account AccountView,
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 5;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if member.is_borrowed() | group.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
+
+    CpiAccount::init_from_account_view(member, &mut accounts[0]);
+
+    CpiAccount::init_from_account_view(member_mint, &mut accounts[1]);
+
+    CpiAccount::init_from_account_view(member_mint_authority, &mut accounts[2]);
+
+    CpiAccount::init_from_account_view(group, &mut accounts[3]);
+
+    CpiAccount::init_from_account_view(group_update_authority, &mut accounts[4]);
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<'account, 'out>(
+    member: &'account AccountView,
+    member_mint: &'account AccountView,
+    member_mint_authority: &'account AccountView,
+    group: &'account AccountView,
+    group_update_authority: &'account AccountView,
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 5;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::writable(member.address()));
+
+    accounts[1].write(InstructionAccount::readonly(member_mint.address()));
+
+    accounts[2].write(InstructionAccount::readonly_signer(
+        member_mint_authority.address(),
+    ));
+
+    accounts[3].write(InstructionAccount::writable(group.address()));
+
+    accounts[4].write(InstructionAccount::readonly_signer(
+        group_update_authority.address(),
+    ));
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    write_bytes(&mut data[..DISCRIMINATOR_LEN], &DISCRIMINATOR);
+
+    Ok(DATA_LEN)
+}
@@ -0,0 +1,14 @@
+//! Instructions of the `spl-token-group-interface`, implemented by the token
+//! program for groups and members stored in the mint.
+
+mod initialize_group;
+mod initialize_member;
+mod update_group_authority;
+mod update_group_max_size;
+
+pub use {
+    initialize_group::*, initialize_member::*, update_group_authority::*, update_group_max_size::*,
+};
+
+/// Length of the token group instruction discriminators.
+const DISCRIMINATOR_LEN: usize = 8;
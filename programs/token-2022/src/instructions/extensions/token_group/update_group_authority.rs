@@ -0,0 +1,204 @@
+use {
+    super::DISCRIMINATOR_LEN,
+    crate::{
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer},
+        instructions::{
+            account_borrow_failed_error, invalid_argument_error, Batch, CpiWriter, IntoBatch,
+        },
+        write_bytes, Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
+    solana_account_view::AccountView,
+    solana_address::Address,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// The instruction discriminator.
+const DISCRIMINATOR: [u8; 8] = [161, 105, 88, 1, 237, 221, 216, 203];
+
+/// Expected number of accounts.
+const ACCOUNTS_LEN: usize = 2;
+
+/// Instruction data length:
+///   - discriminator (8 bytes)
+///   - new authority (32 bytes, zeroed if `None`)
+const DATA_LEN: usize = DISCRIMINATOR_LEN + 32;
+
+/// Update the token group authority.
+///
+/// Accounts expected by this instruction:
+///
+///   0. `[writable]` The group.
+///   1. `[signer]` The current update authority.
+pub struct UpdateGroupAuthority<'a, 'b> {
+    /// The group account.
+    pub group: &'a AccountView,
+
+    /// The current update authority.
+    pub update_authority: &'a AccountView,
+
+    /// The new authority, or `None` to make the group immutable.
+    pub new_authority: Option<&'b Address>,
+
+    /// The token group program, i.e., the token program for groups stored in
+    /// the mint.
+    pub token_program: &'b Address,
+}
+
+impl UpdateGroupAuthority<'_, '_> {
+    pub const DISCRIMINATOR: [u8; 8] = DISCRIMINATOR;
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_signed_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+                signers,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl CpiWriter for UpdateGroupAuthority<'_, '_> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(self.group, self.update_authority, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(self.group, self.update_authority, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(self.new_authority, data)
+    }
+}
+
+impl IntoBatch<Token2022Program> for UpdateGroupAuthority<'_, '_> {
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| write_accounts(self.group, self.update_authority, accounts),
+            |accounts| write_instruction_accounts(self.group, self.update_authority, accounts),
+            |data| write_instruction_data(self.new_authority, data),
+        )
+    }
+}
+
+#[inline(always)]
+fn write_accounts<'account, 'out>(
+    group: &'account AccountView,
+    update_authority: &'account AccountView,
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 2;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if group.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
+
+    CpiAccount::init_from_account_view(group, &mut accounts[0]);
+
+    CpiAccount::init_from_account_view(update_authority, &mut accounts[1]);
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<'account, 'out>(
+    group: &'account AccountView,
+    update_authority: &'account AccountView,
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 2;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::writable(group.address()));
+
+    accounts[1].write(InstructionAccount::readonly_signer(
+        update_authority.address(),
+    ));
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    new_authority: Option<&Address>,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    write_bytes(&mut data[..DISCRIMINATOR_LEN], &DISCRIMINATOR);
+
+    write_bytes(
+        &mut data[DISCRIMINATOR_LEN..DATA_LEN],
+        if let Some(new_authority) = new_authority {
+            new_authority.as_ref()
+        } else {
+            &[0; 32]
+        },
+    );
+
+    Ok(DATA_LEN)
+}
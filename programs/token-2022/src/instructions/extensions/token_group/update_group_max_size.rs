@@ -0,0 +1,202 @@
+use {
+    super::DISCRIMINATOR_LEN,
+    crate::{
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer},
+        instructions::{
+            account_borrow_failed_error, invalid_argument_error, Batch, CpiWriter, IntoBatch,
+        },
+        write_bytes, Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
+    solana_account_view::AccountView,
+    solana_address::Address,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// The instruction discriminator.
+const DISCRIMINATOR: [u8; 8] = [108, 37, 171, 143, 248, 30, 18, 110];
+
+/// Expected number of accounts.
+const ACCOUNTS_LEN: usize = 2;
+
+/// Instruction data length:
+///   - discriminator (8 bytes)
+///   - max size (8 bytes, u64)
+const DATA_LEN: usize = DISCRIMINATOR_LEN + 8;
+
+/// Update the maximum number of members of a token group.
+///
+/// Fails if the new maximum size is lower than the current group size.
+///
+/// Accounts expected by this instruction:
+///
+///   0. `[writable]` The group.
+///   1. `[signer]` The update authority.
+pub struct UpdateGroupMaxSize<'a, 'b> {
+    /// The group account.
+    pub group: &'a AccountView,
+
+    /// The update authority.
+    pub update_authority: &'a AccountView,
+
+    /// The new maximum number of group members.
+    pub max_size: u64,
+
+    /// The token group program, i.e., the token program for groups stored in
+    /// the mint.
+    pub token_program: &'b Address,
+}
+
+impl UpdateGroupMaxSize<'_, '_> {
+    pub const DISCRIMINATOR: [u8; 8] = DISCRIMINATOR;
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_signed_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+                signers,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl CpiWriter for UpdateGroupMaxSize<'_, '_> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(self.group, self.update_authority, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(self.group, self.update_authority, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(self.max_size, data)
+    }
+}
+
+impl IntoBatch<Token2022Program> for UpdateGroupMaxSize<'_, '_> {
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| write_accounts(self.group, self.update_authority, accounts),
+            |accounts| write_instruction_accounts(self.group, self.update_authority, accounts),
+            |data| write_instruction_data(self.max_size, data),
+        )
+    }
+}
+
+#[inline(always)]
+fn write_accounts<'account, 'out>(
+    group: &'account AccountView,
+    update_authority: &'account AccountView,
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 2;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if group.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
+
+    CpiAccount::init_from_account_view(group, &mut accounts[0]);
+
+    CpiAccount::init_from_account_view(update_authority, &mut accounts[1]);
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<'account, 'out>(
+    group: &'account AccountView,
+    update_authority: &'account AccountView,
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 2;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::writable(group.address()));
+
+    accounts[1].write(InstructionAccount::readonly_signer(
+        update_authority.address(),
+    ));
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    max_size: u64,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    write_bytes(&mut data[..DISCRIMINATOR_LEN], &DISCRIMINATOR);
+
+    write_bytes(
+        &mut data[DISCRIMINATOR_LEN..DATA_LEN],
+        &max_size.to_le_bytes(),
+    );
+
+    Ok(DATA_LEN)
+}
@@ -0,0 +1,222 @@
+use {
+    super::DISCRIMINATOR_LEN,
+    crate::{
+        cpi::{self, invoke_unchecked, CpiAccount},
+        instructions::{invalid_argument_error, Batch, CpiWriter, IntoBatch},
+        write_bytes, Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
+    solana_account_view::AccountView,
+    solana_address::Address,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// The instruction discriminator.
+const DISCRIMINATOR: [u8; 8] = [250, 166, 180, 250, 13, 12, 184, 70];
+
+/// Expected number of accounts.
+const ACCOUNTS_LEN: usize = 1;
+
+/// Maximum instruction data length:
+///   - discriminator (8 bytes)
+///   - start (1 or 9 bytes, Option<u64>)
+///   - end (1 or 9 bytes, Option<u64>)
+const MAX_DATA_LEN: usize = DISCRIMINATOR_LEN + 18;
+
+/// Emit the token metadata as return data.
+///
+/// The return data is the borsh-encoded `TokenMetadata`, restricted to the
+/// `start..end` byte range if provided. The complete metadata can be decoded
+/// with `TokenMetadataExtension::from_bytes`.
+///
+/// Accounts expected by this instruction:
+///
+///   0. `[]` The metadata.
+pub struct Emit<'a, 'b> {
+    /// The metadata account.
+    pub metadata: &'a AccountView,
+
+    /// Start of the range of data to emit.
+    pub start: Option<u64>,
+
+    /// End of the range of data to emit.
+    pub end: Option<u64>,
+
+    /// The token metadata program, i.e., the token program for metadata
+    /// stored in the mint.
+    pub token_program: &'b Address,
+}
+
+impl Emit<'_, '_> {
+    pub const DISCRIMINATOR: [u8; 8] = DISCRIMINATOR;
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; MAX_DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Invokes the instruction and returns the emitted metadata, copied into
+    /// `buffer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::IncorrectProgramId`] if the return data was not
+    /// set by `token_program`, or [`ProgramError::InvalidArgument`] if the
+    /// return data does not fit in `buffer`.
+    #[inline(always)]
+    pub fn invoke_and_get_metadata<'buffer>(
+        &self,
+        buffer: &'buffer mut [u8],
+    ) -> Result<&'buffer [u8], ProgramError> {
+        self.invoke()?;
+
+        match cpi::get_return_data() {
+            Some(return_data) if return_data.program_id() == self.token_program => {
+                let metadata = return_data.as_slice();
+                let buffer = buffer
+                    .get_mut(..metadata.len())
+                    .ok_or(ProgramError::InvalidArgument)?;
+                buffer.copy_from_slice(metadata);
+
+                Ok(buffer)
+            }
+            _ => Err(ProgramError::IncorrectProgramId),
+        }
+    }
+}
+
+impl CpiWriter for Emit<'_, '_> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(self.metadata, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(self.metadata, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(self.start, self.end, data)
+    }
+}
+
+impl IntoBatch<Token2022Program> for Emit<'_, '_> {
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| write_accounts(self.metadata, accounts),
+            |accounts| write_instruction_accounts(self.metadata, accounts),
+            |data| write_instruction_data(self.start, self.end, data),
+        )
+    }
+}
+
+#[inline(always)]
+fn write_accounts<'account, 'out>(
+    metadata: &'account AccountView,
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 1;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    CpiAccount::init_from_account_view(metadata, &mut accounts[0]);
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<'account, 'out>(
+    metadata: &'account AccountView,
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 1;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::readonly(metadata.address()));
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    start: Option<u64>,
+    end: Option<u64>,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < MAX_DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    write_bytes(&mut data[..DISCRIMINATOR_LEN], &DISCRIMINATOR);
+
+    let mut offset = DISCRIMINATOR_LEN;
+
+    for value in [start, end] {
+        if let Some(value) = value {
+            data[offset].write(1);
+            write_bytes(&mut data[offset + 1..offset + 9], &value.to_le_bytes());
+            offset += 9;
+        } else {
+            data[offset].write(0);
+            offset += 1;
+        }
+    }
+
+    Ok(offset)
+}
@@ -0,0 +1,139 @@
+//! Instructions of the `spl-token-metadata-interface`, implemented by the
+//! token program for metadata stored in the mint.
+
+mod emit;
+mod initialize;
+mod remove_key;
+mod update_authority;
+mod update_field;
+
+use {crate::write_bytes, core::mem::MaybeUninit, solana_program_error::ProgramError};
+pub use {emit::*, initialize::*, remove_key::*, update_authority::*, update_field::*};
+
+/// Maximum instruction data length of the variable-length token metadata
+/// instructions, bounded by the transaction size.
+pub const MAX_DATA_LEN: usize = 1_232;
+
+/// Length of the token metadata instruction discriminators.
+const DISCRIMINATOR_LEN: usize = 8;
+
+/// A token metadata field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field<'a> {
+    /// The name field, `TokenMetadata::name`.
+    Name,
+
+    /// The symbol field, `TokenMetadata::symbol`.
+    Symbol,
+
+    /// The uri field, `TokenMetadata::uri`.
+    Uri,
+
+    /// A user field, stored as a key in `TokenMetadata::additional_metadata`.
+    Key(&'a str),
+}
+
+/// Return the part of the batch instruction `data` available to an
+/// instruction, since the batch header stores its data length in one byte.
+///
+/// Writing a variable-length instruction that does not fit into it fails
+/// with [`ProgramError::InvalidArgument`] before the batch is modified.
+#[inline(always)]
+fn batch_data(data: &mut [MaybeUninit<u8>]) -> &mut [MaybeUninit<u8>] {
+    let len = data.len().min(u8::MAX as usize);
+    &mut data[..len]
+}
+
+/// Write a borsh-encoded string at `offset`, returning the offset after it.
+///
+/// Returns [`ProgramError::InvalidArgument`] if the string does not fit in
+/// `data`.
+#[inline(always)]
+fn write_str(
+    data: &mut [MaybeUninit<u8>],
+    offset: usize,
+    value: &str,
+) -> Result<usize, ProgramError> {
+    let end = offset
+        .checked_add(4 + value.len())
+        .filter(|end| *end <= data.len())
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    write_bytes(
+        &mut data[offset..offset + 4],
+        &(value.len() as u32).to_le_bytes(),
+    );
+    write_bytes(&mut data[offset + 4..end], value.as_bytes());
+
+    Ok(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::instructions::{Batch, IntoBatch},
+        core::str::from_utf8,
+        pinocchio::{
+            test_utils::{ProgramInput, TestAccount},
+            SUCCESS,
+        },
+        solana_address::Address,
+    };
+
+    #[test]
+    fn batch_rejects_instruction_data_longer_than_u8() {
+        let mut input = ProgramInput::new(
+            &Address::default(),
+            &[
+                TestAccount::new(Address::new_from_array([1; 32]), crate::ID, 1, &[])
+                    .writable()
+                    .into(),
+                TestAccount::new(Address::new_from_array([2; 32]), Address::default(), 1, &[])
+                    .signer()
+                    .into(),
+            ],
+            &[],
+        );
+
+        let result = input.process(|_, accounts, _| {
+            let mut data = [crate::UNINIT_BYTE; MAX_DATA_LEN];
+            let mut instruction_accounts = [const { MaybeUninit::uninit() }; 4];
+            let mut cpi_accounts = [const { MaybeUninit::uninit() }; 4];
+            let mut batch = Batch::new(&mut data, &mut instruction_accounts, &mut cpi_accounts)?;
+
+            // Discriminator (8 bytes), field (1 byte) and value length (4 bytes).
+            let value = [b'a'; u8::MAX as usize - 13];
+            let value = from_utf8(&value).unwrap();
+            let long_value = [b'a'; u8::MAX as usize - 12];
+            let long_value = from_utf8(&long_value).unwrap();
+
+            assert_eq!(
+                UpdateField {
+                    metadata: &accounts[0],
+                    update_authority: &accounts[1],
+                    field: Field::Uri,
+                    value: long_value,
+                    token_program: &crate::ID,
+                }
+                .into_batch(&mut batch),
+                Err(ProgramError::InvalidArgument)
+            );
+            assert_eq!(
+                UpdateField {
+                    metadata: &accounts[0],
+                    update_authority: &accounts[1],
+                    field: Field::Uri,
+                    value,
+                    token_program: &crate::ID,
+                }
+                .into_batch(&mut batch),
+                Ok(())
+            );
+
+            Ok(())
+        });
+
+        assert_eq!(result, SUCCESS);
+    }
+}
@@ -0,0 +1,220 @@
+use {
+    super::{batch_data, write_str, Field, DISCRIMINATOR_LEN, MAX_DATA_LEN},
+    crate::{
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer},
+        instructions::{
+            account_borrow_failed_error, invalid_argument_error, Batch, CpiWriter, IntoBatch,
+        },
+        write_bytes, Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
+    solana_account_view::AccountView,
+    solana_address::Address,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// The instruction discriminator.
+const DISCRIMINATOR: [u8; 8] = [221, 233, 49, 45, 181, 202, 220, 200];
+
+/// Expected number of accounts.
+const ACCOUNTS_LEN: usize = 2;
+
+/// Update a field in the token metadata.
+///
+/// If the field does not exist on the metadata, it is added as an additional
+/// metadata key. The metadata account must have enough space and lamports
+/// for the updated metadata.
+///
+/// In a batch, the instruction data is limited to 255 bytes by the batch
+/// header, so longer strings fail with `ProgramError::InvalidArgument`.
+///
+/// Accounts expected by this instruction:
+///
+///   0. `[writable]` The metadata.
+///   1. `[signer]` The update authority.
+pub struct UpdateField<'a, 'b> {
+    /// The metadata account.
+    pub metadata: &'a AccountView,
+
+    /// The update authority.
+    pub update_authority: &'a AccountView,
+
+    /// The field to update.
+    pub field: Field<'b>,
+
+    /// The value of the field.
+    pub value: &'b str,
+
+    /// The token metadata program, i.e., the token program for metadata
+    /// stored in the mint.
+    pub token_program: &'b Address,
+}
+
+impl UpdateField<'_, '_> {
+    pub const DISCRIMINATOR: [u8; 8] = DISCRIMINATOR;
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; MAX_DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_signed_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+                signers,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl CpiWriter for UpdateField<'_, '_> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(self.metadata, self.update_authority, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(self.metadata, self.update_authority, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(self.field, self.value, data)
+    }
+}
+
+impl IntoBatch<Token2022Program> for UpdateField<'_, '_> {
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| write_accounts(self.metadata, self.update_authority, accounts),
+            |accounts| write_instruction_accounts(self.metadata, self.update_authority, accounts),
+            |data| write_instruction_data(self.field, self.value, batch_data(data)),
+        )
+    }
+}
+
+#[inline(always)]
+fn write_accounts<'account, 'out>(
+    metadata: &'account AccountView,
+    update_authority: &'account AccountView,
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 2;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if metadata.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
+
+    CpiAccount::init_from_account_view(metadata, &mut accounts[0]);
+
+    CpiAccount::init_from_account_view(update_authority, &mut accounts[1]);
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<'account, 'out>(
+    metadata: &'account AccountView,
+    update_authority: &'account AccountView,
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 2;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::writable(metadata.address()));
+
+    accounts[1].write(InstructionAccount::readonly_signer(
+        update_authority.address(),
+    ));
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    field: Field,
+    value: &str,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < DISCRIMINATOR_LEN + 1 {
+        return Err(invalid_argument_error());
+    }
+
+    write_bytes(&mut data[..DISCRIMINATOR_LEN], &DISCRIMINATOR);
+
+    let offset = match field {
+        Field::Name => {
+            data[DISCRIMINATOR_LEN].write(0);
+            DISCRIMINATOR_LEN + 1
+        }
+        Field::Symbol => {
+            data[DISCRIMINATOR_LEN].write(1);
+            DISCRIMINATOR_LEN + 1
+        }
+        Field::Uri => {
+            data[DISCRIMINATOR_LEN].write(2);
+            DISCRIMINATOR_LEN + 1
+        }
+        Field::Key(key) => {
+            data[DISCRIMINATOR_LEN].write(3);
+            write_str(data, DISCRIMINATOR_LEN + 1, key)?
+        }
+    };
+
+    write_str(data, offset, value)
+}
@@ -1,15 +1,30 @@
 use {
-    crate::instructions::ExtensionDiscriminator,
+    crate::{
+        cpi::{invoke_unchecked, CpiAccount, MAX_STATIC_CPI_ACCOUNTS},
+        instructions::{
+            account_borrow_failed_error, extensions::ExtensionDiscriminator,
+            invalid_argument_error, Batch, CpiWriter, IntoBatch,
+        },
+        Token2022Program, UNINIT_BYTE,
+    },
     core::{mem::MaybeUninit, slice::from_raw_parts},
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{
-        cpi::{invoke_with_bounds, MAX_STATIC_CPI_ACCOUNTS},
-        InstructionAccount, InstructionView,
-    },
+    solana_instruction_view::{InstructionAccount, InstructionView},
     solana_program_error::{ProgramError, ProgramResult},
 };
 
+/// The instruction discriminator.
+const DISCRIMINATOR: u8 = 4;
+
+/// Maximum number of accounts expected by this instruction.
+const MAX_ACCOUNTS_LEN: usize = MAX_STATIC_CPI_ACCOUNTS;
+
+/// Instruction data length:
+///   - extension discriminator (1 byte)
+///   - instruction discriminator (1 byte)
+const DATA_LEN: usize = 2;
+
 /// Permissionless instruction to transfer all withheld tokens to the mint.
 ///
 /// Succeeds for frozen accounts.
@@ -33,55 +48,154 @@ pub struct HarvestWithheldTokensToMint<'a, 'b, 'c, Source: AsRef<AccountView>> {
 }
 
 impl<Source: AsRef<AccountView>> HarvestWithheldTokensToMint<'_, '_, '_, Source> {
-    pub const DISCRIMINATOR: u8 = 4;
+    pub const DISCRIMINATOR: u8 = DISCRIMINATOR;
 
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
-        let expected_accounts = 1 + self.sources.len();
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
 
-        if expected_accounts > MAX_STATIC_CPI_ACCOUNTS {
-            return Err(ProgramError::InvalidArgument);
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+            );
         }
 
-        // Instruction accounts.
+        Ok(())
+    }
+}
 
-        let mut instruction_accounts =
-            [const { MaybeUninit::<InstructionAccount>::uninit() }; MAX_STATIC_CPI_ACCOUNTS];
+impl<Source: AsRef<AccountView>> CpiWriter for HarvestWithheldTokensToMint<'_, '_, '_, Source> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(self.mint, self.sources, accounts)
+    }
 
-        instruction_accounts[0].write(InstructionAccount::writable(self.mint.address()));
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(self.mint, self.sources, accounts)
+    }
 
-        for (instruction_account, source) in instruction_accounts[1..]
-            .iter_mut()
-            .zip(self.sources.iter())
-        {
-            instruction_account.write(InstructionAccount::writable(source.as_ref().address()));
-        }
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(data)
+    }
+}
 
-        // Accounts.
+impl<Source: AsRef<AccountView>> IntoBatch<Token2022Program>
+    for HarvestWithheldTokensToMint<'_, '_, '_, Source>
+{
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| write_accounts(self.mint, self.sources, accounts),
+            |accounts| write_instruction_accounts(self.mint, self.sources, accounts),
+            write_instruction_data,
+        )
+    }
+}
 
-        let mut accounts =
-            [const { MaybeUninit::<&AccountView>::uninit() }; MAX_STATIC_CPI_ACCOUNTS];
+#[inline(always)]
+fn write_accounts<'account, 'sources, 'out, Source: AsRef<AccountView>>(
+    mint: &'account AccountView,
+    sources: &'sources [Source],
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'sources: 'out,
+{
+    let expected_accounts = 1 + sources.len();
 
-        accounts[0].write(self.mint);
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
 
-        for (account, source) in accounts[1..].iter_mut().zip(self.sources.iter()) {
-            account.write(source.as_ref());
-        }
+    if mint.is_borrowed() | sources.iter().any(|source| source.as_ref().is_borrowed()) {
+        return Err(account_borrow_failed_error());
+    }
 
-        invoke_with_bounds::<MAX_STATIC_CPI_ACCOUNTS, _>(
-            &InstructionView {
-                program_id: self.token_program,
-                // SAFETY: instruction accounts has `expected_accounts` initialized.
-                accounts: unsafe {
-                    from_raw_parts(instruction_accounts.as_ptr() as _, expected_accounts)
-                },
-                data: &[
-                    ExtensionDiscriminator::TransferFee as u8,
-                    Self::DISCRIMINATOR,
-                ],
-            },
-            // SAFETY: accounts has `expected_accounts` initialized.
-            unsafe { from_raw_parts(accounts.as_ptr() as *const &AccountView, expected_accounts) },
-        )
+    CpiAccount::init_from_account_view(mint, &mut accounts[0]);
+
+    for (account, source) in accounts[1..expected_accounts]
+        .iter_mut()
+        .zip(sources.iter())
+    {
+        CpiAccount::init_from_account_view(source.as_ref(), account);
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<'account, 'sources, 'out, Source: AsRef<AccountView>>(
+    mint: &'account AccountView,
+    sources: &'sources [Source],
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'sources: 'out,
+{
+    let expected_accounts = 1 + sources.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
     }
+
+    accounts[0].write(InstructionAccount::writable(mint.address()));
+
+    for (account, source) in accounts[1..expected_accounts]
+        .iter_mut()
+        .zip(sources.iter())
+    {
+        account.write(InstructionAccount::writable(source.as_ref().address()));
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    data[0].write(ExtensionDiscriminator::TransferFee as u8);
+
+    data[1].write(DISCRIMINATOR);
+
+    Ok(DATA_LEN)
 }
@@ -1,12 +1,34 @@
 use {
-    crate::{instructions::ExtensionDiscriminator, write_bytes, UNINIT_BYTE},
-    core::slice::from_raw_parts,
+    crate::{
+        cpi::{invoke_unchecked, CpiAccount},
+        instructions::{
+            account_borrow_failed_error, extensions::ExtensionDiscriminator,
+            invalid_argument_error, Batch, CpiWriter, IntoBatch,
+        },
+        write_bytes, Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{cpi::invoke, InstructionAccount, InstructionView},
-    solana_program_error::ProgramResult,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
 };
 
+/// The instruction discriminator.
+const DISCRIMINATOR: u8 = 0;
+
+/// Expected number of accounts.
+const ACCOUNTS_LEN: usize = 1;
+
+/// Maximum instruction data length:
+///   - extension discriminator (1 byte)
+///   - instruction discriminator (1 byte)
+///   - transfer fee config authority (1 or 33 bytes, optional Address)
+///   - withdraw withheld authority (1 or 33 bytes, optional Address)
+///   - transfer fee basis points (2 bytes, u16)
+///   - maximum fee (8 bytes, u64)
+const MAX_DATA_LEN: usize = 78;
+
 /// Initialize the transfer fee on a new mint.
 ///
 /// Fails if the mint has already been initialized, so must be called before
@@ -42,71 +64,228 @@ pub struct InitializeTransferFeeConfig<'a, 'b> {
 
 impl InitializeTransferFeeConfig<'_, '_> {
     /// Instruction discriminator.
-    pub const DISCRIMINATOR: u8 = 0;
+    pub const DISCRIMINATOR: u8 = DISCRIMINATOR;
 
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
-        // Instruction data.
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
 
-        let mut instruction_data = [UNINIT_BYTE; 78];
-        // At the start, data length is 2 bytes for the instruction and extension
-        // discriminators, plus 1 byte for the optional transfer fee authority.
-        let mut data_len = 2 + 1;
+        let mut accounts = [const { MaybeUninit::uninit() }; ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
 
-        instruction_data[0].write(ExtensionDiscriminator::TransferFee as u8);
+        let mut instruction_data = [UNINIT_BYTE; MAX_DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
 
-        instruction_data[1].write(Self::DISCRIMINATOR);
-
-        if let Some(authority) = self.transfer_fee_config_authority {
-            instruction_data[2].write(1);
-            write_bytes(&mut instruction_data[3..35], authority.as_ref());
-            // Add 32 bytes for the authority.
-            data_len += size_of::<Address>();
-        } else {
-            instruction_data[2].write(0);
-        }
-        if let Some(authority) = self.withdraw_withheld_authority {
-            // SAFETY: `instruction_data` is allocated to the maximum expected length.
-            unsafe {
-                instruction_data.get_unchecked_mut(data_len).write(1);
-                write_bytes(
-                    instruction_data.get_unchecked_mut(data_len + 1..),
-                    authority.as_ref(),
-                );
-            }
-            data_len += 1 + size_of::<Address>();
-        } else {
-            // SAFETY: `instruction_data` is allocated to the maximum expected length.
-            unsafe { instruction_data.get_unchecked_mut(data_len).write(0) };
-            data_len += 1;
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+            );
         }
 
-        // SAFETY: `instruction_data` is allocated to the maximum expected length.
-        unsafe {
+        Ok(())
+    }
+}
+
+impl CpiWriter for InitializeTransferFeeConfig<'_, '_> {
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(self.mint, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(self.mint, accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(
+            self.transfer_fee_config_authority,
+            self.withdraw_withheld_authority,
+            self.transfer_fee_basis_points,
+            self.maximum_fee,
+            data,
+        )
+    }
+}
+
+impl IntoBatch<Token2022Program> for InitializeTransferFeeConfig<'_, '_> {
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| write_accounts(self.mint, accounts),
+            |accounts| write_instruction_accounts(self.mint, accounts),
+            |data| {
+                write_instruction_data(
+                    self.transfer_fee_config_authority,
+                    self.withdraw_withheld_authority,
+                    self.transfer_fee_basis_points,
+                    self.maximum_fee,
+                    data,
+                )
+            },
+        )
+    }
+}
+
+#[inline(always)]
+fn write_accounts<'account, 'out>(
+    mint: &'account AccountView,
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 1;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if mint.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
+
+    CpiAccount::init_from_account_view(mint, &mut accounts[0]);
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<'account, 'out>(
+    mint: &'account AccountView,
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = 1;
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    accounts[0].write(InstructionAccount::writable(mint.address()));
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    transfer_fee_config_authority: Option<&Address>,
+    withdraw_withheld_authority: Option<&Address>,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < MAX_DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    data[0].write(ExtensionDiscriminator::TransferFee as u8);
+
+    data[1].write(DISCRIMINATOR);
+
+    let mut offset = 2;
+
+    for authority in [transfer_fee_config_authority, withdraw_withheld_authority] {
+        if let Some(authority) = authority {
+            data[offset].write(1);
             write_bytes(
-                instruction_data.get_unchecked_mut(data_len..),
-                &self.transfer_fee_basis_points.to_le_bytes(),
+                &mut data[offset + 1..offset + 1 + size_of::<Address>()],
+                authority.as_ref(),
             );
+            offset += 1 + size_of::<Address>();
+        } else {
+            data[offset].write(0);
+            offset += 1;
         }
-        data_len += 2;
+    }
 
-        // SAFETY: `instruction_data` is allocated to the maximum expected length.
-        unsafe {
-            write_bytes(
-                instruction_data.get_unchecked_mut(data_len..),
-                &self.maximum_fee.to_le_bytes(),
+    write_bytes(
+        &mut data[offset..offset + 2],
+        &transfer_fee_basis_points.to_le_bytes(),
+    );
+
+    write_bytes(
+        &mut data[offset + 2..offset + 10],
+        &maximum_fee.to_le_bytes(),
+    );
+
+    Ok(offset + 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::decode::{TokenInstruction, TransferFeeInstruction},
+    };
+
+    #[test]
+    fn write_instruction_data_with_optional_authorities() {
+        let authority = Address::new_from_array([7; 32]);
+
+        for (transfer_fee_config_authority, withdraw_withheld_authority, expected_len) in [
+            (None, None, 14),
+            (Some(&authority), None, 46),
+            (None, Some(&authority), 46),
+            (Some(&authority), Some(&authority), MAX_DATA_LEN),
+        ] {
+            let mut data = [UNINIT_BYTE; MAX_DATA_LEN];
+
+            let written = write_instruction_data(
+                transfer_fee_config_authority,
+                withdraw_withheld_authority,
+                250,
+                5_000,
+                &mut data,
+            )
+            .unwrap();
+
+            assert_eq!(written, expected_len);
+
+            // SAFETY: `written` bytes of `data` are initialized.
+            let bytes = unsafe { from_raw_parts(data.as_ptr() as *const u8, written) };
+
+            assert_eq!(
+                TokenInstruction::unpack(bytes),
+                Ok(TokenInstruction::TransferFee(
+                    TransferFeeInstruction::InitializeTransferFeeConfig {
+                        transfer_fee_config_authority,
+                        withdraw_withheld_authority,
+                        transfer_fee_basis_points: 250,
+                        maximum_fee: 5_000,
+                    }
+                ))
             );
         }
-        data_len += 8;
-
-        invoke(
-            &InstructionView {
-                program_id: self.token_program,
-                accounts: &[InstructionAccount::writable(self.mint.address())],
-                // SAFETY: instruction data is initialized to `data_len` bytes.
-                data: unsafe { from_raw_parts(instruction_data.as_ptr() as _, data_len) },
-            },
-            &[self.mint],
-        )
     }
 }
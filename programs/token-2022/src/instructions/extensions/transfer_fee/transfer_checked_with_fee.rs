@@ -1,18 +1,33 @@
 use {
     crate::{
-        instructions::{ExtensionDiscriminator, MAX_MULTISIG_SIGNERS},
-        write_bytes, UNINIT_BYTE,
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer},
+        instructions::{
+            account_borrow_failed_error, extensions::ExtensionDiscriminator,
+            invalid_argument_error, Batch, CpiWriter, IntoBatch, MAX_MULTISIG_SIGNERS,
+        },
+        write_bytes, Token2022Program, UNINIT_BYTE,
     },
     core::{mem::MaybeUninit, slice::from_raw_parts},
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{
-        cpi::{invoke_signed_with_bounds, Signer},
-        InstructionAccount, InstructionView,
-    },
+    solana_instruction_view::{InstructionAccount, InstructionView},
     solana_program_error::{ProgramError, ProgramResult},
 };
 
+/// The instruction discriminator.
+const DISCRIMINATOR: u8 = 1;
+
+/// Maximum number of accounts expected by this instruction.
+const MAX_ACCOUNTS_LEN: usize = 4 + MAX_MULTISIG_SIGNERS;
+
+/// Instruction data length:
+///   - extension discriminator (1 byte)
+///   - instruction discriminator (1 byte)
+///   - amount (8 bytes, u64)
+///   - decimals (1 byte, u8)
+///   - fee (8 bytes, u64)
+const DATA_LEN: usize = 19;
+
 /// Transfer, providing expected mint information and fees.
 ///
 /// This instruction succeeds if the mint has no configured transfer fee
@@ -57,9 +72,12 @@ pub struct TransferCheckedWithFee<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>
     /// Expected number of base 10 digits to the right of the decimal place.
     pub decimals: u8,
 
-    /// Expected fee assessed on this transfer, calculated off-chain based
-    /// on the `transfer_fee_basis_points` and `maximum_fee` of the mint.
-    /// May be 0 for a mint without a configured transfer fee.
+    /// Expected fee assessed on this transfer, calculated based on the
+    /// `transfer_fee_basis_points` and `maximum_fee` of the mint, e.g., with
+    /// [`TransferFeeConfigExtension::calculate_epoch_fee`]. May be 0 for a
+    /// mint without a configured transfer fee.
+    ///
+    /// [`TransferFeeConfigExtension::calculate_epoch_fee`]: crate::state::TransferFeeConfigExtension::calculate_epoch_fee
     pub fee: u64,
 
     /// The token program ID.
@@ -70,7 +88,7 @@ impl<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>>
     TransferCheckedWithFee<'a, 'b, 'c, MultisigSigner>
 {
     /// Instruction discriminator.
-    pub const DISCRIMINATOR: u8 = 1;
+    pub const DISCRIMINATOR: u8 = DISCRIMINATOR;
 
     /// Creates a new `TransferCheckedWithFee` instruction with a single
     /// owner/delegate authority.
@@ -138,80 +156,220 @@ impl<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>>
             return Err(ProgramError::InvalidArgument);
         }
 
-        let expected_accounts = 4 + self.multisig_signers.len();
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
+
+        let mut accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_signed_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+                signers,
+            );
+        }
 
-        // Instruction accounts.
+        Ok(())
+    }
+}
 
-        let mut instruction_accounts =
-            [const { MaybeUninit::<InstructionAccount>::uninit() }; 4 + MAX_MULTISIG_SIGNERS];
+impl<MultisigSigner: AsRef<AccountView>> CpiWriter
+    for TransferCheckedWithFee<'_, '_, '_, MultisigSigner>
+{
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(
+            self.source,
+            self.mint,
+            self.destination,
+            self.authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
 
-        instruction_accounts[0].write(InstructionAccount::writable(self.source.address()));
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(
+            self.source,
+            self.mint,
+            self.destination,
+            self.authority,
+            self.multisig_signers,
+            accounts,
+        )
+    }
 
-        instruction_accounts[1].write(InstructionAccount::readonly(self.mint.address()));
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(self.amount, self.decimals, self.fee, data)
+    }
+}
 
-        instruction_accounts[2].write(InstructionAccount::writable(self.destination.address()));
+impl<MultisigSigner: AsRef<AccountView>> IntoBatch<Token2022Program>
+    for TransferCheckedWithFee<'_, '_, '_, MultisigSigner>
+{
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| {
+                write_accounts(
+                    self.source,
+                    self.mint,
+                    self.destination,
+                    self.authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            |accounts| {
+                write_instruction_accounts(
+                    self.source,
+                    self.mint,
+                    self.destination,
+                    self.authority,
+                    self.multisig_signers,
+                    accounts,
+                )
+            },
+            |data| write_instruction_data(self.amount, self.decimals, self.fee, data),
+        )
+    }
+}
 
-        instruction_accounts[3].write(InstructionAccount::new(
-            self.authority.address(),
-            false,
-            self.multisig_signers.is_empty(),
-        ));
+#[inline(always)]
+fn write_accounts<'account, 'multisig, 'out, MultisigSigner: AsRef<AccountView>>(
+    source: &'account AccountView,
+    mint: &'account AccountView,
+    destination: &'account AccountView,
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 4 + multisig_signers.len();
 
-        for (instruction_account, signer) in instruction_accounts[4..]
-            .iter_mut()
-            .zip(self.multisig_signers.iter())
-        {
-            instruction_account.write(InstructionAccount::readonly_signer(
-                signer.as_ref().address(),
-            ));
-        }
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    if source.is_borrowed() | destination.is_borrowed() {
+        return Err(account_borrow_failed_error());
+    }
 
-        // Accounts.
+    CpiAccount::init_from_account_view(source, &mut accounts[0]);
 
-        let mut accounts =
-            [const { MaybeUninit::<&AccountView>::uninit() }; 4 + MAX_MULTISIG_SIGNERS];
+    CpiAccount::init_from_account_view(mint, &mut accounts[1]);
 
-        accounts[0].write(self.source);
+    CpiAccount::init_from_account_view(destination, &mut accounts[2]);
 
-        accounts[1].write(self.mint);
+    CpiAccount::init_from_account_view(authority, &mut accounts[3]);
 
-        accounts[2].write(self.destination);
+    for (account, signer) in accounts[4..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        CpiAccount::init_from_account_view(signer.as_ref(), account);
+    }
 
-        accounts[3].write(self.authority);
+    Ok(expected_accounts)
+}
 
-        for (account, signer) in accounts[4..].iter_mut().zip(self.multisig_signers.iter()) {
-            account.write(signer.as_ref());
-        }
+#[inline(always)]
+fn write_instruction_accounts<'account, 'multisig, 'out, MultisigSigner: AsRef<AccountView>>(
+    source: &'account AccountView,
+    mint: &'account AccountView,
+    destination: &'account AccountView,
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'multisig: 'out,
+{
+    let expected_accounts = 4 + multisig_signers.len();
 
-        // Instruction data.
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
 
-        let mut instruction_data = [UNINIT_BYTE; 19];
+    accounts[0].write(InstructionAccount::writable(source.address()));
 
-        instruction_data[0].write(ExtensionDiscriminator::TransferFee as u8);
+    accounts[1].write(InstructionAccount::readonly(mint.address()));
 
-        instruction_data[1].write(Self::DISCRIMINATOR);
+    accounts[2].write(InstructionAccount::writable(destination.address()));
 
-        write_bytes(&mut instruction_data[2..10], &self.amount.to_le_bytes());
+    accounts[3].write(InstructionAccount::new(
+        authority.address(),
+        false,
+        multisig_signers.is_empty(),
+    ));
 
-        instruction_data[10].write(self.decimals);
+    for (account, signer) in accounts[4..expected_accounts]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        account.write(InstructionAccount::readonly_signer(
+            signer.as_ref().address(),
+        ));
+    }
 
-        write_bytes(&mut instruction_data[11..19], &self.fee.to_le_bytes());
+    Ok(expected_accounts)
+}
 
-        invoke_signed_with_bounds::<{ 4 + MAX_MULTISIG_SIGNERS }, _>(
-            &InstructionView {
-                program_id: self.token_program,
-                // SAFETY: instruction accounts has `expected_accounts` initialized.
-                accounts: unsafe {
-                    from_raw_parts(instruction_accounts.as_ptr() as _, expected_accounts)
-                },
-                // SAFETY: instruction data is initialized.
-                data: unsafe {
-                    from_raw_parts(instruction_data.as_ptr() as _, instruction_data.len())
-                },
-            },
-            // SAFETY: accounts has `expected_accounts` initialized.
-            unsafe { from_raw_parts(accounts.as_ptr() as *const &AccountView, expected_accounts) },
-            signers,
-        )
+#[inline(always)]
+fn write_instruction_data(
+    amount: u64,
+    decimals: u8,
+    fee: u64,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
     }
+
+    data[0].write(ExtensionDiscriminator::TransferFee as u8);
+
+    data[1].write(DISCRIMINATOR);
+
+    write_bytes(&mut data[2..10], &amount.to_le_bytes());
+
+    data[10].write(decimals);
+
+    write_bytes(&mut data[11..19], &fee.to_le_bytes());
+
+    Ok(DATA_LEN)
 }
@@ -1,15 +1,31 @@
 use {
-    crate::instructions::{ExtensionDiscriminator, MAX_MULTISIG_SIGNERS},
+    crate::{
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer, MAX_STATIC_CPI_ACCOUNTS},
+        instructions::{
+            account_borrow_failed_error, extensions::ExtensionDiscriminator,
+            invalid_argument_error, Batch, CpiWriter, IntoBatch, MAX_MULTISIG_SIGNERS,
+        },
+        Token2022Program, UNINIT_BYTE,
+    },
     core::{mem::MaybeUninit, slice::from_raw_parts},
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{
-        cpi::{invoke_signed_with_bounds, Signer, MAX_STATIC_CPI_ACCOUNTS},
-        InstructionAccount, InstructionView,
-    },
+    solana_instruction_view::{InstructionAccount, InstructionView},
     solana_program_error::{ProgramError, ProgramResult},
 };
 
+/// The instruction discriminator.
+const DISCRIMINATOR: u8 = 3;
+
+/// Maximum number of accounts expected by this instruction.
+const MAX_ACCOUNTS_LEN: usize = MAX_STATIC_CPI_ACCOUNTS;
+
+/// Instruction data length:
+///   - extension discriminator (1 byte)
+///   - instruction discriminator (1 byte)
+///   - number of source accounts (1 byte, u8)
+const DATA_LEN: usize = 3;
+
 /// Transfer all withheld tokens to an account. Signed by the mint's
 /// withdraw withheld tokens authority.
 ///
@@ -57,7 +73,7 @@ pub struct WithdrawWithheldTokensFromAccounts<
 impl<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>, Source: AsRef<AccountView>>
     WithdrawWithheldTokensFromAccounts<'a, 'b, 'c, MultisigSigner, Source>
 {
-    pub const DISCRIMINATOR: u8 = 3;
+    pub const DISCRIMINATOR: u8 = DISCRIMINATOR;
 
     /// Creates a new `WithdrawWithheldTokensFromAccounts` instruction
     /// with a single owner/delegate authority.
@@ -104,91 +120,244 @@ impl<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>, Source: AsRef<AccountView>>
             return Err(ProgramError::InvalidArgument);
         }
 
-        let expected_accounts = 3 + self.multisig_signers.len() + self.sources.len();
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_instruction_accounts =
+            self.write_instruction_accounts(&mut instruction_accounts)?;
 
-        if expected_accounts > MAX_STATIC_CPI_ACCOUNTS {
-            return Err(ProgramError::InvalidArgument);
+        let mut accounts = [const { MaybeUninit::uninit() }; MAX_ACCOUNTS_LEN];
+        let written_accounts = self.write_accounts(&mut accounts)?;
+
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+        let written_instruction_data = self.write_instruction_data(&mut instruction_data)?;
+
+        // SAFETY: The written accounts and instruction data are initialized, and
+        // the writable accounts are not borrowed.
+        unsafe {
+            invoke_signed_unchecked(
+                &InstructionView {
+                    program_id: self.token_program,
+                    accounts: from_raw_parts(
+                        instruction_accounts.as_ptr() as _,
+                        written_instruction_accounts,
+                    ),
+                    data: from_raw_parts(instruction_data.as_ptr() as _, written_instruction_data),
+                },
+                from_raw_parts(accounts.as_ptr() as _, written_accounts),
+                signers,
+            );
         }
 
-        // Instruction accounts.
+        Ok(())
+    }
+}
 
-        let mut instruction_accounts =
-            [const { MaybeUninit::<InstructionAccount>::uninit() }; MAX_STATIC_CPI_ACCOUNTS];
+impl<MultisigSigner: AsRef<AccountView>, Source: AsRef<AccountView>> CpiWriter
+    for WithdrawWithheldTokensFromAccounts<'_, '_, '_, MultisigSigner, Source>
+{
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_accounts(
+            self.mint,
+            self.destination,
+            self.authority,
+            self.multisig_signers,
+            self.sources,
+            accounts,
+        )
+    }
 
-        instruction_accounts[0].write(InstructionAccount::readonly(self.mint.address()));
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        write_instruction_accounts(
+            self.mint,
+            self.destination,
+            self.authority,
+            self.multisig_signers,
+            self.sources,
+            accounts,
+        )
+    }
 
-        instruction_accounts[1].write(InstructionAccount::writable(self.destination.address()));
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(self.sources.len() as u8, data)
+    }
+}
 
-        instruction_accounts[2].write(InstructionAccount::new(
-            self.authority.address(),
-            false,
-            self.multisig_signers.is_empty(),
-        ));
+impl<MultisigSigner: AsRef<AccountView>, Source: AsRef<AccountView>> IntoBatch<Token2022Program>
+    for WithdrawWithheldTokensFromAccounts<'_, '_, '_, MultisigSigner, Source>
+{
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        batch.push(
+            |accounts| {
+                write_accounts(
+                    self.mint,
+                    self.destination,
+                    self.authority,
+                    self.multisig_signers,
+                    self.sources,
+                    accounts,
+                )
+            },
+            |accounts| {
+                write_instruction_accounts(
+                    self.mint,
+                    self.destination,
+                    self.authority,
+                    self.multisig_signers,
+                    self.sources,
+                    accounts,
+                )
+            },
+            |data| write_instruction_data(self.sources.len() as u8, data),
+        )
+    }
+}
 
-        for (instruction_account, signer) in instruction_accounts[3..]
-            .iter_mut()
-            .zip(self.multisig_signers.iter())
-        {
-            instruction_account.write(InstructionAccount::readonly_signer(
-                signer.as_ref().address(),
-            ));
-        }
+#[inline(always)]
+fn write_accounts<
+    'account,
+    'multisig,
+    'sources,
+    'out,
+    MultisigSigner: AsRef<AccountView>,
+    Source: AsRef<AccountView>,
+>(
+    mint: &'account AccountView,
+    destination: &'account AccountView,
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    sources: &'sources [Source],
+    accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'multisig: 'out,
+    'sources: 'out,
+{
+    let expected_accounts = 3 + multisig_signers.len() + sources.len();
 
-        // SAFETY: The expected number of accounts has been validated to be less than
-        // the maximum allocated.
-        unsafe {
-            for (instruction_account, source) in instruction_accounts
-                .get_unchecked_mut(3 + self.multisig_signers.len()..)
-                .iter_mut()
-                .zip(self.sources.iter())
-            {
-                instruction_account.write(InstructionAccount::writable(source.as_ref().address()));
-            }
-        }
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
 
-        // Accounts.
+    if destination.is_borrowed() | sources.iter().any(|source| source.as_ref().is_borrowed()) {
+        return Err(account_borrow_failed_error());
+    }
 
-        let mut accounts =
-            [const { MaybeUninit::<&AccountView>::uninit() }; MAX_STATIC_CPI_ACCOUNTS];
+    CpiAccount::init_from_account_view(mint, &mut accounts[0]);
 
-        accounts[0].write(self.mint);
+    CpiAccount::init_from_account_view(destination, &mut accounts[1]);
 
-        accounts[1].write(self.destination);
+    CpiAccount::init_from_account_view(authority, &mut accounts[2]);
 
-        accounts[2].write(self.authority);
+    let sources_index = 3 + multisig_signers.len();
 
-        for (account, signer) in accounts[3..].iter_mut().zip(self.multisig_signers.iter()) {
-            account.write(signer.as_ref());
-        }
+    for (account, signer) in accounts[3..sources_index]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        CpiAccount::init_from_account_view(signer.as_ref(), account);
+    }
 
-        // SAFETY: The expected number of accounts has been validated to be less than
-        // the maximum allocated.
-        unsafe {
-            for (account, source) in accounts
-                .get_unchecked_mut(3 + self.multisig_signers.len()..)
-                .iter_mut()
-                .zip(self.sources.iter())
-            {
-                account.write(source.as_ref());
-            }
-        }
+    for (account, source) in accounts[sources_index..expected_accounts]
+        .iter_mut()
+        .zip(sources.iter())
+    {
+        CpiAccount::init_from_account_view(source.as_ref(), account);
+    }
 
-        invoke_signed_with_bounds::<MAX_STATIC_CPI_ACCOUNTS, _>(
-            &InstructionView {
-                program_id: self.token_program,
-                // SAFETY: instruction accounts has `expected_accounts` initialized.
-                accounts: unsafe {
-                    from_raw_parts(instruction_accounts.as_ptr() as _, expected_accounts)
-                },
-                data: &[
-                    ExtensionDiscriminator::TransferFee as u8,
-                    Self::DISCRIMINATOR,
-                    self.sources.len() as u8,
-                ],
-            },
-            // SAFETY: accounts has `expected_accounts` initialized.
-            unsafe { from_raw_parts(accounts.as_ptr() as *const &AccountView, expected_accounts) },
-            signers,
-        )
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_accounts<
+    'account,
+    'multisig,
+    'sources,
+    'out,
+    MultisigSigner: AsRef<AccountView>,
+    Source: AsRef<AccountView>,
+>(
+    mint: &'account AccountView,
+    destination: &'account AccountView,
+    authority: &'account AccountView,
+    multisig_signers: &'multisig [MultisigSigner],
+    sources: &'sources [Source],
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+    'multisig: 'out,
+    'sources: 'out,
+{
+    let expected_accounts = 3 + multisig_signers.len() + sources.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
     }
+
+    accounts[0].write(InstructionAccount::readonly(mint.address()));
+
+    accounts[1].write(InstructionAccount::writable(destination.address()));
+
+    accounts[2].write(InstructionAccount::new(
+        authority.address(),
+        false,
+        multisig_signers.is_empty(),
+    ));
+
+    let sources_index = 3 + multisig_signers.len();
+
+    for (account, signer) in accounts[3..sources_index]
+        .iter_mut()
+        .zip(multisig_signers.iter())
+    {
+        account.write(InstructionAccount::readonly_signer(
+            signer.as_ref().address(),
+        ));
+    }
+
+    for (account, source) in accounts[sources_index..expected_accounts]
+        .iter_mut()
+        .zip(sources.iter())
+    {
+        account.write(InstructionAccount::writable(source.as_ref().address()));
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    sources_len: u8,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    data[0].write(ExtensionDiscriminator::TransferFee as u8);
+
+    data[1].write(DISCRIMINATOR);
+
+    data[2].write(sources_len);
+
+    Ok(DATA_LEN)
 }
@@ -0,0 +1,468 @@
+//! Extra account metas of the transfer hook interface `Execute` instruction,
+//! stored in the validation account of a mint.
+//!
+//! Shared by `TransferCheckedWithHook`, which resolves the extra accounts to
+//! invoke Token-2022, and transfer hook programs, which check the accounts
+//! they receive.
+
+use solana_program_error::ProgramError;
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+use {solana_account_view::AccountView, solana_address::Address};
+
+/// Seed prefix of the validation account holding the extra account metas of a
+/// mint, derived from `[EXTRA_ACCOUNT_METAS_SEED, mint]` with the transfer hook
+/// program id.
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+/// Discriminator of the transfer hook interface `Execute` instruction, also
+/// used as the type of its extra account metas in the validation account.
+pub const EXECUTE_DISCRIMINATOR: [u8; 8] = [105, 37, 101, 197, 75, 251, 102, 26];
+
+/// Length of a TLV entry header in the validation account: type (8 bytes) and
+/// length (4 bytes).
+pub const TLV_HEADER_LEN: usize = 12;
+
+/// Length of an extra account meta:
+///   - discriminator (1 byte)
+///   - address config (32 bytes)
+///   - is signer (1 byte)
+///   - is writable (1 byte)
+pub const EXTRA_ACCOUNT_META_LEN: usize = 35;
+
+/// Number of `Execute` instruction accounts preceding the extra accounts:
+/// source, mint, destination, authority and validation account.
+pub const EXECUTE_ACCOUNTS_LEN: usize = 5;
+
+/// Maximum number of seeds of a program derived address.
+pub const MAX_SEEDS: usize = 16;
+
+/// Maximum length of a program derived address seed.
+pub const MAX_SEED_LEN: usize = 32;
+
+/// The `Execute` TLV entry of a validation account data.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExecuteEntry {
+    /// The entry at `start` with a value of `length` bytes.
+    Occupied { start: usize, length: usize },
+
+    /// No entry; the free space begins at `start`.
+    Vacant { start: usize },
+}
+
+/// Finds the `Execute` entry in the TLV `data` of a validation account.
+///
+/// Entries are read until the end of the data or a zero type, which marks the
+/// beginning of the free space.
+///
+/// # Errors
+///
+/// Returns [`ProgramError::InvalidAccountData`] if an entry value is out of
+/// bounds.
+#[inline]
+pub fn find_execute_entry(data: &[u8]) -> Result<ExecuteEntry, ProgramError> {
+    let mut start = 0;
+
+    while let Some(header) = data.get(start..start + TLV_HEADER_LEN) {
+        let (discriminator, length) = header.split_at(8);
+
+        if discriminator == [0; 8] {
+            break;
+        }
+
+        let length = u32::from_le_bytes([length[0], length[1], length[2], length[3]]) as usize;
+        let end = start + TLV_HEADER_LEN + length;
+
+        if end > data.len() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if discriminator == EXECUTE_DISCRIMINATOR {
+            return Ok(ExecuteEntry::Occupied { start, length });
+        }
+
+        start = end;
+    }
+
+    Ok(ExecuteEntry::Vacant { start })
+}
+
+/// Returns the extra account metas of the `Execute` instruction, each
+/// [`EXTRA_ACCOUNT_META_LEN`] bytes long, from the TLV data of a validation
+/// account.
+///
+/// # Errors
+///
+/// Returns [`ProgramError::InvalidAccountData`] if the data is malformed or
+/// does not contain the `Execute` extra account metas.
+#[inline]
+pub fn execute_extra_account_metas(data: &[u8]) -> Result<&[u8], ProgramError> {
+    let ExecuteEntry::Occupied { start, length } = find_execute_entry(data)? else {
+        return Err(ProgramError::InvalidAccountData);
+    };
+
+    let value = &data[start + TLV_HEADER_LEN..start + TLV_HEADER_LEN + length];
+
+    let count = value
+        .get(..4)
+        .map(|count| u32::from_le_bytes([count[0], count[1], count[2], count[3]]))
+        .ok_or(ProgramError::InvalidAccountData)? as usize;
+
+    value
+        .get(4..4 + count * EXTRA_ACCOUNT_META_LEN)
+        .ok_or(ProgramError::InvalidAccountData)
+}
+
+/// Resolves the address of an extra account from its `discriminator` and
+/// 32-byte `config`.
+///
+/// The address is either a literal, a program derived address of the
+/// transfer hook `program_id` or of the program at an account index, or is
+/// read from the `execute_data` or account data. Accounts are referenced by
+/// their index in the `Execute` instruction and returned by
+/// `execute_account`.
+///
+/// # Errors
+///
+/// Returns [`ProgramError::NotEnoughAccountKeys`] if a referenced account is
+/// missing, [`ProgramError::AccountDataTooSmall`] if a referenced slice of an
+/// account data is out of bounds, [`ProgramError::InvalidSeeds`] if the
+/// address cannot be derived, or [`ProgramError::InvalidAccountData`] if the
+/// config is malformed or a referenced slice of the instruction data is out
+/// of bounds.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+#[inline]
+pub fn resolve_address<'a>(
+    discriminator: u8,
+    config: &[u8],
+    execute_data: &[u8],
+    program_id: &Address,
+    execute_account: impl Fn(usize) -> Option<&'a AccountView>,
+) -> Result<Address, ProgramError> {
+    match discriminator {
+        // Literal address.
+        0 => Ok(Address::new_from_array(
+            config
+                .try_into()
+                .map_err(|_| ProgramError::InvalidAccountData)?,
+        )),
+        // Program derived address of the transfer hook program.
+        1 => derive_address(config, execute_data, program_id, execute_account),
+        // Address read from instruction or account data.
+        2 => {
+            let mut address = [0; 32];
+
+            match config {
+                [1, index, ..] => address.copy_from_slice(
+                    execute_data
+                        .get(*index as usize..*index as usize + 32)
+                        .ok_or(ProgramError::InvalidAccountData)?,
+                ),
+                [2, account_index, data_index, ..] => copy_account_data(
+                    execute_account(*account_index as usize)
+                        .ok_or(ProgramError::NotEnoughAccountKeys)?,
+                    *data_index as usize,
+                    &mut address,
+                )?,
+                _ => return Err(ProgramError::InvalidAccountData),
+            }
+
+            Ok(Address::new_from_array(address))
+        }
+        // Program derived address of the program at an account index.
+        discriminator if discriminator >= 128 => {
+            let program = execute_account((discriminator - 128) as usize)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+            derive_address(config, execute_data, program.address(), execute_account)
+        }
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Derives a program derived address from the seeds `config`.
+///
+/// Each seed is a literal, a slice of the instruction data, an account
+/// address or a slice of an account data. Seeds are copied into a buffer, so
+/// no account data remains borrowed.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+#[inline(always)]
+fn derive_address<'a>(
+    config: &[u8],
+    execute_data: &[u8],
+    program_id: &Address,
+    execute_account: impl Fn(usize) -> Option<&'a AccountView>,
+) -> Result<Address, ProgramError> {
+    let mut buffer = [0; MAX_SEEDS * MAX_SEED_LEN];
+    let mut seed_ends = [0; MAX_SEEDS];
+    let mut seeds_len = 0;
+    let mut offset = 0;
+    let mut config = config;
+
+    while let Some(&seed_type) = config.first() {
+        if seed_type == 0 {
+            break;
+        }
+
+        if seeds_len == MAX_SEEDS {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let destination = &mut buffer[offset..];
+
+        let (seed_len, config_len) = match *config {
+            // Literal.
+            [1, len, ref rest @ ..] => {
+                let len = len as usize;
+                copy_seed(
+                    rest.get(..len).ok_or(ProgramError::InvalidAccountData)?,
+                    destination,
+                )?;
+                (len, 2 + len)
+            }
+            // Instruction data.
+            [2, index, len, ..] => {
+                let (index, len) = (index as usize, len as usize);
+                copy_seed(
+                    execute_data
+                        .get(index..index + len)
+                        .ok_or(ProgramError::InvalidAccountData)?,
+                    destination,
+                )?;
+                (len, 3)
+            }
+            // Account address.
+            [3, index, ..] => {
+                copy_seed(
+                    execute_account(index as usize)
+                        .ok_or(ProgramError::NotEnoughAccountKeys)?
+                        .address()
+                        .as_ref(),
+                    destination,
+                )?;
+                (MAX_SEED_LEN, 2)
+            }
+            // Account data.
+            [4, account_index, data_index, len, ..] => {
+                let len = len as usize;
+
+                if len > MAX_SEED_LEN {
+                    return Err(ProgramError::InvalidSeeds);
+                }
+
+                copy_account_data(
+                    execute_account(account_index as usize)
+                        .ok_or(ProgramError::NotEnoughAccountKeys)?,
+                    data_index as usize,
+                    &mut destination[..len],
+                )?;
+                (len, 4)
+            }
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+
+        offset += seed_len;
+        seed_ends[seeds_len] = offset;
+        seeds_len += 1;
+        config = &config[config_len..];
+    }
+
+    let mut seeds: [&[u8]; MAX_SEEDS] = [&[]; MAX_SEEDS];
+    let mut start = 0;
+
+    for (seed, end) in seeds.iter_mut().zip(&seed_ends[..seeds_len]) {
+        *seed = &buffer[start..*end];
+        start = *end;
+    }
+
+    Address::try_find_program_address(&seeds[..seeds_len], program_id)
+        .map(|(address, _)| address)
+        .ok_or(ProgramError::InvalidSeeds)
+}
+
+/// Copies a `seed` into `destination`.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+#[inline(always)]
+fn copy_seed(seed: &[u8], destination: &mut [u8]) -> Result<(), ProgramError> {
+    if seed.len() > MAX_SEED_LEN {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    destination[..seed.len()].copy_from_slice(seed);
+
+    Ok(())
+}
+
+/// Copies the data of `account` from `data_index` into `destination`.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+#[inline(always)]
+fn copy_account_data(
+    account: &AccountView,
+    data_index: usize,
+    destination: &mut [u8],
+) -> Result<(), ProgramError> {
+    let data = account.try_borrow()?;
+
+    destination.copy_from_slice(
+        data.get(data_index..data_index + destination.len())
+            .ok_or(ProgramError::AccountDataTooSmall)?,
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use {super::*, std::vec::Vec};
+
+    fn extra_account_meta(discriminator: u8, config: &[u8], is_signer: bool) -> Vec<u8> {
+        let mut meta = std::vec![0; EXTRA_ACCOUNT_META_LEN];
+        meta[0] = discriminator;
+        meta[1..1 + config.len()].copy_from_slice(config);
+        meta[33] = is_signer as u8;
+        meta
+    }
+
+    fn push_tlv_entry(data: &mut Vec<u8>, discriminator: &[u8; 8], metas: &[&[u8]]) {
+        data.extend_from_slice(discriminator);
+        data.extend_from_slice(&(4 + metas.len() as u32 * 35).to_le_bytes());
+        data.extend_from_slice(&(metas.len() as u32).to_le_bytes());
+        metas.iter().for_each(|meta| data.extend_from_slice(meta));
+    }
+
+    #[cfg(feature = "curve25519")]
+    fn execute_data(amount: u64) -> [u8; 16] {
+        let mut data = [0; 16];
+        data[..8].copy_from_slice(&EXECUTE_DISCRIMINATOR);
+        data[8..].copy_from_slice(&amount.to_le_bytes());
+        data
+    }
+
+    #[cfg(feature = "curve25519")]
+    fn no_account(_: usize) -> Option<&'static AccountView> {
+        None
+    }
+
+    #[test]
+    fn find_execute_entry_after_other_entries() {
+        let literal = extra_account_meta(0, &[7; 32], false);
+        let mut data = Vec::new();
+        push_tlv_entry(&mut data, &[1; 8], &[&literal]);
+        let start = data.len();
+        push_tlv_entry(&mut data, &EXECUTE_DISCRIMINATOR, &[&literal]);
+
+        assert_eq!(
+            find_execute_entry(&data),
+            Ok(ExecuteEntry::Occupied {
+                start,
+                length: 4 + EXTRA_ACCOUNT_META_LEN,
+            })
+        );
+
+        // The free space begins after the last entry.
+        data.truncate(start);
+        data.extend_from_slice(&[0; 16]);
+        assert_eq!(
+            find_execute_entry(&data),
+            Ok(ExecuteEntry::Vacant { start })
+        );
+        assert_eq!(
+            find_execute_entry(&[]),
+            Ok(ExecuteEntry::Vacant { start: 0 })
+        );
+    }
+
+    #[test]
+    fn execute_extra_account_metas_finds_execute_entry() {
+        let literal = extra_account_meta(0, &[7; 32], false);
+        let mut data = Vec::new();
+        push_tlv_entry(&mut data, &[1; 8], &[&literal, &literal]);
+        push_tlv_entry(&mut data, &EXECUTE_DISCRIMINATOR, &[&literal]);
+        data.extend_from_slice(&[0; 16]);
+
+        assert_eq!(execute_extra_account_metas(&data).unwrap(), &literal[..]);
+
+        let mut data = Vec::new();
+        push_tlv_entry(&mut data, &[1; 8], &[&literal]);
+        assert_eq!(
+            execute_extra_account_metas(&data),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        let mut data = Vec::new();
+        push_tlv_entry(&mut data, &EXECUTE_DISCRIMINATOR, &[&literal]);
+        data.truncate(data.len() - 1);
+        assert_eq!(
+            execute_extra_account_metas(&data),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[cfg(feature = "curve25519")]
+    #[test]
+    fn resolve_literal_and_instruction_data_addresses() {
+        let program_id = Address::new_from_array([9; 32]);
+        let data = execute_data(42);
+
+        let meta = extra_account_meta(0, &[7; 32], false);
+        assert_eq!(
+            resolve_address(meta[0], &meta[1..33], &data, &program_id, no_account),
+            Ok(Address::new_from_array([7; 32]))
+        );
+
+        // An address read from the instruction data must fit in it.
+        let meta = extra_account_meta(2, &[1, 0], false);
+        assert_eq!(
+            resolve_address(meta[0], &meta[1..33], &data, &program_id, no_account),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        let meta = extra_account_meta(3, &[], false);
+        assert_eq!(
+            resolve_address(meta[0], &meta[1..33], &data, &program_id, no_account),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[cfg(feature = "curve25519")]
+    #[test]
+    fn resolve_seed_derived_addresses() {
+        let program_id = Address::new_from_array([9; 32]);
+        let data = execute_data(42);
+
+        // Literal "seed" followed by the amount from the instruction data.
+        let mut config = Vec::new();
+        config.extend_from_slice(&[1, 4]);
+        config.extend_from_slice(b"seed");
+        config.extend_from_slice(&[2, 8, 8]);
+        let meta = extra_account_meta(1, &config, false);
+
+        let (expected, _) =
+            Address::find_program_address(&[b"seed", &42u64.to_le_bytes()], &program_id);
+        assert_eq!(
+            resolve_address(meta[0], &meta[1..33], &data, &program_id, no_account),
+            Ok(expected)
+        );
+
+        // Seeds referencing a missing account.
+        let meta = extra_account_meta(1, &[3, 5], false);
+        assert_eq!(
+            resolve_address(meta[0], &meta[1..33], &data, &program_id, no_account),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+
+        let meta = extra_account_meta(128 + 5, &[1, 1, 0], false);
+        assert_eq!(
+            resolve_address(meta[0], &meta[1..33], &data, &program_id, no_account),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+
+        // Literal seeds longer than the address config.
+        let meta = extra_account_meta(1, &[1, 31], false);
+        assert_eq!(
+            resolve_address(meta[0], &meta[1..33], &data, &program_id, no_account),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+}
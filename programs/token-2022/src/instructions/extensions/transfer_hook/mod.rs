@@ -1,4 +1,9 @@
+pub mod extra_account_metas;
 pub mod initialize;
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub mod transfer_checked_with_hook;
 pub mod update;
 
-pub use {initialize::*, update::*};
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub use transfer_checked_with_hook::*;
+pub use {extra_account_metas::EXTRA_ACCOUNT_METAS_SEED, initialize::*, update::*};
@@ -0,0 +1,987 @@
+use {
+    super::extra_account_metas::{
+        execute_extra_account_metas, resolve_address, EXECUTE_ACCOUNTS_LEN, EXECUTE_DISCRIMINATOR,
+        EXTRA_ACCOUNT_METAS_SEED, EXTRA_ACCOUNT_META_LEN,
+    },
+    crate::{
+        cpi::{invoke_signed_with_bounds, CpiAccount, Signer, MAX_STATIC_CPI_ACCOUNTS},
+        instructions::{
+            account_borrow_failed_error, invalid_argument_error, Batch, CpiWriter, IntoBatch,
+            MAX_MULTISIG_SIGNERS,
+        },
+        state::{is_extension_not_found_error, Mint, StateWithExtensions, TransferHookExtension},
+        write_bytes, Token2022Program, UNINIT_BYTE,
+    },
+    core::{mem::MaybeUninit, slice::from_raw_parts},
+    solana_account_view::AccountView,
+    solana_address::Address,
+    solana_instruction_view::{InstructionAccount, InstructionView},
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// The `TransferChecked` instruction discriminator.
+const DISCRIMINATOR: u8 = 12;
+
+/// Instruction data length:
+///   - discriminator (1 byte)
+///   - amount (8 bytes, u64)
+///   - decimals (1 byte, u8)
+const DATA_LEN: usize = 10;
+
+/// Transfer tokens from a mint with the `TransferHook` extension, providing
+/// the accounts required by the transfer hook program.
+///
+/// The transfer hook program id is read from the mint. The extra accounts
+/// are resolved from the `ExtraAccountMetaList` validation account of the
+/// mint (literal, seed-derived and account-data-derived addresses) and
+/// looked up in `additional_accounts`, which must also include the transfer
+/// hook program and the validation account. When the mint has no transfer
+/// hook program, this is a plain `TransferChecked`.
+///
+/// Accounts expected by this instruction:
+///
+///   * Single owner/delegate
+///   0. `[writable]` The source account.
+///   1. `[]` The token mint.
+///   2. `[writable]` The destination account.
+///   3. `[signer]` The source account's owner/delegate.
+///   4. `..4+E` The extra accounts required by the transfer hook program.
+///   5. `4+E` `[]` The validation account.
+///   6. `5+E` `[]` The transfer hook program.
+///
+///   * Multisignature owner/delegate
+///   0. `[writable]` The source account.
+///   1. `[]` The token mint.
+///   2. `[writable]` The destination account.
+///   3. `[]` The source account's multisignature owner/delegate.
+///   4. `..4+M` `[signer]` M signer accounts.
+///   5. `4+M..4+M+E` The extra accounts required by the transfer hook program.
+///   6. `4+M+E` `[]` The validation account.
+///   7. `5+M+E` `[]` The transfer hook program.
+pub struct TransferCheckedWithHook<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>> {
+    /// The source account.
+    pub source: &'a AccountView,
+
+    /// The token mint.
+    pub mint: &'a AccountView,
+
+    /// The destination account.
+    pub destination: &'a AccountView,
+
+    /// The source account's owner/delegate or multisignature.
+    pub authority: &'a AccountView,
+
+    /// Multisignature owner/delegate.
+    pub multisig_signers: &'c [MultisigSigner],
+
+    /// The accounts to resolve the transfer hook accounts from.
+    pub additional_accounts: &'c [&'a AccountView],
+
+    /// The amount of tokens to transfer.
+    pub amount: u64,
+
+    /// Expected number of base 10 digits to the right of the decimal place.
+    pub decimals: u8,
+
+    /// The token program.
+    pub token_program: &'b Address,
+}
+
+impl<'a, 'b, 'c, MultisigSigner: AsRef<AccountView>>
+    TransferCheckedWithHook<'a, 'b, 'c, MultisigSigner>
+{
+    /// The `TransferChecked` instruction discriminator.
+    pub const DISCRIMINATOR: u8 = DISCRIMINATOR;
+
+    /// Creates a new `TransferCheckedWithHook` instruction with a single
+    /// owner/delegate authority.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(always)]
+    pub fn new(
+        token_program: &'b Address,
+        source: &'a AccountView,
+        mint: &'a AccountView,
+        destination: &'a AccountView,
+        authority: &'a AccountView,
+        additional_accounts: &'c [&'a AccountView],
+        amount: u64,
+        decimals: u8,
+    ) -> Self {
+        Self::with_multisig_signers(
+            token_program,
+            source,
+            mint,
+            destination,
+            authority,
+            additional_accounts,
+            amount,
+            decimals,
+            &[],
+        )
+    }
+
+    /// Creates a new `TransferCheckedWithHook` instruction with a
+    /// multisignature owner/delegate authority and signer accounts.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(always)]
+    pub fn with_multisig_signers(
+        token_program: &'b Address,
+        source: &'a AccountView,
+        mint: &'a AccountView,
+        destination: &'a AccountView,
+        authority: &'a AccountView,
+        additional_accounts: &'c [&'a AccountView],
+        amount: u64,
+        decimals: u8,
+        multisig_signers: &'c [MultisigSigner],
+    ) -> Self {
+        Self {
+            source,
+            mint,
+            destination,
+            authority,
+            multisig_signers,
+            additional_accounts,
+            amount,
+            decimals,
+            token_program,
+        }
+    }
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        self.invoke_signed_with_bounds::<MAX_STATIC_CPI_ACCOUNTS>(signers)
+    }
+
+    /// Invokes the instruction with at most `MAX_ACCOUNTS` accounts.
+    #[inline(always)]
+    pub fn invoke_with_bounds<const MAX_ACCOUNTS: usize>(&self) -> ProgramResult {
+        self.invoke_signed_with_bounds::<MAX_ACCOUNTS>(&[])
+    }
+
+    /// Invokes the instruction with signer seeds and at most `MAX_ACCOUNTS`
+    /// accounts.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] if the transfer and transfer
+    /// hook accounts exceed `MAX_ACCOUNTS`, or
+    /// [`ProgramError::NotEnoughAccountKeys`] if a transfer hook account,
+    /// including the validation account, is missing from
+    /// `additional_accounts`.
+    #[inline]
+    pub fn invoke_signed_with_bounds<const MAX_ACCOUNTS: usize>(
+        &self,
+        signers: &[Signer],
+    ) -> ProgramResult {
+        let mut instruction_accounts =
+            [const { MaybeUninit::<InstructionAccount>::uninit() }; MAX_ACCOUNTS];
+
+        let mut accounts = [const { MaybeUninit::<&AccountView>::uninit() }; MAX_ACCOUNTS];
+
+        let expected_accounts = self.resolve_accounts(&mut instruction_accounts)?;
+
+        for (index, (account, instruction_account)) in accounts
+            .iter_mut()
+            .zip(instruction_accounts.iter())
+            .take(expected_accounts)
+            .enumerate()
+        {
+            // SAFETY: instruction accounts has `expected_accounts` initialized.
+            let instruction_account = unsafe { instruction_account.assume_init_ref() };
+            account.write(self.resolved_account(index, instruction_account)?);
+        }
+
+        // Instruction data.
+
+        let mut instruction_data = [UNINIT_BYTE; DATA_LEN];
+
+        write_instruction_data(self.amount, self.decimals, &mut instruction_data)?;
+
+        invoke_signed_with_bounds::<MAX_ACCOUNTS, _>(
+            &InstructionView {
+                program_id: self.token_program,
+                // SAFETY: instruction accounts has `expected_accounts` initialized.
+                accounts: unsafe {
+                    from_raw_parts(instruction_accounts.as_ptr() as _, expected_accounts)
+                },
+                // SAFETY: instruction data is initialized.
+                data: unsafe {
+                    from_raw_parts(instruction_data.as_ptr() as _, instruction_data.len())
+                },
+            },
+            // SAFETY: accounts has `expected_accounts` initialized.
+            unsafe { from_raw_parts(accounts.as_ptr() as *const &AccountView, expected_accounts) },
+            signers,
+        )
+    }
+
+    /// Writes the instruction accounts of the transfer accounts followed by
+    /// the transfer hook accounts, returning the total number of accounts.
+    ///
+    /// The account of each instruction account is returned by
+    /// [`Self::resolved_account`], so the accounts are resolved into a single
+    /// buffer.
+    #[inline(always)]
+    fn resolve_accounts<'out>(
+        &self,
+        instruction_accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+    ) -> Result<usize, ProgramError>
+    where
+        'a: 'out,
+        'c: 'out,
+    {
+        let multisig_signers = self.multisig_signers;
+
+        if self.multisig_signers.len() > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let transfer_accounts = 4 + self.multisig_signers.len();
+
+        if transfer_accounts > instruction_accounts.len() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Instruction accounts.
+
+        instruction_accounts[0].write(InstructionAccount::writable(self.source.address()));
+
+        instruction_accounts[1].write(InstructionAccount::readonly(self.mint.address()));
+
+        instruction_accounts[2].write(InstructionAccount::writable(self.destination.address()));
+
+        instruction_accounts[3].write(InstructionAccount::new(
+            self.authority.address(),
+            false,
+            self.multisig_signers.is_empty(),
+        ));
+
+        for (instruction_account, signer) in instruction_accounts[4..]
+            .iter_mut()
+            .zip(multisig_signers.iter())
+        {
+            instruction_account.write(InstructionAccount::readonly_signer(
+                signer.as_ref().address(),
+            ));
+        }
+
+        // Transfer hook accounts.
+
+        match self.transfer_hook_program()? {
+            Some(program) => {
+                self.write_transfer_hook_accounts(program, instruction_accounts, transfer_accounts)
+            }
+            None => Ok(transfer_accounts),
+        }
+    }
+
+    /// Returns the account of the instruction account at `index` written by
+    /// [`Self::resolve_accounts`].
+    ///
+    /// The transfer accounts are the accounts of the instruction and the
+    /// transfer hook accounts are looked up in the additional accounts.
+    #[inline(always)]
+    fn resolved_account<'out>(
+        &self,
+        index: usize,
+        instruction_account: &InstructionAccount,
+    ) -> Result<&'out AccountView, ProgramError>
+    where
+        'a: 'out,
+        'c: 'out,
+    {
+        match index {
+            0 => Ok(self.source),
+            1 => Ok(self.mint),
+            2 => Ok(self.destination),
+            3 => Ok(self.authority),
+            _ => match self.multisig_signers.get(index - 4) {
+                Some(signer) => Ok(signer.as_ref()),
+                None => self.find_account(instruction_account.address),
+            },
+        }
+    }
+
+    /// Returns the transfer hook program of the mint from the additional
+    /// accounts, if the mint has one.
+    #[inline(always)]
+    fn transfer_hook_program(&self) -> Result<Option<&'a AccountView>, ProgramError> {
+        let data = self.mint.try_borrow()?;
+        let mint = StateWithExtensions::<Mint>::from_bytes(&data)?;
+
+        let program_id = match mint.get_extension::<TransferHookExtension>() {
+            Ok(extension) => match extension.program_id.as_ref() {
+                Some(program_id) => program_id,
+                None => return Ok(None),
+            },
+            Err(error) if is_extension_not_found_error(&error) => return Ok(None),
+            Err(error) => return Err(error),
+        };
+
+        self.find_account(program_id).map(Some)
+    }
+
+    /// Returns the additional account with the given `address`.
+    #[inline(always)]
+    fn find_account(&self, address: &Address) -> Result<&'a AccountView, ProgramError> {
+        self.additional_accounts
+            .iter()
+            .find(|account| account.address() == address)
+            .copied()
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    }
+
+    /// Writes the extra accounts, the validation account and the transfer hook
+    /// `program` after the first `len` accounts, returning the total number of
+    /// accounts.
+    #[inline(always)]
+    fn write_transfer_hook_accounts<'out>(
+        &self,
+        program: &'a AccountView,
+        instruction_accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+        mut len: usize,
+    ) -> Result<usize, ProgramError>
+    where
+        'a: 'out,
+    {
+        let validation_address = Address::try_find_program_address(
+            &[EXTRA_ACCOUNT_METAS_SEED, self.mint.address().as_ref()],
+            program.address(),
+        )
+        .ok_or(ProgramError::InvalidSeeds)?
+        .0;
+
+        let validation = self.find_account(&validation_address)?;
+
+        let data = validation.try_borrow()?;
+        let extra_account_metas = execute_extra_account_metas(&data)?;
+
+        let mut execute_data = [0; 16];
+        execute_data[..8].copy_from_slice(&EXECUTE_DISCRIMINATOR);
+        execute_data[8..].copy_from_slice(&self.amount.to_le_bytes());
+
+        let first_extra = len;
+
+        for meta in extra_account_metas.chunks_exact(EXTRA_ACCOUNT_META_LEN) {
+            if len >= instruction_accounts.len() {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            // SAFETY: instruction accounts has `len` initialized.
+            let previous_extras = unsafe {
+                from_raw_parts(
+                    instruction_accounts[first_extra..].as_ptr() as *const InstructionAccount,
+                    len - first_extra,
+                )
+            };
+
+            let execute_account = |index: usize| match index {
+                0 => Some(self.source),
+                1 => Some(self.mint),
+                2 => Some(self.destination),
+                3 => Some(self.authority),
+                4 => Some(validation),
+                _ => previous_extras
+                    .get(index - EXECUTE_ACCOUNTS_LEN)
+                    .and_then(|extra| self.find_account(extra.address).ok()),
+            };
+
+            let address = resolve_address(
+                meta[0],
+                &meta[1..33],
+                &execute_data,
+                program.address(),
+                execute_account,
+            )?;
+            let account = self.find_account(&address)?;
+
+            let mut is_signer = meta[33] != 0;
+            let mut is_writable = meta[34] != 0;
+
+            // Do not escalate the privileges of an account already in the
+            // `Execute` instruction: the transfer accounts and the
+            // validation account are readonly.
+            if [
+                self.source,
+                self.mint,
+                self.destination,
+                self.authority,
+                validation,
+            ]
+            .iter()
+            .any(|execute_account| execute_account.address() == &address)
+            {
+                is_signer = false;
+                is_writable = false;
+            }
+
+            // Nor the privileges of an extra account already resolved.
+            if let Some((previous_signer, previous_writable)) = previous_extras
+                .iter()
+                .filter(|previous| previous.address == &address)
+                .fold(None, |privileges, previous| {
+                    let (is_signer, is_writable) = privileges.unwrap_or((false, false));
+                    Some((
+                        is_signer | previous.is_signer,
+                        is_writable | previous.is_writable,
+                    ))
+                })
+            {
+                is_signer &= previous_signer;
+                is_writable &= previous_writable;
+            }
+
+            instruction_accounts[len].write(InstructionAccount::new(
+                account.address(),
+                is_writable,
+                is_signer,
+            ));
+            len += 1;
+        }
+
+        if len >= instruction_accounts.len() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        instruction_accounts[len].write(InstructionAccount::readonly(validation.address()));
+        len += 1;
+
+        if len >= instruction_accounts.len() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        instruction_accounts[len].write(InstructionAccount::readonly(program.address()));
+
+        Ok(len + 1)
+    }
+
+    /// Writes the accounts of the resolved instruction accounts, checking
+    /// that the writable ones are not borrowed.
+    #[inline(always)]
+    fn write_resolved_accounts<'out>(
+        &self,
+        resolved_instruction_accounts: &[InstructionAccount],
+        accounts: &mut [MaybeUninit<CpiAccount<'out>>],
+    ) -> Result<usize, ProgramError>
+    where
+        'a: 'out,
+        'c: 'out,
+    {
+        let expected_accounts = resolved_instruction_accounts.len();
+
+        if expected_accounts > accounts.len() {
+            return Err(invalid_argument_error());
+        }
+
+        for (index, (account, instruction_account)) in accounts
+            .iter_mut()
+            .zip(resolved_instruction_accounts.iter())
+            .enumerate()
+        {
+            let resolved_account = self.resolved_account(index, instruction_account)?;
+
+            if instruction_account.is_writable && resolved_account.is_borrowed() {
+                return Err(account_borrow_failed_error());
+            }
+
+            CpiAccount::init_from_account_view(resolved_account, account);
+        }
+
+        Ok(expected_accounts)
+    }
+}
+
+// The transfer hook accounts are resolved on every write, so writing both the
+// accounts and the instruction accounts resolves them twice; `into_batch`
+// resolves them once.
+impl<'a, 'c, MultisigSigner: AsRef<AccountView>> CpiWriter
+    for TransferCheckedWithHook<'a, '_, 'c, MultisigSigner>
+{
+    #[inline(always)]
+    fn write_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<CpiAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        let mut resolved_instruction_accounts =
+            [const { MaybeUninit::uninit() }; MAX_STATIC_CPI_ACCOUNTS];
+
+        let len = self.resolve_accounts(&mut resolved_instruction_accounts)?;
+
+        // SAFETY: The first `len` resolved instruction accounts are initialized.
+        self.write_resolved_accounts(
+            unsafe { from_raw_parts(resolved_instruction_accounts.as_ptr() as _, len) },
+            accounts,
+        )
+    }
+
+    #[inline(always)]
+    fn write_instruction_accounts<'cpi>(
+        &self,
+        accounts: &mut [MaybeUninit<InstructionAccount<'cpi>>],
+    ) -> Result<usize, ProgramError>
+    where
+        Self: 'cpi,
+    {
+        self.resolve_accounts(accounts)
+    }
+
+    #[inline(always)]
+    fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError> {
+        write_instruction_data(self.amount, self.decimals, data)
+    }
+}
+
+impl<MultisigSigner: AsRef<AccountView>> IntoBatch<Token2022Program>
+    for TransferCheckedWithHook<'_, '_, '_, MultisigSigner>
+{
+    #[inline(always)]
+    fn into_batch<'account, 'state>(self, batch: &mut Batch<'account, 'state>) -> ProgramResult
+    where
+        Self: 'account + 'state,
+    {
+        let mut resolved_instruction_accounts =
+            [const { MaybeUninit::uninit() }; MAX_STATIC_CPI_ACCOUNTS];
+
+        let len = self.resolve_accounts(&mut resolved_instruction_accounts)?;
+
+        // SAFETY: The first `len` resolved instruction accounts are initialized.
+        let resolved_instruction_accounts =
+            unsafe { from_raw_parts(resolved_instruction_accounts.as_ptr() as _, len) };
+
+        batch.push(
+            |accounts| self.write_resolved_accounts(resolved_instruction_accounts, accounts),
+            |accounts| write_instruction_accounts(resolved_instruction_accounts, accounts),
+            |data| write_instruction_data(self.amount, self.decimals, data),
+        )
+    }
+}
+
+/// Writes the resolved instruction accounts.
+#[inline(always)]
+fn write_instruction_accounts<'account, 'out>(
+    resolved_instruction_accounts: &[InstructionAccount<'account>],
+    accounts: &mut [MaybeUninit<InstructionAccount<'out>>],
+) -> Result<usize, ProgramError>
+where
+    'account: 'out,
+{
+    let expected_accounts = resolved_instruction_accounts.len();
+
+    if expected_accounts > accounts.len() {
+        return Err(invalid_argument_error());
+    }
+
+    for (account, resolved_instruction_account) in accounts
+        .iter_mut()
+        .zip(resolved_instruction_accounts.iter())
+    {
+        account.write(resolved_instruction_account.clone());
+    }
+
+    Ok(expected_accounts)
+}
+
+#[inline(always)]
+fn write_instruction_data(
+    amount: u64,
+    decimals: u8,
+    data: &mut [MaybeUninit<u8>],
+) -> Result<usize, ProgramError> {
+    if data.len() < DATA_LEN {
+        return Err(invalid_argument_error());
+    }
+
+    data[0].write(DISCRIMINATOR);
+
+    write_bytes(&mut data[1..9], &amount.to_le_bytes());
+
+    data[9].write(decimals);
+
+    Ok(DATA_LEN)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use {
+        super::*,
+        crate::state::{AccountType, ExtensionType},
+        pinocchio::{
+            test_utils::{ProgramInput, TestAccount},
+            SUCCESS,
+        },
+        std::vec::Vec,
+    };
+
+    const HOOK_PROGRAM: Address = Address::new_from_array([9; 32]);
+
+    const SOURCE: Address = Address::new_from_array([1; 32]);
+
+    const MINT: Address = Address::new_from_array([2; 32]);
+
+    const DESTINATION: Address = Address::new_from_array([3; 32]);
+
+    const AUTHORITY: Address = Address::new_from_array([4; 32]);
+
+    const EXTRA: Address = Address::new_from_array([5; 32]);
+
+    const DATA_ADDRESS: Address = Address::new_from_array([6; 32]);
+
+    fn extra_account_meta(discriminator: u8, config: &[u8], is_signer: bool) -> Vec<u8> {
+        let mut meta = std::vec![0; EXTRA_ACCOUNT_META_LEN];
+        meta[0] = discriminator;
+        meta[1..1 + config.len()].copy_from_slice(config);
+        meta[33] = is_signer as u8;
+        meta
+    }
+
+    fn writable_extra_account_meta(discriminator: u8, config: &[u8], is_signer: bool) -> Vec<u8> {
+        let mut meta = extra_account_meta(discriminator, config, is_signer);
+        meta[34] = 1;
+        meta
+    }
+
+    /// Mint data with a `TransferHook` extension pointing to `program_id`.
+    fn mint_data(program_id: Option<&Address>) -> Vec<u8> {
+        let mut data = std::vec![0; 166];
+        data[165] = AccountType::Mint as u8;
+
+        if let Some(program_id) = program_id {
+            data.extend_from_slice(&(ExtensionType::TransferHook as u16).to_le_bytes());
+            data.extend_from_slice(&64u16.to_le_bytes());
+            data.extend_from_slice(&[0; 32]);
+            data.extend_from_slice(program_id.as_ref());
+        }
+
+        data
+    }
+
+    fn validation_address() -> Address {
+        Address::find_program_address(&[EXTRA_ACCOUNT_METAS_SEED, MINT.as_ref()], &HOOK_PROGRAM).0
+    }
+
+    /// Address derived from the `"seed"` literal and the first 8 bytes of the
+    /// data of the `EXTRA` account.
+    fn seed_derived_address() -> Address {
+        Address::find_program_address(&[b"seed", &[7; 8]], &HOOK_PROGRAM).0
+    }
+
+    /// Validation account data with the extra account metas:
+    ///   - `EXTRA`, readonly;
+    ///   - the source account, writable signer;
+    ///   - `EXTRA` again, writable signer;
+    ///   - a program derived address with a literal and an account data seed;
+    ///   - an address read from the data of the `EXTRA` account.
+    fn validation_data() -> Vec<u8> {
+        let mut seeds = Vec::new();
+        seeds.extend_from_slice(&[1, 4]);
+        seeds.extend_from_slice(b"seed");
+        seeds.extend_from_slice(&[4, 5, 0, 8]);
+
+        let mut data = Vec::new();
+        push_tlv_entry(
+            &mut data,
+            &EXECUTE_DISCRIMINATOR,
+            &[
+                &extra_account_meta(0, EXTRA.as_ref(), false),
+                &writable_extra_account_meta(0, SOURCE.as_ref(), true),
+                &writable_extra_account_meta(0, EXTRA.as_ref(), true),
+                &writable_extra_account_meta(1, &seeds, false),
+                &extra_account_meta(2, &[2, 5, 8], false),
+            ],
+        );
+        data
+    }
+
+    /// Runs `f` with the transfer accounts and the additional accounts.
+    ///
+    /// The `EXTRA` account data holds the seed used by the program derived
+    /// address and, at offset 8, the `DATA_ADDRESS`.
+    fn with_accounts(
+        mint_data: &[u8],
+        include_validation: bool,
+        f: impl FnOnce(&[AccountView], &[&AccountView]),
+    ) {
+        let mut extra_data = std::vec![7; 8];
+        extra_data.extend_from_slice(DATA_ADDRESS.as_ref());
+
+        let mut accounts: Vec<pinocchio::test_utils::InputAccount> = std::vec![
+            TestAccount::new(SOURCE, crate::ID, 0, &[])
+                .writable()
+                .into(),
+            TestAccount::new(MINT, crate::ID, 0, mint_data).into(),
+            TestAccount::new(DESTINATION, crate::ID, 0, &[])
+                .writable()
+                .into(),
+            TestAccount::new(AUTHORITY, Address::default(), 0, &[])
+                .signer()
+                .into(),
+            TestAccount::new(EXTRA, HOOK_PROGRAM, 0, &extra_data)
+                .writable()
+                .into(),
+            TestAccount::new(DATA_ADDRESS, HOOK_PROGRAM, 0, &[]).into(),
+            TestAccount::new(seed_derived_address(), HOOK_PROGRAM, 0, &[])
+                .writable()
+                .into(),
+            TestAccount::new(HOOK_PROGRAM, Address::default(), 0, &[]).into(),
+        ];
+
+        if include_validation {
+            accounts.push(
+                TestAccount::new(validation_address(), HOOK_PROGRAM, 0, &validation_data()).into(),
+            );
+        }
+
+        let mut input = ProgramInput::new(&Address::default(), &accounts, &[]);
+
+        let result = input.process(|_, accounts, _| {
+            // The source account is also resolved as an extra account.
+            let additional_accounts = accounts[4..]
+                .iter()
+                .chain(&accounts[..1])
+                .collect::<Vec<_>>();
+
+            f(accounts, &additional_accounts);
+
+            Ok(())
+        });
+
+        assert_eq!(result, SUCCESS);
+    }
+
+    fn transfer<'a, 'c>(
+        accounts: &'a [AccountView],
+        additional_accounts: &'c [&'a AccountView],
+    ) -> TransferCheckedWithHook<'a, 'static, 'c, AccountView> {
+        TransferCheckedWithHook::new(
+            &crate::ID,
+            &accounts[0],
+            &accounts[1],
+            &accounts[2],
+            &accounts[3],
+            additional_accounts,
+            42,
+            6,
+        )
+    }
+
+    /// Resolves the accounts of `transfer` into `MAX_ACCOUNTS` slots,
+    /// returning the address, writable and signer flags of each account.
+    fn resolve<const MAX_ACCOUNTS: usize>(
+        transfer: &TransferCheckedWithHook<AccountView>,
+    ) -> Result<Vec<(Address, bool, bool)>, ProgramError> {
+        let mut instruction_accounts =
+            [const { MaybeUninit::<InstructionAccount>::uninit() }; MAX_ACCOUNTS];
+
+        let len = transfer.resolve_accounts(&mut instruction_accounts)?;
+
+        instruction_accounts[..len]
+            .iter()
+            .enumerate()
+            .map(|(index, instruction_account)| {
+                // SAFETY: The first `len` instruction accounts are initialized.
+                let instruction_account = unsafe { instruction_account.assume_init_ref() };
+                let account = transfer.resolved_account(index, instruction_account)?;
+
+                assert_eq!(instruction_account.address, account.address());
+
+                Ok((
+                    Address::new_from_array(account.address().to_bytes()),
+                    instruction_account.is_writable,
+                    instruction_account.is_signer,
+                ))
+            })
+            .collect()
+    }
+
+    fn push_tlv_entry(data: &mut Vec<u8>, discriminator: &[u8; 8], metas: &[&[u8]]) {
+        data.extend_from_slice(discriminator);
+        data.extend_from_slice(&(4 + metas.len() as u32 * 35).to_le_bytes());
+        data.extend_from_slice(&(metas.len() as u32).to_le_bytes());
+        metas.iter().for_each(|meta| data.extend_from_slice(meta));
+    }
+
+    #[test]
+    fn resolve_accounts_with_transfer_hook() {
+        with_accounts(
+            &mint_data(Some(&HOOK_PROGRAM)),
+            true,
+            |accounts, additional| {
+                assert_eq!(
+                    resolve::<16>(&transfer(accounts, additional)),
+                    Ok(std::vec![
+                        (SOURCE, true, false),
+                        (MINT, false, false),
+                        (DESTINATION, true, false),
+                        (AUTHORITY, false, true),
+                        (EXTRA, false, false),
+                        // The source account is already in the `Execute` instruction
+                        // accounts as readonly.
+                        (SOURCE, false, false),
+                        // `EXTRA` was already resolved as readonly.
+                        (EXTRA, false, false),
+                        (seed_derived_address(), true, false),
+                        (DATA_ADDRESS, false, false),
+                        (validation_address(), false, false),
+                        (HOOK_PROGRAM, false, false),
+                    ])
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn resolve_accounts_without_transfer_hook() {
+        with_accounts(&mint_data(None), false, |accounts, additional| {
+            assert_eq!(
+                resolve::<16>(&transfer(accounts, additional)),
+                Ok(std::vec![
+                    (SOURCE, true, false),
+                    (MINT, false, false),
+                    (DESTINATION, true, false),
+                    (AUTHORITY, false, true),
+                ])
+            );
+        });
+    }
+
+    #[test]
+    fn resolve_accounts_missing_validation_account() {
+        with_accounts(
+            &mint_data(Some(&HOOK_PROGRAM)),
+            false,
+            |accounts, additional| {
+                assert_eq!(
+                    resolve::<16>(&transfer(accounts, additional)),
+                    Err(ProgramError::NotEnoughAccountKeys)
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn resolve_accounts_exceeding_max_accounts() {
+        with_accounts(
+            &mint_data(Some(&HOOK_PROGRAM)),
+            true,
+            |accounts, additional| {
+                let transfer = transfer(accounts, additional);
+
+                assert!(resolve::<11>(&transfer).is_ok());
+                // Not enough room for the extra accounts.
+                assert_eq!(resolve::<6>(&transfer), Err(ProgramError::InvalidArgument));
+                // Not enough room for the validation account.
+                assert_eq!(resolve::<9>(&transfer), Err(ProgramError::InvalidArgument));
+                // Not enough room for the transfer hook program.
+                assert_eq!(resolve::<10>(&transfer), Err(ProgramError::InvalidArgument));
+            },
+        );
+    }
+
+    #[cfg(feature = "cpi-mock")]
+    #[test]
+    fn invoke_with_transfer_hook() {
+        use pinocchio::cpi::mock;
+
+        mock::reset();
+
+        with_accounts(
+            &mint_data(Some(&HOOK_PROGRAM)),
+            true,
+            |accounts, additional| {
+                let transfer = transfer(accounts, additional);
+
+                assert_eq!(transfer.invoke(), Ok(()));
+
+                let invocations = mock::take_invocations();
+                assert_eq!(invocations.len(), 1);
+                assert_eq!(invocations[0].program_id, crate::ID);
+                assert_eq!(invocations[0].data, [12, 42, 0, 0, 0, 0, 0, 0, 0, 6]);
+
+                let expected = resolve::<16>(&transfer).unwrap();
+                assert_eq!(invocations[0].accounts.len(), expected.len());
+                for (account, (address, is_writable, is_signer)) in
+                    invocations[0].accounts.iter().zip(expected)
+                {
+                    assert_eq!(account.address, address);
+                    assert_eq!(account.is_writable, is_writable);
+                    assert_eq!(account.is_signer, is_signer);
+                }
+
+                // Bounded invocations fail when the accounts do not fit.
+                assert_eq!(
+                    transfer.invoke_with_bounds::<8>(),
+                    Err(ProgramError::InvalidArgument)
+                );
+            },
+        );
+    }
+
+    #[cfg(feature = "cpi-mock")]
+    #[test]
+    fn into_batch_with_transfer_hook() {
+        use pinocchio::cpi::mock;
+
+        mock::reset();
+
+        with_accounts(
+            &mint_data(Some(&HOOK_PROGRAM)),
+            true,
+            |accounts, additional| {
+                let expected = resolve::<16>(&transfer(accounts, additional)).unwrap();
+
+                let mut data = [UNINIT_BYTE; 64];
+                let mut instruction_accounts = [const { MaybeUninit::uninit() }; 16];
+                let mut cpi_accounts = [const { MaybeUninit::uninit() }; 16];
+
+                let mut batch =
+                    Batch::new(&mut data, &mut instruction_accounts, &mut cpi_accounts).unwrap();
+
+                assert_eq!(
+                    transfer(accounts, additional).into_batch(&mut batch),
+                    Ok(())
+                );
+                assert_eq!(batch.invoke(), Ok(()));
+
+                let invocations = mock::take_invocations();
+                assert_eq!(invocations.len(), 1);
+                assert_eq!(
+                    invocations[0].data,
+                    [
+                        255,
+                        expected.len() as u8,
+                        10,
+                        12,
+                        42,
+                        0,
+                        0,
+                        0,
+                        0,
+                        0,
+                        0,
+                        0,
+                        6
+                    ]
+                );
+                assert_eq!(invocations[0].accounts.len(), expected.len());
+                for (account, (address, is_writable, is_signer)) in
+                    invocations[0].accounts.iter().zip(expected)
+                {
+                    assert_eq!(account.address, address);
+                    assert_eq!(account.is_writable, is_writable);
+                    assert_eq!(account.is_signer, is_signer);
+                }
+            },
+        );
+    }
+}
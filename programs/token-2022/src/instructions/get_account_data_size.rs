@@ -1,5 +1,6 @@
 use {
     crate::{
+        cpi::{invoke_unchecked, CpiAccount},
         instructions::{
             invalid_argument_error, write_extension_types_instruction_data,
             EXTENSION_TYPES_INSTRUCTION_DATA_LEN, MAX_EXTENSION_COUNT,
@@ -9,15 +10,12 @@ use {
     },
     core::{marker::PhantomData, mem::MaybeUninit, slice::from_raw_parts},
     pinocchio_token::{
-        instructions::{batch::Batch, CpiWriter},
+        instructions::{batch::Batch, get_return_data_u64, CpiWriter},
         TokenInterface,
     },
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{
-        cpi::{invoke_unchecked, CpiAccount},
-        InstructionAccount, InstructionView,
-    },
+    solana_instruction_view::{InstructionAccount, InstructionView},
     solana_program_error::{ProgramError, ProgramResult},
 };
 
@@ -35,8 +33,8 @@ const MAX_DATA_LEN: usize = EXTENSION_TYPES_INSTRUCTION_DATA_LEN;
 /// Gets the required size of an account for the given mint as a
 /// little-endian `u64`.
 ///
-/// Return data can be fetched using `sol_get_return_data` and deserializing
-/// the return data as a little-endian `u64`.
+/// Use `invoke_and_get_size` to invoke the instruction and read the size from
+/// the return data.
 ///
 /// Accounts expected by this instruction:
 ///
@@ -128,6 +126,20 @@ impl<'account, 'extensions, Program: TokenInterface>
 
         Ok(())
     }
+
+    /// Invokes the instruction with `Program::ID` and returns the required
+    /// size of the account.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::IncorrectProgramId`] if the return data was not
+    /// set by `Program::ID`, or [`ProgramError::InvalidArgument`] if the
+    /// return data is not a `u64`.
+    #[inline(always)]
+    pub fn invoke_and_get_size(&self) -> Result<u64, ProgramError> {
+        self.invoke()?;
+        get_return_data_u64(&Program::ID)
+    }
 }
 
 impl<Program: TokenInterface> CpiWriter for GetAccountDataSize<'_, '_, Program> {
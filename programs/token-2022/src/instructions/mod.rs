@@ -16,7 +16,7 @@ pub use {
     initialize_non_transferable_mint::*,
     pinocchio_token::instructions::{
         batch::IntoBatch, initialize_multisig::MAX_MULTISIG_SIGNERS, set_authority::AuthorityType,
-        unwrap_lamports::Amount,
+        unwrap_lamports::Amount, CpiWriter,
     },
     reallocate::*,
 };
@@ -33,8 +33,10 @@ const EXTENSION_TYPES_INSTRUCTION_DATA_LEN: usize = 1 + MAX_EXTENSION_COUNT * 2;
 ///
 /// Fails on an invalid mint.
 ///
-/// Return data can be fetched using `sol_get_return_data` and deserialized
-/// with `String::from_utf8`.
+/// Use `invoke_and_get_ui_amount` to invoke the instruction and read the
+/// `UiAmount` string from the return data, or
+/// [`StateWithExtensions::amount_to_ui_amount`] (`ui-amount` feature) to
+/// compute the same string without the cross-program invocation.
 ///
 /// WARNING: For mints using the interest-bearing or scaled-ui-amount
 /// extensions, this instruction uses standard floating-point arithmetic to
@@ -47,6 +49,8 @@ const EXTENSION_TYPES_INSTRUCTION_DATA_LEN: usize = 1 + MAX_EXTENSION_COUNT * 2;
 /// Accounts expected by this instruction:
 ///
 ///   0. `[]` The mint to calculate for.
+///
+/// [`StateWithExtensions::amount_to_ui_amount`]: crate::state::StateWithExtensions::amount_to_ui_amount
 pub type AmountToUiAmount<'account> =
     pinocchio_token::instructions::amount_to_ui_amount::AmountToUiAmount<
         'account,
@@ -214,8 +218,8 @@ pub type FreezeAccount<'account, 'multisig, MultisigSigner> =
 /// Gets the required size of an account for the given mint as a
 /// little-endian `u64`.
 ///
-/// Return data can be fetched using `sol_get_return_data` and deserializing
-/// the return data as a little-endian `u64`.
+/// Use `invoke_and_get_size` to invoke the instruction and read the size from
+/// the return data.
 ///
 /// Accounts expected by this instruction:
 ///
@@ -559,8 +563,10 @@ pub type TransferChecked<'account, 'multisig, MultisigSigner> =
 /// Convert a `UiAmount` of tokens to a little-endian `u64` raw Amount,
 /// using the given mint.
 ///
-/// Return data can be fetched using `sol_get_return_data` and deserializing
-/// the return data as a little-endian `u64`.
+/// Use `invoke_and_get_amount` to invoke the instruction and read the amount
+/// from the return data, or [`StateWithExtensions::ui_amount_to_amount`]
+/// (`ui-amount` feature) to compute the same amount without the cross-program
+/// invocation.
 ///
 /// WARNING: For mints using the interest-bearing or scaled-ui-amount
 /// extensions, this instruction uses standard floating-point arithmetic to
@@ -573,6 +579,8 @@ pub type TransferChecked<'account, 'multisig, MultisigSigner> =
 /// Accounts expected by this instruction:
 ///
 ///   0. `[]` The mint to calculate for.
+///
+/// [`StateWithExtensions::ui_amount_to_amount`]: crate::state::StateWithExtensions::ui_amount_to_amount
 pub type UiAmountToAmount<'account, 'amount> =
     pinocchio_token::instructions::ui_amount_to_amount::UiAmountToAmount<
         'account,
@@ -628,6 +636,11 @@ pub type WithdrawExcessLamports<'account, 'multisig, MultisigSigner> =
         Token2022Program,
     >;
 
+#[cold]
+fn account_borrow_failed_error() -> ProgramError {
+    ProgramError::AccountBorrowFailed
+}
+
 #[cold]
 fn invalid_argument_error() -> ProgramError {
     ProgramError::InvalidArgument
@@ -746,4 +759,123 @@ mod tests {
 
         assert_eq!(bytes, &expected);
     }
+
+    #[cfg(feature = "cpi-mock")]
+    #[test]
+    fn batch_of_extension_instructions() {
+        use {
+            crate::{
+                cpi::{
+                    mock::{self, InvokedAccount},
+                    CpiAccount,
+                },
+                instructions::{
+                    pausable::Pause, transfer_fee::SetTransferFee,
+                    transfer_hook::UpdateTransferHook,
+                },
+            },
+            pinocchio::{
+                test_utils::{ProgramInput, TestAccount},
+                SUCCESS,
+            },
+            solana_account_view::AccountView,
+            solana_address::Address,
+            solana_instruction_view::InstructionAccount,
+        };
+
+        let mint = Address::new_from_array([1; 32]);
+        let authority = Address::new_from_array([2; 32]);
+        let hook_program = Address::new_from_array([3; 32]);
+
+        mock::reset();
+        mock::register_handler(crate::ID, |_, _| Ok(()));
+
+        let mut input = ProgramInput::new(
+            &Address::default(),
+            &[
+                TestAccount::new(Address::new_from_array(mint.to_bytes()), crate::ID, 1, &[])
+                    .writable()
+                    .into(),
+                TestAccount::new(
+                    Address::new_from_array(authority.to_bytes()),
+                    Address::default(),
+                    1,
+                    &[],
+                )
+                .signer()
+                .into(),
+            ],
+            &[],
+        );
+
+        let result = input.process(|_, accounts, _| {
+            let mut data = [UNINIT_BYTE; 64];
+            let mut instruction_accounts =
+                [const { MaybeUninit::<InstructionAccount>::uninit() }; 8];
+            let mut cpi_accounts = [const { MaybeUninit::<CpiAccount>::uninit() }; 8];
+
+            let mut batch = Batch::new(&mut data, &mut instruction_accounts, &mut cpi_accounts)?;
+
+            Pause::<&AccountView>::new(&crate::ID, &accounts[0], &accounts[1])
+                .into_batch(&mut batch)?;
+            UpdateTransferHook::<&AccountView>::new(
+                &crate::ID,
+                &accounts[0],
+                &accounts[1],
+                Some(&hook_program),
+            )
+            .into_batch(&mut batch)?;
+            SetTransferFee::<&AccountView>::new(&crate::ID, &accounts[0], &accounts[1], 50, 1_000)
+                .into_batch(&mut batch)?;
+
+            batch.invoke()
+        });
+
+        assert_eq!(result, SUCCESS);
+
+        let mut expected_data = [0; 1 + 3 * 2 + 2 + 34 + 12];
+        expected_data[..5].copy_from_slice(&[255, 2, 2, ExtensionDiscriminator::Pausable as u8, 1]);
+        expected_data[5..9].copy_from_slice(&[
+            2,
+            34,
+            ExtensionDiscriminator::TransferHook as u8,
+            1,
+        ]);
+        expected_data[9..41].copy_from_slice(hook_program.as_ref());
+        expected_data[41..45].copy_from_slice(&[
+            2,
+            12,
+            ExtensionDiscriminator::TransferFee as u8,
+            5,
+        ]);
+        expected_data[45..47].copy_from_slice(&50u16.to_le_bytes());
+        expected_data[47..].copy_from_slice(&1_000u64.to_le_bytes());
+
+        let invocations = mock::take_invocations();
+        assert_eq!(invocations.len(), 1);
+        assert_eq!(invocations[0].program_id, crate::ID);
+        assert_eq!(invocations[0].data, expected_data);
+
+        let mint_account = || InvokedAccount {
+            address: Address::new_from_array(mint.to_bytes()),
+            is_writable: true,
+            is_signer: false,
+        };
+        let authority_account = || InvokedAccount {
+            address: Address::new_from_array(authority.to_bytes()),
+            is_writable: false,
+            is_signer: true,
+        };
+        assert_eq!(
+            invocations[0].accounts,
+            [
+                mint_account(),
+                authority_account(),
+                mint_account(),
+                authority_account(),
+                mint_account(),
+                authority_account(),
+            ]
+        );
+    }
 }
@@ -1,5 +1,6 @@
 use {
     crate::{
+        cpi::{invoke_signed_with_bounds, Signer},
         instructions::{
             write_extension_types_instruction_data, EXTENSION_TYPES_INSTRUCTION_DATA_LEN,
             MAX_EXTENSION_COUNT, MAX_MULTISIG_SIGNERS,
@@ -10,10 +11,7 @@ use {
     core::{mem::MaybeUninit, slice::from_raw_parts},
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{
-        cpi::{invoke_signed_with_bounds, Signer},
-        InstructionAccount, InstructionView,
-    },
+    solana_instruction_view::{InstructionAccount, InstructionView},
     solana_program_error::{ProgramError, ProgramResult},
 };
 
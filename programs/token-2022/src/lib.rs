@@ -1,8 +1,16 @@
 #![no_std]
 
+pub mod decode;
+pub mod error;
 pub mod instructions;
 pub mod state;
 
+// Cross-program invocation helpers. With the `cpi-mock` feature, invocations
+// are routed through the host-side mock runtime of `pinocchio`.
+#[cfg(feature = "cpi-mock")]
+use pinocchio::cpi;
+#[cfg(not(feature = "cpi-mock"))]
+use solana_instruction_view::cpi;
 use {core::mem::MaybeUninit, pinocchio_token::TokenInterface, solana_address::Address};
 
 solana_address::declare_id!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
@@ -0,0 +1,37 @@
+use super::{
+    sealed, AeCiphertext, ElGamalCiphertext, ElGamalPubkey, ExtensionType, ExtensionValue,
+};
+
+/// Confidential mint and burn extension data for mints (196 bytes).
+///
+/// Holds the encrypted supply of a mint whose tokens are minted and burned
+/// confidentially.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfidentialMintBurnExtension {
+    /// The confidential supply of the mint (encrypted by
+    /// `supply_elgamal_pubkey`).
+    pub confidential_supply: ElGamalCiphertext,
+
+    /// The decryptable confidential supply of the mint.
+    pub decryptable_supply: AeCiphertext,
+
+    /// The ElGamal public key used to encrypt the confidential supply.
+    pub supply_elgamal_pubkey: ElGamalPubkey,
+
+    /// The amount burned since the last supply rotation (encrypted by
+    /// `supply_elgamal_pubkey`).
+    pub pending_burn: ElGamalCiphertext,
+}
+
+impl ConfidentialMintBurnExtension {
+    pub const LEN: usize = core::mem::size_of::<ConfidentialMintBurnExtension>();
+}
+
+impl sealed::Sealed for ConfidentialMintBurnExtension {}
+
+// SAFETY: `ConfidentialMintBurnExtension` is repr(C), contains only byte
+// arrays, has no padding, and all bit patterns are valid.
+unsafe impl ExtensionValue for ConfidentialMintBurnExtension {
+    const TYPE: ExtensionType = ExtensionType::ConfidentialMintBurn;
+}
@@ -0,0 +1,71 @@
+use {
+    super::{
+        sealed, AeCiphertext, ElGamalCiphertext, ElGamalPubkey, ExtensionType, ExtensionValue,
+    },
+    solana_zero_copy::unaligned::{Bool, U64},
+};
+
+/// Confidential transfer extension data for token accounts (295 bytes).
+///
+/// Holds the account ElGamal public key, the encrypted pending and available
+/// balances, and the credit counters used to apply pending balances.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfidentialTransferAccountExtension {
+    /// Whether the account is approved by the mint authority to be used.
+    pub approved: Bool,
+
+    /// The public key associated with ElGamal encryption.
+    pub elgamal_pubkey: ElGamalPubkey,
+
+    /// The low 16 bits of the pending balance (encrypted by
+    /// `elgamal_pubkey`).
+    pub pending_balance_lo: ElGamalCiphertext,
+
+    /// The high 48 bits of the pending balance (encrypted by
+    /// `elgamal_pubkey`).
+    pub pending_balance_hi: ElGamalCiphertext,
+
+    /// The available balance (encrypted by `elgamal_pubkey`).
+    pub available_balance: ElGamalCiphertext,
+
+    /// The decryptable available balance (encrypted with the owner's
+    /// authenticated encryption key).
+    pub decryptable_available_balance: AeCiphertext,
+
+    /// Whether incoming confidential transfers are allowed.
+    pub allow_confidential_credits: Bool,
+
+    /// Whether incoming non-confidential transfers are allowed.
+    pub allow_non_confidential_credits: Bool,
+
+    /// The number of incoming transfers since the last pending balance was
+    /// applied.
+    pub pending_balance_credit_counter: U64,
+
+    /// The maximum number of incoming transfers before the pending balance
+    /// must be applied.
+    pub maximum_pending_balance_credit_counter: U64,
+
+    /// The `pending_balance_credit_counter` value expected by the last
+    /// `ApplyPendingBalance` instruction.
+    pub expected_pending_balance_credit_counter: U64,
+
+    /// The actual `pending_balance_credit_counter` when the last
+    /// `ApplyPendingBalance` instruction was executed.
+    pub actual_pending_balance_credit_counter: U64,
+}
+
+impl ConfidentialTransferAccountExtension {
+    pub const LEN: usize = core::mem::size_of::<ConfidentialTransferAccountExtension>();
+}
+
+impl sealed::Sealed for ConfidentialTransferAccountExtension {}
+
+// SAFETY: `ConfidentialTransferAccountExtension` is repr(C), contains only
+// byte arrays, `Bool` (repr(transparent) over `u8`) and `U64`
+// (repr(transparent) over `[u8; 8]`) fields, has no padding, and all bit
+// patterns are valid.
+unsafe impl ExtensionValue for ConfidentialTransferAccountExtension {
+    const TYPE: ExtensionType = ExtensionType::ConfidentialTransferAccount;
+}
@@ -0,0 +1,24 @@
+use super::{sealed, ElGamalCiphertext, ExtensionType, ExtensionValue};
+
+/// Confidential transfer fee extension data for token accounts (64 bytes).
+///
+/// Tracks the fees withheld on this account from inbound confidential
+/// transfers, encrypted by the mint's withdraw withheld authority ElGamal
+/// public key.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfidentialTransferFeeAmountExtension {
+    pub withheld_amount: ElGamalCiphertext,
+}
+
+impl ConfidentialTransferFeeAmountExtension {
+    pub const LEN: usize = core::mem::size_of::<ConfidentialTransferFeeAmountExtension>();
+}
+
+impl sealed::Sealed for ConfidentialTransferFeeAmountExtension {}
+
+// SAFETY: `ConfidentialTransferFeeAmountExtension` is repr(C), contains only
+// a `[u8; 64]`, has no padding, and all bit patterns are valid.
+unsafe impl ExtensionValue for ConfidentialTransferFeeAmountExtension {
+    const TYPE: ExtensionType = ExtensionType::ConfidentialTransferFeeAmount;
+}
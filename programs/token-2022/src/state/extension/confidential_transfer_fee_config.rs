@@ -0,0 +1,42 @@
+use {
+    super::{sealed, ElGamalCiphertext, ElGamalPubkey, ExtensionType, ExtensionValue},
+    solana_address::Address,
+    solana_nullable::MaybeNull,
+    solana_zero_copy::unaligned::Bool,
+};
+
+/// Confidential transfer fee extension data for mints (129 bytes).
+///
+/// Holds the ElGamal public key used to encrypt the fees withheld from
+/// confidential transfers, and the fees withheld on the mint.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfidentialTransferFeeConfigExtension {
+    /// The authority that can set the withdraw withheld authority ElGamal
+    /// public key.
+    pub authority: MaybeNull<Address>,
+
+    /// The ElGamal public key used to encrypt withheld fees.
+    pub withdraw_withheld_authority_elgamal_pubkey: ElGamalPubkey,
+
+    /// Whether harvesting fees to the mint is enabled.
+    pub harvest_to_mint_enabled: Bool,
+
+    /// The withheld fees on the mint (encrypted by
+    /// `withdraw_withheld_authority_elgamal_pubkey`).
+    pub withheld_amount: ElGamalCiphertext,
+}
+
+impl ConfidentialTransferFeeConfigExtension {
+    pub const LEN: usize = core::mem::size_of::<ConfidentialTransferFeeConfigExtension>();
+}
+
+impl sealed::Sealed for ConfidentialTransferFeeConfigExtension {}
+
+// SAFETY: `ConfidentialTransferFeeConfigExtension` is repr(C), contains only
+// a `MaybeNull<Address>` (repr(transparent) over `[u8; 32]`), byte arrays
+// and a `Bool` (repr(transparent) over `u8`), has no padding, and all bit
+// patterns are valid.
+unsafe impl ExtensionValue for ConfidentialTransferFeeConfigExtension {
+    const TYPE: ExtensionType = ExtensionType::ConfidentialTransferFeeConfig;
+}
@@ -0,0 +1,44 @@
+use {
+    super::{sealed, ElGamalPubkey, ExtensionType, ExtensionValue},
+    solana_address::Address,
+    solana_nullable::MaybeNull,
+    solana_zero_copy::unaligned::Bool,
+};
+
+/// Confidential transfer extension data for mints (65 bytes).
+///
+/// Configures confidential transfers for the mint: the authority that can
+/// approve accounts and update the configuration, whether new accounts are
+/// approved automatically, and the optional auditor ElGamal public key (all
+/// zeros if none).
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConfidentialTransferMintExtension {
+    pub authority: MaybeNull<Address>,
+    pub auto_approve_new_accounts: Bool,
+    pub auditor_elgamal_pubkey: ElGamalPubkey,
+}
+
+impl ConfidentialTransferMintExtension {
+    pub const LEN: usize = core::mem::size_of::<ConfidentialTransferMintExtension>();
+
+    /// Return the auditor ElGamal public key, if any.
+    #[inline(always)]
+    pub fn auditor_elgamal_pubkey(&self) -> Option<&ElGamalPubkey> {
+        if self.auditor_elgamal_pubkey == [0; 32] {
+            None
+        } else {
+            Some(&self.auditor_elgamal_pubkey)
+        }
+    }
+}
+
+impl sealed::Sealed for ConfidentialTransferMintExtension {}
+
+// SAFETY: `ConfidentialTransferMintExtension` is repr(C), contains only a
+// `MaybeNull<Address>` (repr(transparent) over `[u8; 32]`), a `Bool`
+// (repr(transparent) over `u8`) and a `[u8; 32]`, has no padding, and all
+// bit patterns are valid.
+unsafe impl ExtensionValue for ConfidentialTransferMintExtension {
+    const TYPE: ExtensionType = ExtensionType::ConfidentialTransferMint;
+}
@@ -0,0 +1,135 @@
+#[cfg(feature = "ui-amount")]
+use {
+    super::ui_amount::{format_ui_amount, parse_ui_amount, powi, round_amount},
+    solana_program_error::ProgramError,
+};
+use {
+    super::{sealed, ExtensionType, ExtensionValue},
+    solana_address::Address,
+    solana_nullable::MaybeNull,
+    solana_zero_copy::unaligned::{I16, I64},
+};
+
+/// One in basis points.
+#[cfg(feature = "ui-amount")]
+const ONE_IN_BASIS_POINTS: f64 = 10_000.;
+
+/// Number of seconds in a year, used to compound the interest.
+#[cfg(feature = "ui-amount")]
+const SECONDS_PER_YEAR: f64 = 60. * 60. * 24. * 365.24;
+
+/// Interest-bearing configuration extension data for mints (52 bytes).
+///
+/// Interest is continuously compounded on the UI amount of token balances,
+/// without changing the raw amounts: `pre_update_average_rate` applies from
+/// `initialization_timestamp` to `last_update_timestamp`, and `current_rate`
+/// from `last_update_timestamp` onwards. Rates are expressed in basis points.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InterestBearingConfigExtension {
+    pub rate_authority: MaybeNull<Address>,
+    pub initialization_timestamp: I64,
+    pub pre_update_average_rate: I16,
+    pub last_update_timestamp: I64,
+    pub current_rate: I16,
+}
+
+impl InterestBearingConfigExtension {
+    pub const LEN: usize = core::mem::size_of::<InterestBearingConfigExtension>();
+}
+
+#[cfg(feature = "ui-amount")]
+impl InterestBearingConfigExtension {
+    #[inline(always)]
+    fn pre_update_exp(&self) -> Option<f64> {
+        let timespan = i64::from(self.last_update_timestamp)
+            .checked_sub(self.initialization_timestamp.into())?;
+        let numerator =
+            (i16::from(self.pre_update_average_rate) as i128).checked_mul(timespan as i128)? as f64;
+
+        Some(libm::exp(
+            numerator / SECONDS_PER_YEAR / ONE_IN_BASIS_POINTS,
+        ))
+    }
+
+    #[inline(always)]
+    fn post_update_exp(&self, unix_timestamp: i64) -> Option<f64> {
+        let timespan = unix_timestamp.checked_sub(self.last_update_timestamp.into())?;
+        let numerator =
+            (i16::from(self.current_rate) as i128).checked_mul(timespan as i128)? as f64;
+
+        Some(libm::exp(
+            numerator / SECONDS_PER_YEAR / ONE_IN_BASIS_POINTS,
+        ))
+    }
+
+    #[inline(always)]
+    fn total_scale(&self, decimals: u8, unix_timestamp: i64) -> Option<f64> {
+        Some(
+            self.pre_update_exp()? * self.post_update_exp(unix_timestamp)?
+                / powi(10., decimals as i32),
+        )
+    }
+
+    /// Convert a raw `amount` into its UI representation at `unix_timestamp`,
+    /// including the accrued interest, copied into `buffer`.
+    ///
+    /// The string matches the one returned by the token program's
+    /// `AmountToUiAmount` instruction, without the cross-program invocation.
+    /// On-chain, `unix_timestamp` is the `Clock` sysvar `unix_timestamp`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] if the calculation overflows
+    /// or the UI amount does not fit in `buffer`.
+    #[inline]
+    pub fn amount_to_ui_amount<'buffer>(
+        &self,
+        amount: u64,
+        decimals: u8,
+        unix_timestamp: i64,
+        buffer: &'buffer mut [u8],
+    ) -> Result<&'buffer str, ProgramError> {
+        let total_scale = self
+            .total_scale(decimals, unix_timestamp)
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        format_ui_amount(amount as f64 * total_scale, decimals, buffer)
+    }
+
+    /// Convert a UI amount at `unix_timestamp`, including the accrued
+    /// interest, into its raw amount.
+    ///
+    /// The result matches the one returned by the token program's
+    /// `UiAmountToAmount` instruction, without the cross-program invocation.
+    /// On-chain, `unix_timestamp` is the `Clock` sysvar `unix_timestamp`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] if `ui_amount` is not a valid
+    /// number or the raw amount does not fit in a `u64`.
+    #[inline]
+    pub fn ui_amount_to_amount(
+        &self,
+        ui_amount: &str,
+        decimals: u8,
+        unix_timestamp: i64,
+    ) -> Result<u64, ProgramError> {
+        let scaled_amount = parse_ui_amount(ui_amount)?;
+        let total_scale = self
+            .total_scale(decimals, unix_timestamp)
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        round_amount(scaled_amount / total_scale)
+    }
+}
+
+impl sealed::Sealed for InterestBearingConfigExtension {}
+
+// SAFETY: `InterestBearingConfigExtension` is repr(C), contains only a
+// `MaybeNull<Address>` (repr(transparent) over `[u8; 32]`), `I64` and `I16`
+// fields (repr(transparent) over byte arrays), has no padding, and all bit
+// patterns are valid.
+unsafe impl ExtensionValue for InterestBearingConfigExtension {
+    const TYPE: ExtensionType = ExtensionType::InterestBearingConfig;
+}
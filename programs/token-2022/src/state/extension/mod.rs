@@ -1,8 +1,14 @@
+pub mod confidential_mint_burn;
+pub mod confidential_transfer_account;
+pub mod confidential_transfer_fee_amount;
+pub mod confidential_transfer_fee_config;
+pub mod confidential_transfer_mint;
 pub mod cpi_guard;
 pub mod default_account_state;
 pub mod group_member_pointer;
 pub mod group_pointer;
 pub mod immutable_owner;
+pub mod interest_bearing_config;
 pub mod memo_transfer;
 pub mod metadata_pointer;
 pub mod mint_close_authority;
@@ -12,21 +18,34 @@ pub mod pausable;
 pub mod pausable_account;
 pub mod permanent_delegate;
 pub mod permissioned_burn;
+pub mod scaled_ui_amount_config;
 mod state;
+pub mod token_group;
+pub mod token_group_member;
+pub mod token_metadata;
 pub mod transfer_fee_amount;
+pub mod transfer_fee_config;
 pub mod transfer_hook;
 pub mod transfer_hook_account;
+mod ui_amount;
 
 use {
     super::{AccountType, Mint, Multisig},
     solana_program_error::ProgramError,
 };
 pub use {
+    crate::error::TokenError,
+    confidential_mint_burn::ConfidentialMintBurnExtension,
+    confidential_transfer_account::ConfidentialTransferAccountExtension,
+    confidential_transfer_fee_amount::ConfidentialTransferFeeAmountExtension,
+    confidential_transfer_fee_config::ConfidentialTransferFeeConfigExtension,
+    confidential_transfer_mint::ConfidentialTransferMintExtension,
     cpi_guard::CpiGuardExtension,
     default_account_state::DefaultAccountStateExtension,
     group_member_pointer::GroupMemberPointerExtension,
     group_pointer::GroupPointerExtension,
     immutable_owner::ImmutableOwnerExtension,
+    interest_bearing_config::InterestBearingConfigExtension,
     memo_transfer::MemoTransferExtension,
     metadata_pointer::MetadataPointerExtension,
     mint_close_authority::MintCloseAuthorityExtension,
@@ -36,12 +55,28 @@ pub use {
     pausable_account::PausableAccountExtension,
     permanent_delegate::PermanentDelegateExtension,
     permissioned_burn::PermissionedBurnExtension,
+    scaled_ui_amount_config::ScaledUiAmountConfigExtension,
     state::{StateWithExtensions, StateWithExtensionsMut},
+    token_group::TokenGroupExtension,
+    token_group_member::TokenGroupMemberExtension,
+    token_metadata::TokenMetadataExtension,
     transfer_fee_amount::TransferFeeAmountExtension,
+    transfer_fee_config::{TransferFee, TransferFeeConfigExtension},
     transfer_hook::TransferHookExtension,
     transfer_hook_account::TransferHookAccountExtension,
+    ui_amount::{amount_to_ui_amount, ui_amount_to_amount},
 };
 
+/// An ElGamal public key (32 bytes).
+pub type ElGamalPubkey = [u8; 32];
+
+/// An ElGamal ciphertext (64 bytes).
+pub type ElGamalCiphertext = [u8; 64];
+
+/// An authenticated encryption ciphertext (36 bytes), e.g., a decryptable
+/// balance.
+pub type AeCiphertext = [u8; 36];
+
 /// Number of `ExtensionType` variants, including `Uninitialized`.
 ///
 /// Useful for conservatively pre-allocating the output buffer when calling
@@ -64,23 +99,6 @@ const TLV_HEADER_LEN: usize = 4;
 /// SPL Token-2022 `TokenError::ExtensionNotFound` discriminant.
 pub const EXTENSION_NOT_FOUND_ERROR_CODE: u32 = TokenError::ExtensionNotFound as u32;
 
-/// Token-2022 error discriminant values used by this state module.
-///
-/// Keep these values aligned with SPL Token-2022's `TokenError`.
-#[repr(u32)]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum TokenError {
-    /// Extension not found in account data.
-    ExtensionNotFound = 48,
-}
-
-impl From<TokenError> for ProgramError {
-    #[inline(always)]
-    fn from(error: TokenError) -> Self {
-        ProgramError::Custom(error as u32)
-    }
-}
-
 mod sealed {
     pub trait Sealed {}
 }
@@ -158,6 +176,15 @@ pub unsafe trait ExtensionValue: sealed::Sealed + 'static {
     const TYPE: ExtensionType;
 }
 
+/// Variable-length extension values that can be decoded from TLV entries
+/// without copying, borrowing from the account data.
+pub trait VariableLenExtensionValue<'a>: sealed::Sealed + Sized {
+    const TYPE: ExtensionType;
+
+    /// Decode the extension value from the TLV entry value bytes.
+    fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError>;
+}
+
 /// Trait for supported token-2022 base account types that can host TLV
 /// extensions.
 pub trait ExtensionBaseState: Sized {
@@ -280,6 +307,7 @@ pub const fn extension_value_len(extension_type: ExtensionType) -> Option<usize>
         ExtensionType::ImmutableOwner => Some(ImmutableOwnerExtension::LEN),
         ExtensionType::NonTransferableAccount => Some(NonTransferableAccountExtension::LEN),
         ExtensionType::PausableAccount => Some(PausableAccountExtension::LEN),
+        ExtensionType::TransferFeeConfig => Some(TransferFeeConfigExtension::LEN),
         ExtensionType::TransferFeeAmount => Some(TransferFeeAmountExtension::LEN),
         ExtensionType::MintCloseAuthority => Some(MintCloseAuthorityExtension::LEN),
         ExtensionType::NonTransferable => Some(NonTransferableExtension::LEN),
@@ -288,7 +316,22 @@ pub const fn extension_value_len(extension_type: ExtensionType) -> Option<usize>
         ExtensionType::MetadataPointer => Some(MetadataPointerExtension::LEN),
         ExtensionType::GroupPointer => Some(GroupPointerExtension::LEN),
         ExtensionType::GroupMemberPointer => Some(GroupMemberPointerExtension::LEN),
+        ExtensionType::TokenGroup => Some(TokenGroupExtension::LEN),
+        ExtensionType::TokenGroupMember => Some(TokenGroupMemberExtension::LEN),
+        ExtensionType::ConfidentialTransferMint => Some(ConfidentialTransferMintExtension::LEN),
+        ExtensionType::ConfidentialTransferAccount => {
+            Some(ConfidentialTransferAccountExtension::LEN)
+        }
+        ExtensionType::ConfidentialTransferFeeConfig => {
+            Some(ConfidentialTransferFeeConfigExtension::LEN)
+        }
+        ExtensionType::ConfidentialTransferFeeAmount => {
+            Some(ConfidentialTransferFeeAmountExtension::LEN)
+        }
+        ExtensionType::ConfidentialMintBurn => Some(ConfidentialMintBurnExtension::LEN),
         ExtensionType::Pausable => Some(PausableExtension::LEN),
+        ExtensionType::InterestBearingConfig => Some(InterestBearingConfigExtension::LEN),
+        ExtensionType::ScaledUiAmount => Some(ScaledUiAmountConfigExtension::LEN),
         _ => None,
     }
 }
@@ -0,0 +1,120 @@
+#[cfg(feature = "ui-amount")]
+use {
+    super::ui_amount::{format_ui_amount, parse_ui_amount, powi, round_amount},
+    solana_program_error::ProgramError,
+};
+use {
+    super::{sealed, ExtensionType, ExtensionValue},
+    solana_address::Address,
+    solana_nullable::MaybeNull,
+    solana_zero_copy::unaligned::I64,
+};
+
+/// Scaled UI amount configuration extension data for mints (56 bytes).
+///
+/// The UI amount of token balances is the raw amount scaled by `multiplier`,
+/// which is replaced by `new_multiplier` from
+/// `new_multiplier_effective_timestamp` onwards.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScaledUiAmountConfigExtension {
+    pub authority: MaybeNull<Address>,
+    multiplier: [u8; 8],
+    pub new_multiplier_effective_timestamp: I64,
+    new_multiplier: [u8; 8],
+}
+
+impl ScaledUiAmountConfigExtension {
+    pub const LEN: usize = core::mem::size_of::<ScaledUiAmountConfigExtension>();
+
+    #[inline(always)]
+    pub fn multiplier(&self) -> f64 {
+        f64::from_le_bytes(self.multiplier)
+    }
+
+    #[inline(always)]
+    pub fn set_multiplier(&mut self, multiplier: f64) {
+        self.multiplier = multiplier.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn new_multiplier(&self) -> f64 {
+        f64::from_le_bytes(self.new_multiplier)
+    }
+
+    #[inline(always)]
+    pub fn set_new_multiplier(&mut self, new_multiplier: f64) {
+        self.new_multiplier = new_multiplier.to_le_bytes();
+    }
+
+    /// Return the multiplier in effect at `unix_timestamp`.
+    #[inline(always)]
+    pub fn current_multiplier(&self, unix_timestamp: i64) -> f64 {
+        if unix_timestamp >= i64::from(self.new_multiplier_effective_timestamp) {
+            self.new_multiplier()
+        } else {
+            self.multiplier()
+        }
+    }
+}
+
+#[cfg(feature = "ui-amount")]
+impl ScaledUiAmountConfigExtension {
+    /// Convert a raw `amount` into its UI representation at `unix_timestamp`,
+    /// copied into `buffer`.
+    ///
+    /// The scaled amount is truncated to a whole number of base units. The
+    /// string matches the one returned by the token program's
+    /// `AmountToUiAmount` instruction, without the cross-program invocation.
+    /// On-chain, `unix_timestamp` is the `Clock` sysvar `unix_timestamp`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] if the UI amount does not fit
+    /// in `buffer`.
+    #[inline]
+    pub fn amount_to_ui_amount<'buffer>(
+        &self,
+        amount: u64,
+        decimals: u8,
+        unix_timestamp: i64,
+        buffer: &'buffer mut [u8],
+    ) -> Result<&'buffer str, ProgramError> {
+        let scaled_amount = libm::trunc(amount as f64 * self.current_multiplier(unix_timestamp));
+
+        format_ui_amount(scaled_amount / powi(10., decimals as i32), decimals, buffer)
+    }
+
+    /// Convert a UI amount at `unix_timestamp` into its raw amount.
+    ///
+    /// The result matches the one returned by the token program's
+    /// `UiAmountToAmount` instruction, without the cross-program invocation.
+    /// On-chain, `unix_timestamp` is the `Clock` sysvar `unix_timestamp`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] if `ui_amount` is not a valid
+    /// number or the raw amount does not fit in a `u64`.
+    #[inline]
+    pub fn ui_amount_to_amount(
+        &self,
+        ui_amount: &str,
+        decimals: u8,
+        unix_timestamp: i64,
+    ) -> Result<u64, ProgramError> {
+        let scaled_amount = parse_ui_amount(ui_amount)?;
+        let total_multiplier = self.current_multiplier(unix_timestamp) / powi(10., decimals as i32);
+
+        round_amount(scaled_amount / total_multiplier)
+    }
+}
+
+impl sealed::Sealed for ScaledUiAmountConfigExtension {}
+
+// SAFETY: `ScaledUiAmountConfigExtension` is repr(C), contains only a
+// `MaybeNull<Address>` (repr(transparent) over `[u8; 32]`), `[u8; 8]` and
+// `I64` (repr(transparent) over `[u8; 8]`) fields, has no padding, and all
+// bit patterns are valid.
+unsafe impl ExtensionValue for ScaledUiAmountConfigExtension {
+    const TYPE: ExtensionType = ExtensionType::ScaledUiAmount;
+}
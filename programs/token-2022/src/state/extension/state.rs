@@ -2,7 +2,7 @@ use {
     super::{
         extension_not_found_error, validate_extension_account_type, validate_mint_extensions_data,
         validate_token_extensions_data, ExtensionBaseState, ExtensionType, ExtensionValue,
-        BASE_ACCOUNT_LEN, TLV_HEADER_LEN, TLV_START_INDEX,
+        VariableLenExtensionValue, BASE_ACCOUNT_LEN, TLV_HEADER_LEN, TLV_START_INDEX,
     },
     crate::{
         state::{Account, AccountType, Mint, Multisig},
@@ -308,6 +308,16 @@ impl<B: ExtensionBaseState> StateWithExtensions<B> {
         let bytes = self.get_extension_bytes(V::TYPE)?;
         extension_from_bytes(bytes)
     }
+
+    /// Return the variable-length extension value `V`, borrowing from the
+    /// account data.
+    #[inline]
+    pub fn get_variable_len_extension<'a, V: VariableLenExtensionValue<'a>>(
+        &'a self,
+    ) -> Result<V, ProgramError> {
+        let bytes = self.get_extension_bytes(V::TYPE)?;
+        V::from_bytes(bytes)
+    }
 }
 
 /// A base state with TLV extension data backed by an unchecked mutable borrow.
@@ -395,6 +405,16 @@ impl<B: ExtensionBaseState> StateWithExtensionsMut<B> {
         extension_from_bytes(bytes)
     }
 
+    /// Return the variable-length extension value `V`, borrowing from the
+    /// account data.
+    #[inline]
+    pub fn get_variable_len_extension<'a, V: VariableLenExtensionValue<'a>>(
+        &'a self,
+    ) -> Result<V, ProgramError> {
+        let bytes = self.get_extension_bytes(V::TYPE)?;
+        V::from_bytes(bytes)
+    }
+
     #[inline]
     pub fn get_extension_mut<V: ExtensionValue>(&mut self) -> Result<&mut V, ProgramError> {
         let bytes = self.get_extension_bytes_mut(V::TYPE)?;
@@ -439,10 +459,15 @@ mod tests {
                 transfer_hook_account::TransferHookAccountExtension, try_calculate_account_len,
                 TokenError, ACCOUNT_TYPE_INDEX, EXTENSION_NOT_FOUND_ERROR_CODE,
             },
-            AccountState, CpiGuardExtension, GroupMemberPointerExtension, GroupPointerExtension,
-            ImmutableOwnerExtension, MemoTransferExtension, MetadataPointerExtension,
-            MintCloseAuthorityExtension, NonTransferableAccountExtension, NonTransferableExtension,
-            PausableAccountExtension, PausableExtension, TransferFeeAmountExtension,
+            AccountState, ConfidentialMintBurnExtension, ConfidentialTransferAccountExtension,
+            ConfidentialTransferFeeAmountExtension, ConfidentialTransferFeeConfigExtension,
+            ConfidentialTransferMintExtension, CpiGuardExtension, GroupMemberPointerExtension,
+            GroupPointerExtension, ImmutableOwnerExtension, InterestBearingConfigExtension,
+            MemoTransferExtension, MetadataPointerExtension, MintCloseAuthorityExtension,
+            NonTransferableAccountExtension, NonTransferableExtension, PausableAccountExtension,
+            PausableExtension, ScaledUiAmountConfigExtension, TokenGroupExtension,
+            TokenGroupMemberExtension, TokenMetadataExtension, TransferFeeAmountExtension,
+            TransferFeeConfigExtension,
         },
         core::{mem::size_of, ptr::copy_nonoverlapping},
         solana_account_view::{RuntimeAccount, NOT_BORROWED},
@@ -1103,6 +1128,10 @@ mod tests {
             try_calculate_account_len::<Mint>(&[ExtensionType::TransferHook]).unwrap(),
             TLV_START_INDEX + TLV_HEADER_LEN + TransferHookExtension::LEN
         );
+        assert_eq!(
+            try_calculate_account_len::<Mint>(&[ExtensionType::TransferFeeConfig]).unwrap(),
+            TLV_START_INDEX + TLV_HEADER_LEN + TransferFeeConfigExtension::LEN
+        );
         assert_eq!(
             try_calculate_account_len::<Mint>(&[ExtensionType::PermissionedBurn]).unwrap(),
             TLV_START_INDEX + TLV_HEADER_LEN + size_of::<Address>()
@@ -1198,6 +1227,16 @@ mod tests {
         assert_eq!(NonTransferableAccountExtension::LEN, 0);
         assert_eq!(PausableAccountExtension::LEN, 0);
         assert_eq!(TransferFeeAmountExtension::LEN, 8);
+        assert_eq!(TransferFeeConfigExtension::LEN, 108);
+        assert_eq!(InterestBearingConfigExtension::LEN, 52);
+        assert_eq!(ScaledUiAmountConfigExtension::LEN, 56);
+        assert_eq!(TokenGroupExtension::LEN, 80);
+        assert_eq!(TokenGroupMemberExtension::LEN, 72);
+        assert_eq!(ConfidentialTransferMintExtension::LEN, 65);
+        assert_eq!(ConfidentialTransferAccountExtension::LEN, 295);
+        assert_eq!(ConfidentialTransferFeeConfigExtension::LEN, 129);
+        assert_eq!(ConfidentialTransferFeeAmountExtension::LEN, 64);
+        assert_eq!(ConfidentialMintBurnExtension::LEN, 196);
     }
 
     #[test]
@@ -1211,6 +1250,28 @@ mod tests {
         assert_eq!(core::mem::align_of::<NonTransferableAccountExtension>(), 1);
         assert_eq!(core::mem::align_of::<PausableAccountExtension>(), 1);
         assert_eq!(core::mem::align_of::<TransferFeeAmountExtension>(), 1);
+        assert_eq!(core::mem::align_of::<TransferFeeConfigExtension>(), 1);
+        assert_eq!(core::mem::align_of::<InterestBearingConfigExtension>(), 1);
+        assert_eq!(core::mem::align_of::<ScaledUiAmountConfigExtension>(), 1);
+        assert_eq!(core::mem::align_of::<TokenGroupExtension>(), 1);
+        assert_eq!(core::mem::align_of::<TokenGroupMemberExtension>(), 1);
+        assert_eq!(
+            core::mem::align_of::<ConfidentialTransferMintExtension>(),
+            1
+        );
+        assert_eq!(
+            core::mem::align_of::<ConfidentialTransferAccountExtension>(),
+            1
+        );
+        assert_eq!(
+            core::mem::align_of::<ConfidentialTransferFeeConfigExtension>(),
+            1
+        );
+        assert_eq!(
+            core::mem::align_of::<ConfidentialTransferFeeAmountExtension>(),
+            1
+        );
+        assert_eq!(core::mem::align_of::<ConfidentialMintBurnExtension>(), 1);
     }
 
     #[test]
@@ -1225,6 +1286,368 @@ mod tests {
         assert_eq!(ext.delegate.as_ref().unwrap().as_ref(), &delegate);
     }
 
+    #[test]
+    fn transfer_fee_config_extension_read_roundtrip() {
+        let mut value = [0u8; TransferFeeConfigExtension::LEN];
+        value[0..32].copy_from_slice(&[1u8; 32]);
+        value[64..72].copy_from_slice(&500u64.to_le_bytes());
+        // Older transfer fee: epoch 0, maximum fee 10, 100 basis points.
+        value[80..88].copy_from_slice(&10u64.to_le_bytes());
+        value[88..90].copy_from_slice(&100u16.to_le_bytes());
+        // Newer transfer fee: epoch 5, maximum fee 1000, 250 basis points.
+        value[90..98].copy_from_slice(&5u64.to_le_bytes());
+        value[98..106].copy_from_slice(&1_000u64.to_le_bytes());
+        value[106..108].copy_from_slice(&250u16.to_le_bytes());
+
+        let mut tlv_data = Vec::new();
+        push_tlv_entry(&mut tlv_data, ExtensionType::TransferFeeConfig, &value);
+        let data = build_mint_data(&tlv_data);
+
+        let mint = StateWithExtensions::<Mint>::from_bytes(&data).unwrap();
+        let ext = mint.get_extension::<TransferFeeConfigExtension>().unwrap();
+        assert_eq!(
+            ext.transfer_fee_config_authority.as_ref().unwrap().as_ref(),
+            &[1u8; 32]
+        );
+        assert!(ext.withdraw_withheld_authority.as_ref().is_none());
+        assert_eq!(u64::from(ext.withheld_amount), 500);
+        assert_eq!(ext.calculate_epoch_fee(4, 10_000), Some(10));
+        assert_eq!(ext.calculate_epoch_fee(5, 10_000), Some(250));
+    }
+
+    #[cfg(feature = "ui-amount")]
+    #[test]
+    fn mint_amount_to_ui_amount_without_extensions() {
+        let mut data = build_mint_data(&[]);
+        data[44] = 6;
+
+        let mint = StateWithExtensions::<Mint>::from_bytes(&data).unwrap();
+        let mut buffer = [0u8; 32];
+        assert_eq!(
+            mint.amount_to_ui_amount(1_500_000, 0, &mut buffer).unwrap(),
+            "1.5"
+        );
+        assert_eq!(mint.ui_amount_to_amount("1.5", 0), Ok(1_500_000));
+        assert_eq!(
+            mint.ui_amount_to_amount("1.0000001", 0),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[cfg(feature = "ui-amount")]
+    #[test]
+    fn mint_amount_to_ui_amount_with_interest_bearing_config() {
+        // 5% interest from the last update, at `unix_timestamp` 0.
+        let mut value = [0u8; InterestBearingConfigExtension::LEN];
+        value[50..52].copy_from_slice(&500i16.to_le_bytes());
+
+        let mut tlv_data = Vec::new();
+        push_tlv_entry(&mut tlv_data, ExtensionType::InterestBearingConfig, &value);
+        let mut data = build_mint_data(&tlv_data);
+        data[44] = 6;
+
+        let mint = StateWithExtensions::<Mint>::from_bytes(&data).unwrap();
+        let mut buffer = [0u8; 32];
+        assert_eq!(
+            mint.amount_to_ui_amount(1_000_000, 0, &mut buffer).unwrap(),
+            "1"
+        );
+        assert_eq!(mint.ui_amount_to_amount("1", 0), Ok(1_000_000));
+
+        // One year later (365.24 days), the scale is `e^0.05`.
+        let one_year = 31_556_736;
+        assert_eq!(
+            mint.amount_to_ui_amount(1_000_000, one_year, &mut buffer)
+                .unwrap(),
+            "1.051271"
+        );
+        assert_eq!(
+            mint.ui_amount_to_amount("1.051271", one_year),
+            Ok(1_000_000)
+        );
+        assert_eq!(
+            mint.ui_amount_to_amount("not a number", one_year),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[cfg(feature = "ui-amount")]
+    #[test]
+    fn mint_amount_to_ui_amount_with_scaled_ui_amount_config() {
+        let mut extension = ScaledUiAmountConfigExtension::default();
+        extension.set_multiplier(2.0);
+        extension.set_new_multiplier(3.0);
+        extension.new_multiplier_effective_timestamp = 10.into();
+
+        // SAFETY: `ScaledUiAmountConfigExtension` is plain data.
+        let value = unsafe {
+            core::slice::from_raw_parts(
+                &extension as *const _ as *const u8,
+                ScaledUiAmountConfigExtension::LEN,
+            )
+        };
+
+        let mut tlv_data = Vec::new();
+        push_tlv_entry(&mut tlv_data, ExtensionType::ScaledUiAmount, value);
+        let mut data = build_mint_data(&tlv_data);
+        data[44] = 1;
+
+        let mint = StateWithExtensions::<Mint>::from_bytes(&data).unwrap();
+        let ext = mint
+            .get_extension::<ScaledUiAmountConfigExtension>()
+            .unwrap();
+        assert_eq!(ext.current_multiplier(9), 2.0);
+        assert_eq!(ext.current_multiplier(10), 3.0);
+
+        let mut buffer = [0u8; 32];
+        assert_eq!(mint.amount_to_ui_amount(5, 9, &mut buffer).unwrap(), "1");
+        assert_eq!(mint.amount_to_ui_amount(5, 10, &mut buffer).unwrap(), "1.5");
+        assert_eq!(mint.ui_amount_to_amount("1.5", 10), Ok(5));
+        assert_eq!(
+            mint.ui_amount_to_amount("-1", 10),
+            Err(ProgramError::InvalidArgument)
+        );
+
+        // The scaled amount is truncated to whole base units.
+        assert_eq!(ext.amount_to_ui_amount(3, 0, 0, &mut buffer).unwrap(), "6");
+        let mut extension = extension.clone();
+        extension.set_new_multiplier(0.5);
+        assert_eq!(
+            extension
+                .amount_to_ui_amount(3, 0, 10, &mut buffer)
+                .unwrap(),
+            "1"
+        );
+    }
+
+    fn push_borsh_str(buffer: &mut Vec<u8>, value: &str) {
+        buffer.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        buffer.extend_from_slice(value.as_bytes());
+    }
+
+    #[test]
+    fn token_metadata_extension_read_roundtrip() {
+        let mut value = Vec::new();
+        value.extend_from_slice(&[1u8; 32]);
+        value.extend_from_slice(&[2u8; 32]);
+        push_borsh_str(&mut value, "Token");
+        push_borsh_str(&mut value, "TKN");
+        push_borsh_str(&mut value, "https://example.com/token.json");
+        value.extend_from_slice(&2u32.to_le_bytes());
+        push_borsh_str(&mut value, "color");
+        push_borsh_str(&mut value, "blue");
+        push_borsh_str(&mut value, "size");
+        push_borsh_str(&mut value, "");
+
+        let mut tlv_data = Vec::new();
+        push_tlv_entry(&mut tlv_data, ExtensionType::TokenMetadata, &value);
+        let data = build_mint_data(&tlv_data);
+
+        let mint = StateWithExtensions::<Mint>::from_bytes(&data).unwrap();
+        let metadata = mint
+            .get_variable_len_extension::<TokenMetadataExtension>()
+            .unwrap();
+        assert_eq!(
+            metadata.update_authority.as_ref().unwrap().as_ref(),
+            &[1u8; 32]
+        );
+        assert_eq!(metadata.mint.as_ref(), &[2u8; 32]);
+        assert_eq!(metadata.name, "Token");
+        assert_eq!(metadata.symbol, "TKN");
+        assert_eq!(metadata.uri, "https://example.com/token.json");
+        assert_eq!(metadata.additional_metadata().len(), 2);
+        assert_eq!(
+            metadata.additional_metadata().collect::<Vec<_>>(),
+            [("color", "blue"), ("size", "")]
+        );
+        assert_eq!(metadata.get_additional_metadata("size"), Some(""));
+        assert_eq!(metadata.get_additional_metadata("weight"), None);
+
+        // Truncated, trailing and invalid UTF-8 data.
+        for invalid in [
+            &value[..value.len() - 1],
+            &[value.as_slice(), &[0]].concat(),
+            &[&value[..68], &[0xff], &value[69..]].concat(),
+        ] {
+            assert_eq!(
+                TokenMetadataExtension::from_bytes(invalid),
+                Err(ProgramError::InvalidAccountData)
+            );
+        }
+
+        assert!(is_extension_not_found_error(
+            &StateWithExtensions::<Mint>::from_bytes(&build_mint_data(&[]))
+                .unwrap()
+                .get_variable_len_extension::<TokenMetadataExtension>()
+                .unwrap_err()
+        ));
+    }
+
+    #[test]
+    fn token_group_extensions_read_roundtrip() {
+        let mut group = [0u8; TokenGroupExtension::LEN];
+        group[32..64].copy_from_slice(&[3u8; 32]);
+        group[64..72].copy_from_slice(&2u64.to_le_bytes());
+        group[72..80].copy_from_slice(&10u64.to_le_bytes());
+
+        let mut member = [0u8; TokenGroupMemberExtension::LEN];
+        member[0..32].copy_from_slice(&[3u8; 32]);
+        member[32..64].copy_from_slice(&[3u8; 32]);
+        member[64..72].copy_from_slice(&1u64.to_le_bytes());
+
+        let mut tlv_data = Vec::new();
+        push_tlv_entry(&mut tlv_data, ExtensionType::TokenGroup, &group);
+        push_tlv_entry(&mut tlv_data, ExtensionType::TokenGroupMember, &member);
+        let data = build_mint_data(&tlv_data);
+
+        let mint = StateWithExtensions::<Mint>::from_bytes(&data).unwrap();
+
+        let group = mint.get_extension::<TokenGroupExtension>().unwrap();
+        assert!(group.update_authority.as_ref().is_none());
+        assert_eq!(group.mint.as_ref(), &[3u8; 32]);
+        assert_eq!(u64::from(group.size), 2);
+        assert_eq!(u64::from(group.max_size), 10);
+
+        let member = mint.get_extension::<TokenGroupMemberExtension>().unwrap();
+        assert_eq!(member.mint, group.mint);
+        assert_eq!(member.group.as_ref(), &[3u8; 32]);
+        assert_eq!(u64::from(member.member_number), 1);
+
+        assert_eq!(
+            try_calculate_account_len::<Mint>(&[
+                ExtensionType::TokenGroup,
+                ExtensionType::TokenGroupMember,
+            ])
+            .unwrap(),
+            data.len()
+        );
+    }
+
+    #[test]
+    fn confidential_transfer_mint_extensions_read_roundtrip() {
+        let mut mint_value = [0u8; ConfidentialTransferMintExtension::LEN];
+        mint_value[0..32].copy_from_slice(&[1u8; 32]);
+        mint_value[32] = 1;
+
+        let mut fee_config = [0u8; ConfidentialTransferFeeConfigExtension::LEN];
+        fee_config[32..64].copy_from_slice(&[2u8; 32]);
+        fee_config[64] = 1;
+        fee_config[65..129].copy_from_slice(&[3u8; 64]);
+
+        let mut mint_burn = [0u8; ConfidentialMintBurnExtension::LEN];
+        mint_burn[0..64].copy_from_slice(&[4u8; 64]);
+        mint_burn[64..100].copy_from_slice(&[5u8; 36]);
+        mint_burn[100..132].copy_from_slice(&[6u8; 32]);
+        mint_burn[132..196].copy_from_slice(&[7u8; 64]);
+
+        let mut tlv_data = Vec::new();
+        push_tlv_entry(
+            &mut tlv_data,
+            ExtensionType::ConfidentialTransferMint,
+            &mint_value,
+        );
+        push_tlv_entry(
+            &mut tlv_data,
+            ExtensionType::ConfidentialTransferFeeConfig,
+            &fee_config,
+        );
+        push_tlv_entry(
+            &mut tlv_data,
+            ExtensionType::ConfidentialMintBurn,
+            &mint_burn,
+        );
+        let data = build_mint_data(&tlv_data);
+
+        let mint = StateWithExtensions::<Mint>::from_bytes(&data).unwrap();
+
+        let ext = mint
+            .get_extension::<ConfidentialTransferMintExtension>()
+            .unwrap();
+        assert_eq!(ext.authority.as_ref().unwrap().as_ref(), &[1u8; 32]);
+        assert!(bool::from(ext.auto_approve_new_accounts));
+        assert!(ext.auditor_elgamal_pubkey().is_none());
+
+        let ext = mint
+            .get_extension::<ConfidentialTransferFeeConfigExtension>()
+            .unwrap();
+        assert!(ext.authority.as_ref().is_none());
+        assert_eq!(ext.withdraw_withheld_authority_elgamal_pubkey, [2u8; 32]);
+        assert!(bool::from(ext.harvest_to_mint_enabled));
+        assert_eq!(ext.withheld_amount, [3u8; 64]);
+
+        let ext = mint
+            .get_extension::<ConfidentialMintBurnExtension>()
+            .unwrap();
+        assert_eq!(ext.confidential_supply, [4u8; 64]);
+        assert_eq!(ext.decryptable_supply, [5u8; 36]);
+        assert_eq!(ext.supply_elgamal_pubkey, [6u8; 32]);
+        assert_eq!(ext.pending_burn, [7u8; 64]);
+    }
+
+    #[test]
+    fn confidential_transfer_account_extensions_read_roundtrip() {
+        let mut value = [0u8; ConfidentialTransferAccountExtension::LEN];
+        value[0] = 1;
+        value[1..33].copy_from_slice(&[1u8; 32]);
+        value[33..97].copy_from_slice(&[2u8; 64]);
+        value[97..161].copy_from_slice(&[3u8; 64]);
+        value[161..225].copy_from_slice(&[4u8; 64]);
+        value[225..261].copy_from_slice(&[5u8; 36]);
+        value[261] = 1;
+        value[263..271].copy_from_slice(&3u64.to_le_bytes());
+        value[271..279].copy_from_slice(&65_536u64.to_le_bytes());
+        value[279..287].copy_from_slice(&2u64.to_le_bytes());
+        value[287..295].copy_from_slice(&1u64.to_le_bytes());
+
+        let mut tlv_data = Vec::new();
+        push_tlv_entry(
+            &mut tlv_data,
+            ExtensionType::ConfidentialTransferAccount,
+            &value,
+        );
+        push_tlv_entry(
+            &mut tlv_data,
+            ExtensionType::ConfidentialTransferFeeAmount,
+            &[8u8; 64],
+        );
+        let data = build_token_data(&tlv_data);
+
+        let account = StateWithExtensions::<Account>::from_bytes(&data).unwrap();
+
+        let ext = account
+            .get_extension::<ConfidentialTransferAccountExtension>()
+            .unwrap();
+        assert!(bool::from(ext.approved));
+        assert_eq!(ext.elgamal_pubkey, [1u8; 32]);
+        assert_eq!(ext.pending_balance_lo, [2u8; 64]);
+        assert_eq!(ext.pending_balance_hi, [3u8; 64]);
+        assert_eq!(ext.available_balance, [4u8; 64]);
+        assert_eq!(ext.decryptable_available_balance, [5u8; 36]);
+        assert!(bool::from(ext.allow_confidential_credits));
+        assert!(!bool::from(ext.allow_non_confidential_credits));
+        assert_eq!(u64::from(ext.pending_balance_credit_counter), 3);
+        assert_eq!(
+            u64::from(ext.maximum_pending_balance_credit_counter),
+            65_536
+        );
+        assert_eq!(u64::from(ext.expected_pending_balance_credit_counter), 2);
+        assert_eq!(u64::from(ext.actual_pending_balance_credit_counter), 1);
+
+        let ext = account
+            .get_extension::<ConfidentialTransferFeeAmountExtension>()
+            .unwrap();
+        assert_eq!(ext.withheld_amount, [8u8; 64]);
+
+        assert_eq!(
+            try_calculate_account_len::<Account>(&[
+                ExtensionType::ConfidentialTransferAccount,
+                ExtensionType::ConfidentialTransferFeeAmount,
+            ])
+            .unwrap(),
+            data.len()
+        );
+    }
+
     #[test]
     fn transfer_hook_extension_read_roundtrip() {
         let mut value = [0u8; 64];
@@ -0,0 +1,174 @@
+use {
+    super::{sealed, ExtensionType, VariableLenExtensionValue},
+    solana_address::Address,
+    solana_nullable::MaybeNull,
+    solana_program_error::ProgramError,
+};
+
+/// Token metadata extension data for mints (variable length).
+///
+/// The metadata is stored using the borsh layout of the
+/// `spl-token-metadata-interface` `TokenMetadata`:
+///
+///   - update authority (32 bytes, all zeros if none)
+///   - mint (32 bytes)
+///   - name, symbol and uri (`u32` length followed by UTF-8 bytes each)
+///   - additional metadata (`u32` count followed by key/value string pairs)
+///
+/// Strings are borrowed from the account data without copying.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenMetadataExtension<'a> {
+    /// The authority that can sign to update the metadata.
+    pub update_authority: &'a MaybeNull<Address>,
+
+    /// The associated mint, used to counter spoofing to be sure that metadata
+    /// belongs to a particular mint.
+    pub mint: &'a Address,
+
+    /// The longer name of the token.
+    pub name: &'a str,
+
+    /// The shortened symbol for the token.
+    pub symbol: &'a str,
+
+    /// The URI pointing to richer metadata.
+    pub uri: &'a str,
+
+    /// Any additional metadata about the token as key-value pairs.
+    additional_metadata: AdditionalMetadata<'a>,
+}
+
+impl<'a> TokenMetadataExtension<'a> {
+    /// Return an iterator over the additional metadata key-value pairs.
+    #[inline(always)]
+    pub fn additional_metadata(&self) -> AdditionalMetadata<'a> {
+        self.additional_metadata.clone()
+    }
+
+    /// Return the value of the additional metadata `key`, if present.
+    #[inline]
+    pub fn get_additional_metadata(&self, key: &str) -> Option<&'a str> {
+        self.additional_metadata()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| value)
+    }
+}
+
+impl sealed::Sealed for TokenMetadataExtension<'_> {}
+
+impl<'a> VariableLenExtensionValue<'a> for TokenMetadataExtension<'a> {
+    const TYPE: ExtensionType = ExtensionType::TokenMetadata;
+
+    fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        let mut reader = Reader { bytes };
+
+        let update_authority = reader.read_address()?;
+        let mint = reader.read_address()?;
+        let name = reader.read_str()?;
+        let symbol = reader.read_str()?;
+        let uri = reader.read_str()?;
+
+        let count = reader.read_u32()?;
+        let pairs = reader.bytes;
+
+        // Validate all pairs, which must fill the remaining bytes.
+        for _ in 0..count {
+            reader.read_str()?;
+            reader.read_str()?;
+        }
+
+        if !reader.bytes.is_empty() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            // SAFETY: `MaybeNull<Address>` is `repr(transparent)` over
+            // `Address`, with alignment 1.
+            update_authority: unsafe {
+                &*(update_authority as *const Address as *const MaybeNull<Address>)
+            },
+            mint,
+            name,
+            symbol,
+            uri,
+            additional_metadata: AdditionalMetadata { count, pairs },
+        })
+    }
+}
+
+/// Iterator over the additional metadata key-value pairs of a
+/// [`TokenMetadataExtension`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdditionalMetadata<'a> {
+    /// Number of remaining pairs.
+    count: u32,
+
+    /// The remaining (validated) pairs.
+    pairs: &'a [u8],
+}
+
+impl<'a> Iterator for AdditionalMetadata<'a> {
+    type Item = (&'a str, &'a str);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let mut reader = Reader { bytes: self.pairs };
+        // The pairs were validated when parsing the extension.
+        let key = reader.read_str().ok()?;
+        let value = reader.read_str().ok()?;
+
+        self.count -= 1;
+        self.pairs = reader.bytes;
+
+        Some((key, value))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.count as usize, Some(self.count as usize))
+    }
+}
+
+impl ExactSizeIterator for AdditionalMetadata<'_> {}
+
+/// Borsh reader over the extension bytes.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    #[inline(always)]
+    fn read(&mut self, len: usize) -> Result<&'a [u8], ProgramError> {
+        if self.bytes.len() < len {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (value, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Ok(value)
+    }
+
+    #[inline(always)]
+    fn read_u32(&mut self) -> Result<u32, ProgramError> {
+        let bytes = self.read(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    #[inline(always)]
+    fn read_address(&mut self) -> Result<&'a Address, ProgramError> {
+        let bytes = self.read(size_of::<Address>())?;
+        // SAFETY: `bytes` is 32 bytes long and `Address` has alignment 1.
+        Ok(unsafe { &*(bytes.as_ptr() as *const Address) })
+    }
+
+    #[inline(always)]
+    fn read_str(&mut self) -> Result<&'a str, ProgramError> {
+        let len = self.read_u32()? as usize;
+        core::str::from_utf8(self.read(len)?).map_err(|_| ProgramError::InvalidAccountData)
+    }
+}
@@ -0,0 +1,229 @@
+use {
+    super::{sealed, ExtensionType, ExtensionValue},
+    solana_address::Address,
+    solana_nullable::MaybeNull,
+    solana_zero_copy::unaligned::{U16, U64},
+};
+
+/// Maximum transfer fee, in basis points (100%).
+pub const MAX_FEE_BASIS_POINTS: u16 = 10_000;
+
+/// [`MAX_FEE_BASIS_POINTS`] as `u128`, used in the fee calculations.
+const ONE_IN_BASIS_POINTS: u128 = MAX_FEE_BASIS_POINTS as u128;
+
+/// Transfer fee applied from a given epoch (18 bytes).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TransferFee {
+    /// First epoch where the transfer fee takes effect.
+    pub epoch: U64,
+
+    /// Maximum fee assessed on transfers, expressed as an amount of tokens.
+    pub maximum_fee: U64,
+
+    /// Amount of transfer collected as fees, expressed as basis points of
+    /// the transfer amount (increments of 0.01%).
+    pub transfer_fee_basis_points: U16,
+}
+
+impl TransferFee {
+    pub const LEN: usize = core::mem::size_of::<TransferFee>();
+
+    /// Calculate the fee assessed on a transfer of `pre_fee_amount` tokens.
+    ///
+    /// The fee is rounded up and capped at `maximum_fee`, matching the
+    /// token program. Returns `None` on overflow.
+    #[inline]
+    pub fn calculate_fee(&self, pre_fee_amount: u64) -> Option<u64> {
+        let transfer_fee_basis_points = u16::from(self.transfer_fee_basis_points) as u128;
+
+        if transfer_fee_basis_points == 0 || pre_fee_amount == 0 {
+            Some(0)
+        } else {
+            let numerator = (pre_fee_amount as u128).checked_mul(transfer_fee_basis_points)?;
+            let raw_fee: u64 = ceil_div(numerator, ONE_IN_BASIS_POINTS)?.try_into().ok()?;
+
+            Some(core::cmp::min(raw_fee, u64::from(self.maximum_fee)))
+        }
+    }
+
+    /// Calculate the amount received by the destination of a transfer of
+    /// `pre_fee_amount` tokens.
+    #[inline]
+    pub fn calculate_post_fee_amount(&self, pre_fee_amount: u64) -> Option<u64> {
+        pre_fee_amount.checked_sub(self.calculate_fee(pre_fee_amount)?)
+    }
+
+    /// Calculate the amount that must be transferred so that the destination
+    /// receives `post_fee_amount` tokens.
+    #[inline]
+    pub fn calculate_pre_fee_amount(&self, post_fee_amount: u64) -> Option<u64> {
+        let maximum_fee = u64::from(self.maximum_fee);
+        let transfer_fee_basis_points = u16::from(self.transfer_fee_basis_points) as u128;
+
+        match (transfer_fee_basis_points, post_fee_amount) {
+            (0, _) => Some(post_fee_amount),
+            (_, 0) => Some(0),
+            (ONE_IN_BASIS_POINTS, _) => maximum_fee.checked_add(post_fee_amount),
+            _ => {
+                let numerator = (post_fee_amount as u128).checked_mul(ONE_IN_BASIS_POINTS)?;
+                let denominator = ONE_IN_BASIS_POINTS.checked_sub(transfer_fee_basis_points)?;
+                let raw_pre_fee_amount = ceil_div(numerator, denominator)?;
+
+                if raw_pre_fee_amount.checked_sub(post_fee_amount as u128)? >= maximum_fee as u128 {
+                    post_fee_amount.checked_add(maximum_fee)
+                } else {
+                    u64::try_from(raw_pre_fee_amount).ok()
+                }
+            }
+        }
+    }
+
+    /// Calculate the fee assessed on the transfer whose destination receives
+    /// `post_fee_amount` tokens.
+    #[inline]
+    pub fn calculate_inverse_fee(&self, post_fee_amount: u64) -> Option<u64> {
+        let pre_fee_amount = self.calculate_pre_fee_amount(post_fee_amount)?;
+        self.calculate_fee(pre_fee_amount)
+    }
+}
+
+/// Transfer fee configuration extension data for mints (108 bytes).
+///
+/// Holds the transfer fee authorities, the fees withheld on the mint and two
+/// transfer fees: `newer_transfer_fee` takes effect from its epoch onwards,
+/// while `older_transfer_fee` applies to earlier epochs. This allows the
+/// transfer fee to be updated without affecting the current epoch.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TransferFeeConfigExtension {
+    pub transfer_fee_config_authority: MaybeNull<Address>,
+    pub withdraw_withheld_authority: MaybeNull<Address>,
+    pub withheld_amount: U64,
+    pub older_transfer_fee: TransferFee,
+    pub newer_transfer_fee: TransferFee,
+}
+
+impl TransferFeeConfigExtension {
+    pub const LEN: usize = core::mem::size_of::<TransferFeeConfigExtension>();
+
+    /// Return the transfer fee in effect at `epoch`.
+    #[inline(always)]
+    pub fn get_epoch_fee(&self, epoch: u64) -> &TransferFee {
+        if epoch >= u64::from(self.newer_transfer_fee.epoch) {
+            &self.newer_transfer_fee
+        } else {
+            &self.older_transfer_fee
+        }
+    }
+
+    /// Calculate the fee assessed on a transfer of `pre_fee_amount` tokens
+    /// at `epoch`.
+    ///
+    /// This is the `fee` expected by `TransferCheckedWithFee`.
+    #[inline]
+    pub fn calculate_epoch_fee(&self, epoch: u64, pre_fee_amount: u64) -> Option<u64> {
+        self.get_epoch_fee(epoch).calculate_fee(pre_fee_amount)
+    }
+
+    /// Calculate the fee assessed at `epoch` on the transfer whose
+    /// destination receives `post_fee_amount` tokens.
+    #[inline]
+    pub fn calculate_inverse_epoch_fee(&self, epoch: u64, post_fee_amount: u64) -> Option<u64> {
+        self.get_epoch_fee(epoch)
+            .calculate_inverse_fee(post_fee_amount)
+    }
+}
+
+impl sealed::Sealed for TransferFeeConfigExtension {}
+
+// SAFETY: `TransferFeeConfigExtension` is repr(C), contains only
+// `MaybeNull<Address>` fields (repr(transparent) over `[u8; 32]`), a `U64`
+// and `TransferFee` values, which are repr(C) over `U64` and `U16`
+// (repr(transparent) over byte arrays). It has no padding, and all bit
+// patterns are valid.
+unsafe impl ExtensionValue for TransferFeeConfigExtension {
+    const TYPE: ExtensionType = ExtensionType::TransferFeeConfig;
+}
+
+/// Integer division rounding up.
+#[inline(always)]
+fn ceil_div(numerator: u128, denominator: u128) -> Option<u128> {
+    numerator
+        .checked_add(denominator)?
+        .checked_sub(1)?
+        .checked_div(denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer_fee(transfer_fee_basis_points: u16, maximum_fee: u64) -> TransferFee {
+        TransferFee {
+            epoch: 0.into(),
+            maximum_fee: maximum_fee.into(),
+            transfer_fee_basis_points: transfer_fee_basis_points.into(),
+        }
+    }
+
+    #[test]
+    fn calculate_fee_rounds_up_and_caps() {
+        let fee = transfer_fee(100, u64::MAX);
+        assert_eq!(fee.calculate_fee(0), Some(0));
+        assert_eq!(fee.calculate_fee(1), Some(1));
+        assert_eq!(fee.calculate_fee(100), Some(1));
+        assert_eq!(fee.calculate_fee(101), Some(2));
+        assert_eq!(fee.calculate_fee(10_000), Some(100));
+        assert_eq!(fee.calculate_post_fee_amount(10_000), Some(9_900));
+
+        let fee = transfer_fee(100, 5);
+        assert_eq!(fee.calculate_fee(10_000), Some(5));
+
+        let fee = transfer_fee(0, 5);
+        assert_eq!(fee.calculate_fee(10_000), Some(0));
+
+        let fee = transfer_fee(MAX_FEE_BASIS_POINTS, u64::MAX);
+        assert_eq!(fee.calculate_fee(u64::MAX), Some(u64::MAX));
+    }
+
+    #[test]
+    fn calculate_inverse_fee_matches_forward_fee() {
+        for (basis_points, maximum_fee) in [(1, u64::MAX), (100, 50), (250, u64::MAX), (9_999, 7)] {
+            let fee = transfer_fee(basis_points, maximum_fee);
+
+            for post_fee_amount in [0, 1, 99, 100, 101, 5_000, 1_000_000] {
+                let pre_fee_amount = fee.calculate_pre_fee_amount(post_fee_amount).unwrap();
+                let inverse_fee = fee.calculate_inverse_fee(post_fee_amount).unwrap();
+
+                assert_eq!(fee.calculate_fee(pre_fee_amount), Some(inverse_fee));
+                assert_eq!(pre_fee_amount - inverse_fee, post_fee_amount);
+            }
+        }
+
+        let fee = transfer_fee(MAX_FEE_BASIS_POINTS, 10);
+        assert_eq!(fee.calculate_pre_fee_amount(100), Some(110));
+        assert_eq!(fee.calculate_inverse_fee(100), Some(10));
+
+        let fee = transfer_fee(0, 10);
+        assert_eq!(fee.calculate_inverse_fee(100), Some(0));
+    }
+
+    #[test]
+    fn get_epoch_fee_selects_newer_fee_from_its_epoch() {
+        let config = TransferFeeConfigExtension {
+            older_transfer_fee: transfer_fee(100, u64::MAX),
+            newer_transfer_fee: TransferFee {
+                epoch: 10.into(),
+                ..transfer_fee(200, u64::MAX)
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(config.get_epoch_fee(9), &config.older_transfer_fee);
+        assert_eq!(config.get_epoch_fee(10), &config.newer_transfer_fee);
+        assert_eq!(config.calculate_epoch_fee(9, 10_000), Some(100));
+        assert_eq!(config.calculate_epoch_fee(11, 10_000), Some(200));
+        assert_eq!(config.calculate_inverse_epoch_fee(11, 9_800), Some(200));
+    }
+}
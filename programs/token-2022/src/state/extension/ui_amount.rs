@@ -0,0 +1,375 @@
+//! UI amount conversions, matching the token program's `AmountToUiAmount`
+//! and `UiAmountToAmount` instructions.
+
+#[cfg(feature = "ui-amount")]
+use {
+    super::{
+        is_extension_not_found_error, InterestBearingConfigExtension,
+        ScaledUiAmountConfigExtension, StateWithExtensions,
+    },
+    crate::state::Mint,
+};
+use {
+    core::fmt::{self, Write},
+    solana_program_error::ProgramError,
+};
+
+#[cfg(feature = "ui-amount")]
+impl StateWithExtensions<Mint> {
+    /// Convert a raw `amount` into its UI representation, copied into
+    /// `buffer`.
+    ///
+    /// Applies the mint's `InterestBearingConfig` or `ScaledUiAmount`
+    /// extension at `unix_timestamp`, if present. The string matches the one
+    /// returned by the token program's `AmountToUiAmount` instruction, which
+    /// avoids the cross-program invocation and return data roundtrip.
+    ///
+    /// On-chain, `unix_timestamp` is the `Clock` sysvar `unix_timestamp`:
+    ///
+    /// ```ignore
+    /// let mint = StateWithExtensions::<Mint>::from_account_view(mint)?;
+    /// let mut buffer = [0u8; 64];
+    /// let ui_amount =
+    ///     mint.amount_to_ui_amount(amount, Clock::get()?.unix_timestamp, &mut buffer)?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] if the calculation overflows
+    /// or the UI amount does not fit in `buffer`.
+    #[inline]
+    pub fn amount_to_ui_amount<'buffer>(
+        &self,
+        amount: u64,
+        unix_timestamp: i64,
+        buffer: &'buffer mut [u8],
+    ) -> Result<&'buffer str, ProgramError> {
+        let decimals = self.base.decimals();
+
+        match self.get_extension::<InterestBearingConfigExtension>() {
+            Ok(extension) => {
+                return extension.amount_to_ui_amount(amount, decimals, unix_timestamp, buffer)
+            }
+            Err(error) if !is_extension_not_found_error(&error) => return Err(error),
+            Err(_) => (),
+        }
+
+        match self.get_extension::<ScaledUiAmountConfigExtension>() {
+            Ok(extension) => {
+                return extension.amount_to_ui_amount(amount, decimals, unix_timestamp, buffer)
+            }
+            Err(error) if !is_extension_not_found_error(&error) => return Err(error),
+            Err(_) => (),
+        }
+
+        amount_to_ui_amount(amount, decimals, buffer)
+    }
+
+    /// Convert a UI amount into its raw amount.
+    ///
+    /// Applies the mint's `InterestBearingConfig` or `ScaledUiAmount`
+    /// extension at `unix_timestamp`, if present. The result matches the one
+    /// returned by the token program's `UiAmountToAmount` instruction, which
+    /// avoids the cross-program invocation and return data roundtrip.
+    ///
+    /// On-chain, `unix_timestamp` is the `Clock` sysvar `unix_timestamp`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] if `ui_amount` is not a valid
+    /// amount or the raw amount does not fit in a `u64`.
+    #[inline]
+    pub fn ui_amount_to_amount(
+        &self,
+        ui_amount: &str,
+        unix_timestamp: i64,
+    ) -> Result<u64, ProgramError> {
+        let decimals = self.base.decimals();
+
+        match self.get_extension::<InterestBearingConfigExtension>() {
+            Ok(extension) => {
+                return extension.ui_amount_to_amount(ui_amount, decimals, unix_timestamp)
+            }
+            Err(error) if !is_extension_not_found_error(&error) => return Err(error),
+            Err(_) => (),
+        }
+
+        match self.get_extension::<ScaledUiAmountConfigExtension>() {
+            Ok(extension) => {
+                return extension.ui_amount_to_amount(ui_amount, decimals, unix_timestamp)
+            }
+            Err(error) if !is_extension_not_found_error(&error) => return Err(error),
+            Err(_) => (),
+        }
+
+        ui_amount_to_amount(ui_amount, decimals)
+    }
+}
+
+/// Convert a raw `amount` into its UI representation for a mint without
+/// UI amount extensions, copied into `buffer`.
+///
+/// # Errors
+///
+/// Returns [`ProgramError::InvalidArgument`] if the UI amount does not fit in
+/// `buffer`.
+pub fn amount_to_ui_amount(
+    amount: u64,
+    decimals: u8,
+    buffer: &mut [u8],
+) -> Result<&str, ProgramError> {
+    let decimals = decimals as usize;
+
+    if decimals == 0 {
+        let len = write_to_buffer(buffer, format_args!("{amount}"))?;
+        // SAFETY: The buffer contains the formatted (ASCII) amount.
+        return Ok(unsafe { core::str::from_utf8_unchecked(&buffer[..len]) });
+    }
+
+    // Left-pad zeros to `decimals + 1` digits, so there is at least an integer
+    // zero, leaving space for the decimal point.
+    let len = write_to_buffer(
+        buffer,
+        format_args!("{amount:0width$}.", width = decimals + 1),
+    )?;
+
+    // Insert the decimal point.
+    let point = len - 1 - decimals;
+    buffer.copy_within(point..len - 1, point + 1);
+    buffer[point] = b'.';
+
+    Ok(trim_ui_amount(&buffer[..len], decimals as u8))
+}
+
+/// Convert a UI amount into its raw amount for a mint without UI amount
+/// extensions.
+///
+/// # Errors
+///
+/// Returns [`ProgramError::InvalidArgument`] if `ui_amount` is not a valid
+/// amount with at most `decimals` decimal places, or the raw amount does not
+/// fit in a `u64`.
+pub fn ui_amount_to_amount(ui_amount: &str, decimals: u8) -> Result<u64, ProgramError> {
+    let (integer, fraction) = match ui_amount.split_once('.') {
+        Some((integer, fraction)) => (integer, fraction.trim_end_matches('0')),
+        None => (ui_amount, ""),
+    };
+
+    if (integer.is_empty() && fraction.is_empty())
+        || fraction.contains('.')
+        || fraction.len() > decimals as usize
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // The amount is the concatenation of the integer and fraction digits,
+    // right-padded with zeros to `decimals` decimal places, parsed as a `u64`.
+    let integer = integer.strip_prefix('+').unwrap_or(integer);
+    let padding = decimals as usize - fraction.len();
+
+    if integer.len() + fraction.len() + padding == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut amount = 0u64;
+
+    for digit in integer.bytes().chain(fraction.bytes()) {
+        if !digit.is_ascii_digit() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        amount = amount
+            .checked_mul(10)
+            .and_then(|amount| amount.checked_add((digit - b'0') as u64))
+            .ok_or(ProgramError::InvalidArgument)?;
+    }
+
+    for _ in 0..padding {
+        amount = amount
+            .checked_mul(10)
+            .ok_or(ProgramError::InvalidArgument)?;
+    }
+
+    Ok(amount)
+}
+
+/// Format a scaled UI amount with `decimals` decimal places into `buffer`,
+/// trimming trailing zeros.
+#[cfg(feature = "ui-amount")]
+#[inline]
+pub(super) fn format_ui_amount(
+    ui_amount: f64,
+    decimals: u8,
+    buffer: &mut [u8],
+) -> Result<&str, ProgramError> {
+    let len = write_to_buffer(buffer, format_args!("{ui_amount:.*}", decimals as usize))?;
+    Ok(trim_ui_amount(&buffer[..len], decimals))
+}
+
+/// Parse a scaled UI amount.
+#[cfg(feature = "ui-amount")]
+#[inline(always)]
+pub(super) fn parse_ui_amount(ui_amount: &str) -> Result<f64, ProgramError> {
+    ui_amount
+        .parse::<f64>()
+        .map_err(|_| ProgramError::InvalidArgument)
+}
+
+/// Round an unscaled amount to the nearest raw amount.
+#[cfg(feature = "ui-amount")]
+#[inline(always)]
+pub(super) fn round_amount(amount: f64) -> Result<u64, ProgramError> {
+    if amount > (u64::MAX as f64) || amount < (u64::MIN as f64) || amount.is_nan() {
+        Err(ProgramError::InvalidArgument)
+    } else {
+        // Rounding must happen after the division, otherwise large amounts
+        // would result in "inf".
+        Ok(libm::round(amount) as u64)
+    }
+}
+
+/// Raise `base` to the integer power `exponent`.
+///
+/// Uses the same algorithm as `f64::powi` on-chain (`__powidf2`), so results
+/// are identical to the token program's.
+#[cfg(feature = "ui-amount")]
+#[inline]
+pub(super) fn powi(mut base: f64, exponent: i32) -> f64 {
+    let mut power = exponent.unsigned_abs();
+    let mut result = 1.;
+
+    loop {
+        if power & 1 != 0 {
+            result *= base;
+        }
+        power >>= 1;
+        if power == 0 {
+            break;
+        }
+        base *= base;
+    }
+
+    if exponent < 0 {
+        1. / result
+    } else {
+        result
+    }
+}
+
+/// Trim trailing zeros and the decimal point of a UI amount with decimals.
+#[inline]
+fn trim_ui_amount(ui_amount: &[u8], decimals: u8) -> &str {
+    // SAFETY: UI amounts are formatted as ASCII strings.
+    let ui_amount = unsafe { core::str::from_utf8_unchecked(ui_amount) };
+
+    if decimals > 0 {
+        ui_amount.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        ui_amount
+    }
+}
+
+/// Write the formatted arguments into `buffer`, returning the number of bytes
+/// written.
+#[inline]
+fn write_to_buffer(buffer: &mut [u8], args: fmt::Arguments) -> Result<usize, ProgramError> {
+    struct BufferWriter<'a> {
+        buffer: &'a mut [u8],
+        len: usize,
+    }
+
+    impl Write for BufferWriter<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len.checked_add(s.len()).ok_or(fmt::Error)?;
+            self.buffer
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    let mut writer = BufferWriter { buffer, len: 0 };
+    writer
+        .write_fmt(args)
+        .map_err(|_| ProgramError::InvalidArgument)?;
+
+    Ok(writer.len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amount_to_ui_amount_matches_token_program() {
+        let mut buffer = [0u8; 32];
+
+        assert_eq!(amount_to_ui_amount(0, 0, &mut buffer).unwrap(), "0");
+        assert_eq!(amount_to_ui_amount(0, 2, &mut buffer).unwrap(), "0");
+        assert_eq!(amount_to_ui_amount(1, 0, &mut buffer).unwrap(), "1");
+        assert_eq!(amount_to_ui_amount(1, 2, &mut buffer).unwrap(), "0.01");
+        assert_eq!(amount_to_ui_amount(100, 2, &mut buffer).unwrap(), "1");
+        assert_eq!(amount_to_ui_amount(1_050, 3, &mut buffer).unwrap(), "1.05");
+        assert_eq!(
+            amount_to_ui_amount(u64::MAX, 9, &mut buffer).unwrap(),
+            "18446744073.709551615"
+        );
+        assert_eq!(
+            amount_to_ui_amount(u64::MAX, 9, &mut [0u8; 8]),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn ui_amount_to_amount_matches_token_program() {
+        assert_eq!(ui_amount_to_amount("1", 0), Ok(1));
+        assert_eq!(ui_amount_to_amount("1.05", 3), Ok(1_050));
+        assert_eq!(ui_amount_to_amount("1.0500", 3), Ok(1_050));
+        assert_eq!(ui_amount_to_amount(".5", 1), Ok(5));
+        assert_eq!(ui_amount_to_amount("5.", 1), Ok(50));
+        assert_eq!(ui_amount_to_amount("+5", 1), Ok(50));
+        assert_eq!(ui_amount_to_amount("+", 2), Ok(0));
+        assert_eq!(
+            ui_amount_to_amount("18446744073.709551615", 9),
+            Ok(u64::MAX)
+        );
+
+        for (invalid, decimals) in [
+            ("", 9),
+            (".", 9),
+            ("+", 0),
+            ("1.5.0", 9),
+            ("1.005", 2),
+            ("-1", 9),
+            ("1a", 9),
+            ("18446744073.709551616", 9),
+        ] {
+            assert_eq!(
+                ui_amount_to_amount(invalid, decimals),
+                Err(ProgramError::InvalidArgument),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[cfg(feature = "ui-amount")]
+    #[test]
+    fn format_ui_amount_trims_trailing_zeros() {
+        let mut buffer = [0u8; 32];
+
+        assert_eq!(format_ui_amount(1.5, 3, &mut buffer).unwrap(), "1.5");
+        assert_eq!(format_ui_amount(2.0, 3, &mut buffer).unwrap(), "2");
+        assert_eq!(format_ui_amount(1.23456, 2, &mut buffer).unwrap(), "1.23");
+        assert_eq!(format_ui_amount(100.0, 0, &mut buffer).unwrap(), "100");
+    }
+
+    #[cfg(feature = "ui-amount")]
+    #[test]
+    fn powi_matches_exact_powers_of_ten() {
+        assert_eq!(powi(10., 0), 1.);
+        assert_eq!(powi(10., 9), 1_000_000_000.);
+        assert_eq!(powi(10., -2), 0.01);
+    }
+}
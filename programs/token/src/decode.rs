@@ -0,0 +1,777 @@
+//! Decoders of token instruction data.
+//!
+//! The builders in [`instructions`](crate::instructions) encode instruction
+//! data; [`TokenInstruction::unpack`] parses it back into a typed value, e.g.
+//! to recognize token instructions through instructions sysvar introspection.
+//! Addresses and strings are borrowed from the instruction data without
+//! copying.
+
+use {crate::instructions::Amount, solana_address::Address, solana_program_error::ProgramError};
+
+/// A decoded token instruction.
+///
+/// Each variant holds the parameters of the corresponding builder in
+/// [`instructions`](crate::instructions); see the builder for the accounts
+/// expected by the instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TokenInstruction<'a> {
+    /// Initializes a new mint (`0`).
+    InitializeMint {
+        decimals: u8,
+        mint_authority: &'a Address,
+        freeze_authority: Option<&'a Address>,
+    },
+
+    /// Initializes a new account (`1`).
+    InitializeAccount,
+
+    /// Initializes a multisignature account with `m` required signers (`2`).
+    InitializeMultisig { m: u8 },
+
+    /// Transfers tokens (`3`).
+    Transfer { amount: u64 },
+
+    /// Approves a delegate (`4`).
+    Approve { amount: u64 },
+
+    /// Revokes the delegate's authority (`5`).
+    Revoke,
+
+    /// Sets a new authority of a mint or account (`6`).
+    ///
+    /// The authority type is the raw value, since Token-2022 defines more
+    /// authority types than
+    /// [`AuthorityType`](crate::instructions::AuthorityType).
+    SetAuthority {
+        authority_type: u8,
+        new_authority: Option<&'a Address>,
+    },
+
+    /// Mints new tokens to an account (`7`).
+    MintTo { amount: u64 },
+
+    /// Burns tokens (`8`).
+    Burn { amount: u64 },
+
+    /// Closes an account (`9`).
+    CloseAccount,
+
+    /// Freezes an initialized account (`10`).
+    FreezeAccount,
+
+    /// Thaws a frozen account (`11`).
+    ThawAccount,
+
+    /// Transfers tokens, asserting the mint decimals (`12`).
+    TransferChecked { amount: u64, decimals: u8 },
+
+    /// Approves a delegate, asserting the mint decimals (`13`).
+    ApproveChecked { amount: u64, decimals: u8 },
+
+    /// Mints new tokens, asserting the mint decimals (`14`).
+    MintToChecked { amount: u64, decimals: u8 },
+
+    /// Burns tokens, asserting the mint decimals (`15`).
+    BurnChecked { amount: u64, decimals: u8 },
+
+    /// Initializes a new account with the owner in the instruction data
+    /// (`16`).
+    InitializeAccount2 { owner: &'a Address },
+
+    /// Syncs the amount of a native account with its lamports (`17`).
+    SyncNative,
+
+    /// Initializes a new account without the rent sysvar (`18`).
+    InitializeAccount3 { owner: &'a Address },
+
+    /// Initializes a multisignature account without the rent sysvar (`19`).
+    InitializeMultisig2 { m: u8 },
+
+    /// Initializes a new mint without the rent sysvar (`20`).
+    InitializeMint2 {
+        decimals: u8,
+        mint_authority: &'a Address,
+        freeze_authority: Option<&'a Address>,
+    },
+
+    /// Gets the required size of an account for the given mint (`21`).
+    GetAccountDataSize,
+
+    /// Initializes the immutable owner extension of an account (`22`).
+    InitializeImmutableOwner,
+
+    /// Converts an amount of tokens to a UI amount string (`23`).
+    AmountToUiAmount { amount: u64 },
+
+    /// Converts a UI amount string to an amount of tokens (`24`).
+    UiAmountToAmount { ui_amount: &'a str },
+
+    /// Withdraws the excess lamports of an account (`38`).
+    WithdrawExcessLamports,
+
+    /// Transfers lamports out of a native account (`45`).
+    UnwrapLamports { amount: Amount },
+
+    /// Executes a batch of instructions (`255`).
+    Batch { instructions: BatchInstructions<'a> },
+}
+
+impl<'a> TokenInstruction<'a> {
+    /// Unpack a token instruction from its instruction data.
+    ///
+    /// As the token programs do, bytes following the instruction parameters
+    /// are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the discriminator
+    /// is unknown or the instruction data is malformed.
+    #[inline]
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        let (discriminator, rest) = data
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        Ok(match discriminator {
+            0 => {
+                let (decimals, mint_authority, freeze_authority) = unpack_initialize_mint(rest)?;
+
+                TokenInstruction::InitializeMint {
+                    decimals,
+                    mint_authority,
+                    freeze_authority,
+                }
+            }
+            1 => TokenInstruction::InitializeAccount,
+            2 => TokenInstruction::InitializeMultisig {
+                m: unpack_u8(rest)?,
+            },
+            3 => TokenInstruction::Transfer {
+                amount: unpack_u64(rest)?,
+            },
+            4 => TokenInstruction::Approve {
+                amount: unpack_u64(rest)?,
+            },
+            5 => TokenInstruction::Revoke,
+            6 => {
+                let (authority_type, rest) = rest
+                    .split_first()
+                    .ok_or(ProgramError::InvalidInstructionData)?;
+
+                TokenInstruction::SetAuthority {
+                    authority_type: *authority_type,
+                    new_authority: unpack_option_address(rest)?.0,
+                }
+            }
+            7 => TokenInstruction::MintTo {
+                amount: unpack_u64(rest)?,
+            },
+            8 => TokenInstruction::Burn {
+                amount: unpack_u64(rest)?,
+            },
+            9 => TokenInstruction::CloseAccount,
+            10 => TokenInstruction::FreezeAccount,
+            11 => TokenInstruction::ThawAccount,
+            12 => {
+                let (amount, decimals) = unpack_amount_and_decimals(rest)?;
+                TokenInstruction::TransferChecked { amount, decimals }
+            }
+            13 => {
+                let (amount, decimals) = unpack_amount_and_decimals(rest)?;
+                TokenInstruction::ApproveChecked { amount, decimals }
+            }
+            14 => {
+                let (amount, decimals) = unpack_amount_and_decimals(rest)?;
+                TokenInstruction::MintToChecked { amount, decimals }
+            }
+            15 => {
+                let (amount, decimals) = unpack_amount_and_decimals(rest)?;
+                TokenInstruction::BurnChecked { amount, decimals }
+            }
+            16 => TokenInstruction::InitializeAccount2 {
+                owner: unpack_address(rest)?,
+            },
+            17 => TokenInstruction::SyncNative,
+            18 => TokenInstruction::InitializeAccount3 {
+                owner: unpack_address(rest)?,
+            },
+            19 => TokenInstruction::InitializeMultisig2 {
+                m: unpack_u8(rest)?,
+            },
+            20 => {
+                let (decimals, mint_authority, freeze_authority) = unpack_initialize_mint(rest)?;
+
+                TokenInstruction::InitializeMint2 {
+                    decimals,
+                    mint_authority,
+                    freeze_authority,
+                }
+            }
+            21 => TokenInstruction::GetAccountDataSize,
+            22 => TokenInstruction::InitializeImmutableOwner,
+            23 => TokenInstruction::AmountToUiAmount {
+                amount: unpack_u64(rest)?,
+            },
+            24 => TokenInstruction::UiAmountToAmount {
+                ui_amount: core::str::from_utf8(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            },
+            38 => TokenInstruction::WithdrawExcessLamports,
+            45 => TokenInstruction::UnwrapLamports {
+                amount: match rest {
+                    [0, ..] => Amount::All,
+                    [1, amount @ ..] => Amount::Some(unpack_u64(amount)?),
+                    _ => return Err(ProgramError::InvalidInstructionData),
+                },
+            },
+            255 => TokenInstruction::Batch {
+                instructions: BatchInstructions::new(rest)?,
+            },
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+}
+
+/// An instruction of a batch.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BatchInstruction<'a> {
+    /// The number of accounts of the instruction, taken in order from the
+    /// batch accounts.
+    pub accounts_len: u8,
+
+    /// The instruction data, which can be unpacked with
+    /// [`TokenInstruction::unpack`].
+    pub data: &'a [u8],
+}
+
+/// Iterator over the instructions of a batch.
+///
+/// Each instruction is prefixed by its number of accounts and data length
+/// (one byte each).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BatchInstructions<'a> {
+    /// The remaining (validated) instructions.
+    data: &'a [u8],
+}
+
+impl<'a> BatchInstructions<'a> {
+    /// Validate the instructions of a batch, following the batch
+    /// discriminator.
+    #[inline]
+    fn new(data: &'a [u8]) -> Result<Self, ProgramError> {
+        let mut rest = data;
+
+        while let [_, data_len, instruction @ ..] = rest {
+            rest = instruction
+                .get(*data_len as usize..)
+                .ok_or(ProgramError::InvalidInstructionData)?;
+        }
+
+        if !rest.is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { data })
+    }
+}
+
+impl<'a> Iterator for BatchInstructions<'a> {
+    type Item = BatchInstruction<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let [accounts_len, data_len, rest @ ..] = self.data else {
+            return None;
+        };
+
+        // The instructions were validated when unpacking the batch.
+        let (data, rest) = rest.split_at(*data_len as usize);
+        self.data = rest;
+
+        Some(BatchInstruction {
+            accounts_len: *accounts_len,
+            data,
+        })
+    }
+}
+
+/// Unpack the decimals, mint authority and optional freeze authority of an
+/// `InitializeMint` instruction.
+#[inline(always)]
+fn unpack_initialize_mint(data: &[u8]) -> Result<(u8, &Address, Option<&Address>), ProgramError> {
+    let (decimals, rest) = data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let mint_authority = unpack_address(rest)?;
+    let (freeze_authority, _) = unpack_option_address(&rest[size_of::<Address>()..])?;
+
+    Ok((*decimals, mint_authority, freeze_authority))
+}
+
+/// Unpack an amount followed by decimals.
+#[inline(always)]
+fn unpack_amount_and_decimals(data: &[u8]) -> Result<(u64, u8), ProgramError> {
+    Ok((unpack_u64(data)?, unpack_u8(&data[8..])?))
+}
+
+/// Unpack a `u8`.
+#[inline(always)]
+fn unpack_u8(data: &[u8]) -> Result<u8, ProgramError> {
+    data.first()
+        .copied()
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+/// Unpack a little-endian `u64`.
+#[inline(always)]
+fn unpack_u64(data: &[u8]) -> Result<u64, ProgramError> {
+    data.get(..8)
+        .map(|bytes| {
+            u64::from_le_bytes([
+                bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+            ])
+        })
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+/// Unpack an address.
+#[inline(always)]
+fn unpack_address(data: &[u8]) -> Result<&Address, ProgramError> {
+    let bytes = data
+        .get(..size_of::<Address>())
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    // SAFETY: `bytes` is 32 bytes long and `Address` has alignment 1.
+    Ok(unsafe { &*(bytes.as_ptr() as *const Address) })
+}
+
+/// Unpack an optional address, encoded as a `0` byte or a `1` byte followed
+/// by the address, returning it with the remaining data.
+#[inline(always)]
+fn unpack_option_address(data: &[u8]) -> Result<(Option<&Address>, &[u8]), ProgramError> {
+    match data {
+        [0, rest @ ..] => Ok((None, rest)),
+        [1, rest @ ..] => Ok((Some(unpack_address(rest)?), &rest[size_of::<Address>()..])),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            instructions::{
+                amount_to_ui_amount::AmountToUiAmount, approve::Approve,
+                approve_checked::ApproveChecked, burn::Burn, burn_checked::BurnChecked,
+                close_account::CloseAccount, freeze_account::FreezeAccount,
+                get_account_data_size::GetAccountDataSize, initialize_account::InitializeAccount,
+                initialize_account2::InitializeAccount2, initialize_account3::InitializeAccount3,
+                initialize_immutable_owner::InitializeImmutableOwner,
+                initialize_mint::InitializeMint, initialize_mint2::InitializeMint2,
+                initialize_multisig::InitializeMultisig, initialize_multisig2::InitializeMultisig2,
+                mint_to::MintTo, mint_to_checked::MintToChecked, revoke::Revoke,
+                set_authority::SetAuthority, sync_native::SyncNative, thaw_account::ThawAccount,
+                transfer::Transfer, transfer_checked::TransferChecked,
+                ui_amount_to_amount::UiAmountToAmount, unwrap_lamports::UnwrapLamports,
+                withdraw_excess_lamports::WithdrawExcessLamports, AuthorityType, CpiWriter,
+            },
+            TokenProgram,
+        },
+        core::mem::MaybeUninit,
+        pinocchio::{
+            test_utils::{ProgramInput, TestAccount},
+            SUCCESS,
+        },
+        solana_account_view::AccountView,
+    };
+
+    /// Run `f` with four accounts.
+    fn with_accounts(f: impl FnOnce(&[AccountView])) {
+        let mut input = ProgramInput::new(
+            &Address::default(),
+            &[
+                TestAccount::new(Address::new_from_array([1; 32]), crate::ID, 1, &[]).into(),
+                TestAccount::new(Address::new_from_array([2; 32]), crate::ID, 1, &[]).into(),
+                TestAccount::new(Address::new_from_array([3; 32]), crate::ID, 1, &[]).into(),
+                TestAccount::new(Address::new_from_array([4; 32]), crate::ID, 1, &[]).into(),
+            ],
+            &[],
+        );
+
+        let result = input.process(|_, accounts, _| {
+            f(accounts);
+            Ok(())
+        });
+
+        assert_eq!(result, SUCCESS);
+    }
+
+    /// Run `f` with the instruction data written by `instruction`.
+    fn with_data(instruction: &impl CpiWriter, f: impl FnOnce(&[u8])) {
+        let mut data = [MaybeUninit::<u8>::uninit(); 128];
+        let len = instruction.write_instruction_data(&mut data).unwrap();
+
+        // SAFETY: The first `len` bytes are initialized.
+        f(unsafe { core::slice::from_raw_parts(data.as_ptr() as *const u8, len) });
+    }
+
+    /// Assert that the instruction data written by `instruction` unpacks to
+    /// `expected`, and that any truncation of it fails to unpack.
+    fn assert_round_trip(instruction: &impl CpiWriter, expected: TokenInstruction) {
+        with_data(instruction, |data| {
+            assert_eq!(TokenInstruction::unpack(data), Ok(expected));
+
+            for len in 0..data.len() {
+                assert_eq!(
+                    TokenInstruction::unpack(&data[..len]),
+                    Err(ProgramError::InvalidInstructionData)
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn round_trip_initialize_instructions() {
+        let authority = Address::new_from_array([7; 32]);
+        let freeze_authority = Address::new_from_array([8; 32]);
+
+        with_accounts(|a| {
+            assert_round_trip(
+                &InitializeMint::<TokenProgram>::new(&a[0], &a[1], 6, &authority, None),
+                TokenInstruction::InitializeMint {
+                    decimals: 6,
+                    mint_authority: &authority,
+                    freeze_authority: None,
+                },
+            );
+            assert_round_trip(
+                &InitializeMint::<TokenProgram>::new(
+                    &a[0],
+                    &a[1],
+                    6,
+                    &authority,
+                    Some(&freeze_authority),
+                ),
+                TokenInstruction::InitializeMint {
+                    decimals: 6,
+                    mint_authority: &authority,
+                    freeze_authority: Some(&freeze_authority),
+                },
+            );
+            assert_round_trip(
+                &InitializeAccount::<TokenProgram>::new(&a[0], &a[1], &a[2], &a[3]),
+                TokenInstruction::InitializeAccount,
+            );
+            assert_round_trip(
+                &InitializeMultisig::<_, TokenProgram>::new(&a[0], &a[1], &[&a[2], &a[3]], 2),
+                TokenInstruction::InitializeMultisig { m: 2 },
+            );
+            assert_round_trip(
+                &InitializeAccount2::<TokenProgram>::new(&a[0], &a[1], &a[2], &authority),
+                TokenInstruction::InitializeAccount2 { owner: &authority },
+            );
+            assert_round_trip(
+                &InitializeAccount3::<TokenProgram>::new(&a[0], &a[1], &authority),
+                TokenInstruction::InitializeAccount3 { owner: &authority },
+            );
+            assert_round_trip(
+                &InitializeMultisig2::<_, TokenProgram>::new(&a[0], &[&a[1], &a[2]], 1),
+                TokenInstruction::InitializeMultisig2 { m: 1 },
+            );
+            assert_round_trip(
+                &InitializeMint2::<TokenProgram>::new(
+                    &a[0],
+                    9,
+                    &authority,
+                    Some(&freeze_authority),
+                ),
+                TokenInstruction::InitializeMint2 {
+                    decimals: 9,
+                    mint_authority: &authority,
+                    freeze_authority: Some(&freeze_authority),
+                },
+            );
+            assert_round_trip(
+                &InitializeImmutableOwner::<TokenProgram>::new(&a[0]),
+                TokenInstruction::InitializeImmutableOwner,
+            );
+        });
+    }
+
+    #[test]
+    fn round_trip_account_instructions() {
+        let new_authority = Address::new_from_array([7; 32]);
+
+        with_accounts(|a| {
+            assert_round_trip(
+                &Transfer::<_, TokenProgram>::new(&a[0], &a[1], &a[2], 42),
+                TokenInstruction::Transfer { amount: 42 },
+            );
+            assert_round_trip(
+                &Approve::<_, TokenProgram>::new(&a[0], &a[1], &a[2], 42),
+                TokenInstruction::Approve { amount: 42 },
+            );
+            assert_round_trip(
+                &Revoke::<_, TokenProgram>::new(&a[0], &a[1]),
+                TokenInstruction::Revoke,
+            );
+            assert_round_trip(
+                &SetAuthority::<_, TokenProgram>::new(
+                    &a[0],
+                    &a[1],
+                    AuthorityType::CloseAccount,
+                    Some(&new_authority),
+                ),
+                TokenInstruction::SetAuthority {
+                    authority_type: AuthorityType::CloseAccount as u8,
+                    new_authority: Some(&new_authority),
+                },
+            );
+            assert_round_trip(
+                &SetAuthority::<_, TokenProgram>::new(
+                    &a[0],
+                    &a[1],
+                    AuthorityType::MintTokens,
+                    None,
+                ),
+                TokenInstruction::SetAuthority {
+                    authority_type: AuthorityType::MintTokens as u8,
+                    new_authority: None,
+                },
+            );
+            assert_round_trip(
+                &MintTo::<_, TokenProgram>::new(&a[0], &a[1], &a[2], 42),
+                TokenInstruction::MintTo { amount: 42 },
+            );
+            assert_round_trip(
+                &Burn::<_, TokenProgram>::new(&a[0], &a[1], &a[2], 42),
+                TokenInstruction::Burn { amount: 42 },
+            );
+            assert_round_trip(
+                &CloseAccount::<_, TokenProgram>::new(&a[0], &a[1], &a[2]),
+                TokenInstruction::CloseAccount,
+            );
+            assert_round_trip(
+                &FreezeAccount::<_, TokenProgram>::new(&a[0], &a[1], &a[2]),
+                TokenInstruction::FreezeAccount,
+            );
+            assert_round_trip(
+                &ThawAccount::<_, TokenProgram>::new(&a[0], &a[1], &a[2]),
+                TokenInstruction::ThawAccount,
+            );
+            assert_round_trip(
+                &SyncNative::<TokenProgram>::new(&a[0], None),
+                TokenInstruction::SyncNative,
+            );
+            assert_round_trip(
+                &WithdrawExcessLamports::<_, TokenProgram>::new(&a[0], &a[1], &a[2]),
+                TokenInstruction::WithdrawExcessLamports,
+            );
+            assert_round_trip(
+                &UnwrapLamports::<_, TokenProgram>::new(&a[0], &a[1], &a[2], Amount::All),
+                TokenInstruction::UnwrapLamports {
+                    amount: Amount::All,
+                },
+            );
+            assert_round_trip(
+                &UnwrapLamports::<_, TokenProgram>::new(&a[0], &a[1], &a[2], Amount::Some(42)),
+                TokenInstruction::UnwrapLamports {
+                    amount: Amount::Some(42),
+                },
+            );
+        });
+    }
+
+    #[test]
+    fn round_trip_checked_instructions() {
+        with_accounts(|a| {
+            assert_round_trip(
+                &TransferChecked::<_, TokenProgram>::new(&a[0], &a[1], &a[2], &a[3], 42, 6),
+                TokenInstruction::TransferChecked {
+                    amount: 42,
+                    decimals: 6,
+                },
+            );
+            assert_round_trip(
+                &ApproveChecked::<_, TokenProgram>::new(&a[0], &a[1], &a[2], &a[3], 42, 6),
+                TokenInstruction::ApproveChecked {
+                    amount: 42,
+                    decimals: 6,
+                },
+            );
+            assert_round_trip(
+                &MintToChecked::<_, TokenProgram>::new(&a[0], &a[1], &a[2], 42, 6),
+                TokenInstruction::MintToChecked {
+                    amount: 42,
+                    decimals: 6,
+                },
+            );
+            assert_round_trip(
+                &BurnChecked::<_, TokenProgram>::new(&a[0], &a[1], &a[2], 42, 6),
+                TokenInstruction::BurnChecked {
+                    amount: 42,
+                    decimals: 6,
+                },
+            );
+        });
+    }
+
+    #[test]
+    fn round_trip_return_data_instructions() {
+        with_accounts(|a| {
+            assert_round_trip(
+                &GetAccountDataSize::<TokenProgram>::new(&a[0]),
+                TokenInstruction::GetAccountDataSize,
+            );
+            assert_round_trip(
+                &AmountToUiAmount::<TokenProgram>::new(&a[0], 42),
+                TokenInstruction::AmountToUiAmount { amount: 42 },
+            );
+
+            // The UI amount is the rest of the instruction data, so only the
+            // empty data fails to unpack once truncated.
+            with_data(
+                &UiAmountToAmount::<TokenProgram>::new(&a[0], "4.2"),
+                |data| {
+                    assert_eq!(
+                        TokenInstruction::unpack(data),
+                        Ok(TokenInstruction::UiAmountToAmount { ui_amount: "4.2" })
+                    );
+                    assert_eq!(
+                        TokenInstruction::unpack(&data[..2]),
+                        Ok(TokenInstruction::UiAmountToAmount { ui_amount: "4" })
+                    );
+                    assert_eq!(
+                        TokenInstruction::unpack(&[]),
+                        Err(ProgramError::InvalidInstructionData)
+                    );
+                },
+            );
+        });
+
+        assert_eq!(
+            TokenInstruction::unpack(&[24, 0xff]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn unpack_invalid_instructions() {
+        for data in [
+            &[25][..],
+            &[37],
+            &[254],
+            // Invalid option tag.
+            &[6, 0, 2],
+            // Invalid unwrap amount tag.
+            &[45, 2],
+        ] {
+            assert_eq!(
+                TokenInstruction::unpack(data),
+                Err(ProgramError::InvalidInstructionData)
+            );
+        }
+    }
+
+    #[test]
+    fn unpack_truncated_batch() {
+        // Instructions whose data length exceeds the batch data.
+        for data in [&[255, 3, 9, 3][..], &[255, 3, 1, 5, 0, 2]] {
+            assert_eq!(
+                TokenInstruction::unpack(data),
+                Err(ProgramError::InvalidInstructionData)
+            );
+        }
+
+        // An incomplete instruction header.
+        assert_eq!(
+            TokenInstruction::unpack(&[255, 3, 1, 5, 3]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+
+        let Ok(TokenInstruction::Batch { mut instructions }) = TokenInstruction::unpack(&[255])
+        else {
+            panic!("expected an empty batch");
+        };
+        assert_eq!(instructions.next(), None);
+    }
+
+    #[cfg(feature = "cpi-mock")]
+    #[test]
+    fn round_trip_batch() {
+        use {
+            crate::{
+                cpi::{mock, CpiAccount},
+                instructions::{batch::Batch, IntoBatch},
+            },
+            solana_instruction_view::InstructionAccount,
+        };
+
+        mock::reset();
+        mock::register_handler(crate::ID, |_, _| Ok(()));
+
+        with_accounts(|a| {
+            let mut data = [MaybeUninit::uninit(); 64];
+            let mut instruction_accounts =
+                [const { MaybeUninit::<InstructionAccount>::uninit() }; 8];
+            let mut accounts = [const { MaybeUninit::<CpiAccount>::uninit() }; 8];
+
+            let mut batch =
+                Batch::<TokenProgram>::new(&mut data, &mut instruction_accounts, &mut accounts)
+                    .unwrap();
+
+            Transfer::new(&a[0], &a[1], &a[2], 42)
+                .into_batch(&mut batch)
+                .unwrap();
+            UnwrapLamports::new(&a[0], &a[1], &a[2], Amount::Some(7))
+                .into_batch(&mut batch)
+                .unwrap();
+            SyncNative::new(&a[3], None).into_batch(&mut batch).unwrap();
+
+            batch.invoke().unwrap();
+        });
+
+        let invocations = mock::take_invocations();
+        assert_eq!(invocations.len(), 1);
+
+        let data = invocations[0].data.as_slice();
+        let Ok(TokenInstruction::Batch { mut instructions }) = TokenInstruction::unpack(data)
+        else {
+            panic!("expected a batch");
+        };
+
+        let instruction = instructions.next().unwrap();
+        assert_eq!(instruction.accounts_len, 3);
+        assert_eq!(
+            TokenInstruction::unpack(instruction.data),
+            Ok(TokenInstruction::Transfer { amount: 42 })
+        );
+
+        let instruction = instructions.next().unwrap();
+        assert_eq!(instruction.accounts_len, 3);
+        assert_eq!(
+            TokenInstruction::unpack(instruction.data),
+            Ok(TokenInstruction::UnwrapLamports {
+                amount: Amount::Some(7),
+            })
+        );
+
+        let instruction = instructions.next().unwrap();
+        assert_eq!(instruction.accounts_len, 1);
+        assert_eq!(
+            TokenInstruction::unpack(instruction.data),
+            Ok(TokenInstruction::SyncNative)
+        );
+
+        assert_eq!(instructions.next(), None);
+
+        // Any truncation of the last instruction fails to unpack.
+        let last_len = 2 + instruction.data.len();
+        for len in data.len() - last_len + 1..data.len() {
+            assert_eq!(
+                TokenInstruction::unpack(&data[..len]),
+                Err(ProgramError::InvalidInstructionData)
+            );
+        }
+    }
+}
@@ -1,18 +1,16 @@
 use {
     crate::{
+        cpi::{invoke_unchecked, CpiAccount},
         instructions::{
-            invalid_argument_error, write_bytes, CpiWriter, UNINIT_BYTE, UNINIT_CPI_ACCOUNT,
-            UNINIT_INSTRUCTION_ACCOUNT,
+            get_return_data, invalid_argument_error, write_bytes, CpiWriter, UNINIT_BYTE,
+            UNINIT_CPI_ACCOUNT, UNINIT_INSTRUCTION_ACCOUNT,
         },
         TokenInterface,
     },
     core::{marker::PhantomData, mem::MaybeUninit, slice::from_raw_parts},
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{
-        cpi::{invoke_unchecked, CpiAccount},
-        InstructionAccount, InstructionView,
-    },
+    solana_instruction_view::{InstructionAccount, InstructionView},
     solana_program_error::{ProgramError, ProgramResult},
 };
 
@@ -32,8 +30,8 @@ const DATA_LEN: usize = 9;
 ///
 /// Fails on an invalid mint.
 ///
-/// Return data can be fetched using `sol_get_return_data` and deserialized
-/// with `String::from_utf8`.
+/// Use `invoke_and_get_ui_amount` to invoke the instruction and read the
+/// `UiAmount` string from the return data.
 ///
 /// Accounts expected by this instruction:
 ///
@@ -122,6 +120,31 @@ impl<'account, Program: TokenInterface> AmountToUiAmount<'account, Program> {
 
         Ok(())
     }
+
+    /// Invokes the instruction with `Program::ID` and returns the `UiAmount`
+    /// string, copied into `buffer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::IncorrectProgramId`] if the return data was not
+    /// set by `Program::ID`, or [`ProgramError::InvalidArgument`] if the
+    /// return data does not fit in `buffer` or is not a valid UTF-8 string.
+    #[inline(always)]
+    pub fn invoke_and_get_ui_amount<'buffer>(
+        &self,
+        buffer: &'buffer mut [u8],
+    ) -> Result<&'buffer str, ProgramError> {
+        self.invoke()?;
+
+        let return_data = get_return_data(&Program::ID)?;
+        let ui_amount = return_data.as_slice();
+        let buffer = buffer
+            .get_mut(..ui_amount.len())
+            .ok_or_else(invalid_argument_error)?;
+        buffer.copy_from_slice(ui_amount);
+
+        core::str::from_utf8(buffer).map_err(|_| invalid_argument_error())
+    }
 }
 
 impl<Program: TokenInterface> CpiWriter for AmountToUiAmount<'_, Program> {
@@ -219,3 +242,37 @@ fn write_instruction_data(
 
     Ok(DATA_LEN)
 }
+
+#[cfg(all(test, feature = "cpi-mock"))]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            instructions::test_utils::{mock_return_data, with_mint},
+            TokenProgram,
+        },
+    };
+
+    #[test]
+    fn test_invoke_and_get_ui_amount() {
+        with_mint(|mint| {
+            let instruction = AmountToUiAmount::<TokenProgram>::new(mint, 1_500);
+            let mut buffer = [0u8; 8];
+
+            mock_return_data(crate::ID, *b"1.5");
+            assert_eq!(instruction.invoke_and_get_ui_amount(&mut buffer), Ok("1.5"));
+
+            mock_return_data(Address::new_from_array([2; 32]), *b"1.5");
+            assert_eq!(
+                instruction.invoke_and_get_ui_amount(&mut buffer),
+                Err(ProgramError::IncorrectProgramId)
+            );
+
+            mock_return_data(crate::ID, *b"1.5");
+            assert_eq!(
+                instruction.invoke_and_get_ui_amount(&mut buffer[..2]),
+                Err(ProgramError::InvalidArgument)
+            );
+        });
+    }
+}
@@ -1,13 +1,14 @@
 #[cfg(feature = "alloc")]
 use alloc::boxed::Box;
 use {
-    crate::{instructions::invalid_argument_error, TokenInterface},
-    core::{marker::PhantomData, mem::MaybeUninit, slice::from_raw_parts},
-    solana_address::Address,
-    solana_instruction_view::{
+    crate::{
         cpi::{invoke_signed_unchecked, CpiAccount, Signer, MAX_CPI_ACCOUNTS},
-        InstructionAccount, InstructionView,
+        instructions::invalid_argument_error,
+        TokenInterface,
     },
+    core::{marker::PhantomData, mem::MaybeUninit, slice::from_raw_parts},
+    solana_address::Address,
+    solana_instruction_view::{InstructionAccount, InstructionView},
     solana_program_error::{ProgramError, ProgramResult},
 };
 
@@ -205,6 +206,12 @@ where
             &mut self.instruction_accounts[self.instruction_accounts_len..],
         )?;
 
+        // The instruction header stores the number of accounts and the data
+        // length in one byte each.
+        if written_instruction_accounts > u8::MAX as usize || written_data > u8::MAX as usize {
+            return Err(invalid_argument_error());
+        }
+
         // If all writes succeeded, update the lengths and write the instruction
         // header.
 
@@ -279,3 +286,40 @@ pub trait IntoBatch<Program: TokenInterface> {
     where
         Self: 'account + 'state;
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::TokenProgram};
+
+    #[test]
+    fn push_rejects_instruction_data_longer_than_u8() {
+        let mut data = [const { MaybeUninit::uninit() }; 512];
+        let mut instruction_accounts = [const { MaybeUninit::uninit() }; 4];
+        let mut accounts = [const { MaybeUninit::uninit() }; 4];
+
+        let mut batch =
+            Batch::<TokenProgram>::new(&mut data, &mut instruction_accounts, &mut accounts)
+                .unwrap();
+
+        let write_data = |len: usize| {
+            move |data: &mut [MaybeUninit<u8>]| {
+                data[..len].fill(MaybeUninit::new(1));
+                Ok(len)
+            }
+        };
+
+        assert_eq!(
+            batch.push(|_| Ok(0), |_| Ok(0), write_data(u8::MAX as usize + 1)),
+            Err(ProgramError::InvalidArgument)
+        );
+
+        // The batch is left unchanged.
+        assert_eq!(batch.data_len, 1);
+
+        assert_eq!(
+            batch.push(|_| Ok(0), |_| Ok(0), write_data(u8::MAX as usize)),
+            Ok(())
+        );
+        assert_eq!(batch.data_len, 1 + IX_HEADER_SIZE + u8::MAX as usize);
+    }
+}
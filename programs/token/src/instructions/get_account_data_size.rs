@@ -1,18 +1,16 @@
 use {
     crate::{
+        cpi::{invoke_unchecked, CpiAccount},
         instructions::{
-            invalid_argument_error, CpiWriter, UNINIT_BYTE, UNINIT_CPI_ACCOUNT,
-            UNINIT_INSTRUCTION_ACCOUNT,
+            get_return_data_u64, invalid_argument_error, CpiWriter, UNINIT_BYTE,
+            UNINIT_CPI_ACCOUNT, UNINIT_INSTRUCTION_ACCOUNT,
         },
         TokenInterface,
     },
     core::{marker::PhantomData, mem::MaybeUninit, slice::from_raw_parts},
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{
-        cpi::{invoke_unchecked, CpiAccount},
-        InstructionAccount, InstructionView,
-    },
+    solana_instruction_view::{InstructionAccount, InstructionView},
     solana_program_error::{ProgramError, ProgramResult},
 };
 
@@ -29,8 +27,8 @@ const DATA_LEN: usize = 1;
 /// Gets the required size of an account for the given mint as a
 /// little-endian `u64`.
 ///
-/// Return data can be fetched using `sol_get_return_data` and deserializing
-/// the return data as a little-endian `u64`.
+/// Use `invoke_and_get_size` to invoke the instruction and read the size from
+/// the return data.
 ///
 /// Accounts expected by this instruction:
 ///
@@ -113,6 +111,20 @@ impl<'account, Program: TokenInterface> GetAccountDataSize<'account, Program> {
 
         Ok(())
     }
+
+    /// Invokes the instruction with `Program::ID` and returns the required
+    /// size of the account.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::IncorrectProgramId`] if the return data was not
+    /// set by `Program::ID`, or [`ProgramError::InvalidArgument`] if the
+    /// return data is not a `u64`.
+    #[inline(always)]
+    pub fn invoke_and_get_size(&self) -> Result<u64, ProgramError> {
+        self.invoke()?;
+        get_return_data_u64(&Program::ID)
+    }
 }
 
 impl<Program: TokenInterface> CpiWriter for GetAccountDataSize<'_, Program> {
@@ -205,3 +217,36 @@ fn write_instruction_data(data: &mut [MaybeUninit<u8>]) -> Result<usize, Program
 
     Ok(DATA_LEN)
 }
+
+#[cfg(all(test, feature = "cpi-mock"))]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            instructions::test_utils::{mock_return_data, with_mint},
+            TokenProgram,
+        },
+    };
+
+    #[test]
+    fn test_invoke_and_get_size() {
+        with_mint(|mint| {
+            let instruction = GetAccountDataSize::<TokenProgram>::new(mint);
+
+            mock_return_data(crate::ID, 165u64.to_le_bytes());
+            assert_eq!(instruction.invoke_and_get_size(), Ok(165));
+
+            mock_return_data(Address::new_from_array([2; 32]), 165u64.to_le_bytes());
+            assert_eq!(
+                instruction.invoke_and_get_size(),
+                Err(ProgramError::IncorrectProgramId)
+            );
+
+            mock_return_data(crate::ID, [165, 0, 0, 0]);
+            assert_eq!(
+                instruction.invoke_and_get_size(),
+                Err(ProgramError::InvalidArgument)
+            );
+        });
+    }
+}
@@ -1,5 +1,6 @@
 use {
     crate::{
+        cpi::{invoke_unchecked, CpiAccount},
         instructions::{
             account_borrow_failed_error, invalid_argument_error, write_bytes, CpiWriter,
             UNINIT_BYTE, UNINIT_CPI_ACCOUNT, UNINIT_INSTRUCTION_ACCOUNT,
@@ -9,10 +10,7 @@ use {
     core::{marker::PhantomData, mem::MaybeUninit, slice::from_raw_parts},
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{
-        cpi::{invoke_unchecked, CpiAccount},
-        InstructionAccount, InstructionView,
-    },
+    solana_instruction_view::{InstructionAccount, InstructionView},
     solana_program_error::{ProgramError, ProgramResult},
 };
 
@@ -3,9 +3,13 @@ pub use crate::instructions::{
     unwrap_lamports::Amount,
 };
 use {
-    crate::TokenProgram,
+    crate::{
+        cpi::{self, CpiAccount},
+        incorrect_program_id, TokenProgram,
+    },
     core::mem::MaybeUninit,
-    solana_instruction_view::{cpi::CpiAccount, InstructionAccount},
+    solana_address::Address,
+    solana_instruction_view::InstructionAccount,
     solana_program_error::ProgramError,
 };
 
@@ -53,8 +57,8 @@ const UNINIT_INSTRUCTION_ACCOUNT: MaybeUninit<InstructionAccount> =
 ///
 /// Fails on an invalid mint.
 ///
-/// Return data can be fetched using `sol_get_return_data` and deserialized
-/// with `String::from_utf8`.
+/// Use `invoke_and_get_ui_amount` to invoke the instruction and read the
+/// `UiAmount` string from the return data.
 ///
 /// Accounts expected by this instruction:
 ///
@@ -191,8 +195,8 @@ pub type FreezeAccount<'account, 'multisig, MultisigSigner> =
 /// Gets the required size of an account for the given mint as a
 /// little-endian `u64`.
 ///
-/// Return data can be fetched using `sol_get_return_data` and deserializing
-/// the return data as a little-endian `u64`.
+/// Use `invoke_and_get_size` to invoke the instruction and read the size from
+/// the return data.
 ///
 /// Accounts expected by this instruction:
 ///
@@ -469,8 +473,8 @@ pub type TransferChecked<'account, 'multisig, MultisigSigner> =
 /// using the given mint. In this version of the program, the mint can
 /// only specify the number of decimals.
 ///
-/// Return data can be fetched using `sol_get_return_data` and deserializing
-/// the return data as a little-endian `u64`.
+/// Use `invoke_and_get_amount` to invoke the instruction and read the amount
+/// from the return data.
 ///
 /// Accounts expected by this instruction:
 ///
@@ -531,6 +535,32 @@ fn invalid_argument_error() -> ProgramError {
     ProgramError::InvalidArgument
 }
 
+/// Returns the return data set by `program`.
+#[inline(always)]
+fn get_return_data(program: &Address) -> Result<cpi::ReturnData, ProgramError> {
+    match cpi::get_return_data() {
+        Some(return_data) if return_data.program_id() == program => Ok(return_data),
+        _ => Err(incorrect_program_id()),
+    }
+}
+
+/// Returns the return data set by `program`, deserialized as a little-endian
+/// `u64`.
+///
+/// # Errors
+///
+/// Returns [`ProgramError::IncorrectProgramId`] if the return data was not set
+/// by `program`, or [`ProgramError::InvalidArgument`] if the return data is not
+/// a `u64`.
+#[inline(always)]
+pub fn get_return_data_u64(program: &Address) -> Result<u64, ProgramError> {
+    get_return_data(program)?
+        .as_slice()
+        .try_into()
+        .map(u64::from_le_bytes)
+        .map_err(|_| invalid_argument_error())
+}
+
 /// A helper function to write bytes from a source slice to a destination slice
 /// of `MaybeUninit<u8>`.
 #[inline(always)]
@@ -575,3 +605,58 @@ pub trait CpiWriter {
     /// Returns the number of bytes written.
     fn write_instruction_data(&self, data: &mut [MaybeUninit<u8>]) -> Result<usize, ProgramError>;
 }
+
+#[cfg(all(test, feature = "cpi-mock"))]
+pub(crate) mod test_utils {
+    use {
+        pinocchio::{
+            cpi::{self, mock},
+            test_utils::{ProgramInput, TestAccount},
+            SUCCESS,
+        },
+        solana_account_view::AccountView,
+        solana_address::Address,
+        solana_instruction_view::InstructionView,
+    };
+
+    /// Mock the token program to set `return_data` from `program`.
+    ///
+    /// When `program` is not the token program, the token program invokes
+    /// `program`, which sets the return data.
+    pub(crate) fn mock_return_data<const N: usize>(program: Address, return_data: [u8; N]) {
+        mock::reset();
+
+        if program != crate::ID {
+            let callee = Address::new_from_array(*program.as_array());
+            mock::register_handler(crate::ID, move |_, _| {
+                let instruction = InstructionView {
+                    program_id: &callee,
+                    accounts: &[],
+                    data: &[],
+                };
+                cpi::invoke::<0, &AccountView>(&instruction, &[])
+            });
+        }
+
+        mock::register_handler(program, move |_, _| {
+            cpi::set_return_data(&return_data);
+            Ok(())
+        });
+    }
+
+    /// Run `f` with a mint account.
+    pub(crate) fn with_mint(f: impl FnOnce(&AccountView)) {
+        let mut input = ProgramInput::new(
+            &Address::default(),
+            &[TestAccount::new(Address::new_from_array([1; 32]), crate::ID, 1, &[0; 82]).into()],
+            &[],
+        );
+
+        let result = input.process(|_, accounts, _| {
+            f(&accounts[0]);
+            Ok(())
+        });
+
+        assert_eq!(result, SUCCESS);
+    }
+}
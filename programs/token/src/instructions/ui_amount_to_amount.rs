@@ -1,18 +1,16 @@
 use {
     crate::{
+        cpi::{invoke_unchecked, CpiAccount},
         instructions::{
-            invalid_argument_error, write_bytes, CpiWriter, UNINIT_BYTE, UNINIT_CPI_ACCOUNT,
-            UNINIT_INSTRUCTION_ACCOUNT,
+            get_return_data_u64, invalid_argument_error, write_bytes, CpiWriter, UNINIT_BYTE,
+            UNINIT_CPI_ACCOUNT, UNINIT_INSTRUCTION_ACCOUNT,
         },
         TokenInterface,
     },
     core::{marker::PhantomData, mem::MaybeUninit, slice::from_raw_parts},
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{
-        cpi::{invoke_unchecked, CpiAccount},
-        InstructionAccount, InstructionView,
-    },
+    solana_instruction_view::{InstructionAccount, InstructionView},
     solana_program_error::{ProgramError, ProgramResult},
 };
 
@@ -31,8 +29,8 @@ const MAX_DATA_LEN: usize = 255;
 /// using the given mint. In this version of the program, the mint can
 /// only specify the number of decimals.
 ///
-/// Return data can be fetched using `sol_get_return_data` and deserializing
-/// the return data as a little-endian `u64`.
+/// Use `invoke_and_get_amount` to invoke the instruction and read the amount
+/// from the return data.
 ///
 /// Accounts expected by this instruction:
 ///
@@ -119,6 +117,20 @@ impl<'account, 'amount, Program: TokenInterface> UiAmountToAmount<'account, 'amo
 
         Ok(())
     }
+
+    /// Invokes the instruction with `Program::ID` and returns the raw
+    /// amount.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::IncorrectProgramId`] if the return data was not
+    /// set by `Program::ID`, or [`ProgramError::InvalidArgument`] if the
+    /// return data is not a `u64`.
+    #[inline(always)]
+    pub fn invoke_and_get_amount(&self) -> Result<u64, ProgramError> {
+        self.invoke()?;
+        get_return_data_u64(&Program::ID)
+    }
 }
 
 impl<Program: TokenInterface> super::batch::IntoBatch<Program>
@@ -220,3 +232,36 @@ fn write_instruction_data(
 
     Ok(expected_data_len)
 }
+
+#[cfg(all(test, feature = "cpi-mock"))]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            instructions::test_utils::{mock_return_data, with_mint},
+            TokenProgram,
+        },
+    };
+
+    #[test]
+    fn test_invoke_and_get_amount() {
+        with_mint(|mint| {
+            let instruction = UiAmountToAmount::<TokenProgram>::new(mint, "1.5");
+
+            mock_return_data(crate::ID, 1_500u64.to_le_bytes());
+            assert_eq!(instruction.invoke_and_get_amount(), Ok(1_500));
+
+            mock_return_data(Address::new_from_array([2; 32]), 1_500u64.to_le_bytes());
+            assert_eq!(
+                instruction.invoke_and_get_amount(),
+                Err(ProgramError::IncorrectProgramId)
+            );
+
+            mock_return_data(crate::ID, [220, 5]);
+            assert_eq!(
+                instruction.invoke_and_get_amount(),
+                Err(ProgramError::InvalidArgument)
+            );
+        });
+    }
+}
@@ -1,5 +1,6 @@
 use {
     crate::{
+        cpi::{invoke_signed_unchecked, CpiAccount, Signer},
         instructions::{
             account_borrow_failed_error, initialize_multisig::MAX_MULTISIG_SIGNERS,
             invalid_argument_error, CpiWriter, UNINIT_BYTE, UNINIT_CPI_ACCOUNT,
@@ -10,10 +11,7 @@ use {
     core::{marker::PhantomData, mem::MaybeUninit, slice::from_raw_parts},
     solana_account_view::AccountView,
     solana_address::Address,
-    solana_instruction_view::{
-        cpi::{invoke_signed_unchecked, CpiAccount, Signer},
-        InstructionAccount, InstructionView,
-    },
+    solana_instruction_view::{InstructionAccount, InstructionView},
     solana_program_error::{ProgramError, ProgramResult},
 };
 
@@ -2,11 +2,18 @@
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
+// Cross-program invocation helpers. With the `cpi-mock` feature, invocations
+// are routed through the host-side mock runtime of `pinocchio`.
+#[cfg(feature = "cpi-mock")]
+use pinocchio::cpi;
+#[cfg(not(feature = "cpi-mock"))]
+use solana_instruction_view::cpi;
 use {
     solana_address::Address,
     solana_program_error::{ProgramError, ProgramResult},
 };
 
+pub mod decode;
 pub mod instructions;
 pub mod state;
 
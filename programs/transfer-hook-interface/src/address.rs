@@ -0,0 +1,11 @@
+use {crate::EXTRA_ACCOUNT_METAS_SEED, solana_address::Address};
+
+/// Returns the validation account address holding the extra account metas of
+/// `mint` for the transfer hook `program_id`, together with its bump seed.
+///
+/// The address is derived from the seeds `[EXTRA_ACCOUNT_METAS_SEED, mint]`
+/// with the transfer hook program id.
+#[inline(always)]
+pub fn get_extra_account_metas_address(mint: &Address, program_id: &Address) -> (Address, u8) {
+    Address::find_program_address(&[EXTRA_ACCOUNT_METAS_SEED, mint.as_ref()], program_id)
+}
@@ -0,0 +1,118 @@
+//! Error types of the SPL Transfer Hook interface.
+
+use {core::fmt, solana_program_error::ProgramError};
+
+/// Errors returned by SPL Transfer Hook interface programs.
+///
+/// Transfer hook programs return these errors as [`ProgramError::Custom`] with
+/// the enum discriminant as the error code. Codes start at a hash-derived
+/// offset to avoid collisions with the errors of other programs.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransferHookError {
+    /// Incorrect account provided.
+    IncorrectAccount = 2_110_272_652,
+    /// Mint has no mint authority.
+    MintHasNoMintAuthority,
+    /// Incorrect mint authority has signed the instruction.
+    IncorrectMintAuthority,
+    /// Program called outside of a token transfer.
+    ProgramCalledOutsideOfTransfer,
+}
+
+impl TransferHookError {
+    /// Return the description of the error.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            TransferHookError::IncorrectAccount => "Incorrect account provided",
+            TransferHookError::MintHasNoMintAuthority => "Mint has no mint authority",
+            TransferHookError::IncorrectMintAuthority => {
+                "Incorrect mint authority has signed the instruction"
+            }
+            TransferHookError::ProgramCalledOutsideOfTransfer => {
+                "Program called outside of a token transfer"
+            }
+        }
+    }
+}
+
+impl fmt::Display for TransferHookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<TransferHookError> for ProgramError {
+    #[inline(always)]
+    fn from(error: TransferHookError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl TryFrom<u32> for TransferHookError {
+    type Error = ProgramError;
+
+    /// Decode a transfer hook error from its error code.
+    ///
+    /// Returns the code as a [`ProgramError::Custom`] if it is not a transfer
+    /// hook error code.
+    #[inline(always)]
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            2_110_272_652..=2_110_272_655 => {
+                // SAFETY: `value` is guaranteed to be in the range of the enum variants.
+                Ok(unsafe { core::mem::transmute::<u32, TransferHookError>(value) })
+            }
+            _ => Err(ProgramError::Custom(value)),
+        }
+    }
+}
+
+impl TryFrom<ProgramError> for TransferHookError {
+    type Error = ProgramError;
+
+    /// Decode a transfer hook error from a [`ProgramError::Custom`] error,
+    /// e.g., the result of a failed cross-program invocation.
+    ///
+    /// Returns the original error if it is not a transfer hook error.
+    #[inline(always)]
+    fn try_from(error: ProgramError) -> Result<Self, Self::Error> {
+        match error {
+            ProgramError::Custom(code) => TransferHookError::try_from(code),
+            error => Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(TransferHookError::IncorrectAccount as u32, 2_110_272_652);
+        assert_eq!(
+            TransferHookError::ProgramCalledOutsideOfTransfer as u32,
+            2_110_272_655
+        );
+
+        for code in 2_110_272_652..=2_110_272_655 {
+            let error = TransferHookError::try_from(code).unwrap();
+            assert_eq!(error as u32, code);
+            assert!(!error.as_str().is_empty());
+            assert_eq!(
+                TransferHookError::try_from(ProgramError::from(error)),
+                Ok(error)
+            );
+        }
+
+        assert_eq!(
+            TransferHookError::try_from(2_110_272_656),
+            Err(ProgramError::Custom(2_110_272_656))
+        );
+        assert_eq!(
+            TransferHookError::try_from(ProgramError::InvalidArgument),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Instructions of the SPL Transfer Hook interface.
+
+pub use pinocchio_token_2022::instructions::transfer_hook::extra_account_metas::EXECUTE_DISCRIMINATOR;
+use {
+    crate::state::{unpack_metas, ExtraAccountMeta},
+    solana_program_error::ProgramError,
+};
+
+/// Discriminator of the `InitializeExtraAccountMetaList` instruction.
+pub const INITIALIZE_EXTRA_ACCOUNT_META_LIST_DISCRIMINATOR: [u8; 8] =
+    [43, 34, 13, 49, 167, 88, 235, 235];
+
+/// Discriminator of the `UpdateExtraAccountMetaList` instruction.
+pub const UPDATE_EXTRA_ACCOUNT_META_LIST_DISCRIMINATOR: [u8; 8] =
+    [157, 105, 42, 146, 102, 85, 241, 174];
+
+/// Instructions supported by transfer hook interface programs.
+///
+/// Instruction data starts with an 8-byte discriminator, the first 8 bytes of
+/// the SHA-256 hash of `spl-transfer-hook-interface:<instruction>`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransferHookInstruction<'a> {
+    /// Runs additional transfer logic.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The source account.
+    ///   1. `[]` The token mint.
+    ///   2. `[]` The destination account.
+    ///   3. `[]` The source account's owner/delegate.
+    ///   4. `[]` The validation account.
+    ///   5. `..5+M` `M` extra accounts, declared in the validation account.
+    Execute {
+        /// The amount of tokens being transferred.
+        amount: u64,
+    },
+
+    /// Initializes the extra account metas of a mint in the first free TLV
+    /// entry of its validation account.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The validation account.
+    ///   1. `[]` The token mint.
+    ///   2. `[signer]` The mint authority.
+    ///   3. `[]` The system program.
+    InitializeExtraAccountMetaList {
+        /// The extra account metas to write.
+        extra_account_metas: &'a [ExtraAccountMeta],
+    },
+
+    /// Overwrites the extra account metas of a mint in its validation
+    /// account.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The validation account.
+    ///   1. `[]` The token mint.
+    ///   2. `[signer]` The mint authority.
+    UpdateExtraAccountMetaList {
+        /// The new extra account metas.
+        extra_account_metas: &'a [ExtraAccountMeta],
+    },
+}
+
+impl<'a> TransferHookInstruction<'a> {
+    /// Unpack a transfer hook instruction from its instruction data.
+    ///
+    /// The `Execute` discriminator is followed by the amount (`u64`);
+    /// the extra account meta list instructions are followed by the number
+    /// of metas (`u32`) and exactly that many metas, borrowed from the
+    /// instruction data without copying.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the data does not
+    /// hold a transfer hook instruction.
+    #[inline]
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        if data.len() < 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let (discriminator, rest) = data.split_at(8);
+
+        match discriminator {
+            discriminator if discriminator == EXECUTE_DISCRIMINATOR => {
+                let amount = rest.get(..8).ok_or(ProgramError::InvalidInstructionData)?;
+
+                Ok(TransferHookInstruction::Execute {
+                    amount: u64::from_le_bytes([
+                        amount[0], amount[1], amount[2], amount[3], amount[4], amount[5],
+                        amount[6], amount[7],
+                    ]),
+                })
+            }
+            discriminator if discriminator == INITIALIZE_EXTRA_ACCOUNT_META_LIST_DISCRIMINATOR => {
+                Ok(TransferHookInstruction::InitializeExtraAccountMetaList {
+                    extra_account_metas: unpack_metas(rest)
+                        .ok_or(ProgramError::InvalidInstructionData)?,
+                })
+            }
+            discriminator if discriminator == UPDATE_EXTRA_ACCOUNT_META_LIST_DISCRIMINATOR => {
+                Ok(TransferHookInstruction::UpdateExtraAccountMetaList {
+                    extra_account_metas: unpack_metas(rest)
+                        .ok_or(ProgramError::InvalidInstructionData)?,
+                })
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use {super::*, solana_address::Address, std::vec::Vec};
+
+    #[test]
+    fn unpack_execute() {
+        let mut data = Vec::from(EXECUTE_DISCRIMINATOR);
+        data.extend_from_slice(&42u64.to_le_bytes());
+
+        assert_eq!(
+            TransferHookInstruction::unpack(&data),
+            Ok(TransferHookInstruction::Execute { amount: 42 })
+        );
+        assert_eq!(
+            TransferHookInstruction::unpack(&data[..15]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn unpack_extra_account_meta_list_instructions() {
+        let meta =
+            ExtraAccountMeta::new_with_address(&Address::new_from_array([7; 32]), true, true);
+
+        let mut data = Vec::from(INITIALIZE_EXTRA_ACCOUNT_META_LIST_DISCRIMINATOR);
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.push(meta.discriminator);
+        data.extend_from_slice(&meta.address_config);
+        data.extend_from_slice(&[1, 1]);
+
+        assert_eq!(
+            TransferHookInstruction::unpack(&data),
+            Ok(TransferHookInstruction::InitializeExtraAccountMetaList {
+                extra_account_metas: &[meta],
+            })
+        );
+
+        data[..8].copy_from_slice(&UPDATE_EXTRA_ACCOUNT_META_LIST_DISCRIMINATOR);
+        assert_eq!(
+            TransferHookInstruction::unpack(&data),
+            Ok(TransferHookInstruction::UpdateExtraAccountMetaList {
+                extra_account_metas: &[meta],
+            })
+        );
+
+        // The number of metas must match the instruction data.
+        data.push(0);
+        assert_eq!(
+            TransferHookInstruction::unpack(&data),
+            Err(ProgramError::InvalidInstructionData)
+        );
+
+        assert_eq!(
+            TransferHookInstruction::unpack(&[0; 16]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
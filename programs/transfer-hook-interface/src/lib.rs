@@ -0,0 +1,15 @@
+#![no_std]
+
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+mod address;
+pub mod error;
+pub mod instruction;
+pub mod state;
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+mod validation;
+
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub use address::*;
+pub use pinocchio_token_2022::instructions::transfer_hook::extra_account_metas::EXTRA_ACCOUNT_METAS_SEED;
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+pub use validation::*;
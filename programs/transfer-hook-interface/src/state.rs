@@ -0,0 +1,635 @@
+//! Extra account metas stored in the validation account of a mint.
+
+use {
+    crate::instruction::EXECUTE_DISCRIMINATOR,
+    core::slice::from_raw_parts,
+    pinocchio_token_2022::instructions::transfer_hook::extra_account_metas::{
+        find_execute_entry, ExecuteEntry, MAX_SEED_LEN, TLV_HEADER_LEN,
+    },
+    solana_address::Address,
+    solana_program_error::{ProgramError, ProgramResult},
+    solana_zero_copy::unaligned::Bool,
+};
+
+/// Length of the extra account metas count preceding the metas.
+const COUNT_LEN: usize = 4;
+
+/// An extra account required by the `Execute` instruction (35 bytes).
+///
+/// The `discriminator` determines how the `address_config` resolves the
+/// account address:
+///
+///   - `0`: the address itself.
+///   - `1`: a program derived address of the transfer hook program, from the
+///     packed [`Seed`]s.
+///   - `2`: an address read from the instruction or account data, from the
+///     packed [`PubkeyData`].
+///   - `128 + i`: a program derived address of the program at account index
+///     `i`, from the packed [`Seed`]s.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ExtraAccountMeta {
+    /// How the address is resolved.
+    pub discriminator: u8,
+
+    /// The address or the configuration to resolve it.
+    pub address_config: [u8; 32],
+
+    /// Whether the account must be a signer.
+    pub is_signer: Bool,
+
+    /// Whether the account must be writable.
+    pub is_writable: Bool,
+}
+
+impl ExtraAccountMeta {
+    pub const LEN: usize = core::mem::size_of::<ExtraAccountMeta>();
+
+    /// Creates an extra account meta with a literal `address`.
+    #[inline(always)]
+    pub fn new_with_address(address: &Address, is_signer: bool, is_writable: bool) -> Self {
+        Self {
+            discriminator: 0,
+            address_config: address.to_bytes(),
+            is_signer: is_signer.into(),
+            is_writable: is_writable.into(),
+        }
+    }
+
+    /// Creates an extra account meta for a program derived address of the
+    /// transfer hook program.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidSeeds`] if the packed `seeds` do not fit
+    /// in the address config or a seed is longer than 32 bytes.
+    #[inline]
+    pub fn new_with_seeds(
+        seeds: &[Seed],
+        is_signer: bool,
+        is_writable: bool,
+    ) -> Result<Self, ProgramError> {
+        Ok(Self {
+            discriminator: 1,
+            address_config: Seed::pack_into_address_config(seeds)?,
+            is_signer: is_signer.into(),
+            is_writable: is_writable.into(),
+        })
+    }
+
+    /// Creates an extra account meta for a program derived address of the
+    /// program at `program_index` in the `Execute` accounts.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidArgument`] if `program_index` is greater
+    /// than 127, or [`ProgramError::InvalidSeeds`] if the packed `seeds` do
+    /// not fit in the address config or a seed is longer than 32 bytes.
+    #[inline]
+    pub fn new_external_pda_with_seeds(
+        program_index: u8,
+        seeds: &[Seed],
+        is_signer: bool,
+        is_writable: bool,
+    ) -> Result<Self, ProgramError> {
+        if program_index >= 128 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(Self {
+            discriminator: 128 + program_index,
+            address_config: Seed::pack_into_address_config(seeds)?,
+            is_signer: is_signer.into(),
+            is_writable: is_writable.into(),
+        })
+    }
+
+    /// Creates an extra account meta for an address read from the instruction
+    /// or account data.
+    #[inline(always)]
+    pub fn new_with_pubkey_data(
+        pubkey_data: &PubkeyData,
+        is_signer: bool,
+        is_writable: bool,
+    ) -> Self {
+        Self {
+            discriminator: 2,
+            address_config: pubkey_data.pack_into_address_config(),
+            is_signer: is_signer.into(),
+            is_writable: is_writable.into(),
+        }
+    }
+}
+
+/// A seed of a program derived address extra account.
+///
+/// Seeds are packed into the address config of an [`ExtraAccountMeta`] as a
+/// type byte followed by their configuration, terminated by a zero byte or
+/// the end of the config.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Seed<'a> {
+    /// Literal bytes, packed as `[1, length, bytes..]`.
+    Literal { bytes: &'a [u8] },
+
+    /// A slice of the `Execute` instruction data, packed as
+    /// `[2, index, length]`.
+    InstructionData { index: u8, length: u8 },
+
+    /// The address of the account at `index` in the `Execute` accounts,
+    /// packed as `[3, index]`.
+    AccountKey { index: u8 },
+
+    /// A slice of the data of the account at `account_index` in the `Execute`
+    /// accounts, packed as `[4, account_index, data_index, length]`.
+    AccountData {
+        account_index: u8,
+        data_index: u8,
+        length: u8,
+    },
+}
+
+impl<'a> Seed<'a> {
+    /// Return the length of the packed seed.
+    #[inline(always)]
+    pub const fn packed_len(&self) -> usize {
+        match self {
+            Seed::Literal { bytes } => 2 + bytes.len(),
+            Seed::InstructionData { .. } => 3,
+            Seed::AccountKey { .. } => 2,
+            Seed::AccountData { .. } => 4,
+        }
+    }
+
+    /// Return the length of the seed bytes.
+    #[inline(always)]
+    pub const fn seed_len(&self) -> usize {
+        match self {
+            Seed::Literal { bytes } => bytes.len(),
+            Seed::InstructionData { length, .. } | Seed::AccountData { length, .. } => {
+                *length as usize
+            }
+            Seed::AccountKey { .. } => size_of::<Address>(),
+        }
+    }
+
+    /// Pack `seeds` into an address config.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidSeeds`] if the packed `seeds` do not fit
+    /// in the address config or a seed is longer than 32 bytes.
+    #[inline]
+    pub fn pack_into_address_config(seeds: &[Seed]) -> Result<[u8; 32], ProgramError> {
+        let mut config = [0; 32];
+        let mut offset = 0;
+
+        for seed in seeds {
+            if seed.seed_len() > MAX_SEED_LEN {
+                return Err(ProgramError::InvalidSeeds);
+            }
+
+            let end = offset + seed.packed_len();
+            let packed = config
+                .get_mut(offset..end)
+                .ok_or(ProgramError::InvalidSeeds)?;
+
+            match *seed {
+                Seed::Literal { bytes } => {
+                    packed[0] = 1;
+                    // The length fits in the 32 bytes config.
+                    packed[1] = bytes.len() as u8;
+                    packed[2..].copy_from_slice(bytes);
+                }
+                Seed::InstructionData { index, length } => {
+                    packed.copy_from_slice(&[2, index, length]);
+                }
+                Seed::AccountKey { index } => {
+                    packed.copy_from_slice(&[3, index]);
+                }
+                Seed::AccountData {
+                    account_index,
+                    data_index,
+                    length,
+                } => {
+                    packed.copy_from_slice(&[4, account_index, data_index, length]);
+                }
+            }
+
+            offset = end;
+        }
+
+        Ok(config)
+    }
+
+    /// Unpack the first seed of an address `config`, returning it with the
+    /// remaining config, or `None` at the end of the seeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidAccountData`] if the config is
+    /// malformed, or [`ProgramError::InvalidSeeds`] if the seed is longer
+    /// than 32 bytes.
+    #[inline]
+    pub fn unpack(config: &'a [u8]) -> Result<Option<(Self, &'a [u8])>, ProgramError> {
+        let seed = match *config {
+            [] | [0, ..] => return Ok(None),
+            [1, length, ref rest @ ..] => Seed::Literal {
+                bytes: rest
+                    .get(..length as usize)
+                    .ok_or(ProgramError::InvalidAccountData)?,
+            },
+            [2, index, length, ..] => Seed::InstructionData { index, length },
+            [3, index, ..] => Seed::AccountKey { index },
+            [4, account_index, data_index, length, ..] => Seed::AccountData {
+                account_index,
+                data_index,
+                length,
+            },
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+
+        if seed.seed_len() > MAX_SEED_LEN {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Some((seed, &config[seed.packed_len()..])))
+    }
+}
+
+/// The location of an address in the instruction or account data.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PubkeyData {
+    /// The 32 bytes of the `Execute` instruction data at `index`, packed as
+    /// `[1, index]`.
+    InstructionData { index: u8 },
+
+    /// The 32 bytes of the data of the account at `account_index` in the
+    /// `Execute` accounts at `data_index`, packed as
+    /// `[2, account_index, data_index]`.
+    AccountData { account_index: u8, data_index: u8 },
+}
+
+impl PubkeyData {
+    /// Pack the address location into an address config.
+    #[inline(always)]
+    pub fn pack_into_address_config(&self) -> [u8; 32] {
+        let mut config = [0; 32];
+
+        match *self {
+            PubkeyData::InstructionData { index } => {
+                config[..2].copy_from_slice(&[1, index]);
+            }
+            PubkeyData::AccountData {
+                account_index,
+                data_index,
+            } => {
+                config[..3].copy_from_slice(&[2, account_index, data_index]);
+            }
+        }
+
+        config
+    }
+
+    /// Unpack the address location from an address `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidAccountData`] if the config is
+    /// malformed.
+    #[inline]
+    pub fn unpack(config: &[u8]) -> Result<Self, ProgramError> {
+        match *config {
+            [1, index, ..] => Ok(PubkeyData::InstructionData { index }),
+            [2, account_index, data_index, ..] => Ok(PubkeyData::AccountData {
+                account_index,
+                data_index,
+            }),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+/// The extra account metas of the `Execute` instruction.
+///
+/// The metas are stored in the TLV data of the validation account of a mint,
+/// as an entry with the `Execute` discriminator as type, and a value with the
+/// number of metas (`u32`) followed by the metas. The metas are borrowed
+/// from the account data without copying.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExtraAccountMetaList<'a> {
+    metas: &'a [ExtraAccountMeta],
+}
+
+impl<'a> ExtraAccountMetaList<'a> {
+    /// Return the length of the TLV entry holding `num_metas` extra account
+    /// metas, which is the length of a validation account holding only the
+    /// `Execute` extra account metas.
+    #[inline(always)]
+    pub const fn size_of(num_metas: usize) -> usize {
+        TLV_HEADER_LEN + COUNT_LEN + num_metas * ExtraAccountMeta::LEN
+    }
+
+    /// Return the extra account metas of the `Execute` instruction from the
+    /// TLV data of a validation account.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidAccountData`] if the data is malformed
+    /// or does not contain the `Execute` extra account metas.
+    #[inline]
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self, ProgramError> {
+        match find_execute_entry(data)? {
+            ExecuteEntry::Occupied { start, length } => {
+                let value = &data[start + TLV_HEADER_LEN..start + TLV_HEADER_LEN + length];
+
+                Ok(Self {
+                    metas: unpack_metas(value).ok_or(ProgramError::InvalidAccountData)?,
+                })
+            }
+            ExecuteEntry::Vacant { .. } => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// Return the extra account metas.
+    #[inline(always)]
+    pub fn metas(&self) -> &'a [ExtraAccountMeta] {
+        self.metas
+    }
+
+    /// Write the `Execute` extra account `metas` into the first free TLV
+    /// entry of a validation account `data`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::AccountAlreadyInitialized`] if the data already
+    /// contains the `Execute` extra account metas,
+    /// [`ProgramError::AccountDataTooSmall`] if the metas do not fit in the
+    /// data, or [`ProgramError::InvalidAccountData`] if the data is
+    /// malformed.
+    #[inline]
+    pub fn init(data: &mut [u8], metas: &[ExtraAccountMeta]) -> ProgramResult {
+        match find_execute_entry(data)? {
+            ExecuteEntry::Occupied { .. } => Err(ProgramError::AccountAlreadyInitialized),
+            ExecuteEntry::Vacant { start } => {
+                let entry = data
+                    .get_mut(start..start + Self::size_of(metas.len()))
+                    .ok_or(ProgramError::AccountDataTooSmall)?;
+
+                write_entry(entry, metas);
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Overwrite the `Execute` extra account metas of a validation account
+    /// `data` with `metas`.
+    ///
+    /// The number of metas can only change if the entry is the last one of
+    /// the data; the account must be resized beforehand if the metas do not
+    /// fit, and the bytes no longer used are zeroed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::UninitializedAccount`] if the data does not
+    /// contain the `Execute` extra account metas,
+    /// [`ProgramError::AccountDataTooSmall`] if the metas do not fit in the
+    /// data, or [`ProgramError::InvalidAccountData`] if the data is
+    /// malformed or the entry cannot be resized.
+    #[inline]
+    pub fn update(data: &mut [u8], metas: &[ExtraAccountMeta]) -> ProgramResult {
+        let (start, length) = match find_execute_entry(data)? {
+            ExecuteEntry::Occupied { start, length } => (start, length),
+            ExecuteEntry::Vacant { .. } => return Err(ProgramError::UninitializedAccount),
+        };
+
+        let end = start + TLV_HEADER_LEN + length;
+        let new_end = start + Self::size_of(metas.len());
+
+        if new_end != end && data[end..].iter().any(|byte| *byte != 0) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        write_entry(
+            data.get_mut(start..new_end)
+                .ok_or(ProgramError::AccountDataTooSmall)?,
+            metas,
+        );
+
+        if new_end < end {
+            data[new_end..end].fill(0);
+        }
+
+        Ok(())
+    }
+}
+
+/// Write the `Execute` TLV entry with `metas` into `entry`, which must be
+/// exactly `ExtraAccountMetaList::size_of(metas.len())` bytes long.
+#[inline(always)]
+fn write_entry(entry: &mut [u8], metas: &[ExtraAccountMeta]) {
+    let (header, value) = entry.split_at_mut(TLV_HEADER_LEN);
+    let (count, value) = value.split_at_mut(COUNT_LEN);
+
+    header[..8].copy_from_slice(&EXECUTE_DISCRIMINATOR);
+    header[8..].copy_from_slice(&((COUNT_LEN + value.len()) as u32).to_le_bytes());
+    count.copy_from_slice(&(metas.len() as u32).to_le_bytes());
+
+    // SAFETY: `ExtraAccountMeta` is repr(C), contains only `u8`, `[u8; 32]`
+    // and `Bool` (repr(transparent) over `u8`) fields and has no padding.
+    value.copy_from_slice(unsafe {
+        from_raw_parts(metas.as_ptr() as *const u8, size_of_val(metas))
+    });
+}
+
+/// Unpack the extra account metas from a `value` with the number of metas
+/// (`u32`) followed by exactly that many metas.
+#[inline(always)]
+pub(crate) fn unpack_metas(value: &[u8]) -> Option<&[ExtraAccountMeta]> {
+    if value.len() < COUNT_LEN {
+        return None;
+    }
+
+    let (count, metas) = value.split_at(COUNT_LEN);
+    let count = u32::from_le_bytes([count[0], count[1], count[2], count[3]]) as usize;
+
+    if metas.len() != count.checked_mul(ExtraAccountMeta::LEN)? {
+        return None;
+    }
+
+    // SAFETY: `metas` is `count` metas long, `ExtraAccountMeta` has alignment
+    // 1 and all bit patterns are valid.
+    Some(unsafe { from_raw_parts(metas.as_ptr() as *const ExtraAccountMeta, count) })
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use {
+        super::*,
+        pinocchio_token_2022::instructions::transfer_hook::extra_account_metas::EXTRA_ACCOUNT_META_LEN,
+        std::vec,
+    };
+
+    #[test]
+    fn test_extra_account_meta_layout() {
+        assert_eq!(ExtraAccountMeta::LEN, EXTRA_ACCOUNT_META_LEN);
+        assert_eq!(core::mem::align_of::<ExtraAccountMeta>(), 1);
+    }
+
+    #[test]
+    fn pack_and_unpack_seeds() {
+        let seeds = [
+            Seed::Literal { bytes: b"seed" },
+            Seed::InstructionData {
+                index: 8,
+                length: 8,
+            },
+            Seed::AccountKey { index: 1 },
+            Seed::AccountData {
+                account_index: 4,
+                data_index: 2,
+                length: 32,
+            },
+        ];
+
+        let config = Seed::pack_into_address_config(&seeds).unwrap();
+        assert_eq!(
+            &config[..15],
+            &[1, 4, b's', b'e', b'e', b'd', 2, 8, 8, 3, 1, 4, 4, 2, 32]
+        );
+
+        let mut rest: &[u8] = &config;
+        for expected in seeds {
+            let (seed, remaining) = Seed::unpack(rest).unwrap().unwrap();
+            assert_eq!(seed, expected);
+            rest = remaining;
+        }
+        assert_eq!(Seed::unpack(rest), Ok(None));
+
+        // Seeds that do not fit in the config or are too long.
+        assert_eq!(
+            Seed::pack_into_address_config(&[Seed::Literal { bytes: &[7; 31] }]),
+            Err(ProgramError::InvalidSeeds)
+        );
+        assert_eq!(
+            Seed::pack_into_address_config(&[Seed::InstructionData {
+                index: 0,
+                length: 33,
+            }]),
+            Err(ProgramError::InvalidSeeds)
+        );
+        assert_eq!(Seed::unpack(&[5]), Err(ProgramError::InvalidAccountData));
+        assert_eq!(
+            Seed::unpack(&[1, 4, 0]),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn pack_and_unpack_pubkey_data() {
+        for pubkey_data in [
+            PubkeyData::InstructionData { index: 8 },
+            PubkeyData::AccountData {
+                account_index: 2,
+                data_index: 32,
+            },
+        ] {
+            let meta = ExtraAccountMeta::new_with_pubkey_data(&pubkey_data, false, true);
+            assert_eq!(meta.discriminator, 2);
+            assert_eq!(PubkeyData::unpack(&meta.address_config), Ok(pubkey_data));
+        }
+
+        assert_eq!(
+            PubkeyData::unpack(&[0; 32]),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            ExtraAccountMeta::new_external_pda_with_seeds(128, &[], false, false),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn init_update_and_read_extra_account_meta_list() {
+        let first =
+            ExtraAccountMeta::new_with_address(&Address::new_from_array([7; 32]), false, true);
+        let second =
+            ExtraAccountMeta::new_with_seeds(&[Seed::AccountKey { index: 0 }], true, false)
+                .unwrap();
+
+        let mut data = vec![0; ExtraAccountMetaList::size_of(2)];
+        assert_eq!(
+            ExtraAccountMetaList::from_bytes(&data),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            ExtraAccountMetaList::update(&mut data, &[first]),
+            Err(ProgramError::UninitializedAccount)
+        );
+
+        ExtraAccountMetaList::init(&mut data, &[first, second]).unwrap();
+        assert_eq!(&data[..8], &EXECUTE_DISCRIMINATOR);
+        assert_eq!(
+            ExtraAccountMetaList::from_bytes(&data).unwrap().metas(),
+            &[first, second]
+        );
+        assert_eq!(
+            ExtraAccountMetaList::init(&mut data, &[first]),
+            Err(ProgramError::AccountAlreadyInitialized)
+        );
+
+        // Shrinking the list zeroes the bytes no longer used.
+        ExtraAccountMetaList::update(&mut data, &[second]).unwrap();
+        assert_eq!(
+            ExtraAccountMetaList::from_bytes(&data).unwrap().metas(),
+            &[second]
+        );
+        assert!(data[ExtraAccountMetaList::size_of(1)..]
+            .iter()
+            .all(|byte| *byte == 0));
+
+        ExtraAccountMetaList::update(&mut data, &[second, first]).unwrap();
+        assert_eq!(
+            ExtraAccountMetaList::from_bytes(&data).unwrap().metas(),
+            &[second, first]
+        );
+        assert_eq!(
+            ExtraAccountMetaList::update(&mut data, &[first, first, first]),
+            Err(ProgramError::AccountDataTooSmall)
+        );
+    }
+
+    #[test]
+    fn extra_account_meta_list_after_other_entries() {
+        let meta =
+            ExtraAccountMeta::new_with_address(&Address::new_from_array([7; 32]), false, false);
+
+        // An entry of another instruction precedes the `Execute` entry.
+        let mut data = vec![0; TLV_HEADER_LEN + 4 + ExtraAccountMetaList::size_of(1)];
+        data[..8].copy_from_slice(&[1; 8]);
+        data[8..12].copy_from_slice(&4u32.to_le_bytes());
+
+        ExtraAccountMetaList::init(&mut data, &[meta]).unwrap();
+        assert_eq!(&data[16..24], &EXECUTE_DISCRIMINATOR);
+        assert_eq!(
+            ExtraAccountMetaList::from_bytes(&data).unwrap().metas(),
+            &[meta]
+        );
+
+        // The count must match the length of the entry.
+        data[28] = 2;
+        assert_eq!(
+            ExtraAccountMetaList::from_bytes(&data),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        // The entry length must fit in the data.
+        data[24] = 255;
+        assert_eq!(
+            ExtraAccountMetaList::from_bytes(&data),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+}
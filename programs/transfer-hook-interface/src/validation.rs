@@ -0,0 +1,341 @@
+use {
+    crate::{
+        error::TransferHookError,
+        get_extra_account_metas_address,
+        state::{ExtraAccountMeta, ExtraAccountMetaList},
+    },
+    pinocchio_token_2022::{
+        instructions::transfer_hook::extra_account_metas::{resolve_address, EXECUTE_ACCOUNTS_LEN},
+        state::{
+            is_extension_not_found_error, Account, StateWithExtensions,
+            TransferHookAccountExtension,
+        },
+    },
+    solana_account_view::AccountView,
+    solana_address::Address,
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// Asserts that the token `account` is being transferred.
+///
+/// Token-2022 sets the `transferring` flag of the `TransferHookAccount`
+/// extension of the source and destination accounts for the duration of the
+/// `Execute` instruction, so a set flag proves that the transfer hook program
+/// was invoked by a token transfer.
+///
+/// # Errors
+///
+/// Returns [`ProgramError::InvalidAccountOwner`] if the account is not owned
+/// by Token-2022, or [`TransferHookError::ProgramCalledOutsideOfTransfer`] if
+/// the account is not being transferred.
+#[inline]
+pub fn assert_transferring(account: &AccountView) -> ProgramResult {
+    let account = StateWithExtensions::<Account>::from_account_view(account)?;
+
+    match account.get_extension::<TransferHookAccountExtension>() {
+        Ok(extension) if bool::from(extension.transferring) => Ok(()),
+        Ok(_) => Err(TransferHookError::ProgramCalledOutsideOfTransfer.into()),
+        Err(error) if is_extension_not_found_error(&error) => {
+            Err(TransferHookError::ProgramCalledOutsideOfTransfer.into())
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Checks the `accounts` of an `Execute` instruction of the transfer hook
+/// `program_id`.
+///
+/// The source and destination accounts must be being transferred, the
+/// validation account must be the one of the mint, and the extra accounts
+/// must match the extra account metas it declares.
+///
+/// # Errors
+///
+/// Returns [`ProgramError::NotEnoughAccountKeys`] if an account is missing,
+/// [`TransferHookError::ProgramCalledOutsideOfTransfer`] if the source or
+/// destination account is not being transferred, or
+/// [`TransferHookError::IncorrectAccount`] if the validation account or an
+/// extra account does not match.
+#[inline]
+pub fn check_execute_accounts(
+    program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [source, mint, destination, _authority, validation, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    assert_transferring(source)?;
+    assert_transferring(destination)?;
+
+    if validation.address() != &get_extra_account_metas_address(mint.address(), program_id).0 {
+        return Err(TransferHookError::IncorrectAccount.into());
+    }
+
+    let data = validation.try_borrow()?;
+
+    ExtraAccountMetaList::from_bytes(&data)?.check_accounts(program_id, accounts, instruction_data)
+}
+
+impl ExtraAccountMetaList<'_> {
+    /// Checks that the extra accounts of an `Execute` instruction of the
+    /// transfer hook `program_id`, following the first 5 `accounts`, match
+    /// the extra account metas.
+    ///
+    /// Each extra account must have the resolved address, and be a signer and
+    /// writable exactly as its meta declares. Additional trailing accounts
+    /// are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::NotEnoughAccountKeys`] if an extra account is
+    /// missing, or [`TransferHookError::IncorrectAccount`] if an extra
+    /// account does not match its meta.
+    #[inline]
+    pub fn check_accounts(
+        &self,
+        program_id: &Address,
+        accounts: &[AccountView],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let extra_accounts = accounts
+            .get(EXECUTE_ACCOUNTS_LEN..EXECUTE_ACCOUNTS_LEN + self.metas().len())
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        for (index, (meta, account)) in self.metas().iter().zip(extra_accounts).enumerate() {
+            // Only the accounts preceding an extra account are available to
+            // resolve its address.
+            let address = meta.resolve(
+                program_id,
+                &accounts[..EXECUTE_ACCOUNTS_LEN + index],
+                instruction_data,
+            )?;
+
+            if account.address() != &address
+                || bool::from(meta.is_signer) != account.is_signer()
+                || bool::from(meta.is_writable) != account.is_writable()
+            {
+                return Err(TransferHookError::IncorrectAccount.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ExtraAccountMeta {
+    /// Resolves the address of the extra account of an `Execute` instruction
+    /// of the transfer hook `program_id`.
+    ///
+    /// Account indices in the address config refer to `accounts`, and
+    /// instruction data indices to `instruction_data`, which includes the
+    /// instruction discriminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::NotEnoughAccountKeys`] if a referenced account
+    /// is missing, [`ProgramError::AccountDataTooSmall`] if a referenced slice
+    /// of an account data is out of bounds, [`ProgramError::InvalidSeeds`] if
+    /// the address cannot be derived, or [`ProgramError::InvalidAccountData`]
+    /// if the address config is malformed or a referenced slice of the
+    /// instruction data is out of bounds.
+    #[inline]
+    pub fn resolve(
+        &self,
+        program_id: &Address,
+        accounts: &[AccountView],
+        instruction_data: &[u8],
+    ) -> Result<Address, ProgramError> {
+        resolve_address(
+            self.discriminator,
+            &self.address_config,
+            instruction_data,
+            program_id,
+            |index| accounts.get(index),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use {
+        super::*,
+        crate::state::{PubkeyData, Seed},
+        pinocchio::{
+            test_utils::{InputAccount, ProgramInput, TestAccount},
+            SUCCESS,
+        },
+        pinocchio_token_2022::state::ExtensionType,
+        std::{vec, vec::Vec},
+    };
+
+    /// Length of a token account with the account type.
+    const TLV_START_INDEX: usize = Account::BASE_LEN + 1;
+
+    const PROGRAM_ID: Address = Address::new_from_array([9; 32]);
+
+    const LITERAL: Address = Address::new_from_array([7; 32]);
+
+    /// Runs `f` with the accounts of a program input.
+    fn with_accounts<T>(accounts: &[InputAccount], f: impl FnOnce(&[AccountView]) -> T) -> T {
+        let mut input = ProgramInput::new(&PROGRAM_ID, accounts, &[]);
+        let mut output = None;
+
+        let result = input.process(|_, accounts, _| {
+            output = Some(f(accounts));
+            Ok(())
+        });
+
+        assert_eq!(result, SUCCESS);
+        output.unwrap()
+    }
+
+    fn build_token_data(transferring: Option<bool>) -> Vec<u8> {
+        let mut data = vec![0u8; TLV_START_INDEX];
+        data[Account::BASE_LEN] = 2;
+
+        if let Some(transferring) = transferring {
+            data.extend_from_slice(&(ExtensionType::TransferHookAccount as u16).to_le_bytes());
+            data.extend_from_slice(&1u16.to_le_bytes());
+            data.push(transferring as u8);
+        }
+
+        data
+    }
+
+    #[test]
+    fn assert_transferring_requires_transferring_token_account() {
+        let token_account = |owner: Address, transferring: Option<bool>| -> InputAccount {
+            TestAccount::new(
+                Address::new_from_array([1; 32]),
+                owner,
+                1,
+                &build_token_data(transferring),
+            )
+            .into()
+        };
+
+        let accounts = [
+            token_account(pinocchio_token_2022::ID, Some(true)),
+            token_account(pinocchio_token_2022::ID, Some(false)),
+            token_account(pinocchio_token_2022::ID, None),
+            token_account(Address::new_from_array([2; 32]), Some(true)),
+        ];
+
+        with_accounts(&accounts, |accounts| {
+            assert_eq!(assert_transferring(&accounts[0]), Ok(()));
+
+            for account in &accounts[1..3] {
+                assert_eq!(
+                    assert_transferring(account),
+                    Err(TransferHookError::ProgramCalledOutsideOfTransfer.into())
+                );
+            }
+
+            assert_eq!(
+                assert_transferring(&accounts[3]),
+                Err(ProgramError::InvalidAccountOwner)
+            );
+        });
+    }
+
+    /// The address derived from the `"seed"` literal, the amount and the
+    /// address of the mint.
+    fn derived_address() -> Address {
+        Address::find_program_address(&[b"seed", &42u64.to_le_bytes(), &[1; 32]], &PROGRAM_ID).0
+    }
+
+    /// The `Execute` accounts followed by the extra `accounts`.
+    fn execute_accounts(extra_accounts: &[TestAccount]) -> Vec<InputAccount> {
+        [[0; 32], [1; 32], [2; 32], [3; 32], [4; 32]]
+            .into_iter()
+            .map(|address| {
+                TestAccount::new(Address::new_from_array(address), PROGRAM_ID, 1, &[]).into()
+            })
+            .chain(extra_accounts.iter().cloned().map(InputAccount::from))
+            .collect()
+    }
+
+    #[test]
+    fn check_accounts_resolves_extra_accounts() {
+        let mut instruction_data = Vec::from(crate::instruction::EXECUTE_DISCRIMINATOR);
+        instruction_data.extend_from_slice(&42u64.to_le_bytes());
+
+        let metas = [
+            ExtraAccountMeta::new_with_address(&LITERAL, true, false),
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal { bytes: b"seed" },
+                    Seed::InstructionData {
+                        index: 8,
+                        length: 8,
+                    },
+                    Seed::AccountKey { index: 1 },
+                ],
+                false,
+                false,
+            )
+            .unwrap(),
+            // The address stored in the data of the first extra account.
+            ExtraAccountMeta::new_with_pubkey_data(
+                &PubkeyData::AccountData {
+                    account_index: 5,
+                    data_index: 0,
+                },
+                false,
+                false,
+            ),
+        ];
+
+        let data = list_data(&metas);
+        let list = ExtraAccountMetaList::from_bytes(&data).unwrap();
+
+        let derived = derived_address();
+        let literal = TestAccount::new(LITERAL, PROGRAM_ID, 1, derived.as_ref());
+        let derived = TestAccount::new(derived, PROGRAM_ID, 1, &[]);
+
+        let check_accounts = |extra_accounts: &[TestAccount]| {
+            with_accounts(&execute_accounts(extra_accounts), |accounts| {
+                list.check_accounts(&PROGRAM_ID, accounts, &instruction_data)
+            })
+        };
+
+        assert_eq!(
+            check_accounts(&[literal.clone().signer(), derived.clone(), derived.clone()]),
+            Ok(())
+        );
+
+        // A missing extra account.
+        assert_eq!(
+            check_accounts(&[literal.clone().signer(), derived.clone()]),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+
+        // An extra account with a different address.
+        assert_eq!(
+            check_accounts(&[derived.clone(), literal.clone().signer(), derived.clone()]),
+            Err(TransferHookError::IncorrectAccount.into())
+        );
+
+        // An extra account that must be a signer.
+        assert_eq!(
+            check_accounts(&[literal.clone(), derived.clone(), derived.clone()]),
+            Err(TransferHookError::IncorrectAccount.into())
+        );
+
+        // A writable extra account with a readonly meta.
+        assert_eq!(
+            check_accounts(&[literal.signer(), derived.clone().writable(), derived]),
+            Err(TransferHookError::IncorrectAccount.into())
+        );
+    }
+
+    fn list_data(metas: &[ExtraAccountMeta]) -> Vec<u8> {
+        let mut data = vec![0; ExtraAccountMetaList::size_of(metas.len())];
+        ExtraAccountMetaList::init(&mut data, metas).unwrap();
+        data
+    }
+}
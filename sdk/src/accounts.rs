@@ -0,0 +1,332 @@
+//! Declarative validation of instruction accounts.
+//!
+//! The [`crate::accounts!`] macro declares a struct with one named field per
+//! instruction account, together with the constraints each account must
+//! satisfy. The struct implements the [`Accounts`] trait, which destructures
+//! the `&mut [AccountView]` received by the program entrypoint into the named
+//! fields and validates the constraints, without any allocation.
+//!
+//! The following constraints are supported:
+//!
+//! | Constraint      | Check                   | Error                                        |
+//! | --------------- | ----------------------- | -------------------------------------------- |
+//! | `signer`        | account is a signer     | [`ProgramError::MissingRequiredSignature`]   |
+//! | `writable`      | account is writable     | [`ProgramError::Immutable`]                  |
+//! | `owner = X`     | account is owned by `X` | [`ProgramError::InvalidAccountOwner`]        |
+//! | `address = X`   | account address is `X`  | [`ProgramError::InvalidArgument`]            |
+//! | `executable`    | account is executable   | [`ProgramError::IncorrectProgramId`]         |
+//! | `uninitialized` | account data is empty   | [`ProgramError::AccountAlreadyInitialized`]  |
+//!
+//! When there are fewer accounts than fields, a
+//! [`ProgramError::NotEnoughAccountKeys`] error is returned.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use pinocchio::{accounts, accounts::Accounts, AccountView, Address, ProgramResult};
+//!
+//! accounts! {
+//!     /// Accounts for the `Deposit` instruction.
+//!     pub struct DepositAccounts<'a> {
+//!         /// Account funding the deposit.
+//!         pub payer: [signer, writable],
+//!         /// Vault account owned by the program.
+//!         pub vault: [writable, owner = crate::ID],
+//!         /// System program.
+//!         pub system_program: [address = SYSTEM_PROGRAM_ID, executable],
+//!     }
+//! }
+//!
+//! pub fn process_deposit(
+//!     _program_id: &Address,
+//!     accounts: &mut [AccountView],
+//!     instruction_data: &[u8],
+//! ) -> ProgramResult {
+//!     let DepositAccounts { payer, vault, .. } = DepositAccounts::try_from_accounts(accounts)?;
+//!     // ...
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{error::ProgramError, hint::unlikely, AccountView, Address, ProgramResult};
+
+/// Trait for types that can be created from the instruction accounts.
+///
+/// This trait is usually implemented using the [`crate::accounts!`] macro.
+pub trait Accounts<'a>: Sized {
+    /// Number of accounts expected.
+    const LEN: usize;
+
+    /// Create the type from the instruction accounts, validating the
+    /// constraints of each account.
+    ///
+    /// Accounts beyond [`Self::LEN`] are ignored.
+    fn try_from_accounts(accounts: &'a mut [AccountView]) -> Result<Self, ProgramError>;
+}
+
+/// Check that the account is a signer.
+///
+/// Returns a [`ProgramError::MissingRequiredSignature`] error otherwise.
+#[inline(always)]
+pub fn check_signer(account: &AccountView) -> ProgramResult {
+    if unlikely(!account.is_signer()) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+/// Check that the account is writable.
+///
+/// Returns a [`ProgramError::Immutable`] error otherwise.
+#[inline(always)]
+pub fn check_writable(account: &AccountView) -> ProgramResult {
+    if unlikely(!account.is_writable()) {
+        return Err(ProgramError::Immutable);
+    }
+    Ok(())
+}
+
+/// Check that the account is owned by the given program.
+///
+/// Returns a [`ProgramError::InvalidAccountOwner`] error otherwise.
+#[inline(always)]
+pub fn check_owner(account: &AccountView, owner: &Address) -> ProgramResult {
+    if unlikely(!account.owned_by(owner)) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    Ok(())
+}
+
+/// Check that the account has the given address.
+///
+/// Returns a [`ProgramError::InvalidArgument`] error otherwise.
+#[inline(always)]
+pub fn check_address(account: &AccountView, address: &Address) -> ProgramResult {
+    if unlikely(account.address() != address) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Check that the account is executable.
+///
+/// Returns a [`ProgramError::IncorrectProgramId`] error otherwise.
+#[inline(always)]
+pub fn check_executable(account: &AccountView) -> ProgramResult {
+    if unlikely(!account.executable()) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+/// Check that the account is uninitialized, i.e., its data is empty.
+///
+/// Returns a [`ProgramError::AccountAlreadyInitialized`] error otherwise.
+#[inline(always)]
+pub fn check_uninitialized(account: &AccountView) -> ProgramResult {
+    if unlikely(!account.is_data_empty()) {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    Ok(())
+}
+
+/// Declare a struct of named instruction accounts with constraints.
+///
+/// Each field is declared with a list of constraints, which can be empty, and
+/// has the type `&'a mut AccountView`. The macro implements the
+/// [`Accounts`](crate::accounts::Accounts) trait for the struct, validating
+/// the constraints in the order they are declared. See the
+/// [`accounts`](mod@crate::accounts) module documentation for the list of
+/// supported constraints.
+///
+/// ```ignore
+/// accounts! {
+///     pub struct CloseAccounts<'a> {
+///         pub authority: [signer],
+///         pub account: [writable, owner = crate::ID],
+///         pub destination: [writable],
+///         pub unchecked: [],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! accounts {
+    (
+        $( #[$attr:meta] )*
+        $vis:vis struct $name:ident<$lifetime:lifetime> {
+            $(
+                $( #[$field_attr:meta] )*
+                $field_vis:vis $field:ident : [ $( $constraint:ident $( = $value:expr )? ),* $(,)? ]
+            ),* $(,)?
+        }
+    ) => {
+        $( #[$attr] )*
+        $vis struct $name<$lifetime> {
+            $(
+                $( #[$field_attr] )*
+                $field_vis $field: &$lifetime mut $crate::AccountView,
+            )*
+        }
+
+        impl<$lifetime> $crate::accounts::Accounts<$lifetime> for $name<$lifetime> {
+            const LEN: usize = [$( stringify!($field) ),*].len();
+
+            #[inline(always)]
+            fn try_from_accounts(
+                accounts: &$lifetime mut [$crate::AccountView],
+            ) -> Result<Self, $crate::error::ProgramError> {
+                let [$( $field, )* ..] = accounts else {
+                    return Err($crate::error::ProgramError::NotEnoughAccountKeys);
+                };
+
+                $(
+                    $( $crate::accounts!(@check $field, $constraint $( = $value )?); )*
+                )*
+
+                Ok(Self { $( $field, )* })
+            }
+        }
+    };
+    ( @check $field:ident, signer ) => {
+        $crate::accounts::check_signer($field)?
+    };
+    ( @check $field:ident, writable ) => {
+        $crate::accounts::check_writable($field)?
+    };
+    ( @check $field:ident, owner = $owner:expr ) => {
+        $crate::accounts::check_owner($field, &$owner)?
+    };
+    ( @check $field:ident, address = $address:expr ) => {
+        $crate::accounts::check_address($field, &$address)?
+    };
+    ( @check $field:ident, executable ) => {
+        $crate::accounts::check_executable($field)?
+    };
+    ( @check $field:ident, uninitialized ) => {
+        $crate::accounts::check_uninitialized($field)?
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::account::{RuntimeAccount, NOT_BORROWED},
+        core::mem::size_of,
+    };
+
+    const OWNER: Address = Address::new_from_array([1u8; 32]);
+
+    const PROGRAM: Address = Address::new_from_array([2u8; 32]);
+
+    crate::accounts! {
+        struct TestAccounts<'a> {
+            authority: [signer],
+            account: [writable, owner = OWNER, uninitialized],
+            program: [address = PROGRAM, executable],
+            unchecked: [],
+        }
+    }
+
+    /// Create the memory for an account with the specified flags.
+    fn create_account(
+        signer: bool,
+        writable: bool,
+        executable: bool,
+        data_len: u64,
+        address: Address,
+        owner: Address,
+    ) -> [u64; size_of::<RuntimeAccount>() / size_of::<u64>() + 1] {
+        let mut data = [0u64; size_of::<RuntimeAccount>() / size_of::<u64>() + 1];
+        let account = data.as_mut_ptr() as *mut RuntimeAccount;
+        // SAFETY: `data` is large enough to hold a `RuntimeAccount` and the
+        // account data.
+        unsafe {
+            (*account).borrow_state = NOT_BORROWED;
+            (*account).is_signer = signer as u8;
+            (*account).is_writable = writable as u8;
+            (*account).executable = executable as u8;
+            (*account).address = address;
+            (*account).owner = owner;
+            (*account).data_len = data_len;
+        }
+        data
+    }
+
+    #[test]
+    fn test_accounts() {
+        let mut authority = create_account(true, false, false, 0, Address::default(), OWNER);
+        let mut account = create_account(false, true, false, 0, Address::default(), OWNER);
+        let mut program = create_account(false, false, true, 0, PROGRAM, Address::default());
+        let mut unchecked = create_account(false, false, false, 0, OWNER, OWNER);
+
+        let mut accounts = [&mut authority, &mut account, &mut program, &mut unchecked]
+            .map(|data| unsafe { AccountView::new_unchecked(data.as_mut_ptr() as *mut _) });
+
+        assert_eq!(TestAccounts::LEN, 4);
+
+        // Not enough accounts.
+
+        assert_eq!(
+            TestAccounts::try_from_accounts(&mut accounts[..3]).err(),
+            Some(ProgramError::NotEnoughAccountKeys)
+        );
+
+        // Valid accounts.
+
+        let TestAccounts {
+            authority,
+            account,
+            program,
+            unchecked,
+        } = TestAccounts::try_from_accounts(&mut accounts).unwrap();
+
+        assert!(authority.is_signer());
+        assert!(account.is_writable());
+        assert_eq!(program.address(), &PROGRAM);
+        assert!(unchecked.owned_by(&OWNER));
+
+        // Failed constraints.
+
+        unsafe { (*accounts[0].account_mut_ptr()).is_signer = 0 };
+        assert_eq!(
+            TestAccounts::try_from_accounts(&mut accounts).err(),
+            Some(ProgramError::MissingRequiredSignature)
+        );
+        unsafe { (*accounts[0].account_mut_ptr()).is_signer = 1 };
+
+        unsafe { (*accounts[1].account_mut_ptr()).is_writable = 0 };
+        assert_eq!(
+            TestAccounts::try_from_accounts(&mut accounts).err(),
+            Some(ProgramError::Immutable)
+        );
+        unsafe { (*accounts[1].account_mut_ptr()).is_writable = 1 };
+
+        unsafe { (*accounts[1].account_mut_ptr()).owner = PROGRAM };
+        assert_eq!(
+            TestAccounts::try_from_accounts(&mut accounts).err(),
+            Some(ProgramError::InvalidAccountOwner)
+        );
+        unsafe { (*accounts[1].account_mut_ptr()).owner = OWNER };
+
+        unsafe { (*accounts[1].account_mut_ptr()).data_len = 8 };
+        assert_eq!(
+            TestAccounts::try_from_accounts(&mut accounts).err(),
+            Some(ProgramError::AccountAlreadyInitialized)
+        );
+        unsafe { (*accounts[1].account_mut_ptr()).data_len = 0 };
+
+        unsafe { (*accounts[2].account_mut_ptr()).address = OWNER };
+        assert_eq!(
+            TestAccounts::try_from_accounts(&mut accounts).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+        unsafe { (*accounts[2].account_mut_ptr()).address = PROGRAM };
+
+        unsafe { (*accounts[2].account_mut_ptr()).executable = 0 };
+        assert_eq!(
+            TestAccounts::try_from_accounts(&mut accounts).err(),
+            Some(ProgramError::IncorrectProgramId)
+        );
+    }
+}
@@ -0,0 +1,726 @@
+//! Host-side mock runtime for cross-program invocations.
+//!
+//! Off-chain, cross-program invocations are no-ops. With the `cpi-mock`
+//! feature, the `invoke*` functions of the [`cpi`](super) module record every
+//! invocation – program id, instruction accounts, instruction data and signer
+//! seeds – in a thread-local registry. Tests can inspect the recorded
+//! [`Invocation`]s and register handlers for a program id to emulate the
+//! callee, e.g., by mutating the lamports or data of the accounts passed to the
+//! invocation.
+//!
+//! ```ignore
+//! use pinocchio::cpi::mock;
+//!
+//! mock::register_handler(SYSTEM_PROGRAM_ID, |invocation, accounts| {
+//!     let lamports = u64::from_le_bytes(invocation.data[4..12].try_into().unwrap());
+//!     accounts[0].set_lamports(accounts[0].lamports() - lamports);
+//!     accounts[1].set_lamports(accounts[1].lamports() + lamports);
+//!     Ok(())
+//! });
+//!
+//! Transfer { from, to, lamports: 100 }.invoke()?;
+//!
+//! let invocations = mock::take_invocations();
+//! assert_eq!(invocations[0].program_id, SYSTEM_PROGRAM_ID);
+//! ```
+//!
+//! Handlers can also set return data with [`set_return_data`], which the
+//! caller reads with [`get_return_data`] after the invocation, as on-chain.
+//!
+//! When a handler returns an error, the invocation panics, similarly to how a
+//! failed cross-program invocation aborts the transaction on-chain. The
+//! registry is thread-local, so tests running in parallel do not interfere
+//! with each other. It is kept in `std::thread_local` storage, which is why
+//! the `cpi-mock` feature enables the `std` feature and this module is only
+//! available when the program is not compiled for the `solana` target.
+
+use {
+    super::{CpiAccount, Seed, Signer, MAX_CPI_ACCOUNTS, MAX_RETURN_DATA, MAX_STATIC_CPI_ACCOUNTS},
+    crate::{
+        account::{AccountView, RuntimeAccount},
+        error::ProgramError,
+        instruction::InstructionView,
+        Address, ProgramResult,
+    },
+    alloc::{boxed::Box, vec::Vec},
+    core::{cell::RefCell, mem::offset_of},
+};
+
+/// Handler for the invocations of a program.
+type Handler = Box<dyn FnMut(&Invocation, &mut [AccountView]) -> ProgramResult>;
+
+/// State of the mock runtime.
+#[derive(Default)]
+struct Runtime {
+    /// Recorded invocations.
+    invocations: Vec<Invocation>,
+
+    /// Registered handlers.
+    ///
+    /// A handler is temporarily set to `None` while it is executing.
+    handlers: Vec<(Address, Option<Handler>)>,
+
+    /// Programs whose handlers are executing, innermost last.
+    programs: Vec<Address>,
+
+    /// Program that most recently set the return data, and the return data.
+    return_data: Option<(Address, Vec<u8>)>,
+}
+
+std::thread_local! {
+    static RUNTIME: RefCell<Runtime> = RefCell::new(Runtime::default());
+}
+
+/// An instruction account of a recorded invocation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvokedAccount {
+    /// Address of the account.
+    pub address: Address,
+
+    /// Indicates whether the account is writable.
+    pub is_writable: bool,
+
+    /// Indicates whether the account signed the instruction.
+    pub is_signer: bool,
+}
+
+/// A recorded cross-program invocation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Invocation {
+    /// Address of the invoked program.
+    pub program_id: Address,
+
+    /// Instruction accounts.
+    pub accounts: Vec<InvokedAccount>,
+
+    /// Instruction data.
+    pub data: Vec<u8>,
+
+    /// Seeds of each signer.
+    pub signers: Vec<Vec<Vec<u8>>>,
+}
+
+/// Register a handler for the invocations of `program_id`.
+///
+/// The handler receives the recorded invocation and the accounts passed to
+/// the invocation, in the same order as the instruction accounts. A previously
+/// registered handler for the same program is replaced.
+pub fn register_handler<F>(program_id: Address, handler: F)
+where
+    F: FnMut(&Invocation, &mut [AccountView]) -> ProgramResult + 'static,
+{
+    RUNTIME.with_borrow_mut(|runtime| {
+        runtime.handlers.retain(|(id, _)| id != &program_id);
+        runtime.handlers.push((program_id, Some(Box::new(handler))));
+    });
+}
+
+/// Return the invocations recorded so far.
+pub fn invocations() -> Vec<Invocation> {
+    RUNTIME.with_borrow(|runtime| runtime.invocations.clone())
+}
+
+/// Return and clear the invocations recorded so far.
+pub fn take_invocations() -> Vec<Invocation> {
+    RUNTIME.with_borrow_mut(|runtime| core::mem::take(&mut runtime.invocations))
+}
+
+/// Set the running program's return data.
+///
+/// Mock version of [`solana_instruction_view::cpi::set_return_data`]. The
+/// return data is attributed to the program whose handler is executing, or to
+/// the default address outside of a handler.
+///
+/// # Panics
+///
+/// Panics if `data` is longer than [`MAX_RETURN_DATA`].
+pub fn set_return_data(data: &[u8]) {
+    assert!(
+        data.len() <= MAX_RETURN_DATA,
+        "Return data is longer than MAX_RETURN_DATA"
+    );
+
+    RUNTIME.with_borrow_mut(|runtime| {
+        let program_id = runtime.programs.last().map(to_owned).unwrap_or_default();
+        runtime.return_data = Some((program_id, data.to_vec()));
+    });
+}
+
+/// Get the return data from an invoked program.
+///
+/// Mock version of [`solana_instruction_view::cpi::get_return_data`]. As
+/// on-chain, the return data is cleared before every invocation and empty
+/// return data is reported as `None`.
+pub fn get_return_data() -> Option<ReturnData> {
+    RUNTIME.with_borrow(|runtime| {
+        runtime
+            .return_data
+            .as_ref()
+            .filter(|(_, data)| !data.is_empty())
+            .map(|(program_id, data)| ReturnData {
+                program_id: to_owned(program_id),
+                data: data.clone(),
+            })
+    })
+}
+
+/// Struct to hold the return data from an invoked program.
+///
+/// Mock version of [`solana_instruction_view::cpi::ReturnData`].
+#[derive(Debug)]
+pub struct ReturnData {
+    /// Program that most recently set the return data.
+    program_id: Address,
+
+    /// Return data set by the program.
+    data: Vec<u8>,
+}
+
+impl ReturnData {
+    /// Returns the program that most recently set the return data.
+    pub fn program_id(&self) -> &Address {
+        &self.program_id
+    }
+
+    /// Return the data set by the program.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Clear the recorded invocations and registered handlers.
+pub fn reset() {
+    RUNTIME.with_borrow_mut(|runtime| *runtime = Runtime::default());
+}
+
+/// Convert an [`Address`] reference into an owned value.
+#[inline(always)]
+fn to_owned(address: &Address) -> Address {
+    Address::new_from_array(*address.as_array())
+}
+
+/// Invoke a cross-program instruction from an array of `AccountView`s.
+///
+/// Mock version of [`solana_instruction_view::cpi::invoke`].
+#[inline(always)]
+pub fn invoke<const ACCOUNTS: usize, A: AsRef<AccountView>>(
+    instruction: &InstructionView,
+    account_views: &[A; ACCOUNTS],
+) -> ProgramResult {
+    invoke_signed::<ACCOUNTS, A>(instruction, account_views, &[])
+}
+
+/// Invoke a cross-program instruction from a slice of `AccountView`s.
+///
+/// Mock version of [`solana_instruction_view::cpi::invoke_with_bounds`].
+#[inline(always)]
+pub fn invoke_with_bounds<const MAX_ACCOUNTS: usize, A: AsRef<AccountView>>(
+    instruction: &InstructionView,
+    account_views: &[A],
+) -> ProgramResult {
+    invoke_signed_with_bounds::<MAX_ACCOUNTS, A>(instruction, account_views, &[])
+}
+
+/// Invoke a cross-program instruction from a slice of `AccountView`s.
+///
+/// Mock version of [`solana_instruction_view::cpi::invoke_with_slice`].
+#[inline(always)]
+pub fn invoke_with_slice<A: AsRef<AccountView>>(
+    instruction: &InstructionView,
+    account_views: &[A],
+) -> ProgramResult {
+    invoke_signed_with_slice(instruction, account_views, &[])
+}
+
+/// Invoke a cross-program instruction with signatures from an array of
+/// `AccountView`s.
+///
+/// Mock version of [`solana_instruction_view::cpi::invoke_signed`].
+#[inline(always)]
+pub fn invoke_signed<const ACCOUNTS: usize, A: AsRef<AccountView>>(
+    instruction: &InstructionView,
+    account_views: &[A; ACCOUNTS],
+    signers_seeds: &[Signer],
+) -> ProgramResult {
+    const {
+        assert!(
+            ACCOUNTS <= MAX_STATIC_CPI_ACCOUNTS,
+            "ACCOUNTS is greater than allowed MAX_STATIC_CPI_ACCOUNTS"
+        );
+    }
+
+    inner_invoke_signed_with_slice(instruction, account_views.as_slice(), signers_seeds)
+}
+
+/// Invoke a cross-program instruction with signatures from a slice of
+/// `AccountView`s.
+///
+/// Mock version of [`solana_instruction_view::cpi::invoke_signed_with_bounds`].
+#[inline(always)]
+pub fn invoke_signed_with_bounds<const MAX_ACCOUNTS: usize, A: AsRef<AccountView>>(
+    instruction: &InstructionView,
+    account_views: &[A],
+    signers_seeds: &[Signer],
+) -> ProgramResult {
+    const {
+        assert!(
+            MAX_ACCOUNTS <= MAX_STATIC_CPI_ACCOUNTS,
+            "MAX_ACCOUNTS is greater than allowed MAX_STATIC_CPI_ACCOUNTS"
+        );
+    }
+
+    if MAX_ACCOUNTS < instruction.accounts.len() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    inner_invoke_signed_with_slice(instruction, account_views, signers_seeds)
+}
+
+/// Invoke a cross-program instruction with signatures from a slice of
+/// `AccountView`s.
+///
+/// Mock version of [`solana_instruction_view::cpi::invoke_signed_with_slice`].
+#[inline(always)]
+pub fn invoke_signed_with_slice<A: AsRef<AccountView>>(
+    instruction: &InstructionView,
+    account_views: &[A],
+    signers_seeds: &[Signer],
+) -> ProgramResult {
+    if MAX_CPI_ACCOUNTS < instruction.accounts.len() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    inner_invoke_signed_with_slice(instruction, account_views, signers_seeds)
+}
+
+/// Validate the `account_views` against the instruction accounts and invoke
+/// the instruction.
+///
+/// This performs the same validation as the on-chain implementation.
+fn inner_invoke_signed_with_slice<A: AsRef<AccountView>>(
+    instruction: &InstructionView,
+    account_views: &[A],
+    signers_seeds: &[Signer],
+) -> ProgramResult {
+    if account_views.len() < instruction.accounts.len() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let accounts = account_views
+        .iter()
+        .zip(instruction.accounts.iter())
+        .map(|(account_view, instruction_account)| {
+            if account_view.as_ref().address() != instruction_account.address {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            if instruction_account.is_writable {
+                account_view.as_ref().check_borrow_mut()?;
+            }
+
+            Ok(CpiAccount::from(account_view.as_ref()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // SAFETY: The accounts are borrowable according to their mutability on the
+    // instruction.
+    unsafe { invoke_signed_unchecked(instruction, &accounts, signers_seeds) };
+
+    Ok(())
+}
+
+/// Invoke a cross-program instruction but don't enforce Rust's aliasing rules.
+///
+/// Mock version of [`solana_instruction_view::cpi::invoke_unchecked`].
+///
+/// # Safety
+///
+/// See [`solana_instruction_view::cpi::invoke_unchecked`].
+#[inline(always)]
+pub unsafe fn invoke_unchecked(instruction: &InstructionView, accounts: &[CpiAccount]) {
+    invoke_signed_unchecked(instruction, accounts, &[])
+}
+
+/// Invoke a cross-program instruction with signatures but don't enforce Rust's
+/// aliasing rules.
+///
+/// Mock version of [`solana_instruction_view::cpi::invoke_signed_unchecked`].
+/// The invocation is recorded and dispatched to the handler registered for
+/// the program, if any.
+///
+/// # Panics
+///
+/// Panics if the handler returns an error.
+///
+/// # Safety
+///
+/// See [`solana_instruction_view::cpi::invoke_signed_unchecked`].
+pub unsafe fn invoke_signed_unchecked(
+    instruction: &InstructionView,
+    accounts: &[CpiAccount],
+    signers_seeds: &[Signer],
+) {
+    let invocation = Invocation {
+        program_id: to_owned(instruction.program_id),
+        accounts: instruction
+            .accounts
+            .iter()
+            .map(|account| InvokedAccount {
+                address: to_owned(account.address),
+                is_writable: account.is_writable,
+                is_signer: account.is_signer,
+            })
+            .collect(),
+        data: instruction.data.to_vec(),
+        signers: signers_seeds
+            .iter()
+            .map(|signer| signer.seeds().iter().map(|seed| seed.to_vec()).collect())
+            .collect(),
+    };
+
+    RUNTIME.with_borrow_mut(|runtime| {
+        runtime.invocations.push(invocation.clone());
+        runtime.return_data = None;
+    });
+
+    // Take the handler out of the registry while it executes, so it can
+    // perform nested invocations.
+    let Some(mut handler) = RUNTIME.with_borrow_mut(|runtime| {
+        runtime
+            .handlers
+            .iter_mut()
+            .find(|(id, _)| id == &invocation.program_id)
+            .and_then(|(_, handler)| handler.take())
+    }) else {
+        return;
+    };
+
+    let mut account_views = accounts
+        .iter()
+        .take(instruction.accounts.len())
+        .map(|account| account.account_view())
+        .collect::<Vec<_>>();
+
+    RUNTIME.with_borrow_mut(|runtime| runtime.programs.push(to_owned(&invocation.program_id)));
+
+    let result = handler(&invocation, &mut account_views);
+
+    RUNTIME.with_borrow_mut(|runtime| {
+        runtime.programs.pop();
+
+        if let Some((_, slot)) = runtime
+            .handlers
+            .iter_mut()
+            .find(|(id, slot)| id == &invocation.program_id && slot.is_none())
+        {
+            *slot = Some(handler);
+        }
+    });
+
+    if let Err(error) = result {
+        panic!(
+            "Cross-program invocation to {:?} failed: {:?}",
+            invocation.program_id, error
+        );
+    }
+}
+
+/// Accessor for the seeds of a [`Signer`].
+///
+/// `Signer` does not expose its seeds; this reads them through the
+/// `SolSignerSeedsC` layout expected by the `sol_invoke_signed_c` syscall.
+pub(crate) trait SignerSeeds<'bytes> {
+    /// Return the seeds of the signer.
+    fn seeds(&self) -> &[Seed<'bytes>];
+}
+
+/// Layout of a [`Signer`], as expected by the `sol_invoke_signed_c` syscall.
+#[repr(C)]
+struct SignerLayout<'bytes> {
+    /// Pointer to the seeds.
+    seeds: *const Seed<'bytes>,
+
+    /// Number of seeds.
+    len: u64,
+}
+
+const _: () = {
+    assert!(size_of::<SignerLayout>() == size_of::<Signer>());
+    assert!(align_of::<SignerLayout>() == align_of::<Signer>());
+};
+
+impl<'bytes> SignerSeeds<'bytes> for Signer<'bytes, '_> {
+    #[inline(always)]
+    fn seeds(&self) -> &[Seed<'bytes>] {
+        // SAFETY: `Signer` has the `SolSignerSeedsC` layout and its seeds
+        // outlive it.
+        unsafe {
+            let signer = &*(self as *const Signer as *const SignerLayout);
+            core::slice::from_raw_parts(signer.seeds, signer.len as usize)
+        }
+    }
+}
+
+/// Accessors for the account of a [`CpiAccount`].
+///
+/// `CpiAccount` does not expose its fields; this reads them through the
+/// `SolAccountInfo` layout expected by the `sol_invoke_signed_c` syscall,
+/// which starts with a pointer to the account address.
+pub(crate) trait CpiAccountView {
+    /// Return the address of the account.
+    fn address(&self) -> &Address;
+
+    /// Return the [`AccountView`] that the `CpiAccount` was created from.
+    ///
+    /// # Safety
+    ///
+    /// The returned `AccountView` must not outlive the `CpiAccount`.
+    unsafe fn account_view(&self) -> AccountView;
+}
+
+impl CpiAccountView for CpiAccount<'_> {
+    #[inline(always)]
+    fn address(&self) -> &Address {
+        // SAFETY: The first field of a `CpiAccount` is a pointer to the address
+        // of the account, which outlives the `CpiAccount`.
+        unsafe { &**(self as *const CpiAccount as *const *const Address) }
+    }
+
+    #[inline(always)]
+    unsafe fn account_view(&self) -> AccountView {
+        // All `CpiAccount` constructors point the address to the `address` field
+        // of the `RuntimeAccount` of an `AccountView`.
+        AccountView::new_unchecked(
+            (self.address() as *const Address)
+                .cast::<u8>()
+                .sub(offset_of!(RuntimeAccount, address))
+                .cast::<RuntimeAccount>()
+                .cast_mut(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            cpi::{self, Seed},
+            instruction::InstructionAccount,
+            test_utils::{ProgramInput, TestAccount},
+            SUCCESS,
+        },
+        alloc::vec,
+    };
+
+    const PROGRAM_ID: Address = Address::new_from_array([5u8; 32]);
+
+    const CALLEE_ID: Address = Address::new_from_array([9u8; 32]);
+
+    fn process_instruction(
+        _program_id: &Address,
+        accounts: &mut [AccountView],
+        _instruction_data: &[u8],
+    ) -> ProgramResult {
+        let [from, to] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let instruction_accounts = [
+            InstructionAccount::writable_signer(from.address()),
+            InstructionAccount::writable(to.address()),
+        ];
+        let instruction = InstructionView {
+            program_id: &CALLEE_ID,
+            accounts: &instruction_accounts,
+            data: &[1, 2, 3],
+        };
+
+        let bump = [254];
+        let seeds = [Seed::from(b"vault"), Seed::from(&bump)];
+
+        cpi::invoke_signed(&instruction, &[&*from, &*to], &[Signer::from(&seeds)])?;
+
+        // The handler updated the account data.
+        assert_eq!(to.try_borrow()?.as_ref(), &[3, 2, 1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invoke_signed() {
+        reset();
+
+        register_handler(CALLEE_ID, |invocation, accounts| {
+            assert_eq!(accounts.len(), 2);
+
+            let lamports = accounts[0].lamports();
+            accounts[0].set_lamports(0);
+            accounts[1].set_lamports(accounts[1].lamports() + lamports);
+
+            let mut data = accounts[1].try_borrow_mut()?;
+            data.copy_from_slice(&invocation.data);
+            data.reverse();
+
+            Ok(())
+        });
+
+        let mut input = ProgramInput::new(
+            &PROGRAM_ID,
+            &[
+                TestAccount::new(Address::new_from_array([1u8; 32]), PROGRAM_ID, 10, &[])
+                    .signer()
+                    .writable()
+                    .into(),
+                TestAccount::new(Address::new_from_array([2u8; 32]), CALLEE_ID, 5, &[0; 3])
+                    .writable()
+                    .into(),
+            ],
+            &[],
+        );
+
+        assert_eq!(input.process(process_instruction), SUCCESS);
+
+        assert_eq!(input.lamports(0), 0);
+        assert_eq!(input.lamports(1), 15);
+
+        let invocations = take_invocations();
+
+        assert_eq!(invocations.len(), 1);
+        assert_eq!(
+            invocations[0],
+            Invocation {
+                program_id: CALLEE_ID,
+                accounts: vec![
+                    InvokedAccount {
+                        address: Address::new_from_array([1u8; 32]),
+                        is_writable: true,
+                        is_signer: true,
+                    },
+                    InvokedAccount {
+                        address: Address::new_from_array([2u8; 32]),
+                        is_writable: true,
+                        is_signer: false,
+                    },
+                ],
+                data: vec![1, 2, 3],
+                signers: vec![vec![b"vault".to_vec(), vec![254]]],
+            }
+        );
+        assert!(super::invocations().is_empty());
+    }
+
+    #[test]
+    fn test_return_data() {
+        reset();
+
+        register_handler(CALLEE_ID, |invocation, _| {
+            cpi::set_return_data(&invocation.data);
+            Ok(())
+        });
+
+        let invoke = |data: &[u8]| {
+            let instruction = InstructionView {
+                program_id: &CALLEE_ID,
+                accounts: &[],
+                data,
+            };
+            cpi::invoke::<0, &AccountView>(&instruction, &[])
+        };
+
+        assert!(cpi::get_return_data().is_none());
+
+        invoke(&[1, 2, 3]).unwrap();
+
+        let return_data = cpi::get_return_data().unwrap();
+        assert_eq!(return_data.program_id(), &CALLEE_ID);
+        assert_eq!(return_data.as_slice(), &[1, 2, 3]);
+
+        assert_eq!(
+            cpi::get_return_data_from(&CALLEE_ID).unwrap().as_slice(),
+            &[1, 2, 3]
+        );
+        assert_eq!(
+            cpi::get_return_data_from(&PROGRAM_ID).err(),
+            Some(ProgramError::IncorrectProgramId)
+        );
+
+        // Empty return data.
+        invoke(&[]).unwrap();
+        assert_eq!(
+            cpi::get_return_data_from(&CALLEE_ID).err(),
+            Some(ProgramError::IncorrectProgramId)
+        );
+
+        // The return data is cleared before every invocation.
+        register_handler(CALLEE_ID, |_, _| Ok(()));
+        cpi::set_return_data(&[4]);
+        invoke(&[5]).unwrap();
+        assert!(cpi::get_return_data().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Cross-program invocation")]
+    fn test_invoke_handler_error() {
+        reset();
+
+        register_handler(CALLEE_ID, |_, _| Err(ProgramError::InvalidArgument));
+
+        let mut input = ProgramInput::new(
+            &PROGRAM_ID,
+            &[
+                TestAccount::new(Address::new_from_array([1u8; 32]), PROGRAM_ID, 10, &[])
+                    .signer()
+                    .writable()
+                    .into(),
+                TestAccount::new(Address::new_from_array([2u8; 32]), CALLEE_ID, 5, &[0; 3])
+                    .writable()
+                    .into(),
+            ],
+            &[],
+        );
+
+        input.process(process_instruction);
+    }
+
+    #[test]
+    fn test_signer_seeds() {
+        let bump = [254];
+        let seeds = [Seed::from(b"vault"), Seed::from(&bump)];
+        let signer = Signer::from(&seeds);
+
+        let signer_seeds = signer.seeds();
+
+        assert_eq!(signer_seeds.len(), 2);
+        assert_eq!(&*signer_seeds[0], b"vault");
+        assert_eq!(&*signer_seeds[1], &bump);
+    }
+
+    #[test]
+    fn test_cpi_account_view() {
+        let address = Address::new_from_array([3; 32]);
+        let mut input = ProgramInput::new(
+            &Address::default(),
+            &[TestAccount::new(address, Address::default(), 1, &[1, 2, 3])
+                .writable()
+                .into()],
+            &[],
+        );
+
+        let result = input.process(|_, accounts, _| {
+            let account = CpiAccount::from(&accounts[0]);
+
+            assert_eq!(account.address(), &address);
+
+            // SAFETY: The account view does not outlive the `CpiAccount`.
+            let account_view = unsafe { account.account_view() };
+
+            assert_eq!(account_view.account_ptr(), accounts[0].account_ptr());
+            assert_eq!(account_view.try_borrow()?.as_ref(), &[1, 2, 3]);
+
+            Ok(())
+        });
+
+        assert_eq!(result, SUCCESS);
+    }
+}
@@ -0,0 +1,44 @@
+//! Cross-program invocation helpers.
+//!
+//! This module re-exports the [`solana_instruction_view::cpi`] helpers. When
+//! the `cpi-mock` feature is enabled and the program is not compiled for the
+//! `solana` target, the `invoke*` and return data functions are replaced by the
+//! ones in the [`mock`] module, which record each invocation and dispatch it to
+//! handlers registered by the test.
+
+use crate::{error::ProgramError, Address};
+#[cfg(all(
+    feature = "cpi-mock",
+    not(any(target_os = "solana", target_arch = "bpf"))
+))]
+pub use mock::{
+    get_return_data, invoke, invoke_signed, invoke_signed_unchecked, invoke_signed_with_bounds,
+    invoke_signed_with_slice, invoke_unchecked, invoke_with_bounds, invoke_with_slice,
+    set_return_data, ReturnData,
+};
+pub use solana_instruction_view::cpi::*;
+
+#[cfg(all(
+    feature = "cpi-mock",
+    not(any(target_os = "solana", target_arch = "bpf"))
+))]
+pub mod mock;
+
+/// Get the return data set by `program_id`.
+///
+/// The return data is a per-transaction buffer holding the data set by the
+/// program that most recently called [`set_return_data`], which is not
+/// necessarily the program invoked last. This checks that the return data was
+/// set by `program_id`.
+///
+/// # Errors
+///
+/// Returns [`ProgramError::IncorrectProgramId`] if no return data is set or
+/// the return data was set by a program other than `program_id`.
+#[inline]
+pub fn get_return_data_from(program_id: &Address) -> Result<ReturnData, ProgramError> {
+    match get_return_data() {
+        Some(return_data) if return_data.program_id() == program_id => Ok(return_data),
+        _ => Err(ProgramError::IncorrectProgramId),
+    }
+}
@@ -0,0 +1,237 @@
+//! Declarative instruction dispatch based on instruction discriminators.
+//!
+//! Programs usually start processing an instruction by matching on the first
+//! byte(s) of the instruction data to select the instruction handler. The
+//! macros in this module generate that boilerplate: the
+//! [`crate::instruction_dispatcher!`] macro declares a function compatible
+//! with [`crate::program_entrypoint!`], while the
+//! [`crate::dispatch_entrypoint!`] macro declares the program entrypoint
+//! directly and allows instructions to have a "fast path" that skips the
+//! account deserialization entirely.
+
+use core::{mem::size_of, slice::from_raw_parts};
+
+/// Trait representing an instruction discriminator.
+///
+/// A discriminator is a fixed-size prefix of the instruction data used to
+/// identify the instruction to process. The trait is implemented for `u8`
+/// (single byte discriminator) and `[u8; N]` (e.g., 8-byte Anchor-style
+/// discriminators).
+pub trait Discriminator: Sized {
+    /// Split the instruction data into the discriminator and the remaining
+    /// instruction data.
+    ///
+    /// Returns `None` if the instruction data is too short to contain the
+    /// discriminator.
+    fn split(instruction_data: &[u8]) -> Option<(Self, &[u8])>;
+}
+
+impl Discriminator for u8 {
+    #[inline(always)]
+    fn split(instruction_data: &[u8]) -> Option<(Self, &[u8])> {
+        instruction_data
+            .split_first()
+            .map(|(discriminator, data)| (*discriminator, data))
+    }
+}
+
+impl<const N: usize> Discriminator for [u8; N] {
+    #[inline(always)]
+    fn split(instruction_data: &[u8]) -> Option<(Self, &[u8])> {
+        instruction_data
+            .split_first_chunk::<N>()
+            .map(|(discriminator, data)| (*discriminator, data))
+    }
+}
+
+/// Return the instruction data slice from the `instruction_data` pointer
+/// received by the program entrypoint.
+///
+/// # Safety
+///
+/// The caller must ensure that the `instruction_data` pointer is the one
+/// received by the program entrypoint, i.e., it is preceded by the instruction
+/// data length as serialized by the SVM loader.
+#[inline(always)]
+pub unsafe fn instruction_data<'a>(instruction_data: *const u8) -> &'a [u8] {
+    // The instruction data length is located 8-bytes before the instruction data.
+    let ix_data_len = *(instruction_data.sub(size_of::<u64>()) as *const u64) as usize;
+    from_raw_parts(instruction_data, ix_data_len)
+}
+
+/// Declare a function that dispatches an instruction to its handler based on
+/// the instruction discriminator.
+///
+/// The generated function has the signature expected by the
+/// [`crate::program_entrypoint!`] and [`crate::entrypoint!`] macros, as well as
+/// the [`crate::entrypoint::process_program_input`] function. The
+/// discriminator type can be any type implementing the
+/// [`Discriminator`](crate::entrypoint::dispatch::Discriminator) trait, e.g.,
+/// `u8` or `[u8; 8]`.
+///
+/// Each handler receives the instruction data *without* the discriminator
+/// and must have the following signature:
+///
+/// ```ignore
+/// fn handler(
+///     program_id: &Address,
+///     accounts: &mut [AccountView],
+///     instruction_data: &[u8],
+/// ) -> ProgramResult;
+/// ```
+///
+/// The dispatcher returns [`ProgramError::InvalidInstructionData`] when the
+/// instruction data does not match any of the discriminators.
+///
+/// [`ProgramError::InvalidInstructionData`]: crate::error::ProgramError::InvalidInstructionData
+///
+/// # Example
+///
+/// ```ignore
+/// use pinocchio::{instruction_dispatcher, program_entrypoint};
+///
+/// const DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+/// const WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+///
+/// program_entrypoint!(process_instruction);
+///
+/// instruction_dispatcher! {
+///     pub fn process_instruction<[u8; 8]> {
+///         DEPOSIT => process_deposit,
+///         WITHDRAW => process_withdraw,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! instruction_dispatcher {
+    (
+        $vis:vis fn $name:ident<$discriminator:ty> {
+            $( $tag:pat => $handler:expr ),* $(,)?
+        }
+    ) => {
+        #[inline(always)]
+        $vis fn $name(
+            program_id: &$crate::Address,
+            accounts: &mut [$crate::AccountView],
+            instruction_data: &[u8],
+        ) -> $crate::ProgramResult {
+            match <$discriminator as $crate::entrypoint::dispatch::Discriminator>::split(
+                instruction_data,
+            ) {
+                $( Some(($tag, instruction_data)) => $handler(program_id, accounts, instruction_data), )*
+                _ => Err($crate::error::ProgramError::InvalidInstructionData),
+            }
+        }
+    };
+}
+
+/// Declare the program entrypoint with instruction dispatch.
+///
+/// This macro is similar to the [`crate::program_entrypoint!`] macro, but the
+/// instruction is dispatched to a handler based on the instruction
+/// discriminator. Instructions listed under `instructions` are dispatched
+/// after the input is processed by
+/// [`crate::entrypoint::process_program_input`], using the same handler
+/// signature as the [`crate::instruction_dispatcher!`] macro.
+///
+/// Instructions listed under `fast_path` are dispatched *before* any account
+/// is deserialized. The handler receives an
+/// [`InstructionContext`](crate::entrypoint::InstructionContext) to read the
+/// accounts on demand, and the instruction data without the discriminator:
+///
+/// ```ignore
+/// fn handler(
+///     context: InstructionContext,
+///     instruction_data: &[u8],
+/// ) -> ProgramResult;
+/// ```
+///
+/// Fast path discriminators are checked first, so a discriminator should not
+/// be listed in both sections. Note that this macro does not set up a global
+/// allocator nor a panic handler.
+///
+/// # Example
+///
+/// ```ignore
+/// use pinocchio::{default_allocator, default_panic_handler, dispatch_entrypoint};
+///
+/// dispatch_entrypoint! {
+///     discriminator: u8,
+///     fast_path: {
+///         2 => process_ping,
+///     },
+///     instructions: {
+///         0 => process_initialize,
+///         1 => process_transfer,
+///     },
+/// }
+/// default_allocator!();
+/// default_panic_handler!();
+/// ```
+#[macro_export]
+macro_rules! dispatch_entrypoint {
+    (
+        discriminator: $discriminator:ty,
+        fast_path: {
+            $( $fast_tag:pat => $fast_handler:expr ),* $(,)?
+        },
+        instructions: {
+            $( $tag:pat => $handler:expr ),* $(,)?
+        } $(,)?
+    ) => {
+        /// Program entrypoint.
+        #[no_mangle]
+        pub unsafe extern "C" fn entrypoint(
+            program_input: *mut u8,
+            instruction_data: *mut u8,
+        ) -> u64 {
+            $crate::instruction_dispatcher! {
+                fn process_instruction<$discriminator> {
+                    $( $tag => $handler, )*
+                }
+            }
+
+            // SAFETY: The `instruction_data` pointer is received from the runtime.
+            match <$discriminator as $crate::entrypoint::dispatch::Discriminator>::split(unsafe {
+                $crate::entrypoint::dispatch::instruction_data(instruction_data)
+            }) {
+                $(
+                    Some(($fast_tag, data)) => {
+                        // SAFETY: Passing the program input received from the runtime.
+                        let context = unsafe {
+                            $crate::entrypoint::InstructionContext::new_unchecked(program_input)
+                        };
+
+                        return match $fast_handler(context, data) {
+                            Ok(_) => $crate::SUCCESS,
+                            Err(error) => error.into(),
+                        };
+                    }
+                )*
+                _ => (),
+            }
+
+            $crate::entrypoint::process_program_input(
+                program_input,
+                instruction_data,
+                process_instruction,
+            )
+        }
+    };
+    (
+        discriminator: $discriminator:ty,
+        instructions: {
+            $( $tag:pat => $handler:expr ),* $(,)?
+        } $(,)?
+    ) => {
+        $crate::program_entrypoint!({
+            $crate::instruction_dispatcher! {
+                fn process_instruction<$discriminator> {
+                    $( $tag => $handler, )*
+                }
+            }
+
+            process_instruction
+        });
+    };
+}
@@ -94,6 +94,7 @@ macro_rules! align_pointer {
     };
 }
 
+pub mod dispatch;
 pub mod lazy;
 
 /// Start address of the memory region used for program heap.
@@ -1329,6 +1330,141 @@ mod tests {
         }
     }
 
+    /// Asserts that the instruction data received by the handler does not
+    /// include the discriminator.
+    fn assert_dispatched(
+        program_id: &Address,
+        accounts: &mut [AccountView],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        assert!(program_id == &MOCK_PROGRAM_ID);
+        assert_eq!(accounts.len(), 3);
+        assert_eq!(instruction_data, &MOCK_INSTRUCTION_DATA[8..]);
+
+        Ok(())
+    }
+
+    const DISPATCH_TAG: [u8; 8] = [3u8; 8];
+
+    crate::instruction_dispatcher! {
+        fn process_u8_instruction<u8> {
+            0 => assert_accounts,
+            1 => assert_accounts,
+        }
+    }
+
+    crate::instruction_dispatcher! {
+        fn process_array_instruction<[u8; 8]> {
+            [0, 0, 0, 0, 0, 0, 0, 0] => assert_accounts,
+            DISPATCH_TAG => assert_dispatched,
+        }
+    }
+
+    mod dispatch_program {
+        use {
+            super::{assert_dispatched, DISPATCH_TAG, MOCK_INSTRUCTION_DATA},
+            crate::{entrypoint::InstructionContext, error::ProgramError, ProgramResult},
+        };
+
+        pub const FAST_PATH_ERROR: u32 = 42;
+
+        fn process_fast_path(
+            context: InstructionContext,
+            instruction_data: &[u8],
+        ) -> ProgramResult {
+            assert_eq!(context.remaining(), 3);
+            assert_eq!(instruction_data, &MOCK_INSTRUCTION_DATA[8..]);
+
+            Err(ProgramError::Custom(FAST_PATH_ERROR))
+        }
+
+        crate::dispatch_entrypoint! {
+            discriminator: [u8; 8],
+            fast_path: {
+                [3, _, _, _, _, _, _, 4] => process_fast_path,
+            },
+            instructions: {
+                DISPATCH_TAG => assert_dispatched,
+            },
+        }
+    }
+
+    #[test]
+    fn test_instruction_dispatcher() {
+        let mut accounts = [];
+
+        // Unknown and missing discriminators.
+
+        assert_eq!(
+            process_u8_instruction(&MOCK_PROGRAM_ID, &mut accounts, &MOCK_INSTRUCTION_DATA),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            process_u8_instruction(&MOCK_PROGRAM_ID, &mut accounts, &[]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            process_array_instruction(&MOCK_PROGRAM_ID, &mut accounts, &[0u8; 7]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+
+        // 8-byte discriminator.
+
+        let (mut program_input, instruction_data) =
+            unsafe { create_input(3, &MOCK_INSTRUCTION_DATA) };
+
+        unsafe {
+            assert_eq!(
+                process_program_input(
+                    program_input.as_mut_ptr(),
+                    instruction_data,
+                    process_array_instruction,
+                ),
+                0
+            );
+        }
+    }
+
+    #[test]
+    fn test_dispatch_entrypoint() {
+        // Standard path.
+
+        let (mut program_input, instruction_data) =
+            unsafe { create_input(3, &MOCK_INSTRUCTION_DATA) };
+
+        unsafe {
+            assert_eq!(
+                dispatch_program::entrypoint(program_input.as_mut_ptr(), instruction_data),
+                0
+            );
+        }
+
+        // Fast path.
+
+        let mut fast_path_data = MOCK_INSTRUCTION_DATA;
+        fast_path_data[7] = 4;
+
+        let (mut program_input, instruction_data) = unsafe { create_input(3, &fast_path_data) };
+
+        unsafe {
+            assert_eq!(
+                dispatch_program::entrypoint(program_input.as_mut_ptr(), instruction_data),
+                u64::from(ProgramError::Custom(dispatch_program::FAST_PATH_ERROR))
+            );
+        }
+
+        // Unknown discriminator.
+
+        let (mut program_input, instruction_data) = unsafe { create_input(3, &[1u8; 8]) };
+
+        unsafe {
+            assert_eq!(
+                dispatch_program::entrypoint(program_input.as_mut_ptr(), instruction_data),
+                u64::from(ProgramError::InvalidInstructionData)
+            );
+        }
+    }
+
     #[test]
     fn test_bump_allocator() {
         // alloc the entire
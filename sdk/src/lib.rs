@@ -122,6 +122,44 @@
 //! }
 //! ```
 //!
+//! ### Instruction dispatch with [`dispatch_entrypoint!`]
+//!
+//! Most programs select the instruction handler by matching on a discriminator
+//! at the start of the instruction data. The [`instruction_dispatcher!`] macro
+//! declares a `process_instruction` function that performs this match for
+//! 1-byte (`u8`) or 8-byte (`[u8; 8]`) discriminators, returning
+//! `InvalidInstructionData` for unknown discriminators. The
+//! [`dispatch_entrypoint!`] macro builds on it to declare the program
+//! entrypoint, turning the fast-path pattern above into a first-class feature:
+//! instructions listed under `fast_path` are dispatched before any account is
+//! deserialized and receive an
+//! [`InstructionContext`](entrypoint::InstructionContext) instead.
+//!
+//! ```ignore
+//! use pinocchio::{default_allocator, default_panic_handler, dispatch_entrypoint};
+//!
+//! dispatch_entrypoint! {
+//!   discriminator: u8,
+//!   fast_path: {
+//!     2 => process_ping,
+//!   },
+//!   instructions: {
+//!     0 => process_initialize,
+//!     1 => process_transfer,
+//!   },
+//! }
+//! default_allocator!();
+//! default_panic_handler!();
+//! ```
+//!
+//! ### Account validation with [`accounts!`]
+//!
+//! Instruction handlers receive the accounts as a `&mut [AccountView]`. The
+//! [`accounts!`] macro declares a struct with a named field for each account
+//! and the constraints it must satisfy (e.g., `signer`, `writable` or
+//! `owner = X`). The struct implements the [`accounts::Accounts`] trait, which
+//! destructures the slice and validates the constraints without allocating.
+//!
 //! ### [`lazy_program_entrypoint!`]
 //!
 //! The [`entrypoint!`] macro looks similar to the "standard" one found in
@@ -271,6 +309,54 @@
 //! pinocchio = { version = "0.11", features = ["cpi"] }
 //! ```
 //!
+//! ### `cpi-mock`
+//!
+//! The `cpi-mock` feature replaces the `invoke*` and return data functions of
+//! the [`crate::cpi`] module with a host-side mock runtime when the program is
+//! not compiled for the `solana` target. Each cross-program invocation is
+//! recorded and dispatched to the handlers registered in the
+//! [`crate::cpi::mock`] module, so tests can observe and emulate the invoked
+//! programs, including the return data they set. It enables the `cpi`,
+//! `alloc` and `std` features and has no effect on-chain:
+//! ```ignore
+//! [dev-dependencies]
+//! pinocchio = { version = "0.11", features = ["cpi-mock"] }
+//! ```
+//!
+//! ### `curve25519`
+//!
+//! The `curve25519` feature enables searching for program derived address bump
+//! seeds with the [`crate::pda`] helpers when the program is not compiled for
+//! the `solana` target, using a pure-Rust implementation of the curve
+//! operations. On-chain, the search uses syscalls and does not require this
+//! feature:
+//! ```ignore
+//! [dev-dependencies]
+//! pinocchio = { version = "0.11", features = ["curve25519"] }
+//! ```
+//!
+//! ### `sysvar-mock`
+//!
+//! The `sysvar-mock` feature enables a host-side sysvar provider when the
+//! program is not compiled for the `solana` target.
+//! [`Sysvar::get`](crate::sysvars::Sysvar::get) and
+//! [`get_sysvar`](crate::sysvars::get_sysvar) read the sysvar data set in the
+//! [`crate::sysvars::mock`] module instead of returning zeroed data, so tests
+//! can exercise real sysvar values. It enables the `alloc` and `std` features
+//! and has no effect on-chain:
+//! ```ignore
+//! [dev-dependencies]
+//! pinocchio = { version = "0.11", features = ["sysvar-mock"] }
+//! ```
+//!
+//! ### `std`
+//!
+//! The `std` feature links the standard library when the program is not
+//! compiled for the `solana` target. The crate remains `no_std` on-chain; the
+//! standard library is only used by the host-side mocks, which keep their
+//! registries in `std::thread_local` storage. It is enabled by the `cpi-mock`
+//! and `sysvar-mock` features and is not needed otherwise.
+//!
 //! ### `account-resize`
 //!
 //! The `account-resize` feature allows a program to grow or shrink an
@@ -278,6 +364,18 @@
 //! stores the original data length so it can verify that the resize stays
 //! within the permitted bounds. This adds a small setup cost for each account.
 //!
+//! ### `test-utils`
+//!
+//! The `test-utils` feature enables the [`crate::test_utils`] module, which
+//! builds program input buffers with the same layout serialized by the SVM
+//! loader. This allows running the entrypoint processing functions in plain
+//! `cargo test` and reading the accounts state back. It requires the `alloc`
+//! feature and is intended to be used as a dev-dependency:
+//! ```ignore
+//! [dev-dependencies]
+//! pinocchio = { version = "0.11", features = ["test-utils"] }
+//! ```
+//!
 //! ### `unsafe-account-resize`
 //!
 //! The `unsafe-account-resize` feature, like `account-resize`, allows a program
@@ -341,13 +439,26 @@
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(all(feature = "std", not(any(target_os = "solana", target_arch = "bpf"))))]
+extern crate std;
 
+pub mod accounts;
+#[cfg(feature = "cpi")]
+pub mod cpi;
 pub mod entrypoint;
+#[cfg(feature = "sha2")]
+pub mod pda;
+pub mod precompiles;
 pub mod sysvars;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 
 // Re-export the `solana_define_syscall` for downstream use.
 #[cfg(any(target_os = "solana", target_arch = "bpf"))]
 pub use solana_define_syscall::definitions as syscalls;
+// Re-export the `solana_instruction_view` for downstream use.
+#[cfg(feature = "cpi")]
+pub use solana_instruction_view as instruction;
 #[cfg(feature = "account-resize")]
 use {
     core::ptr::write_bytes,
@@ -363,9 +474,6 @@ pub use {
     solana_address::{self as address, Address},
     solana_program_error::{self as error, ProgramResult},
 };
-// Re-export the `solana_instruction_view` for downstream use.
-#[cfg(feature = "cpi")]
-pub use {solana_instruction_view as instruction, solana_instruction_view::cpi};
 
 /// Maximum number of accounts that a transaction may process.
 ///
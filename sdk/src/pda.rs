@@ -0,0 +1,275 @@
+//! Program derived address helpers.
+//!
+//! A [program derived address][pda] (PDA) is derived from a list of seeds, a
+//! bump seed and a program id. Finding the bump seed that makes the address
+//! valid (off-curve) is expensive – on-chain, each attempt costs `1500`
+//! compute units – while deriving the address from known seeds and bump only
+//! requires a SHA-256 hash. The helpers in this module favour the latter:
+//!
+//! - [`find`] searches for the bump seed once and caches it, so subsequent
+//!   derivations only hash the seeds;
+//! - [`verify_pda`] checks that an account address is derived from the given
+//!   seeds and bump;
+//! - [`PdaSeeds`] holds the seeds and bump of a PDA, and converts into a
+//!   [`Signer`] for cross-program invocations signed by the PDA.
+//!
+//! Searching for a bump seed uses the `sol_try_find_program_address` syscall
+//! on-chain. Off-chain, it requires the `curve25519` feature, which provides a
+//! pure-Rust implementation.
+//!
+//! [pda]: https://solana.com/docs/core/pda
+
+use crate::{error::ProgramError, AccountView, Address, ProgramResult};
+#[cfg(feature = "cpi")]
+use {
+    crate::cpi::{Seed, Signer},
+    core::slice::from_raw_parts,
+};
+
+/// Find a valid program derived address and its bump seed.
+///
+/// Returns `None` in the statistically improbable event that no bump seed
+/// derives a valid address.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+#[inline(always)]
+pub fn find_program_address(seeds: &[&[u8]], program_id: &Address) -> Option<(Address, u8)> {
+    Address::try_find_program_address(seeds, program_id)
+}
+
+/// Find a valid program derived address, caching its bump seed.
+///
+/// When `bump` is `None`, the bump seed is searched for and stored in `bump`.
+/// Otherwise, the address is derived from the cached bump seed without
+/// searching, which only requires hashing the seeds.
+///
+/// # Important
+///
+/// A cached bump seed is not validated: it must come from a previous search
+/// with the same seeds and program id, e.g., stored in the program's account
+/// data.
+#[cfg(any(target_os = "solana", target_arch = "bpf", feature = "curve25519"))]
+#[inline]
+pub fn find<const N: usize>(
+    seeds: &[&[u8]; N],
+    bump: &mut Option<u8>,
+    program_id: &Address,
+) -> Option<Address> {
+    match *bump {
+        Some(bump) => Some(Address::derive_address(seeds, Some(bump), program_id)),
+        None => {
+            let (address, found) = find_program_address(seeds, program_id)?;
+            *bump = Some(found);
+            Some(address)
+        }
+    }
+}
+
+/// Verify that the address of `account` is the program derived address of
+/// `seeds` and `bump` for `program_id`.
+///
+/// This only hashes the seeds, instead of using the more expensive
+/// `sol_create_program_address` syscall, since the address being compared
+/// against is known.
+///
+/// # Errors
+///
+/// Returns [`ProgramError::InvalidSeeds`] if the account address does not
+/// match the derived address.
+#[inline]
+pub fn verify_pda<const N: usize>(
+    account: &AccountView,
+    seeds: &[&[u8]; N],
+    bump: u8,
+    program_id: &Address,
+) -> ProgramResult {
+    if account.address() != &Address::derive_address(seeds, Some(bump), program_id) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(())
+}
+
+/// The seeds and bump seed of a program derived address.
+///
+/// `PdaSeeds` converts into a [`Signer`], which removes the need to build a
+/// `[Seed; N]` array to sign cross-program invocations with the PDA:
+///
+/// ```ignore
+/// let bump = [bump];
+/// let seeds = PdaSeeds::new([b"vault", owner.address().as_ref()], &bump);
+///
+/// Transfer { from: vault, to, lamports }.invoke_signed(&[seeds.signer()])?;
+/// ```
+#[cfg(feature = "cpi")]
+#[repr(C)]
+#[derive(Debug)]
+pub struct PdaSeeds<'a, const N: usize> {
+    /// The seeds.
+    seeds: [Seed<'a>; N],
+
+    /// The bump seed, which immediately follows the seeds.
+    bump: Seed<'a>,
+}
+
+#[cfg(feature = "cpi")]
+impl<'a, const N: usize> PdaSeeds<'a, N> {
+    /// Create a new `PdaSeeds` from the seeds and bump seed.
+    #[inline(always)]
+    pub fn new(seeds: [&'a [u8]; N], bump: &'a [u8; 1]) -> Self {
+        Self {
+            seeds: seeds.map(Seed::from),
+            bump: Seed::from(bump),
+        }
+    }
+
+    /// Return the bump seed.
+    #[inline(always)]
+    pub fn bump(&self) -> u8 {
+        self.bump[0]
+    }
+
+    /// Return all seeds, including the bump seed.
+    #[inline(always)]
+    pub fn as_seeds(&self) -> &[Seed<'a>] {
+        // SAFETY: `PdaSeeds` is `repr(C)`, so the bump seed immediately follows
+        // the `N` seeds without padding.
+        unsafe { from_raw_parts(self.seeds.as_ptr(), N + 1) }
+    }
+
+    /// Return a [`Signer`] for the program derived address.
+    #[inline(always)]
+    pub fn signer(&self) -> Signer<'a, '_> {
+        Signer::from(self.as_seeds())
+    }
+
+    /// Derive the program derived address for `program_id`.
+    #[inline]
+    pub fn address(&self, program_id: &Address) -> Address {
+        Address::derive_address(
+            &self.seeds.each_ref().map(|seed| &**seed),
+            Some(self.bump()),
+            program_id,
+        )
+    }
+
+    /// Verify that the address of `account` is the program derived address
+    /// for `program_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidSeeds`] if the account address does not
+    /// match the derived address.
+    #[inline]
+    pub fn verify(&self, account: &AccountView, program_id: &Address) -> ProgramResult {
+        if account.address() != &self.address(program_id) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl<'a, 'b, const N: usize> From<&'b PdaSeeds<'a, N>> for Signer<'a, 'b> {
+    #[inline(always)]
+    fn from(seeds: &'b PdaSeeds<'a, N>) -> Self {
+        seeds.signer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            cpi,
+            instruction::InstructionView,
+            test_utils::{ProgramInput, TestAccount},
+            SUCCESS,
+        },
+    };
+
+    const PROGRAM_ID: Address = Address::new_from_array([5u8; 32]);
+
+    #[test]
+    fn test_find() {
+        let (expected, expected_bump) =
+            Address::find_program_address(&[b"vault", &[1u8; 32]], &PROGRAM_ID);
+
+        let mut bump = None;
+        let address = find(&[b"vault", &[1u8; 32]], &mut bump, &PROGRAM_ID).unwrap();
+        assert_eq!(address, expected);
+        assert_eq!(bump, Some(expected_bump));
+
+        // The cached bump seed is used.
+        let address = find(&[b"vault", &[1u8; 32]], &mut bump, &PROGRAM_ID).unwrap();
+        assert_eq!(address, expected);
+
+        let mut bump = Some(expected_bump.wrapping_sub(1));
+        let address = find(&[b"vault", &[1u8; 32]], &mut bump, &PROGRAM_ID).unwrap();
+        assert_ne!(address, expected);
+    }
+
+    #[test]
+    fn test_pda_seeds() {
+        cpi::mock::reset();
+
+        let (address, bump) = Address::find_program_address(&[b"vault"], &PROGRAM_ID);
+
+        let bump = [bump];
+        let seeds = PdaSeeds::new([b"vault"], &bump);
+
+        assert_eq!(seeds.bump(), bump[0]);
+        assert_eq!(seeds.address(&PROGRAM_ID), address);
+        assert_eq!(
+            seeds
+                .as_seeds()
+                .iter()
+                .map(|seed| &**seed)
+                .collect::<alloc::vec::Vec<_>>(),
+            [b"vault".as_slice(), &bump]
+        );
+
+        let mut input = ProgramInput::new(
+            &PROGRAM_ID,
+            &[
+                TestAccount::new(address, PROGRAM_ID, 1, &[]).into(),
+                TestAccount::new(Address::new_from_array([1u8; 32]), PROGRAM_ID, 1, &[]).into(),
+            ],
+            &[],
+        );
+
+        let result = input.process(|program_id, accounts, _| {
+            let [vault, other] = accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            seeds.verify(vault, program_id)?;
+            verify_pda(vault, &[b"vault"], bump[0], program_id)?;
+
+            assert_eq!(
+                seeds.verify(other, program_id),
+                Err(ProgramError::InvalidSeeds)
+            );
+            assert_eq!(
+                verify_pda(vault, &[b"vault"], bump[0].wrapping_sub(1), program_id),
+                Err(ProgramError::InvalidSeeds)
+            );
+
+            let instruction = InstructionView {
+                program_id: &PROGRAM_ID,
+                accounts: &[],
+                data: &[],
+            };
+            cpi::invoke_signed::<0, &AccountView>(&instruction, &[], &[(&seeds).into()])?;
+
+            Ok(())
+        });
+
+        assert_eq!(result, SUCCESS);
+        assert_eq!(
+            cpi::mock::take_invocations()[0].signers,
+            [[b"vault".to_vec(), bump.to_vec()]]
+        );
+    }
+}
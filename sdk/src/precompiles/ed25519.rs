@@ -0,0 +1,128 @@
+//! Ed25519 signature verification precompile.
+
+use {
+    super::{PrecompileInstruction, SignatureOffsets, SignedMessage},
+    crate::{error::ProgramError, sysvars::instructions::Instructions, Address},
+    core::ops::Deref,
+};
+
+/// Ed25519 precompile program ID `Ed25519SigVerify111111111111111111111111111`.
+pub const ED25519_PROGRAM_ID: Address = Address::new_from_array([
+    0x03, 0x7d, 0x46, 0xd6, 0x7c, 0x93, 0xfb, 0xbe, 0x12, 0xf9, 0x42, 0x8f, 0x83, 0x8d, 0x40, 0xff,
+    0x05, 0x70, 0x74, 0x49, 0x27, 0xf4, 0x8a, 0x64, 0xfc, 0xca, 0x70, 0x44, 0x80, 0x00, 0x00, 0x00,
+]);
+
+/// Length of an Ed25519 public key, in bytes.
+pub const PUBLIC_KEY_LEN: usize = 32;
+
+/// Offset of the signature offsets in the instruction data.
+///
+/// The instruction data starts with the number of signatures (`u8`) followed
+/// by a padding byte.
+pub const SIGNATURE_OFFSETS_START: usize = 2;
+
+/// A message signed by an Ed25519 public key.
+pub type Ed25519SignedMessage<'a> = SignedMessage<'a, PUBLIC_KEY_LEN>;
+
+/// An Ed25519 precompile instruction of the currently executing `Transaction`.
+#[derive(Debug)]
+pub struct Ed25519Instruction<'a, T>(PrecompileInstruction<'a, T>)
+where
+    T: Deref<Target = [u8]>;
+
+impl<'a, T> Ed25519Instruction<'a, T>
+where
+    T: Deref<Target = [u8]>,
+{
+    /// Load the Ed25519 precompile instruction at `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::IncorrectProgramId`] if the instruction is not
+    /// an Ed25519 precompile instruction, or
+    /// [`ProgramError::InvalidInstructionData`] if the instruction does not
+    /// exist or its data is too short to hold the signature offsets.
+    #[inline]
+    pub fn load(instructions: &'a Instructions<T>, index: usize) -> Result<Self, ProgramError> {
+        PrecompileInstruction::load(
+            instructions,
+            index,
+            &ED25519_PROGRAM_ID,
+            SIGNATURE_OFFSETS_START,
+            SignatureOffsets::LEN,
+        )
+        .map(Self)
+    }
+
+    /// Return the index of the instruction in the `Transaction`.
+    #[inline(always)]
+    pub fn index(&self) -> usize {
+        self.0.index
+    }
+
+    /// Return the number of signatures verified by the instruction.
+    #[inline(always)]
+    pub fn num_signatures(&self) -> usize {
+        self.0.num_signatures()
+    }
+
+    /// Return the signature offsets at `index`.
+    #[inline]
+    pub fn signature_offsets(&self, index: usize) -> Option<SignatureOffsets> {
+        self.0
+            .offsets(SIGNATURE_OFFSETS_START, index)
+            .map(SignatureOffsets::from_bytes)
+    }
+
+    /// Return the signed message at `index`, resolving the offsets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the index is out of
+    /// bounds or the offsets reference data outside of the instructions.
+    #[inline]
+    pub fn get(&self, index: usize) -> Result<Ed25519SignedMessage<'a>, ProgramError> {
+        self.0.signed_message(SIGNATURE_OFFSETS_START, index)
+    }
+
+    /// Return an iterator over the signed messages of the instruction.
+    #[inline]
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = Result<Ed25519SignedMessage<'a>, ProgramError>> + '_ {
+        (0..self.num_signatures()).map(|index| self.get(index))
+    }
+}
+
+/// Check that the currently executing `Transaction` includes an Ed25519
+/// precompile instruction verifying the signature of `message` by
+/// `public_key`.
+///
+/// # Errors
+///
+/// Returns [`ProgramError::MissingRequiredSignature`] if no Ed25519
+/// precompile instruction verifies the signature of `message` by `public_key`.
+pub fn verify_ed25519_signed<T>(
+    instructions: &Instructions<T>,
+    public_key: &[u8; PUBLIC_KEY_LEN],
+    message: &[u8],
+) -> Result<(), ProgramError>
+where
+    T: Deref<Target = [u8]>,
+{
+    let signed = (0..instructions.num_instructions()).any(|index| {
+        Ed25519Instruction::load(instructions, index).is_ok_and(|instruction| {
+            instruction.iter().any(|signed_message| {
+                signed_message.is_ok_and(|signed_message| {
+                    signed_message.public_key == public_key && signed_message.message == message
+                })
+            })
+        })
+    });
+
+    if signed {
+        Ok(())
+    } else {
+        Err(ProgramError::MissingRequiredSignature)
+    }
+}
@@ -0,0 +1,379 @@
+//! Parsers for the signature verification precompile instructions.
+//!
+//! Precompile instructions verify signatures before the program instructions
+//! of a transaction execute; a transaction including a precompile instruction
+//! with an invalid signature fails. Programs can therefore check – using the
+//! [instructions sysvar](crate::sysvars::instructions) – that a transaction
+//! includes a precompile instruction verifying a specific message signed by a
+//! specific key.
+//!
+//! The precompile instruction data contains a list of signature offsets, which
+//! reference the public key, signature and message bytes. These bytes can be
+//! part of the precompile instruction data or of the data of any other
+//! instruction of the transaction. The parsers in this module resolve these
+//! references and expose the signed messages.
+
+pub mod ed25519;
+pub mod secp256k1;
+pub mod secp256r1;
+
+use {
+    crate::{error::ProgramError, sysvars::instructions::Instructions, Address},
+    core::ops::Deref,
+};
+
+/// Length of a signature, in bytes.
+pub const SIGNATURE_LEN: usize = 64;
+
+/// Offsets of a signature in an Ed25519 or secp256r1 precompile instruction.
+///
+/// An instruction index of `u16::MAX` refers to the precompile instruction
+/// itself.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SignatureOffsets {
+    /// Offset of the signature.
+    pub signature_offset: u16,
+
+    /// Index of the instruction containing the signature.
+    pub signature_instruction_index: u16,
+
+    /// Offset of the public key.
+    pub public_key_offset: u16,
+
+    /// Index of the instruction containing the public key.
+    pub public_key_instruction_index: u16,
+
+    /// Offset of the message.
+    pub message_data_offset: u16,
+
+    /// Size of the message, in bytes.
+    pub message_data_size: u16,
+
+    /// Index of the instruction containing the message.
+    pub message_instruction_index: u16,
+}
+
+impl SignatureOffsets {
+    /// The length of the serialized `SignatureOffsets`.
+    pub const LEN: usize = 14;
+
+    /// Deserialize the signature offsets from their little-endian
+    /// representation.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8; Self::LEN]) -> Self {
+        let field = |index: usize| u16::from_le_bytes([bytes[index * 2], bytes[index * 2 + 1]]);
+
+        Self {
+            signature_offset: field(0),
+            signature_instruction_index: field(1),
+            public_key_offset: field(2),
+            public_key_instruction_index: field(3),
+            message_data_offset: field(4),
+            message_data_size: field(5),
+            message_instruction_index: field(6),
+        }
+    }
+}
+
+/// A message signed by a public key, as verified by a precompile instruction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SignedMessage<'a, const PUBLIC_KEY_LEN: usize> {
+    /// The public key.
+    pub public_key: &'a [u8; PUBLIC_KEY_LEN],
+
+    /// The signature.
+    pub signature: &'a [u8; SIGNATURE_LEN],
+
+    /// The signed message.
+    pub message: &'a [u8],
+}
+
+/// A precompile instruction of the currently executing `Transaction`.
+///
+/// The instruction data starts with the number of signatures (`u8`), followed
+/// by the serialized signature offsets of each signature.
+#[derive(Debug)]
+struct PrecompileInstruction<'a, T>
+where
+    T: Deref<Target = [u8]>,
+{
+    instructions: &'a Instructions<T>,
+    index: usize,
+    data: &'a [u8],
+}
+
+impl<'a, T> PrecompileInstruction<'a, T>
+where
+    T: Deref<Target = [u8]>,
+{
+    /// Load the instruction at `index`, checking that it is an instruction of
+    /// `program_id` with `offsets_len` bytes of signature offsets per
+    /// signature starting at `offsets_start`.
+    #[inline]
+    fn load(
+        instructions: &'a Instructions<T>,
+        index: usize,
+        program_id: &Address,
+        offsets_start: usize,
+        offsets_len: usize,
+    ) -> Result<Self, ProgramError> {
+        let instruction = instructions.load_instruction_at(index)?;
+
+        if instruction.get_program_id() != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let data = instruction.get_instruction_data();
+
+        match data.first() {
+            Some(num_signatures)
+                if data.len() >= offsets_start + *num_signatures as usize * offsets_len =>
+            {
+                Ok(Self {
+                    instructions,
+                    index,
+                    data,
+                })
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+
+    #[inline(always)]
+    fn num_signatures(&self) -> usize {
+        self.data[0] as usize
+    }
+
+    /// Return the serialized signature offsets at `index`, whose signature
+    /// offsets start at `start`.
+    #[inline]
+    fn offsets<const LEN: usize>(&self, start: usize, index: usize) -> Option<&'a [u8; LEN]> {
+        if index >= self.num_signatures() {
+            return None;
+        }
+        let offset = start + index * LEN;
+        self.data.get(offset..offset + LEN)?.try_into().ok()
+    }
+
+    /// Return the `len` bytes at `offset` of the data of the instruction at
+    /// `instruction_index`.
+    ///
+    /// The `instruction_index` is resolved to the precompile instruction when
+    /// it is `None`.
+    #[inline]
+    fn resolve(
+        &self,
+        instruction_index: Option<usize>,
+        offset: u16,
+        len: usize,
+    ) -> Result<&'a [u8], ProgramError> {
+        let instruction = self
+            .instructions
+            .load_instruction_at(instruction_index.unwrap_or(self.index))?;
+        let offset = offset as usize;
+
+        instruction
+            .get_instruction_data()
+            .get(offset..offset + len)
+            .ok_or(ProgramError::InvalidInstructionData)
+    }
+
+    /// Resolve the signed message at `index` of an Ed25519 or secp256r1
+    /// precompile instruction, whose signature offsets start at `start`.
+    #[inline]
+    fn signed_message<const PUBLIC_KEY_LEN: usize>(
+        &self,
+        start: usize,
+        index: usize,
+    ) -> Result<SignedMessage<'a, PUBLIC_KEY_LEN>, ProgramError> {
+        let offsets = self
+            .offsets(start, index)
+            .map(SignatureOffsets::from_bytes)
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let index = |index: u16| (index != u16::MAX).then_some(index as usize);
+
+        let public_key = self.resolve(
+            index(offsets.public_key_instruction_index),
+            offsets.public_key_offset,
+            PUBLIC_KEY_LEN,
+        )?;
+        let signature = self.resolve(
+            index(offsets.signature_instruction_index),
+            offsets.signature_offset,
+            SIGNATURE_LEN,
+        )?;
+        let message = self.resolve(
+            index(offsets.message_instruction_index),
+            offsets.message_data_offset,
+            offsets.message_data_size as usize,
+        )?;
+
+        Ok(SignedMessage {
+            // SAFETY: The slices were resolved with the expected lengths.
+            public_key: unsafe { &*(public_key.as_ptr() as *const [u8; PUBLIC_KEY_LEN]) },
+            signature: unsafe { &*(signature.as_ptr() as *const [u8; SIGNATURE_LEN]) },
+            message,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{
+            ed25519::{verify_ed25519_signed, Ed25519Instruction, ED25519_PROGRAM_ID},
+            secp256k1::{Secp256k1Instruction, SECP256K1_PROGRAM_ID},
+            secp256r1::{Secp256r1Instruction, SECP256R1_PROGRAM_ID},
+            *,
+        },
+        crate::Address,
+        alloc::vec::Vec,
+    };
+
+    /// Serialize instructions (program id, data) without accounts using the
+    /// instructions sysvar layout.
+    fn serialize(instructions: &[(&Address, &[u8])]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(instructions.len() as u16).to_le_bytes());
+        data.resize(2 * (instructions.len() + 1), 0);
+
+        for (index, (program_id, instruction_data)) in instructions.iter().enumerate() {
+            let offset = (data.len() as u16).to_le_bytes();
+            data[2 + index * 2..4 + index * 2].copy_from_slice(&offset);
+
+            data.extend_from_slice(&0u16.to_le_bytes());
+            data.extend_from_slice(program_id.as_array());
+            data.extend_from_slice(&(instruction_data.len() as u16).to_le_bytes());
+            data.extend_from_slice(instruction_data);
+        }
+
+        // Current instruction index.
+        data.extend_from_slice(&(instructions.len() as u16 - 1).to_le_bytes());
+        data
+    }
+
+    fn offsets(offsets: [u16; 7]) -> Vec<u8> {
+        offsets
+            .iter()
+            .flat_map(|offset| offset.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn test_ed25519() {
+        let public_key = [1u8; 32];
+        let signature = [2u8; 64];
+
+        // Public key and signature in the precompile instruction, message in
+        // the program instruction.
+        let mut precompile = [1, 0].to_vec();
+        let start = 2 + SignatureOffsets::LEN as u16;
+        precompile.extend(offsets([start + 32, u16::MAX, start, u16::MAX, 1, 5, 1]));
+        precompile.extend_from_slice(&public_key);
+        precompile.extend_from_slice(&signature);
+
+        let program_id = Address::new_from_array([9u8; 32]);
+        let data = serialize(&[
+            (&ED25519_PROGRAM_ID, &precompile),
+            (&program_id, b"\x00hello"),
+        ]);
+        let instructions = Instructions::new(data.as_slice()).unwrap();
+
+        let instruction = Ed25519Instruction::load(&instructions, 0).unwrap();
+        assert_eq!(instruction.num_signatures(), 1);
+        assert!(instruction.signature_offsets(1).is_none());
+
+        let signed_message = instruction.get(0).unwrap();
+        assert_eq!(signed_message.public_key, &public_key);
+        assert_eq!(signed_message.signature, &signature);
+        assert_eq!(signed_message.message, b"hello");
+        assert_eq!(instruction.iter().count(), 1);
+
+        assert!(verify_ed25519_signed(&instructions, &public_key, b"hello").is_ok());
+        assert_eq!(
+            verify_ed25519_signed(&instructions, &public_key, b"hell"),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+        assert_eq!(
+            verify_ed25519_signed(&instructions, &[3u8; 32], b"hello"),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+
+        // Not a precompile instruction.
+        assert_eq!(
+            Ed25519Instruction::load(&instructions, 1).err(),
+            Some(ProgramError::IncorrectProgramId)
+        );
+        assert_eq!(
+            Secp256r1Instruction::load(&instructions, 0).err(),
+            Some(ProgramError::IncorrectProgramId)
+        );
+    }
+
+    #[test]
+    fn test_invalid_offsets() {
+        // Message past the end of the program instruction data.
+        let mut precompile = [2, 0].to_vec();
+        precompile.extend(offsets([0, 1, 0, 1, 1, 64, 1]));
+        precompile.extend(offsets([0, 2, 0, 1, 0, 0, 1]));
+
+        let program_id = Address::new_from_array([9u8; 32]);
+        let data = serialize(&[(&ED25519_PROGRAM_ID, &precompile), (&program_id, &[0; 64])]);
+        let instructions = Instructions::new(data.as_slice()).unwrap();
+        let instruction = Ed25519Instruction::load(&instructions, 0).unwrap();
+
+        assert_eq!(
+            instruction.get(0).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+        // Instruction index out of bounds.
+        assert_eq!(
+            instruction.get(1).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            instruction.get(2).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+
+        // Signature offsets past the end of the data.
+        let data = serialize(&[(&SECP256R1_PROGRAM_ID, &[1, 0, 0])]);
+        let instructions = Instructions::new(data.as_slice()).unwrap();
+        assert_eq!(
+            Secp256r1Instruction::load(&instructions, 0).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        let eth_address = [1u8; 20];
+        let mut signature = [2u8; 65];
+        signature[64] = 1;
+
+        let mut precompile = [1].to_vec();
+        precompile.extend_from_slice(&12u16.to_le_bytes());
+        precompile.push(0);
+        precompile.extend_from_slice(&77u16.to_le_bytes());
+        precompile.push(0);
+        precompile.extend_from_slice(&97u16.to_le_bytes());
+        precompile.extend_from_slice(&5u16.to_le_bytes());
+        precompile.push(0);
+        assert_eq!(precompile.len(), 12);
+        precompile.extend_from_slice(&signature);
+        precompile.extend_from_slice(&eth_address);
+        precompile.extend_from_slice(b"hello");
+
+        let data = serialize(&[(&SECP256K1_PROGRAM_ID, &precompile)]);
+        let instructions = Instructions::new(data.as_slice()).unwrap();
+
+        let instruction = Secp256k1Instruction::load(&instructions, 0).unwrap();
+        assert_eq!(instruction.num_signatures(), 1);
+
+        let signed_message = instruction.get(0).unwrap();
+        assert_eq!(signed_message.eth_address, &eth_address);
+        assert_eq!(signed_message.signature, &[2u8; 64]);
+        assert_eq!(signed_message.recovery_id, 1);
+        assert_eq!(signed_message.message, b"hello");
+    }
+}
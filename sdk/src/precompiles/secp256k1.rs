@@ -0,0 +1,186 @@
+//! Secp256k1 signature recovery precompile.
+
+use {
+    super::{PrecompileInstruction, SIGNATURE_LEN},
+    crate::{error::ProgramError, sysvars::instructions::Instructions, Address},
+    core::ops::Deref,
+};
+
+/// Secp256k1 precompile program ID
+/// `KeccakSecp256k11111111111111111111111111111`.
+pub const SECP256K1_PROGRAM_ID: Address = Address::new_from_array([
+    0x04, 0xc6, 0xfc, 0x20, 0xf0, 0x50, 0xcc, 0xf0, 0x55, 0x84, 0xd7, 0x21, 0x1c, 0x9f, 0x8c, 0xf5,
+    0x9e, 0xc1, 0x47, 0x85, 0xbb, 0x16, 0x6a, 0x1e, 0x28, 0x30, 0xe8, 0x12, 0x20, 0x00, 0x00, 0x00,
+]);
+
+/// Length of an Ethereum address, in bytes.
+pub const ETH_ADDRESS_LEN: usize = 20;
+
+/// Offset of the signature offsets in the instruction data.
+///
+/// The instruction data starts with the number of signatures (`u8`).
+pub const SIGNATURE_OFFSETS_START: usize = 1;
+
+/// Offsets of a signature in a secp256k1 precompile instruction.
+///
+/// Instruction indices are absolute indices in the `Transaction`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Secp256k1SignatureOffsets {
+    /// Offset of the signature, followed by the recovery ID.
+    pub signature_offset: u16,
+
+    /// Index of the instruction containing the signature.
+    pub signature_instruction_index: u8,
+
+    /// Offset of the Ethereum address.
+    pub eth_address_offset: u16,
+
+    /// Index of the instruction containing the Ethereum address.
+    pub eth_address_instruction_index: u8,
+
+    /// Offset of the message.
+    pub message_data_offset: u16,
+
+    /// Size of the message, in bytes.
+    pub message_data_size: u16,
+
+    /// Index of the instruction containing the message.
+    pub message_instruction_index: u8,
+}
+
+impl Secp256k1SignatureOffsets {
+    /// The length of the serialized `Secp256k1SignatureOffsets`.
+    pub const LEN: usize = 11;
+
+    /// Deserialize the signature offsets from their little-endian
+    /// representation.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8; Self::LEN]) -> Self {
+        let field = |index: usize| u16::from_le_bytes([bytes[index], bytes[index + 1]]);
+
+        Self {
+            signature_offset: field(0),
+            signature_instruction_index: bytes[2],
+            eth_address_offset: field(3),
+            eth_address_instruction_index: bytes[5],
+            message_data_offset: field(6),
+            message_data_size: field(8),
+            message_instruction_index: bytes[10],
+        }
+    }
+}
+
+/// A message signed by the key of an Ethereum address, as verified by a
+/// secp256k1 precompile instruction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Secp256k1SignedMessage<'a> {
+    /// The Ethereum address of the public key.
+    pub eth_address: &'a [u8; ETH_ADDRESS_LEN],
+
+    /// The signature.
+    pub signature: &'a [u8; SIGNATURE_LEN],
+
+    /// The recovery ID of the signature.
+    pub recovery_id: u8,
+
+    /// The signed message.
+    pub message: &'a [u8],
+}
+
+/// A secp256k1 precompile instruction of the currently executing
+/// `Transaction`.
+#[derive(Debug)]
+pub struct Secp256k1Instruction<'a, T>(PrecompileInstruction<'a, T>)
+where
+    T: Deref<Target = [u8]>;
+
+impl<'a, T> Secp256k1Instruction<'a, T>
+where
+    T: Deref<Target = [u8]>,
+{
+    /// Load the secp256k1 precompile instruction at `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::IncorrectProgramId`] if the instruction is not
+    /// a secp256k1 precompile instruction, or
+    /// [`ProgramError::InvalidInstructionData`] if the instruction does not
+    /// exist or its data is too short to hold the signature offsets.
+    #[inline]
+    pub fn load(instructions: &'a Instructions<T>, index: usize) -> Result<Self, ProgramError> {
+        PrecompileInstruction::load(
+            instructions,
+            index,
+            &SECP256K1_PROGRAM_ID,
+            SIGNATURE_OFFSETS_START,
+            Secp256k1SignatureOffsets::LEN,
+        )
+        .map(Self)
+    }
+
+    /// Return the index of the instruction in the `Transaction`.
+    #[inline(always)]
+    pub fn index(&self) -> usize {
+        self.0.index
+    }
+
+    /// Return the number of signatures verified by the instruction.
+    #[inline(always)]
+    pub fn num_signatures(&self) -> usize {
+        self.0.num_signatures()
+    }
+
+    /// Return the signature offsets at `index`.
+    #[inline]
+    pub fn signature_offsets(&self, index: usize) -> Option<Secp256k1SignatureOffsets> {
+        self.0
+            .offsets(SIGNATURE_OFFSETS_START, index)
+            .map(Secp256k1SignatureOffsets::from_bytes)
+    }
+
+    /// Return the signed message at `index`, resolving the offsets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the index is out of
+    /// bounds or the offsets reference data outside of the instructions.
+    #[inline]
+    pub fn get(&self, index: usize) -> Result<Secp256k1SignedMessage<'a>, ProgramError> {
+        let offsets = self
+            .signature_offsets(index)
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        let eth_address = self.0.resolve(
+            Some(offsets.eth_address_instruction_index as usize),
+            offsets.eth_address_offset,
+            ETH_ADDRESS_LEN,
+        )?;
+        // The signature is followed by the recovery ID.
+        let signature = self.0.resolve(
+            Some(offsets.signature_instruction_index as usize),
+            offsets.signature_offset,
+            SIGNATURE_LEN + 1,
+        )?;
+        let message = self.0.resolve(
+            Some(offsets.message_instruction_index as usize),
+            offsets.message_data_offset,
+            offsets.message_data_size as usize,
+        )?;
+
+        Ok(Secp256k1SignedMessage {
+            // SAFETY: The slices were resolved with the expected lengths.
+            eth_address: unsafe { &*(eth_address.as_ptr() as *const [u8; ETH_ADDRESS_LEN]) },
+            signature: unsafe { &*(signature.as_ptr() as *const [u8; SIGNATURE_LEN]) },
+            recovery_id: signature[SIGNATURE_LEN],
+            message,
+        })
+    }
+
+    /// Return an iterator over the signed messages of the instruction.
+    #[inline]
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = Result<Secp256k1SignedMessage<'a>, ProgramError>> + '_ {
+        (0..self.num_signatures()).map(|index| self.get(index))
+    }
+}
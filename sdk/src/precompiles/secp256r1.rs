@@ -0,0 +1,96 @@
+//! Secp256r1 signature verification precompile.
+
+use {
+    super::{PrecompileInstruction, SignatureOffsets, SignedMessage},
+    crate::{error::ProgramError, sysvars::instructions::Instructions, Address},
+    core::ops::Deref,
+};
+
+/// Secp256r1 precompile program ID
+/// `Secp256r1SigVerify1111111111111111111111111`.
+pub const SECP256R1_PROGRAM_ID: Address = Address::new_from_array([
+    0x06, 0x92, 0x0d, 0xec, 0x2f, 0xea, 0x71, 0xb5, 0xb7, 0x23, 0x81, 0x4d, 0x74, 0x2d, 0xa9, 0x03,
+    0x1c, 0x83, 0xe7, 0x5f, 0xdb, 0x79, 0x5d, 0x56, 0x8e, 0x75, 0x47, 0x80, 0x20, 0x00, 0x00, 0x00,
+]);
+
+/// Length of a compressed secp256r1 public key, in bytes.
+pub const PUBLIC_KEY_LEN: usize = 33;
+
+/// Offset of the signature offsets in the instruction data.
+///
+/// The instruction data starts with the number of signatures (`u8`) followed
+/// by a padding byte.
+pub const SIGNATURE_OFFSETS_START: usize = 2;
+
+/// A message signed by a secp256r1 public key.
+pub type Secp256r1SignedMessage<'a> = SignedMessage<'a, PUBLIC_KEY_LEN>;
+
+/// A secp256r1 precompile instruction of the currently executing `Transaction`.
+#[derive(Debug)]
+pub struct Secp256r1Instruction<'a, T>(PrecompileInstruction<'a, T>)
+where
+    T: Deref<Target = [u8]>;
+
+impl<'a, T> Secp256r1Instruction<'a, T>
+where
+    T: Deref<Target = [u8]>,
+{
+    /// Load the secp256r1 precompile instruction at `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::IncorrectProgramId`] if the instruction is not
+    /// a secp256r1 precompile instruction, or
+    /// [`ProgramError::InvalidInstructionData`] if the instruction does not
+    /// exist or its data is too short to hold the signature offsets.
+    #[inline]
+    pub fn load(instructions: &'a Instructions<T>, index: usize) -> Result<Self, ProgramError> {
+        PrecompileInstruction::load(
+            instructions,
+            index,
+            &SECP256R1_PROGRAM_ID,
+            SIGNATURE_OFFSETS_START,
+            SignatureOffsets::LEN,
+        )
+        .map(Self)
+    }
+
+    /// Return the index of the instruction in the `Transaction`.
+    #[inline(always)]
+    pub fn index(&self) -> usize {
+        self.0.index
+    }
+
+    /// Return the number of signatures verified by the instruction.
+    #[inline(always)]
+    pub fn num_signatures(&self) -> usize {
+        self.0.num_signatures()
+    }
+
+    /// Return the signature offsets at `index`.
+    #[inline]
+    pub fn signature_offsets(&self, index: usize) -> Option<SignatureOffsets> {
+        self.0
+            .offsets(SIGNATURE_OFFSETS_START, index)
+            .map(SignatureOffsets::from_bytes)
+    }
+
+    /// Return the signed message at `index`, resolving the offsets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidInstructionData`] if the index is out of
+    /// bounds or the offsets reference data outside of the instructions.
+    #[inline]
+    pub fn get(&self, index: usize) -> Result<Secp256r1SignedMessage<'a>, ProgramError> {
+        self.0.signed_message(SIGNATURE_OFFSETS_START, index)
+    }
+
+    /// Return an iterator over the signed messages of the instruction.
+    #[inline]
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = Result<Secp256r1SignedMessage<'a>, ProgramError>> + '_ {
+        (0..self.num_signatures()).map(|index| self.get(index))
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
     error::ProgramError,
     hint::unlikely,
     impl_sysvar_get,
-    sysvars::Sysvar,
+    sysvars::{PartialSysvar, Sysvar, SysvarField},
     Address,
 };
 
@@ -82,6 +82,30 @@ impl Sysvar for Clock {
     impl_sysvar_get!(CLOCK_ID, 0);
 }
 
+impl PartialSysvar for Clock {
+    const ID: Address = CLOCK_ID;
+
+    const DATA_LEN: usize = Self::LEN;
+}
+
+impl Clock {
+    /// The [`Clock::slot`] field.
+    pub const SLOT: SysvarField<Self, Slot> = unsafe { SysvarField::new(0) };
+
+    /// The [`Clock::epoch_start_timestamp`] field.
+    pub const EPOCH_START_TIMESTAMP: SysvarField<Self, UnixTimestamp> =
+        unsafe { SysvarField::new(8) };
+
+    /// The [`Clock::epoch`] field.
+    pub const EPOCH: SysvarField<Self, Epoch> = unsafe { SysvarField::new(16) };
+
+    /// The [`Clock::leader_schedule_epoch`] field.
+    pub const LEADER_SCHEDULE_EPOCH: SysvarField<Self, Epoch> = unsafe { SysvarField::new(24) };
+
+    /// The [`Clock::unix_timestamp`] field.
+    pub const UNIX_TIMESTAMP: SysvarField<Self, UnixTimestamp> = unsafe { SysvarField::new(32) };
+}
+
 impl Clock {
     /// The length of the `Clock` sysvar account data.
     pub const LEN: usize = 8 + 8 + 8 + 8 + 8;
@@ -0,0 +1,287 @@
+//! Epoch rewards distribution status.
+
+use crate::{
+    account::{AccountView, Ref},
+    error::ProgramError,
+    hint::unlikely,
+    impl_sysvar_get,
+    sysvars::{slot_hashes::Hash, PartialSysvar, Sysvar, SysvarField},
+    Address,
+};
+
+/// The ID of the epoch rewards sysvar.
+pub const EPOCH_REWARDS_ID: Address = Address::new_from_array([
+    6, 167, 213, 23, 24, 220, 63, 238, 2, 165, 88, 191, 131, 206, 102, 225, 68, 66, 42, 28, 52,
+    149, 11, 39, 193, 134, 155, 90, 156, 0, 0, 0,
+]);
+
+/// Status of the epoch rewards distribution.
+///
+/// The epoch rewards are distributed over multiple blocks at the beginning of
+/// an epoch. While the distribution is [`active`](Self::active), stake accounts
+/// cannot be modified.
+///
+/// The fields are stored as little-endian bytes, so the struct has the same
+/// (bincode) byte layout as the sysvar account data.
+#[repr(C)]
+#[cfg_attr(feature = "copy", derive(Copy))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EpochRewards {
+    /// The starting block height stored as little-endian bytes.
+    distribution_starting_block_height_le: [u8; 8],
+
+    /// The number of partitions stored as little-endian bytes.
+    num_partitions_le: [u8; 8],
+
+    /// The blockhash of the parent block of the first block in the epoch.
+    parent_blockhash: Hash,
+
+    /// The total rewards points stored as little-endian bytes.
+    total_points_le: [u8; 16],
+
+    /// The total rewards stored as little-endian bytes.
+    total_rewards_le: [u8; 8],
+
+    /// The distributed rewards stored as little-endian bytes.
+    distributed_rewards_le: [u8; 8],
+
+    /// Whether the rewards period is active, stored as `0` or `1`.
+    active: u8,
+}
+
+// Assert that the size of the `EpochRewards` struct is as expected (81 bytes).
+const _ASSERT_STRUCT_LEN: () = assert!(size_of::<EpochRewards>() == EpochRewards::LEN);
+
+// Assert that the alignment of the `EpochRewards` struct is as expected (1
+// byte).
+const _ASSERT_STRUCT_ALIGN: () = assert!(align_of::<EpochRewards>() == 1);
+
+impl Sysvar for EpochRewards {
+    impl_sysvar_get!(EPOCH_REWARDS_ID, 0);
+}
+
+impl PartialSysvar for EpochRewards {
+    const ID: Address = EPOCH_REWARDS_ID;
+
+    const DATA_LEN: usize = Self::LEN;
+}
+
+impl EpochRewards {
+    /// The [`EpochRewards::distribution_starting_block_height`] field.
+    pub const DISTRIBUTION_STARTING_BLOCK_HEIGHT: SysvarField<Self, u64> =
+        unsafe { SysvarField::new(0) };
+
+    /// The [`EpochRewards::num_partitions`] field.
+    pub const NUM_PARTITIONS: SysvarField<Self, u64> = unsafe { SysvarField::new(8) };
+
+    /// The [`EpochRewards::parent_blockhash`] field.
+    pub const PARENT_BLOCKHASH: SysvarField<Self, Hash> = unsafe { SysvarField::new(16) };
+
+    /// The [`EpochRewards::total_points`] field.
+    pub const TOTAL_POINTS: SysvarField<Self, u128> = unsafe { SysvarField::new(48) };
+
+    /// The [`EpochRewards::total_rewards`] field.
+    pub const TOTAL_REWARDS: SysvarField<Self, u64> = unsafe { SysvarField::new(64) };
+
+    /// The [`EpochRewards::distributed_rewards`] field.
+    pub const DISTRIBUTED_REWARDS: SysvarField<Self, u64> = unsafe { SysvarField::new(72) };
+
+    /// The [`EpochRewards::active`] field, stored as `0` or `1`.
+    ///
+    /// The field is fetched as a `u8` since a byte other than `0` or `1` is
+    /// not a valid `bool`.
+    pub const ACTIVE: SysvarField<Self, u8> = unsafe { SysvarField::new(80) };
+}
+
+impl EpochRewards {
+    /// The length of the `EpochRewards` sysvar account data.
+    pub const LEN: usize = 8 + 8 + 32 + 16 + 8 + 8 + 1;
+
+    /// Return an `EpochRewards` from the given account view.
+    ///
+    /// This method performs a check on the account view address.
+    #[inline]
+    pub fn from_account_view(
+        account_view: &AccountView,
+    ) -> Result<Ref<'_, EpochRewards>, ProgramError> {
+        if unlikely(account_view.address() != &EPOCH_REWARDS_ID) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Ref::map(account_view.try_borrow()?, |data| unsafe {
+            Self::from_bytes_unchecked(data)
+        }))
+    }
+
+    /// Return an `EpochRewards` from the given account view.
+    ///
+    /// This method performs a check on the account view address, but does not
+    /// perform the borrow check.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data -
+    /// e.g., there are no mutable borrows of the account data.
+    #[inline]
+    pub unsafe fn from_account_view_unchecked(
+        account_view: &AccountView,
+    ) -> Result<&Self, ProgramError> {
+        if unlikely(account_view.address() != &EPOCH_REWARDS_ID) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Self::from_bytes_unchecked(account_view.borrow_unchecked()))
+    }
+
+    /// Return an `EpochRewards` from the given bytes.
+    ///
+    /// This method performs a length validation.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() < Self::LEN {
+            return Err(ProgramError::InvalidArgument);
+        }
+        // SAFETY: `bytes` has been validated to be at least `Self::LEN` bytes
+        // long; `EpochRewards` is byte-aligned and all bit patterns are valid.
+        Ok(unsafe { Self::from_bytes_unchecked(bytes) })
+    }
+
+    /// Return an `EpochRewards` from the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` has the expected length.
+    #[inline]
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        &*(bytes.as_ptr() as *const EpochRewards)
+    }
+
+    /// Returns the starting block height of the rewards distribution in the
+    /// current epoch.
+    #[inline(always)]
+    pub fn distribution_starting_block_height(&self) -> u64 {
+        u64::from_le_bytes(self.distribution_starting_block_height_le)
+    }
+
+    /// Returns the number of partitions in the rewards distribution in the
+    /// current epoch, used to generate an `EpochRewardsHasher`.
+    #[inline(always)]
+    pub fn num_partitions(&self) -> u64 {
+        u64::from_le_bytes(self.num_partitions_le)
+    }
+
+    /// Returns the blockhash of the parent block of the first block in the
+    /// epoch, used to seed an `EpochRewardsHasher`.
+    #[inline(always)]
+    pub fn parent_blockhash(&self) -> &Hash {
+        &self.parent_blockhash
+    }
+
+    /// Returns the total rewards points calculated for the current epoch,
+    /// where points equals the sum of (delegated stake * credits observed) for
+    /// all delegations.
+    #[inline(always)]
+    pub fn total_points(&self) -> u128 {
+        u128::from_le_bytes(self.total_points_le)
+    }
+
+    /// Returns the total rewards calculated for the current epoch. This may be
+    /// greater than the total `distributed_rewards` at the end of the rewards
+    /// period, due to rounding and inability to deliver rewards smaller than 1
+    /// lamport.
+    #[inline(always)]
+    pub fn total_rewards(&self) -> u64 {
+        u64::from_le_bytes(self.total_rewards_le)
+    }
+
+    /// Returns the rewards currently distributed for the current epoch, in
+    /// lamports.
+    #[inline(always)]
+    pub fn distributed_rewards(&self) -> u64 {
+        u64::from_le_bytes(self.distributed_rewards_le)
+    }
+
+    /// Returns whether the rewards period (including calculation and
+    /// distribution) is active.
+    #[inline(always)]
+    pub fn active(&self) -> bool {
+        self.active != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            sysvars::mock,
+            test_utils::{ProgramInput, TestAccount},
+            SUCCESS,
+        },
+    };
+
+    fn epoch_rewards_bytes() -> [u8; EpochRewards::LEN] {
+        let mut bytes = [0u8; EpochRewards::LEN];
+        bytes[0..8].copy_from_slice(&1_000u64.to_le_bytes());
+        bytes[8..16].copy_from_slice(&4u64.to_le_bytes());
+        bytes[16..48].copy_from_slice(&[7u8; 32]);
+        bytes[48..64].copy_from_slice(&(u64::MAX as u128 + 1).to_le_bytes());
+        bytes[64..72].copy_from_slice(&500u64.to_le_bytes());
+        bytes[72..80].copy_from_slice(&200u64.to_le_bytes());
+        bytes[80] = 1;
+        bytes
+    }
+
+    fn assert_epoch_rewards(epoch_rewards: &EpochRewards) {
+        assert_eq!(epoch_rewards.distribution_starting_block_height(), 1_000);
+        assert_eq!(epoch_rewards.num_partitions(), 4);
+        assert_eq!(epoch_rewards.parent_blockhash(), &[7u8; 32]);
+        assert_eq!(epoch_rewards.total_points(), u64::MAX as u128 + 1);
+        assert_eq!(epoch_rewards.total_rewards(), 500);
+        assert_eq!(epoch_rewards.distributed_rewards(), 200);
+        assert!(epoch_rewards.active());
+    }
+
+    #[test]
+    fn test_epoch_rewards() {
+        let mut bytes = epoch_rewards_bytes();
+
+        assert_epoch_rewards(EpochRewards::from_bytes(&bytes).unwrap());
+        assert_eq!(
+            EpochRewards::from_bytes(&bytes[..EpochRewards::LEN - 1]),
+            Err(ProgramError::InvalidArgument)
+        );
+
+        // Sysvar data read through the host-side sysvar provider.
+        mock::set_sysvar(EPOCH_REWARDS_ID, &bytes);
+        assert_epoch_rewards(&EpochRewards::get().unwrap());
+
+        bytes[80] = 0;
+        mock::set_sysvar(EPOCH_REWARDS_ID, &bytes);
+        assert!(!EpochRewards::get().unwrap().active());
+        assert_eq!(EpochRewards::fetch_field(EpochRewards::ACTIVE), Ok(0));
+    }
+
+    #[test]
+    fn test_from_account_view() {
+        let bytes = epoch_rewards_bytes();
+        let mut input = ProgramInput::new(
+            &Address::default(),
+            &[
+                TestAccount::new(EPOCH_REWARDS_ID, Address::default(), 1, &bytes).into(),
+                TestAccount::new(Address::default(), Address::default(), 1, &bytes).into(),
+            ],
+            &[],
+        );
+
+        let result = input.process(|_, accounts, _| {
+            assert_epoch_rewards(&*EpochRewards::from_account_view(&accounts[0])?);
+            assert_eq!(
+                EpochRewards::from_account_view(&accounts[1]).err(),
+                Some(ProgramError::InvalidArgument)
+            );
+
+            Ok(())
+        });
+
+        assert_eq!(result, SUCCESS);
+    }
+}
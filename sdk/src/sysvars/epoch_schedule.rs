@@ -0,0 +1,388 @@
+//! Configuration for epochs and slots.
+
+use crate::{
+    account::{AccountView, Ref},
+    error::ProgramError,
+    hint::unlikely,
+    impl_sysvar_get,
+    sysvars::{
+        clock::{Epoch, Slot},
+        PartialSysvar, Sysvar, SysvarField,
+    },
+    Address,
+};
+
+/// The ID of the epoch schedule sysvar.
+pub const EPOCH_SCHEDULE_ID: Address = Address::new_from_array([
+    6, 167, 213, 23, 24, 220, 63, 238, 2, 211, 228, 127, 1, 0, 248, 176, 84, 247, 148, 46, 96, 89,
+    30, 63, 80, 135, 25, 168, 5, 0, 0, 0,
+]);
+
+/// The default number of slots per epoch.
+pub const DEFAULT_SLOTS_PER_EPOCH: u64 = 432_000;
+
+/// The default slot offset for the leader schedule.
+pub const DEFAULT_LEADER_SCHEDULE_SLOT_OFFSET: u64 = DEFAULT_SLOTS_PER_EPOCH;
+
+/// The minimum number of slots per epoch.
+///
+/// During warmup, the first epoch has this many slots and each following
+/// epoch doubles in length.
+pub const MINIMUM_SLOTS_PER_EPOCH: u64 = 32;
+
+/// Configuration for epochs and slots.
+///
+/// The layout of the struct matches the (bincode) layout of the sysvar
+/// account data, so it can be used directly over the account data.
+#[repr(C)]
+#[cfg_attr(feature = "copy", derive(Copy))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpochSchedule {
+    /// The maximum number of slots in each epoch.
+    slots_per_epoch: [u8; 8],
+
+    /// A number of slots before beginning of an epoch to calculate a leader
+    /// schedule for that epoch.
+    leader_schedule_slot_offset: [u8; 8],
+
+    /// Whether epochs start short and grow.
+    warmup: u8,
+
+    /// The first epoch after the warmup period.
+    first_normal_epoch: [u8; 8],
+
+    /// The first slot after the warmup period.
+    first_normal_slot: [u8; 8],
+}
+
+// Assert that the size of the `EpochSchedule` struct is as expected (33 bytes).
+const _ASSERT_STRUCT_LEN: () = assert!(size_of::<EpochSchedule>() == EpochSchedule::LEN);
+
+// Assert that the alignment of the `EpochSchedule` struct is as expected (1
+// byte).
+const _ASSERT_STRUCT_ALIGN: () = assert!(align_of::<EpochSchedule>() == 1);
+
+impl Sysvar for EpochSchedule {
+    impl_sysvar_get!(EPOCH_SCHEDULE_ID, 0);
+}
+
+impl PartialSysvar for EpochSchedule {
+    const ID: Address = EPOCH_SCHEDULE_ID;
+
+    const DATA_LEN: usize = Self::LEN;
+}
+
+impl EpochSchedule {
+    /// The [`EpochSchedule::slots_per_epoch`] field.
+    pub const SLOTS_PER_EPOCH: SysvarField<Self, u64> = unsafe { SysvarField::new(0) };
+
+    /// The [`EpochSchedule::leader_schedule_slot_offset`] field.
+    pub const LEADER_SCHEDULE_SLOT_OFFSET: SysvarField<Self, u64> = unsafe { SysvarField::new(8) };
+
+    /// The [`EpochSchedule::first_normal_epoch`] field.
+    pub const FIRST_NORMAL_EPOCH: SysvarField<Self, Epoch> = unsafe { SysvarField::new(17) };
+
+    /// The [`EpochSchedule::first_normal_slot`] field.
+    pub const FIRST_NORMAL_SLOT: SysvarField<Self, Slot> = unsafe { SysvarField::new(25) };
+}
+
+impl EpochSchedule {
+    /// The length of the `EpochSchedule` sysvar account data.
+    pub const LEN: usize = 8 + 8 + 1 + 8 + 8;
+
+    /// Create a new `EpochSchedule`.
+    ///
+    /// When `warmup` is `true`, the first epoch has
+    /// [`MINIMUM_SLOTS_PER_EPOCH`] slots and each following epoch doubles in
+    /// length until reaching `slots_per_epoch`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slots_per_epoch` is less than [`MINIMUM_SLOTS_PER_EPOCH`].
+    pub fn new(slots_per_epoch: u64, leader_schedule_slot_offset: u64, warmup: bool) -> Self {
+        assert!(slots_per_epoch >= MINIMUM_SLOTS_PER_EPOCH);
+
+        let (first_normal_epoch, first_normal_slot) = if warmup {
+            let next_power_of_two = slots_per_epoch.next_power_of_two();
+            let log2_slots_per_epoch = next_power_of_two
+                .trailing_zeros()
+                .saturating_sub(MINIMUM_SLOTS_PER_EPOCH.trailing_zeros());
+
+            (
+                u64::from(log2_slots_per_epoch),
+                next_power_of_two.saturating_sub(MINIMUM_SLOTS_PER_EPOCH),
+            )
+        } else {
+            (0, 0)
+        };
+
+        Self {
+            slots_per_epoch: slots_per_epoch.to_le_bytes(),
+            leader_schedule_slot_offset: leader_schedule_slot_offset.to_le_bytes(),
+            warmup: warmup as u8,
+            first_normal_epoch: first_normal_epoch.to_le_bytes(),
+            first_normal_slot: first_normal_slot.to_le_bytes(),
+        }
+    }
+
+    /// Return an `EpochSchedule` from the given account view.
+    ///
+    /// This method performs a check on the account view address.
+    #[inline]
+    pub fn from_account_view(
+        account_view: &AccountView,
+    ) -> Result<Ref<'_, EpochSchedule>, ProgramError> {
+        if unlikely(account_view.address() != &EPOCH_SCHEDULE_ID) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Ref::map(account_view.try_borrow()?, |data| unsafe {
+            Self::from_bytes_unchecked(data)
+        }))
+    }
+
+    /// Return an `EpochSchedule` from the given account view.
+    ///
+    /// This method performs a check on the account view address, but does not
+    /// perform the borrow check.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data -
+    /// e.g., there are no mutable borrows of the account data.
+    #[inline]
+    pub unsafe fn from_account_view_unchecked(
+        account_view: &AccountView,
+    ) -> Result<&Self, ProgramError> {
+        if unlikely(account_view.address() != &EPOCH_SCHEDULE_ID) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Self::from_bytes_unchecked(account_view.borrow_unchecked()))
+    }
+
+    /// Return an `EpochSchedule` from the given bytes.
+    ///
+    /// This method performs a length validation.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() < Self::LEN {
+            return Err(ProgramError::InvalidArgument);
+        }
+        // SAFETY: `bytes` has been validated to be at least `Self::LEN` bytes long
+        // and `EpochSchedule` has an alignment of 1; all bit patterns are valid.
+        Ok(unsafe { Self::from_bytes_unchecked(bytes) })
+    }
+
+    /// Return an `EpochSchedule` from the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` has the expected length.
+    #[inline]
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        &*(bytes.as_ptr() as *const EpochSchedule)
+    }
+
+    /// The maximum number of slots in each epoch.
+    #[inline(always)]
+    pub fn slots_per_epoch(&self) -> u64 {
+        u64::from_le_bytes(self.slots_per_epoch)
+    }
+
+    /// A number of slots before beginning of an epoch to calculate a leader
+    /// schedule for that epoch.
+    #[inline(always)]
+    pub fn leader_schedule_slot_offset(&self) -> u64 {
+        u64::from_le_bytes(self.leader_schedule_slot_offset)
+    }
+
+    /// Whether epochs start short and grow.
+    #[inline(always)]
+    pub fn warmup(&self) -> bool {
+        self.warmup != 0
+    }
+
+    /// The first epoch after the warmup period.
+    #[inline(always)]
+    pub fn first_normal_epoch(&self) -> Epoch {
+        u64::from_le_bytes(self.first_normal_epoch)
+    }
+
+    /// The first slot after the warmup period.
+    #[inline(always)]
+    pub fn first_normal_slot(&self) -> Slot {
+        u64::from_le_bytes(self.first_normal_slot)
+    }
+
+    /// Return the number of slots in the given epoch.
+    pub fn get_slots_in_epoch(&self, epoch: Epoch) -> u64 {
+        if epoch < self.first_normal_epoch() {
+            2u64.saturating_pow(
+                (epoch as u32).saturating_add(MINIMUM_SLOTS_PER_EPOCH.trailing_zeros()),
+            )
+        } else {
+            self.slots_per_epoch()
+        }
+    }
+
+    /// Return the epoch of the given slot.
+    #[inline(always)]
+    pub fn get_epoch(&self, slot: Slot) -> Epoch {
+        self.get_epoch_and_slot_index(slot).0
+    }
+
+    /// Return the epoch of the given slot and the index of the slot within
+    /// the epoch.
+    pub fn get_epoch_and_slot_index(&self, slot: Slot) -> (Epoch, u64) {
+        let first_normal_slot = self.first_normal_slot();
+
+        if slot < first_normal_slot {
+            let epoch = slot
+                .saturating_add(MINIMUM_SLOTS_PER_EPOCH)
+                .saturating_add(1)
+                .next_power_of_two()
+                .trailing_zeros()
+                .saturating_sub(MINIMUM_SLOTS_PER_EPOCH.trailing_zeros())
+                .saturating_sub(1);
+
+            let epoch_len =
+                2u64.saturating_pow(epoch.saturating_add(MINIMUM_SLOTS_PER_EPOCH.trailing_zeros()));
+
+            (
+                u64::from(epoch),
+                slot.saturating_sub(epoch_len.saturating_sub(MINIMUM_SLOTS_PER_EPOCH)),
+            )
+        } else {
+            let slots_per_epoch = self.slots_per_epoch();
+            let normal_slot_index = slot.saturating_sub(first_normal_slot);
+            let normal_epoch_index = normal_slot_index.checked_div(slots_per_epoch).unwrap_or(0);
+            let epoch = self.first_normal_epoch().saturating_add(normal_epoch_index);
+            let slot_index = normal_slot_index.checked_rem(slots_per_epoch).unwrap_or(0);
+
+            (epoch, slot_index)
+        }
+    }
+
+    /// Return the first slot of the given epoch.
+    pub fn get_first_slot_in_epoch(&self, epoch: Epoch) -> Slot {
+        let first_normal_epoch = self.first_normal_epoch();
+
+        if epoch <= first_normal_epoch {
+            2u64.saturating_pow(epoch as u32)
+                .saturating_sub(1)
+                .saturating_mul(MINIMUM_SLOTS_PER_EPOCH)
+        } else {
+            epoch
+                .saturating_sub(first_normal_epoch)
+                .saturating_mul(self.slots_per_epoch())
+                .saturating_add(self.first_normal_slot())
+        }
+    }
+
+    /// Return the last slot of the given epoch.
+    #[inline(always)]
+    pub fn get_last_slot_in_epoch(&self, epoch: Epoch) -> Slot {
+        self.get_first_slot_in_epoch(epoch)
+            .saturating_add(self.get_slots_in_epoch(epoch))
+            .saturating_sub(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warmup() {
+        let schedule = EpochSchedule::new(DEFAULT_SLOTS_PER_EPOCH, DEFAULT_SLOTS_PER_EPOCH, true);
+
+        assert!(schedule.warmup());
+        assert_eq!(schedule.first_normal_epoch(), 14);
+        assert_eq!(schedule.first_normal_slot(), 524_256);
+
+        assert_eq!(schedule.get_slots_in_epoch(0), 32);
+        assert_eq!(schedule.get_slots_in_epoch(1), 64);
+        assert_eq!(schedule.get_slots_in_epoch(13), 262_144);
+        assert_eq!(schedule.get_slots_in_epoch(14), DEFAULT_SLOTS_PER_EPOCH);
+
+        assert_eq!(schedule.get_epoch_and_slot_index(0), (0, 0));
+        assert_eq!(schedule.get_epoch_and_slot_index(31), (0, 31));
+        assert_eq!(schedule.get_epoch_and_slot_index(32), (1, 0));
+        assert_eq!(schedule.get_epoch_and_slot_index(95), (1, 63));
+        assert_eq!(schedule.get_epoch_and_slot_index(96), (2, 0));
+        assert_eq!(schedule.get_epoch_and_slot_index(524_255), (13, 262_143));
+        assert_eq!(schedule.get_epoch_and_slot_index(524_256), (14, 0));
+        assert_eq!(
+            schedule.get_epoch_and_slot_index(524_256 + DEFAULT_SLOTS_PER_EPOCH + 1),
+            (15, 1)
+        );
+
+        assert_eq!(schedule.get_first_slot_in_epoch(0), 0);
+        assert_eq!(schedule.get_first_slot_in_epoch(1), 32);
+        assert_eq!(schedule.get_first_slot_in_epoch(2), 96);
+        assert_eq!(schedule.get_first_slot_in_epoch(14), 524_256);
+        assert_eq!(
+            schedule.get_first_slot_in_epoch(15),
+            524_256 + DEFAULT_SLOTS_PER_EPOCH
+        );
+        assert_eq!(schedule.get_last_slot_in_epoch(0), 31);
+        assert_eq!(schedule.get_last_slot_in_epoch(13), 524_255);
+
+        // The first and last slot of each epoch map back to the epoch.
+        for epoch in 0..20 {
+            let first_slot = schedule.get_first_slot_in_epoch(epoch);
+            let last_slot = schedule.get_last_slot_in_epoch(epoch);
+
+            assert_eq!(schedule.get_epoch_and_slot_index(first_slot), (epoch, 0));
+            assert_eq!(
+                schedule.get_epoch_and_slot_index(last_slot),
+                (epoch, schedule.get_slots_in_epoch(epoch) - 1)
+            );
+            assert_eq!(schedule.get_epoch(last_slot + 1), epoch + 1);
+        }
+    }
+
+    #[test]
+    fn test_no_warmup() {
+        let schedule = EpochSchedule::new(MINIMUM_SLOTS_PER_EPOCH * 4, 0, false);
+
+        assert!(!schedule.warmup());
+        assert_eq!(schedule.first_normal_epoch(), 0);
+        assert_eq!(schedule.first_normal_slot(), 0);
+
+        assert_eq!(schedule.get_slots_in_epoch(0), 128);
+        assert_eq!(schedule.get_epoch_and_slot_index(127), (0, 127));
+        assert_eq!(schedule.get_epoch_and_slot_index(300), (2, 44));
+        assert_eq!(schedule.get_first_slot_in_epoch(3), 384);
+        assert_eq!(schedule.get_last_slot_in_epoch(3), 511);
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let mut bytes = [0u8; EpochSchedule::LEN];
+        bytes[0..8].copy_from_slice(&DEFAULT_SLOTS_PER_EPOCH.to_le_bytes());
+        bytes[8..16].copy_from_slice(&DEFAULT_LEADER_SCHEDULE_SLOT_OFFSET.to_le_bytes());
+        bytes[16] = 1;
+        bytes[17..25].copy_from_slice(&14u64.to_le_bytes());
+        bytes[25..33].copy_from_slice(&524_256u64.to_le_bytes());
+
+        let schedule = EpochSchedule::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            schedule,
+            &EpochSchedule::new(
+                DEFAULT_SLOTS_PER_EPOCH,
+                DEFAULT_LEADER_SCHEDULE_SLOT_OFFSET,
+                true
+            )
+        );
+        assert_eq!(
+            schedule.leader_schedule_slot_offset(),
+            DEFAULT_LEADER_SCHEDULE_SLOT_OFFSET
+        );
+
+        // Sysvar data read through the host-side sysvar provider.
+        crate::sysvars::mock::set_sysvar(EPOCH_SCHEDULE_ID, &bytes);
+        assert_eq!(&EpochSchedule::get().unwrap(), schedule);
+
+        assert!(EpochSchedule::from_bytes(&bytes[..EpochSchedule::LEN - 1]).is_err());
+    }
+}
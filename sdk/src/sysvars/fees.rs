@@ -2,9 +2,15 @@
 
 use {
     super::{clock::DEFAULT_MS_PER_SLOT, Sysvar},
-    crate::impl_sysvar_get,
+    crate::{impl_sysvar_get, Address},
 };
 
+/// The ID of the fees sysvar.
+pub const FEES_ID: Address = Address::new_from_array([
+    6, 167, 213, 23, 24, 226, 90, 141, 131, 80, 60, 37, 26, 122, 240, 113, 38, 253, 114, 0, 223,
+    111, 196, 237, 82, 106, 156, 144, 0, 0, 0, 0,
+]);
+
 /// Fee calculator for processing transactions
 #[cfg_attr(feature = "copy", derive(Copy))]
 #[derive(Clone, Debug)]
@@ -100,5 +106,5 @@ impl Fees {
 }
 
 impl Sysvar for Fees {
-    impl_sysvar_get!(sol_get_fees_sysvar);
+    impl_sysvar_get!(FEES_ID, sol_get_fees_sysvar);
 }
@@ -41,6 +41,55 @@ where
         Instructions { data }
     }
 
+    /// Creates a new `Instructions` struct, validating the layout of the data.
+    ///
+    /// `data` is the instructions sysvar account data. This function
+    /// bounds-checks the whole buffer once – the offset table, each serialized
+    /// instruction and the current instruction index – so that all other
+    /// methods can access the data without further checks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::InvalidAccountData`] if the data is not a valid
+    /// instructions sysvar data.
+    #[inline]
+    pub fn new(data: T) -> Result<Self, ProgramError> {
+        // The serialized instructions are followed by the current instruction
+        // index.
+        let Some(end) = data.len().checked_sub(size_of::<u16>()) else {
+            return Err(ProgramError::InvalidAccountData);
+        };
+
+        let num_instructions = read_u16(&data, 0, end)? as usize;
+        let mut offsets_end = size_of::<u16>();
+
+        for _ in 0..num_instructions {
+            let mut offset = read_u16(&data, offsets_end, end)? as usize;
+            offsets_end += size_of::<u16>();
+
+            let num_accounts = read_u16(&data, offset, end)? as usize;
+            offset += size_of::<u16>()
+                + num_accounts * IntrospectedInstructionAccount::LEN
+                + ADDRESS_BYTES;
+
+            let data_len = read_u16(&data, offset, end)? as usize;
+            offset += size_of::<u16>() + data_len;
+
+            if offset > end {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        // SAFETY: `end` is within the data.
+        let current_index = unsafe { *(data.as_ptr().add(end) as *const [u8; 2]) };
+
+        if u16::from_le_bytes(current_index) as usize >= num_instructions {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Instructions { data })
+    }
+
     /// Load the number of instructions in the currently executing
     /// `Transaction`.
     #[inline(always)]
@@ -97,6 +146,88 @@ where
         Ok(unsafe { self.deserialize_instruction_unchecked(index) })
     }
 
+    /// Returns an iterator over the instructions of the currently executing
+    /// `Transaction`.
+    #[inline(always)]
+    pub fn iter(&self) -> IntrospectedInstructions<'_, T> {
+        IntrospectedInstructions {
+            instructions: self,
+            index: 0,
+            len: self.num_instructions(),
+        }
+    }
+
+    /// Finds the first instruction of the currently executing `Transaction`
+    /// that invokes `program_id`.
+    ///
+    /// Returns the index of the instruction together with the instruction, or
+    /// `None` if no instruction invokes `program_id`.
+    #[inline]
+    pub fn find_instruction_by_program(
+        &self,
+        program_id: &Address,
+    ) -> Option<(usize, IntrospectedInstruction<'_>)> {
+        self.iter()
+            .enumerate()
+            .find(|(_, instruction)| instruction.get_program_id() == program_id)
+    }
+
+    /// Finds the closest instruction preceding the current `Instruction` that
+    /// invokes `program_id`.
+    ///
+    /// Returns the index of the instruction together with the instruction, or
+    /// `None` if no preceding instruction invokes `program_id`. This can be
+    /// used to enforce that an instruction is preceded by an instruction of a
+    /// given program, e.g., the compute budget program.
+    #[inline]
+    pub fn find_previous_by_program(
+        &self,
+        program_id: &Address,
+    ) -> Option<(usize, IntrospectedInstruction<'_>)> {
+        let current_index = self.load_current_index() as usize;
+
+        (0..current_index).rev().find_map(|index| {
+            // SAFETY: `index` is less than the current index, which is less than
+            // the number of instructions.
+            let instruction = unsafe { self.deserialize_instruction_unchecked(index) };
+            (instruction.get_program_id() == program_id).then_some((index, instruction))
+        })
+    }
+
+    /// Asserts that `program_id` is invoked by the current `Instruction` and by
+    /// no other instruction of the currently executing `Transaction`.
+    ///
+    /// The instructions sysvar only includes top-level instructions, so this
+    /// checks that:
+    ///   - the program ID of the current top-level instruction is `program_id`,
+    ///     i.e., a program calling this with its own ID is not executing as a
+    ///     cross-program invocation of another program;
+    ///   - no other top-level instruction of the transaction invokes
+    ///     `program_id`.
+    ///
+    /// Cross-program invocations of the current instruction are not included in
+    /// the sysvar, so a program invoking itself through another program is not
+    /// detected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::IncorrectProgramId`] if the current instruction
+    /// does not invoke `program_id` or if another instruction invokes it.
+    #[inline]
+    pub fn assert_no_cpi_reentry(&self, program_id: &Address) -> Result<(), ProgramError> {
+        let current_index = self.load_current_index() as usize;
+
+        for (index, instruction) in self.iter().enumerate() {
+            let invokes_program = instruction.get_program_id() == program_id;
+
+            if invokes_program != (index == current_index) {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Creates and returns an `IntrospectedInstruction` relative to the current
     /// `Instruction` in the currently executing `Transaction.
     #[inline(always)]
@@ -115,6 +246,94 @@ where
     }
 }
 
+impl<'a> Instructions<Ref<'a, [u8]>> {
+    /// Creates a new `Instructions` struct by borrowing the data of the
+    /// instructions sysvar account, validating the layout of the data.
+    ///
+    /// Unlike the `TryFrom<&AccountView>` implementation, this reads the whole
+    /// data once, so that the other methods can rely on its layout.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProgramError::UnsupportedSysvar`] if the account is not the
+    /// instructions sysvar, or [`ProgramError::InvalidAccountData`] if the
+    /// data is not a valid instructions sysvar data.
+    #[inline]
+    pub fn from_account_view(account_view: &'a AccountView) -> Result<Self, ProgramError> {
+        if account_view.address() != &INSTRUCTIONS_ID {
+            return Err(ProgramError::UnsupportedSysvar);
+        }
+
+        Self::new(account_view.try_borrow()?)
+    }
+}
+
+/// Read a little-endian `u16` at `offset`, checking that it ends before `end`.
+#[inline(always)]
+fn read_u16(data: &[u8], offset: usize, end: usize) -> Result<u16, ProgramError> {
+    if offset + size_of::<u16>() > end {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    // SAFETY: The 2 bytes at `offset` are within the data.
+    Ok(u16::from_le_bytes(unsafe {
+        *(data.as_ptr().add(offset) as *const [u8; 2])
+    }))
+}
+
+/// Iterator over the instructions of the instructions sysvar.
+#[derive(Debug)]
+pub struct IntrospectedInstructions<'a, T>
+where
+    T: Deref<Target = [u8]>,
+{
+    instructions: &'a Instructions<T>,
+    index: usize,
+    len: usize,
+}
+
+impl<'a, T> Iterator for IntrospectedInstructions<'a, T>
+where
+    T: Deref<Target = [u8]>,
+{
+    type Item = IntrospectedInstruction<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+        // SAFETY: The index is less than the number of instructions.
+        let instruction = unsafe {
+            self.instructions
+                .deserialize_instruction_unchecked(self.index)
+        };
+        self.index += 1;
+        Some(instruction)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for IntrospectedInstructions<'_, T> where T: Deref<Target = [u8]> {}
+
+impl<'a, T> IntoIterator for &'a Instructions<T>
+where
+    T: Deref<Target = [u8]>,
+{
+    type Item = IntrospectedInstruction<'a>;
+    type IntoIter = IntrospectedInstructions<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Borrows the data of the instructions sysvar account without validating its
+/// layout. Use [`Instructions::from_account_view`] to validate it.
 impl<'a> TryFrom<&'a AccountView> for Instructions<Ref<'a, [u8]>> {
     type Error = ProgramError;
 
@@ -138,7 +357,7 @@ pub struct IntrospectedInstruction<'a> {
     marker: PhantomData<&'a [u8]>,
 }
 
-impl IntrospectedInstruction<'_> {
+impl<'a> IntrospectedInstruction<'a> {
     /// Create a new `IntrospectedInstruction`.
     ///
     /// # Safety
@@ -211,7 +430,7 @@ impl IntrospectedInstruction<'_> {
 
     /// Get the program ID of the `Instruction`.
     #[inline(always)]
-    pub fn get_program_id(&self) -> &Address {
+    pub fn get_program_id(&self) -> &'a Address {
         // SAFETY: The first 2 bytes represent the number of accounts in the
         // instruction.
         let num_accounts = self.num_account_metas();
@@ -227,7 +446,7 @@ impl IntrospectedInstruction<'_> {
 
     /// Get the instruction data of the `Instruction`.
     #[inline(always)]
-    pub fn get_instruction_data(&self) -> &[u8] {
+    pub fn get_instruction_data(&self) -> &'a [u8] {
         // SAFETY: The first 2 bytes represent the number of accounts in the
         // instruction.
         let offset =
@@ -289,3 +508,187 @@ impl IntrospectedInstructionAccount {
         InstructionAccount::new(&self.key, self.is_writable(), self.is_signer())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            test_utils::{ProgramInput, TestAccount},
+            SUCCESS,
+        },
+        alloc::vec::Vec,
+    };
+
+    /// Serialize instructions (program id, number of accounts, data) using the
+    /// instructions sysvar layout.
+    fn serialize(instructions: &[(Address, usize, &[u8])], current_index: u16) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(instructions.len() as u16).to_le_bytes());
+        data.resize(size_of::<u16>() * (instructions.len() + 1), 0);
+
+        for (index, (program_id, num_accounts, instruction_data)) in instructions.iter().enumerate()
+        {
+            let offset = (data.len() as u16).to_le_bytes();
+            data[2 + index * 2..4 + index * 2].copy_from_slice(&offset);
+
+            data.extend_from_slice(&(*num_accounts as u16).to_le_bytes());
+            for account in 0..*num_accounts {
+                data.push(0b11);
+                data.extend_from_slice(&[account as u8; ADDRESS_BYTES]);
+            }
+            data.extend_from_slice(program_id.as_array());
+            data.extend_from_slice(&(instruction_data.len() as u16).to_le_bytes());
+            data.extend_from_slice(instruction_data);
+        }
+
+        data.extend_from_slice(&current_index.to_le_bytes());
+        data
+    }
+
+    fn program(seed: u8) -> Address {
+        Address::new_from_array([seed; ADDRESS_BYTES])
+    }
+
+    #[test]
+    fn test_new() {
+        let data = serialize(&[(program(1), 2, &[1, 2, 3]), (program(2), 0, &[])], 1);
+        let instructions = Instructions::new(data.as_slice()).unwrap();
+
+        assert_eq!(instructions.num_instructions(), 2);
+        assert_eq!(instructions.load_current_index(), 1);
+
+        let instruction = instructions.load_instruction_at(0).unwrap();
+        assert_eq!(instruction.get_program_id(), &program(1));
+        assert_eq!(instruction.num_account_metas(), 2);
+        assert_eq!(instruction.get_instruction_data(), &[1, 2, 3]);
+
+        // Every truncation of the data is rejected.
+        for len in 0..data.len() {
+            assert_eq!(
+                Instructions::new(&data[..len]).err(),
+                Some(ProgramError::InvalidAccountData)
+            );
+        }
+
+        // Current index out of range.
+        let data = serialize(&[(program(1), 0, &[])], 1);
+        assert!(Instructions::new(data.as_slice()).is_err());
+
+        // Offset pointing past the end of the data.
+        let mut data = serialize(&[(program(1), 0, &[])], 0);
+        data[2..4].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert!(Instructions::new(data.as_slice()).is_err());
+
+        // Data length larger than the remaining data.
+        let mut data = serialize(&[(program(1), 1, &[1])], 0);
+        let data_len_offset = 4 + 2 + IntrospectedInstructionAccount::LEN + ADDRESS_BYTES;
+        data[data_len_offset..data_len_offset + 2].copy_from_slice(&2u16.to_le_bytes());
+        assert!(Instructions::new(data.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_iter_and_find() {
+        let data = serialize(
+            &[
+                (program(1), 0, &[0]),
+                (program(2), 1, &[1]),
+                (program(1), 2, &[2]),
+                (program(3), 3, &[3]),
+            ],
+            3,
+        );
+        let instructions = Instructions::new(data.as_slice()).unwrap();
+
+        let iter = instructions.iter();
+        assert_eq!(iter.len(), 4);
+        for (index, instruction) in iter.enumerate() {
+            assert_eq!(instruction.num_account_metas(), index);
+            assert_eq!(instruction.get_instruction_data(), &[index as u8]);
+        }
+
+        let (index, _) = instructions
+            .find_instruction_by_program(&program(1))
+            .unwrap();
+        assert_eq!(index, 0);
+        assert!(instructions
+            .find_instruction_by_program(&program(4))
+            .is_none());
+
+        let (index, instruction) = instructions.find_previous_by_program(&program(1)).unwrap();
+        assert_eq!(index, 2);
+        assert_eq!(instruction.get_instruction_data(), &[2]);
+        // The current instruction is not a previous instruction.
+        assert!(instructions.find_previous_by_program(&program(3)).is_none());
+    }
+
+    #[test]
+    fn test_assert_no_cpi_reentry() {
+        let data = serialize(&[(program(1), 0, &[]), (program(2), 0, &[])], 1);
+        let instructions = Instructions::new(data.as_slice()).unwrap();
+
+        assert!(instructions.assert_no_cpi_reentry(&program(2)).is_ok());
+        // Invoked through CPI from the current instruction.
+        assert_eq!(
+            instructions.assert_no_cpi_reentry(&program(3)),
+            Err(ProgramError::IncorrectProgramId)
+        );
+
+        let data = serialize(&[(program(2), 0, &[]), (program(2), 0, &[])], 1);
+        let instructions = Instructions::new(data.as_slice()).unwrap();
+
+        // Invoked by another top-level instruction.
+        assert_eq!(
+            instructions.assert_no_cpi_reentry(&program(2)),
+            Err(ProgramError::IncorrectProgramId)
+        );
+    }
+
+    #[test]
+    fn test_try_from_account_view() {
+        let data = serialize(&[(program(1), 1, &[1, 2, 3])], 0);
+        let mut input = ProgramInput::new(
+            &Address::default(),
+            &[
+                TestAccount::new(INSTRUCTIONS_ID, Address::default(), 1, &data).into(),
+                TestAccount::new(program(1), Address::default(), 1, &data).into(),
+                TestAccount::new(
+                    INSTRUCTIONS_ID,
+                    Address::default(),
+                    1,
+                    &data[..data.len() - 1],
+                )
+                .into(),
+            ],
+            &[],
+        );
+
+        let result = input.process(|_, accounts, _| {
+            let instructions = Instructions::try_from(&accounts[0])?;
+            assert_eq!(instructions.num_instructions(), 1);
+            assert_eq!(
+                instructions.load_instruction_at(0)?.get_instruction_data(),
+                &[1, 2, 3]
+            );
+
+            assert_eq!(
+                Instructions::try_from(&accounts[1]).err(),
+                Some(ProgramError::UnsupportedSysvar)
+            );
+            // Only `from_account_view` validates the data layout.
+            assert!(Instructions::try_from(&accounts[2]).is_ok());
+            assert_eq!(
+                Instructions::from_account_view(&accounts[2]).err(),
+                Some(ProgramError::InvalidAccountData)
+            );
+            assert_eq!(
+                Instructions::from_account_view(&accounts[1]).err(),
+                Some(ProgramError::UnsupportedSysvar)
+            );
+
+            Ok(())
+        });
+
+        assert_eq!(result, SUCCESS);
+    }
+}
@@ -0,0 +1,192 @@
+//! Information about the last restart of the cluster.
+
+use crate::{
+    account::{AccountView, Ref},
+    error::ProgramError,
+    hint::unlikely,
+    impl_sysvar_get,
+    sysvars::{clock::Slot, PartialSysvar, Sysvar, SysvarField},
+    Address,
+};
+
+/// The ID of the last restart slot sysvar.
+pub const LAST_RESTART_SLOT_ID: Address = Address::new_from_array([
+    6, 167, 213, 23, 25, 6, 221, 225, 205, 63, 148, 125, 202, 180, 200, 244, 244, 245, 27, 173, 15,
+    152, 19, 184, 0, 210, 137, 71, 31, 192, 0, 0,
+]);
+
+/// Information about the last restart of the cluster.
+#[repr(C)]
+#[cfg_attr(feature = "copy", derive(Copy))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LastRestartSlot {
+    /// The last restart `Slot`.
+    ///
+    /// This is `0` if the cluster has not been restarted.
+    pub last_restart_slot: Slot,
+}
+
+impl Sysvar for LastRestartSlot {
+    impl_sysvar_get!(LAST_RESTART_SLOT_ID, 0);
+}
+
+impl PartialSysvar for LastRestartSlot {
+    const ID: Address = LAST_RESTART_SLOT_ID;
+
+    const DATA_LEN: usize = Self::LEN;
+}
+
+impl LastRestartSlot {
+    /// The [`LastRestartSlot::last_restart_slot`] field.
+    pub const LAST_RESTART_SLOT: SysvarField<Self, Slot> = unsafe { SysvarField::new(0) };
+}
+
+impl LastRestartSlot {
+    /// The length of the `LastRestartSlot` sysvar account data.
+    pub const LEN: usize = 8;
+
+    /// Return a `LastRestartSlot` from the given account view.
+    ///
+    /// This method performs a check on the account view address.
+    #[inline]
+    pub fn from_account_view(
+        account_view: &AccountView,
+    ) -> Result<Ref<'_, LastRestartSlot>, ProgramError> {
+        if unlikely(account_view.address() != &LAST_RESTART_SLOT_ID) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Ref::map(account_view.try_borrow()?, |data| unsafe {
+            Self::from_bytes_unchecked(data)
+        }))
+    }
+
+    /// Return a `LastRestartSlot` from the given account view.
+    ///
+    /// This method performs a check on the account view address, but does not
+    /// perform the borrow check.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that it is safe to borrow the account data -
+    /// e.g., there are no mutable borrows of the account data.
+    #[inline]
+    pub unsafe fn from_account_view_unchecked(
+        account_view: &AccountView,
+    ) -> Result<&Self, ProgramError> {
+        if unlikely(account_view.address() != &LAST_RESTART_SLOT_ID) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Self::from_bytes_unchecked(account_view.borrow_unchecked()))
+    }
+
+    /// Return a `LastRestartSlot` from the given bytes.
+    ///
+    /// This method performs a length and alignment validation.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() < Self::LEN {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if !bytes.as_ptr().cast::<LastRestartSlot>().is_aligned() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        // SAFETY: `bytes` has been validated to be at least `Self::LEN` bytes long
+        // and aligned; all bit patterns are valid.
+        Ok(unsafe { Self::from_bytes_unchecked(bytes) })
+    }
+
+    /// Return a `LastRestartSlot` from the given bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bytes` has the expected length and
+    /// alignment.
+    #[inline]
+    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        &*(bytes.as_ptr() as *const LastRestartSlot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            sysvars::mock,
+            test_utils::{ProgramInput, TestAccount},
+            SUCCESS,
+        },
+    };
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(size_of::<LastRestartSlot>(), LastRestartSlot::LEN);
+        assert_eq!(align_of::<LastRestartSlot>(), 8);
+
+        // Use a `u64` buffer so the bytes are aligned.
+        let buffer = [42u64.to_le()];
+        // SAFETY: The buffer holds `LastRestartSlot::LEN` initialized bytes.
+        let data = unsafe {
+            core::slice::from_raw_parts(buffer.as_ptr() as *const u8, LastRestartSlot::LEN)
+        };
+
+        assert_eq!(
+            LastRestartSlot::from_bytes(data).unwrap().last_restart_slot,
+            42
+        );
+        assert_eq!(
+            LastRestartSlot::from_bytes(&data[..7]),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_get() {
+        mock::reset();
+        assert_eq!(
+            LastRestartSlot::get().err(),
+            Some(ProgramError::UnsupportedSysvar)
+        );
+
+        mock::set_sysvar(LAST_RESTART_SLOT_ID, &42u64.to_le_bytes());
+
+        assert_eq!(
+            LastRestartSlot::get(),
+            Ok(LastRestartSlot {
+                last_restart_slot: 42
+            })
+        );
+        assert_eq!(
+            LastRestartSlot::fetch_field(LastRestartSlot::LAST_RESTART_SLOT),
+            Ok(42)
+        );
+    }
+
+    #[test]
+    fn test_from_account_view() {
+        let data = 42u64.to_le_bytes();
+        let mut input = ProgramInput::new(
+            &Address::default(),
+            &[
+                TestAccount::new(LAST_RESTART_SLOT_ID, Address::default(), 1, &data).into(),
+                TestAccount::new(Address::default(), Address::default(), 1, &data).into(),
+            ],
+            &[],
+        );
+
+        let result = input.process(|_, accounts, _| {
+            assert_eq!(
+                LastRestartSlot::from_account_view(&accounts[0])?.last_restart_slot,
+                42
+            );
+            assert_eq!(
+                LastRestartSlot::from_account_view(&accounts[1]).err(),
+                Some(ProgramError::InvalidArgument)
+            );
+
+            Ok(())
+        });
+
+        assert_eq!(result, SUCCESS);
+    }
+}
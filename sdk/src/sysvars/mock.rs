@@ -0,0 +1,211 @@
+//! Host-side sysvar provider.
+//!
+//! Off-chain, there is no runtime to load sysvars from. With the
+//! `sysvar-mock` feature, [`Sysvar::get`](super::Sysvar::get),
+//! [`get_sysvar`](super::get_sysvar) and the helpers built on top of them read
+//! the sysvar data from a thread-local registry keyed by the sysvar
+//! [`Address`]. The data is stored in the same (bincode) byte layout used by
+//! the runtime, and reads follow the same `offset`/`length` semantics as the
+//! `sol_get_sysvar` syscall:
+//!
+//! - reading a sysvar that was not set fails with
+//!   [`ProgramError::UnsupportedSysvar`](crate::error::ProgramError::UnsupportedSysvar);
+//! - reading past the end of the sysvar data fails with
+//!   [`ProgramError::InvalidArgument`](crate::error::ProgramError::InvalidArgument).
+//!
+//! ```ignore
+//! use pinocchio::sysvars::{clock::{Clock, CLOCK_ID}, mock, Sysvar};
+//!
+//! let mut data = [0u8; 40];
+//! data[..8].copy_from_slice(&42u64.to_le_bytes());
+//! mock::set_sysvar(CLOCK_ID, &data);
+//!
+//! assert_eq!(Clock::get()?.slot, 42);
+//! ```
+//!
+//! The registry is thread-local, so tests running in parallel do not interfere
+//! with each other. It is kept in `std::thread_local` storage, which is why
+//! the `sysvar-mock` feature enables the `std` feature and this module is only
+//! available when the program is not compiled for the `solana` target.
+
+use {
+    super::{OFFSET_LENGTH_EXCEEDS_SYSVAR, SYSVAR_NOT_FOUND},
+    crate::Address,
+    alloc::vec::Vec,
+    core::cell::RefCell,
+};
+
+std::thread_local! {
+    static SYSVARS: RefCell<Vec<(Address, Vec<u8>)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Set the data of the sysvar `sysvar_id`.
+///
+/// Previously set data for the same sysvar is replaced.
+pub fn set_sysvar(sysvar_id: Address, data: &[u8]) {
+    SYSVARS.with_borrow_mut(
+        |sysvars| match sysvars.iter_mut().find(|(id, _)| id == &sysvar_id) {
+            Some((_, current)) => *current = data.to_vec(),
+            None => sysvars.push((sysvar_id, data.to_vec())),
+        },
+    );
+}
+
+/// Return a copy of the data of the sysvar `sysvar_id`, if it was set.
+pub fn sysvar(sysvar_id: &Address) -> Option<Vec<u8>> {
+    SYSVARS.with_borrow(|sysvars| {
+        sysvars
+            .iter()
+            .find(|(id, _)| id == sysvar_id)
+            .map(|(_, data)| data.clone())
+    })
+}
+
+/// Remove the data of the sysvar `sysvar_id`.
+pub fn remove_sysvar(sysvar_id: &Address) {
+    SYSVARS.with_borrow_mut(|sysvars| sysvars.retain(|(id, _)| id != sysvar_id));
+}
+
+/// Remove the data of all sysvars.
+pub fn reset() {
+    SYSVARS.with_borrow_mut(|sysvars| sysvars.clear());
+}
+
+/// Copy `len` bytes of the sysvar data starting at `offset` into `dst`.
+///
+/// Mock version of the `sol_get_sysvar` syscall, returning the same codes.
+///
+/// # Safety
+///
+/// The caller must ensure that the `dst` pointer is valid and has enough space
+/// to hold `len` bytes of data.
+pub(crate) unsafe fn get_sysvar(dst: *mut u8, sysvar_id: &Address, offset: u64, len: u64) -> u64 {
+    SYSVARS.with_borrow(|sysvars| {
+        let Some((_, data)) = sysvars.iter().find(|(id, _)| id == sysvar_id) else {
+            return SYSVAR_NOT_FOUND;
+        };
+
+        match offset
+            .checked_add(len)
+            .filter(|end| *end <= data.len() as u64)
+        {
+            Some(_) => {
+                core::ptr::copy_nonoverlapping(
+                    data.as_ptr().add(offset as usize),
+                    dst,
+                    len as usize,
+                );
+                crate::SUCCESS
+            }
+            None => OFFSET_LENGTH_EXCEEDS_SYSVAR,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            error::ProgramError,
+            sysvars::{
+                clock::{Clock, CLOCK_ID},
+                fees::{Fees, FEES_ID},
+                get_sysvar as fetch,
+                rent::{Rent, ACCOUNT_STORAGE_OVERHEAD, RENT_ID},
+                Sysvar,
+            },
+        },
+        core::mem::offset_of,
+    };
+
+    #[test]
+    fn test_sysvar_get() {
+        reset();
+
+        assert_eq!(Clock::get().err(), Some(ProgramError::UnsupportedSysvar));
+
+        let mut clock = [0u8; size_of::<Clock>()];
+        clock[0..8].copy_from_slice(&100u64.to_le_bytes());
+        clock[8..16].copy_from_slice(&1_000i64.to_le_bytes());
+        clock[16..24].copy_from_slice(&2u64.to_le_bytes());
+        clock[24..32].copy_from_slice(&3u64.to_le_bytes());
+        clock[32..40].copy_from_slice(&1_400i64.to_le_bytes());
+        set_sysvar(CLOCK_ID, &clock);
+
+        let clock = Clock::get().unwrap();
+        assert_eq!(clock.slot, 100);
+        assert_eq!(clock.epoch_start_timestamp, 1_000);
+        assert_eq!(clock.epoch, 2);
+        assert_eq!(clock.leader_schedule_epoch, 3);
+        assert_eq!(clock.unix_timestamp, 1_400);
+
+        // The rent sysvar data is longer than the `Rent` struct.
+        let mut rent = [0u8; 17];
+        rent[0..8].copy_from_slice(&3_480u64.to_le_bytes());
+        rent[8..16].copy_from_slice(&2.0f64.to_le_bytes());
+        rent[16] = 50;
+        set_sysvar(RENT_ID, &rent);
+
+        assert_eq!(
+            Rent::get().unwrap().try_minimum_balance(0).unwrap(),
+            ACCOUNT_STORAGE_OVERHEAD * 3_480
+        );
+
+        // Data shorter than the sysvar struct.
+        set_sysvar(CLOCK_ID, &[0u8; 8]);
+        assert_eq!(Clock::get().err(), Some(ProgramError::InvalidArgument));
+
+        remove_sysvar(&CLOCK_ID);
+        assert_eq!(Clock::get().err(), Some(ProgramError::UnsupportedSysvar));
+
+        // Legacy sysvar syscall, which writes the in-memory layout of the type.
+        assert_eq!(Fees::get().err(), Some(ProgramError::UnsupportedSysvar));
+        let mut fees = [0u8; size_of::<Fees>()];
+        let offset = offset_of!(Fees, fee_calculator.lamports_per_signature);
+        fees[offset..offset + 8].copy_from_slice(&5_000u64.to_le_bytes());
+        set_sysvar(FEES_ID, &fees);
+        assert_eq!(
+            Fees::get().unwrap().fee_calculator.lamports_per_signature,
+            5_000
+        );
+    }
+
+    #[test]
+    fn test_get_sysvar() {
+        reset();
+
+        let id = Address::new_from_array([7u8; 32]);
+        let mut buffer = [0u8; 4];
+
+        assert_eq!(
+            fetch(&mut buffer, &id, 0).err(),
+            Some(ProgramError::UnsupportedSysvar)
+        );
+
+        set_sysvar(id, &[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(sysvar(&id), Some([1, 2, 3, 4, 5, 6, 7, 8].to_vec()));
+
+        fetch(&mut buffer, &id, 0).unwrap();
+        assert_eq!(buffer, [1, 2, 3, 4]);
+
+        fetch(&mut buffer, &id, 4).unwrap();
+        assert_eq!(buffer, [5, 6, 7, 8]);
+
+        fetch(&mut buffer[..0], &id, 8).unwrap();
+
+        assert_eq!(
+            fetch(&mut buffer, &id, 5).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            fetch(&mut buffer, &id, usize::MAX).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+
+        // Setting the data again replaces it.
+        set_sysvar(id, &[9, 9, 9, 9]);
+        fetch(&mut buffer, &id, 0).unwrap();
+        assert_eq!(buffer, [9, 9, 9, 9]);
+    }
+}
@@ -2,15 +2,26 @@
 
 #[cfg(any(target_os = "solana", target_arch = "bpf"))]
 use crate::syscalls::sol_get_sysvar;
-use crate::{error::ProgramError, Address};
-#[cfg(not(any(target_os = "solana", target_arch = "bpf")))]
-use core::hint::black_box;
+use {
+    crate::{error::ProgramError, Address},
+    core::{marker::PhantomData, mem::MaybeUninit},
+};
 
 pub mod clock;
+pub mod epoch_rewards;
+pub mod epoch_schedule;
 pub mod fees;
 pub mod instructions;
+pub mod last_restart_slot;
+#[cfg(all(
+    feature = "sysvar-mock",
+    not(any(target_os = "solana", target_arch = "bpf"))
+))]
+pub mod mock;
 pub mod rent;
 pub mod slot_hashes;
+pub mod slot_history;
+pub mod stake_history;
 
 /// Return value indicating that the `offset + length` is greater than the
 /// length of the sysvar data.
@@ -38,10 +49,131 @@ pub trait Sysvar: Sized {
     }
 }
 
+/// A sysvar whose fields can be fetched individually.
+///
+/// Fetching a single field with the `sol_get_sysvar` syscall copies only the
+/// bytes of that field, which saves stack space and compute units compared to
+/// fetching the whole sysvar. The fields of a sysvar are declared as
+/// [`SysvarField`] constants on its type, e.g.,
+/// [`Clock::UNIX_TIMESTAMP`](clock::Clock::UNIX_TIMESTAMP), or returned by
+/// functions for sysvars holding a list of entries, e.g.,
+/// [`slot_hashes::entry_field`].
+///
+/// ```ignore
+/// use pinocchio::sysvars::{clock::Clock, PartialSysvar};
+///
+/// let unix_timestamp = Clock::fetch_field(Clock::UNIX_TIMESTAMP)?;
+/// ```
+pub trait PartialSysvar: Sized {
+    /// The ID of the sysvar.
+    const ID: Address;
+
+    /// The length of the sysvar account data.
+    const DATA_LEN: usize;
+
+    /// Fetch a single field of the sysvar directly from the runtime.
+    #[inline(always)]
+    fn fetch_field<T>(field: SysvarField<Self, T>) -> Result<T, ProgramError> {
+        field.fetch()
+    }
+}
+
+/// A field of the sysvar `S` holding a value of type `T`.
+///
+/// The field is identified by its byte offset in the (bincode) layout of the
+/// sysvar account data.
+pub struct SysvarField<S: PartialSysvar, T> {
+    /// Offset of the field in the sysvar account data.
+    offset: usize,
+
+    /// The sysvar and field types.
+    _types: PhantomData<fn() -> (S, T)>,
+}
+
+impl<S: PartialSysvar, T> SysvarField<S, T> {
+    /// Create a new `SysvarField` at `offset`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the `size_of::<T>()` bytes at `offset` in
+    /// the sysvar account data are a valid representation of `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field does not fit in the sysvar account data.
+    #[inline(always)]
+    pub const unsafe fn new(offset: usize) -> Self {
+        assert!(offset + size_of::<T>() <= S::DATA_LEN);
+
+        Self {
+            offset,
+            _types: PhantomData,
+        }
+    }
+
+    /// Return the offset of the field in the sysvar account data.
+    #[inline(always)]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Fetch the value of the field directly from the runtime.
+    #[inline(always)]
+    pub fn fetch(&self) -> Result<T, ProgramError> {
+        let mut value = MaybeUninit::<T>::uninit();
+
+        // SAFETY: The allocation is valid for the size of `T`.
+        unsafe {
+            get_sysvar_unchecked(
+                value.as_mut_ptr() as *mut u8,
+                &S::ID,
+                self.offset,
+                size_of::<T>(),
+            )?;
+        }
+
+        // SAFETY: The syscall initialized the memory and the field constructor
+        // guarantees that the bytes are a valid representation of `T`.
+        Ok(unsafe { value.assume_init() })
+    }
+}
+
 /// Implements the [`Sysvar::get`] method for both SBF and host targets.
 #[macro_export]
 macro_rules! impl_sysvar_get {
-    ($syscall_name:ident) => {
+    // These variants take only the name of a legacy sysvar syscall and forward
+    // it with the corresponding sysvar ID to the variant below.
+    (sol_get_clock_sysvar) => {
+        $crate::impl_sysvar_get!($crate::sysvars::clock::CLOCK_ID, sol_get_clock_sysvar);
+    };
+    (sol_get_epoch_rewards_sysvar) => {
+        $crate::impl_sysvar_get!(
+            $crate::sysvars::epoch_rewards::EPOCH_REWARDS_ID,
+            sol_get_epoch_rewards_sysvar
+        );
+    };
+    (sol_get_epoch_schedule_sysvar) => {
+        $crate::impl_sysvar_get!(
+            $crate::sysvars::epoch_schedule::EPOCH_SCHEDULE_ID,
+            sol_get_epoch_schedule_sysvar
+        );
+    };
+    (sol_get_fees_sysvar) => {
+        $crate::impl_sysvar_get!($crate::sysvars::fees::FEES_ID, sol_get_fees_sysvar);
+    };
+    (sol_get_last_restart_slot) => {
+        $crate::impl_sysvar_get!(
+            $crate::sysvars::last_restart_slot::LAST_RESTART_SLOT_ID,
+            sol_get_last_restart_slot
+        );
+    };
+    (sol_get_rent_sysvar) => {
+        $crate::impl_sysvar_get!($crate::sysvars::rent::RENT_ID, sol_get_rent_sysvar);
+    };
+    // This variant uses one of the legacy sysvar syscalls, e.g.,
+    // `sol_get_fees_sysvar`. The sysvar ID is only used off-chain, to read the
+    // sysvar data from the host-side provider.
+    ($sysvar_id:expr, $syscall_name:ident) => {
         fn get() -> Result<Self, $crate::error::ProgramError> {
             let mut var = core::mem::MaybeUninit::<Self>::uninit();
             let var_addr = var.as_mut_ptr() as *mut _ as *mut u8;
@@ -50,7 +182,18 @@ macro_rules! impl_sysvar_get {
             let result = unsafe { $crate::syscalls::$syscall_name(var_addr) };
 
             #[cfg(not(any(target_os = "solana", target_arch = "bpf")))]
-            let result = core::hint::black_box(var_addr as *const _ as u64);
+            let result = {
+                let sysvar_id = &$sysvar_id;
+                // SAFETY: The allocation is valid for the size of `Self`.
+                unsafe {
+                    $crate::sysvars::host_get_sysvar(
+                        var_addr,
+                        sysvar_id,
+                        0,
+                        core::mem::size_of::<Self>() as u64,
+                    )
+                }
+            };
 
             match result {
                 $crate::SUCCESS => {
@@ -91,9 +234,15 @@ macro_rules! impl_sysvar_get {
 
             #[cfg(not(target_os = "solana"))]
             let result = {
+                let (sysvar_id, padding) = (&$syscall_id, $padding);
                 // SAFETY: The allocation is valid for the size of `Self`.
-                unsafe { var_addr.write_bytes(0, size_of::<Self>()) };
-                core::hint::black_box(var_addr as *const _ as u64)
+                unsafe {
+                    let length = core::mem::size_of::<Self>() - padding;
+                    // Make sure all bytes are initialized.
+                    var_addr.add(length).write_bytes(0, padding);
+
+                    $crate::sysvars::host_get_sysvar(var_addr, sysvar_id, 0, length as u64)
+                }
             };
 
             match result {
@@ -151,8 +300,42 @@ pub unsafe fn get_sysvar_unchecked(
 
     #[cfg(not(any(target_os = "solana", target_arch = "bpf")))]
     {
-        black_box((dst, sysvar_id, offset, len));
-        Ok(())
+        let result = unsafe { host_get_sysvar(dst, sysvar_id, offset as u64, len as u64) };
+
+        match result {
+            crate::SUCCESS => Ok(()),
+            OFFSET_LENGTH_EXCEEDS_SYSVAR => Err(ProgramError::InvalidArgument),
+            SYSVAR_NOT_FOUND => Err(ProgramError::UnsupportedSysvar),
+            // Unexpected errors are folded into `UnsupportedSysvar`.
+            _ => Err(ProgramError::UnsupportedSysvar),
+        }
+    }
+}
+
+/// Host-side handler for retrieving a slice of sysvar data.
+///
+/// With the `sysvar-mock` feature, the data is read from the [`mock`] sysvar
+/// registry; otherwise, `len` zero bytes are written to `dst`. Returns the same
+/// codes as the `sol_get_sysvar` syscall.
+///
+/// # Safety
+///
+/// The caller must ensure that the `dst` pointer is valid and has enough space
+/// to hold the requested `len` bytes of data.
+#[cfg(not(target_os = "solana"))]
+#[doc(hidden)]
+#[inline]
+pub unsafe fn host_get_sysvar(dst: *mut u8, sysvar_id: &Address, offset: u64, len: u64) -> u64 {
+    #[cfg(all(feature = "sysvar-mock", not(target_arch = "bpf")))]
+    {
+        mock::get_sysvar(dst, sysvar_id, offset, len)
+    }
+
+    #[cfg(not(all(feature = "sysvar-mock", not(target_arch = "bpf"))))]
+    {
+        core::hint::black_box((sysvar_id, offset));
+        dst.write_bytes(0, len as usize);
+        crate::SUCCESS
     }
 }
 
@@ -163,3 +346,81 @@ pub fn get_sysvar(dst: &mut [u8], sysvar_id: &Address, offset: usize) -> Result<
     // SAFETY: Use the length of the slice as the length parameter.
     unsafe { get_sysvar_unchecked(dst.as_mut_ptr(), sysvar_id, offset, dst.len()) }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{
+            clock::{Clock, CLOCK_ID},
+            mock,
+            rent::{Rent, RENT_ID},
+            slot_hashes::{self, SlotHashes, MAX_ENTRIES, MAX_SIZE, SLOTHASHES_ID},
+            *,
+        },
+        alloc::vec,
+    };
+
+    #[test]
+    fn test_partial_sysvar() {
+        mock::reset();
+        assert_eq!(
+            Clock::fetch_field(Clock::SLOT).err(),
+            Some(ProgramError::UnsupportedSysvar)
+        );
+
+        let mut clock = [0u8; Clock::LEN];
+        clock[0..8].copy_from_slice(&100u64.to_le_bytes());
+        clock[16..24].copy_from_slice(&2u64.to_le_bytes());
+        clock[32..40].copy_from_slice(&(-1_400i64).to_le_bytes());
+        mock::set_sysvar(CLOCK_ID, &clock);
+
+        assert_eq!(Clock::fetch_field(Clock::SLOT).unwrap(), 100);
+        assert_eq!(Clock::EPOCH.fetch().unwrap(), 2);
+        assert_eq!(Clock::UNIX_TIMESTAMP.offset(), 32);
+        assert_eq!(Clock::fetch_field(Clock::UNIX_TIMESTAMP).unwrap(), -1_400);
+
+        let mut rent = [0u8; 17];
+        rent[0..8].copy_from_slice(&3_480u64.to_le_bytes());
+        mock::set_sysvar(RENT_ID, &rent);
+
+        assert_eq!(Rent::fetch_field(Rent::LAMPORTS_PER_BYTE).unwrap(), 3_480);
+
+        let mut slot_hashes = vec![0u8; MAX_SIZE];
+        slot_hashes[0..8].copy_from_slice(&2u64.to_le_bytes());
+        slot_hashes[48..56].copy_from_slice(&41u64.to_le_bytes());
+        slot_hashes[56..88].copy_from_slice(&[1u8; 32]);
+        mock::set_sysvar(SLOTHASHES_ID, &slot_hashes);
+
+        let entry = slot_hashes::entry_field(1).unwrap().fetch().unwrap();
+        assert_eq!(entry.slot(), 41);
+        assert_eq!(entry.hash, [1u8; 32]);
+        assert_eq!(
+            SlotHashes::fetch_field(slot_hashes::entry_field(MAX_ENTRIES - 1).unwrap())
+                .unwrap()
+                .slot(),
+            0
+        );
+        assert!(slot_hashes::entry_field(MAX_ENTRIES).is_none());
+    }
+
+    #[test]
+    fn test_legacy_sysvar_get() {
+        struct Fees(u64);
+
+        impl Sysvar for Fees {
+            impl_sysvar_get!(sol_get_fees_sysvar);
+        }
+
+        mock::reset();
+        mock::set_sysvar(fees::FEES_ID, &5_000u64.to_le_bytes());
+
+        assert_eq!(Fees::get().map(|fees| fees.0), Ok(5_000));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sysvar_field_out_of_bounds() {
+        // The `Clock` sysvar data is 40 bytes long.
+        let _field: SysvarField<Clock, u64> = unsafe { SysvarField::new(33) };
+    }
+}
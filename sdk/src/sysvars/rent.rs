@@ -3,8 +3,12 @@
 //! This is required for the rent sysvar implementation.
 use {
     crate::{
-        account::AccountView, error::ProgramError, hint::unlikely, impl_sysvar_get,
-        sysvars::Sysvar, Address,
+        account::AccountView,
+        error::ProgramError,
+        hint::unlikely,
+        impl_sysvar_get,
+        sysvars::{PartialSysvar, Sysvar, SysvarField},
+        Address,
     },
     core::mem::{align_of, size_of},
 };
@@ -216,6 +220,19 @@ impl Sysvar for Rent {
     impl_sysvar_get!(RENT_ID, 0);
 }
 
+impl PartialSysvar for Rent {
+    const ID: Address = RENT_ID;
+
+    // The sysvar data also holds the (deprecated) `exemption_threshold` and
+    // `burn_percent` fields.
+    const DATA_LEN: usize = 8 + 8 + 1;
+}
+
+impl Rent {
+    /// The `lamports_per_byte` field.
+    pub const LAMPORTS_PER_BYTE: SysvarField<Self, u64> = unsafe { SysvarField::new(0) };
+}
+
 #[cfg(test)]
 #[allow(deprecated)]
 mod tests {
@@ -20,7 +20,7 @@ use {
         account::{AccountView, Ref},
         error::ProgramError,
         hint::unlikely,
-        sysvars::clock::Slot,
+        sysvars::{clock::Slot, PartialSysvar, SysvarField},
         Address,
     },
     core::{mem, ops::Deref, slice::from_raw_parts},
@@ -253,6 +253,29 @@ impl<'a, T: Deref<Target = [u8]>> IntoIterator for &'a SlotHashes<T> {
     }
 }
 
+impl<T: Deref<Target = [u8]>> PartialSysvar for SlotHashes<T> {
+    const ID: Address = SLOTHASHES_ID;
+
+    const DATA_LEN: usize = MAX_SIZE;
+}
+
+/// Returns the field of the `SlotHashes` entry at `index`, or `None` if
+/// `index` is not less than `MAX_ENTRIES`.
+///
+/// Fetching the field copies a single `SlotHashEntry` from the sysvar. Note
+/// that the sysvar may hold fewer than `MAX_ENTRIES` entries, in which case
+/// the bytes past the last entry are zeroed.
+#[inline(always)]
+pub const fn entry_field(
+    index: usize,
+) -> Option<SysvarField<SlotHashes<&'static [u8]>, SlotHashEntry>> {
+    if index >= MAX_ENTRIES {
+        return None;
+    }
+    // SAFETY: All bit patterns are valid for `SlotHashEntry`.
+    Some(unsafe { SysvarField::new(NUM_ENTRIES_SIZE + index * ENTRY_SIZE) })
+}
+
 impl<'a> SlotHashes<Ref<'a, [u8]>> {
     /// Creates a `SlotHashes` instance by safely borrowing data from an
     /// `AccountView`.
@@ -292,16 +315,12 @@ impl SlotHashes<Box<[u8]>> {
     pub fn fetch() -> Result<Self, ProgramError> {
         let mut sysvar_data = Box::<[u8]>::new_uninit_slice(MAX_SIZE);
         unsafe {
-            #[cfg(any(target_os = "solana", target_arch = "bpf"))]
             crate::sysvars::get_sysvar_unchecked(
                 sysvar_data.as_mut_ptr() as *mut _,
                 &SLOTHASHES_ID,
                 0,
                 MAX_SIZE,
             )?;
-
-            #[cfg(not(any(target_os = "solana", target_arch = "bpf")))]
-            core::ptr::write_bytes(sysvar_data.as_mut_ptr(), 0, MAX_SIZE);
         }
         // SAFETY: The data was initialized by the syscall.
         Ok(unsafe { SlotHashes::new_unchecked(sysvar_data.assume_init()) })
@@ -450,33 +450,36 @@ fn test_from_account_view_constructor() {
     }
 }
 
-/// Host-side sanity test: ensure the `SlotHashes::fetch()` helper compiles and
-/// allocates a MAX_SIZE-sized buffer without panicking.
+/// Host-side test: ensure the `SlotHashes::fetch()` helper allocates a
+/// MAX_SIZE-sized buffer and copies the sysvar data.
 ///
-/// On non-Solana targets the underlying syscall is stubbed; the returned buffer
-/// is zero-initialized and contains zero entries.  We overwrite
-/// that buffer with deterministic fixture data and then exercise the normal
-/// `SlotHashes` getters to make sure the view itself works.  We do not verify
-/// that the syscall populated real on-chain bytes, as doing so requires an
-/// environment outside the scope of host `cargo test`.
+/// On non-Solana targets the sysvar data is read from the host-side sysvar
+/// provider, so the fixture data is set there and then read back through the
+/// normal `SlotHashes` getters.
 #[cfg(feature = "alloc")]
 #[test]
 fn test_fetch_allocates_buffer_host() {
     const START_SLOT: u64 = 500;
     let entries = generate_mock_entries(5, START_SLOT, DecrementStrategy::Strictly1);
-    let data = create_mock_data(&entries);
+    let mut data = create_mock_data(&entries);
+    data.resize(MAX_SIZE, 0);
+    crate::sysvars::mock::set_sysvar(SLOTHASHES_ID, &data);
 
     // This should allocate a 20_488-byte boxed slice and *not* panic.
-    let mut slot_hashes =
+    let slot_hashes =
         SlotHashes::<alloc::boxed::Box<[u8]>>::fetch().expect("fetch() should allocate");
 
-    // Overwrite the stubbed contents with known data so we can reuse the
-    // remainder of the test harness.
-    slot_hashes.data[..data.len()].copy_from_slice(&data);
-
+    assert_eq!(slot_hashes.data.len(), MAX_SIZE);
     assert_eq!(slot_hashes.len(), entries.len());
     for (i, entry) in slot_hashes.into_iter().enumerate() {
         assert_eq!(entry.slot(), entries[i].0);
         assert_eq!(entry.hash, entries[i].1);
     }
+
+    // Fetching fails when the sysvar is not available.
+    crate::sysvars::mock::remove_sysvar(&SLOTHASHES_ID);
+    assert_eq!(
+        SlotHashes::<alloc::boxed::Box<[u8]>>::fetch().err(),
+        Some(ProgramError::UnsupportedSysvar)
+    );
 }
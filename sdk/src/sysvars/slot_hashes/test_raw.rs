@@ -1,6 +1,6 @@
 //! Tests focusing on low-level `slot_hashes::raw` helpers.
 
-use super::{raw, *};
+use super::{raw, test_utils::*, *};
 
 #[test]
 fn test_validate_buffer_size() {
@@ -136,9 +136,12 @@ fn test_fetch_into_offset_validation() {
 
 /// Host-only smoke test for `raw::fetch_into`.
 ///
-/// On a host build the underlying sysvar syscall is stubbed out.
+/// On a host build the sysvar data is read from the host-side sysvar provider,
+/// which is set to an empty (zeroed) `SlotHashes` sysvar.
 #[test]
 fn test_fetch_into_host_stub() {
+    crate::sysvars::mock::set_sysvar(SLOTHASHES_ID, &[0u8; MAX_SIZE]);
+
     // 1. Full-size buffer, offset 0.
     let mut full = alloc::vec![0u8; MAX_SIZE];
     let n = raw::fetch_into(&mut full, 0).expect("fetch_into(full, 0)");
@@ -179,6 +182,11 @@ fn test_fetch_into_host_stub() {
 fn test_fetch_into_offset_avoids_incorrect_entry_count() {
     // When fetch_into is called with offset != 0, the first
     // 8 bytes of the buffer contains header data, not entry data.
+    let entries = generate_mock_entries(3, 100, DecrementStrategy::Strictly1);
+    let mut data = create_mock_data(&entries);
+    data.resize(MAX_SIZE, 0);
+    crate::sysvars::mock::set_sysvar(SLOTHASHES_ID, &data);
+
     let mut buffer = alloc::vec![0u8; 3 * ENTRY_SIZE];
 
     // Call fetch_into with offset 8 (skipping the 8-byte header)
@@ -199,4 +207,8 @@ fn test_fetch_into_offset_avoids_incorrect_entry_count() {
     let mut second_entry_buffer = alloc::vec![0u8; ENTRY_SIZE];
     let second_result = raw::fetch_into(&mut second_entry_buffer, 48).unwrap();
     assert_eq!(second_result, 1);
+    assert_eq!(
+        &second_entry_buffer[..SLOT_SIZE],
+        &entries[1].0.to_le_bytes()
+    );
 }
@@ -0,0 +1,323 @@
+//! Efficient, zero-copy access to `SlotHistory` sysvar data.
+//!
+//! The `SlotHistory` sysvar is a bitvector indicating which of the last
+//! [`MAX_ENTRIES`] slots are present in the ledger, together with the next
+//! slot to be recorded.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use {
+    crate::{
+        account::{AccountView, Ref},
+        error::ProgramError,
+        hint::unlikely,
+        sysvars::{clock::Slot, PartialSysvar, SysvarField},
+        Address,
+    },
+    core::{mem, ops::Deref},
+};
+
+/// `SysvarS1otHistory11111111111111111111111111`
+pub const SLOT_HISTORY_ID: Address = Address::new_from_array([
+    6, 167, 213, 23, 25, 47, 10, 175, 200, 117, 226, 225, 132, 87, 124, 80, 105, 207, 200, 70, 73,
+    227, 235, 146, 120, 47, 149, 141, 72, 0, 0, 0,
+]);
+/// Number of slots tracked by the sysvar.
+pub const MAX_ENTRIES: u64 = 1024 * 1024;
+/// Size of a block of the bitvector.
+pub const BLOCK_SIZE: usize = mem::size_of::<u64>();
+/// Number of blocks of the bitvector.
+pub const NUM_BLOCKS: usize = MAX_ENTRIES as usize / (BLOCK_SIZE * 8);
+/// Sysvar data is:
+/// `tag`       (1 byte): `1`, indicating that the bitvector is allocated
+/// `blocks`    (8 bytes): little-endian number of blocks (`NUM_BLOCKS`)
+/// `bits`      (`NUM_BLOCKS × 8 bytes`): little-endian `u64` blocks
+/// `len`       (8 bytes): little-endian number of bits (`MAX_ENTRIES`)
+/// `next_slot` (8 bytes): little-endian next slot
+/// Offset of the bitvector blocks in the sysvar data.
+pub const BITS_OFFSET: usize = 1 + mem::size_of::<u64>();
+/// Offset of the next slot in the sysvar data.
+pub const NEXT_SLOT_OFFSET: usize = BITS_OFFSET + NUM_BLOCKS * BLOCK_SIZE + mem::size_of::<u64>();
+/// Size of the sysvar data in bytes.
+pub const SIZE: usize = NEXT_SLOT_OFFSET + mem::size_of::<Slot>();
+
+/// Result of checking whether a slot is present in the `SlotHistory`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SlotCheck {
+    /// The slot is present in the ledger.
+    Found,
+    /// The slot is not present in the ledger.
+    NotFound,
+    /// The slot is older than the slots tracked by the sysvar.
+    TooOld,
+    /// The slot is newer than the newest slot recorded by the sysvar.
+    TooNew,
+}
+
+/// `SlotHistory` provides read-only, zero-copy access to `SlotHistory` sysvar
+/// bytes.
+#[derive(Debug)]
+pub struct SlotHistory<T: Deref<Target = [u8]>> {
+    data: T,
+}
+
+/// Read a little-endian `u64` from the sysvar data at `offset`.
+///
+/// # Safety
+///
+/// Caller must ensure that `data` has at least `offset + 8` bytes.
+#[inline(always)]
+unsafe fn read_u64(data: &[u8], offset: usize) -> u64 {
+    debug_assert!(data.len() >= offset + mem::size_of::<u64>());
+    u64::from_le_bytes(*(data.as_ptr().add(offset) as *const [u8; 8]))
+}
+
+/// Check a slot against the next slot and the bitvector block holding it.
+#[inline(always)]
+fn check_slot(slot: Slot, next_slot: Slot, block: impl FnOnce(usize) -> u64) -> SlotCheck {
+    if slot >= next_slot {
+        SlotCheck::TooNew
+    } else if slot < next_slot.saturating_sub(MAX_ENTRIES) {
+        SlotCheck::TooOld
+    } else {
+        let bit = slot % MAX_ENTRIES;
+        if block(bit as usize / (BLOCK_SIZE * 8)) & (1 << (bit % (BLOCK_SIZE as u64 * 8))) != 0 {
+            SlotCheck::Found
+        } else {
+            SlotCheck::NotFound
+        }
+    }
+}
+
+impl<T: Deref<Target = [u8]>> SlotHistory<T> {
+    /// Creates a `SlotHistory` instance with validation of the buffer size
+    /// and bitvector header.
+    #[inline(always)]
+    pub fn new(data: T) -> Result<Self, ProgramError> {
+        if data.len() < SIZE {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+
+        // SAFETY: `data` is guaranteed to have at least `SIZE` bytes.
+        let (blocks, len) = unsafe { (read_u64(&data, 1), read_u64(&data, NEXT_SLOT_OFFSET - 8)) };
+
+        if data[0] != 1 || blocks != NUM_BLOCKS as u64 || len != MAX_ENTRIES {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // SAFETY: `data` is validated to be of the expected size and format.
+        Ok(unsafe { Self::new_unchecked(data) })
+    }
+
+    /// Creates a `SlotHistory` instance without validation.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `data` represents valid `SlotHistory` data
+    /// and has at least `SIZE` bytes.
+    #[inline(always)]
+    pub unsafe fn new_unchecked(data: T) -> Self {
+        SlotHistory { data }
+    }
+
+    /// Returns the next slot to be recorded.
+    #[inline(always)]
+    pub fn next_slot(&self) -> Slot {
+        // SAFETY: `SlotHistory` invariants guarantee that `self.data` has at least
+        // `SIZE` bytes.
+        unsafe { read_u64(&self.data, NEXT_SLOT_OFFSET) }
+    }
+
+    /// Returns the newest slot recorded.
+    #[inline(always)]
+    pub fn newest(&self) -> Slot {
+        self.next_slot().saturating_sub(1)
+    }
+
+    /// Returns the oldest slot tracked.
+    #[inline(always)]
+    pub fn oldest(&self) -> Slot {
+        self.next_slot().saturating_sub(MAX_ENTRIES)
+    }
+
+    /// Checks whether `slot` is present in the ledger.
+    #[inline]
+    pub fn check(&self, slot: Slot) -> SlotCheck {
+        check_slot(slot, self.next_slot(), |index| {
+            // SAFETY: `index` is less than `NUM_BLOCKS`, so the block is within
+            // the sysvar data.
+            unsafe { read_u64(&self.data, BITS_OFFSET + index * BLOCK_SIZE) }
+        })
+    }
+}
+
+impl<T: Deref<Target = [u8]>> PartialSysvar for SlotHistory<T> {
+    const ID: Address = SLOT_HISTORY_ID;
+
+    const DATA_LEN: usize = SIZE;
+}
+
+/// The field of the `SlotHistory` next slot.
+pub const NEXT_SLOT_FIELD: SysvarField<SlotHistory<&'static [u8]>, Slot> =
+    unsafe { SysvarField::new(NEXT_SLOT_OFFSET) };
+
+/// Returns the field of the bitvector block at `index`, or `None` if `index`
+/// is not less than `NUM_BLOCKS`.
+///
+/// Bit `i` of the block at `index` indicates whether the slot `s` with
+/// `s % MAX_ENTRIES == index * 64 + i` is present in the ledger.
+#[inline(always)]
+pub const fn block_field(index: usize) -> Option<SysvarField<SlotHistory<&'static [u8]>, u64>> {
+    if index >= NUM_BLOCKS {
+        return None;
+    }
+    // SAFETY: All bit patterns are valid for `u64`.
+    Some(unsafe { SysvarField::new(BITS_OFFSET + index * BLOCK_SIZE) })
+}
+
+impl<'a> SlotHistory<Ref<'a, [u8]>> {
+    /// Creates a `SlotHistory` instance by safely borrowing data from an
+    /// `AccountView`.
+    ///
+    /// # Errors
+    /// - `ProgramError::InvalidArgument` if the account key doesn't match the
+    ///   `SlotHistory` sysvar ID
+    /// - `ProgramError::AccountBorrowFailed` if the account data is already
+    ///   mutably borrowed
+    #[inline(always)]
+    pub fn from_account_view(account_view: &'a AccountView) -> Result<Self, ProgramError> {
+        if unlikely(account_view.address() != &SLOT_HISTORY_ID) {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Self::new(account_view.try_borrow()?)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl SlotHistory<Box<[u8]>> {
+    /// Fetches the `SlotHistory` sysvar data directly via syscall.
+    ///
+    /// This copies the full sysvar data (`SIZE` bytes).
+    #[inline(always)]
+    pub fn fetch() -> Result<Self, ProgramError> {
+        let mut sysvar_data = Box::<[u8]>::new_uninit_slice(SIZE);
+        // SAFETY: The allocation is valid for `SIZE` bytes.
+        unsafe {
+            crate::sysvars::get_sysvar_unchecked(
+                sysvar_data.as_mut_ptr() as *mut _,
+                &SLOT_HISTORY_ID,
+                0,
+                SIZE,
+            )?;
+        }
+        // SAFETY: The data was initialized by the syscall.
+        Self::new(unsafe { sysvar_data.assume_init() })
+    }
+}
+
+/// Fetches the next slot of the `SlotHistory` sysvar via syscall.
+#[inline]
+pub fn fetch_next_slot() -> Result<Slot, ProgramError> {
+    NEXT_SLOT_FIELD.fetch()
+}
+
+/// Checks whether `slot` is present in the ledger via syscall, without copying
+/// the whole sysvar data.
+///
+/// Only the next slot and the bitvector block holding `slot` are copied.
+#[inline]
+pub fn fetch_check(slot: Slot) -> Result<SlotCheck, ProgramError> {
+    let next_slot = fetch_next_slot()?;
+    let mut result = Ok(());
+
+    let check = check_slot(slot, next_slot, |index| match block_field(index) {
+        Some(field) => field.fetch().unwrap_or_else(|error| {
+            result = Err(error);
+            0
+        }),
+        // `index` is always less than `NUM_BLOCKS`.
+        None => 0,
+    });
+
+    result.map(|_| check)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, alloc::vec::Vec};
+
+    /// Build `SlotHistory` sysvar bytes with the given slots recorded.
+    fn build_slot_history(slots: &[Slot], next_slot: Slot) -> Vec<u8> {
+        let mut blocks = alloc::vec![0u64; NUM_BLOCKS];
+        for slot in slots {
+            let bit = slot % MAX_ENTRIES;
+            blocks[bit as usize / 64] |= 1 << (bit % 64);
+        }
+
+        let mut data = Vec::with_capacity(SIZE);
+        data.push(1);
+        data.extend_from_slice(&(NUM_BLOCKS as u64).to_le_bytes());
+        blocks
+            .iter()
+            .for_each(|block| data.extend_from_slice(&block.to_le_bytes()));
+        data.extend_from_slice(&MAX_ENTRIES.to_le_bytes());
+        data.extend_from_slice(&next_slot.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_slot_history() {
+        let next_slot = MAX_ENTRIES + 100;
+        let data = build_slot_history(&[64, 150, MAX_ENTRIES + 63, MAX_ENTRIES + 99], next_slot);
+        assert_eq!(data.len(), SIZE);
+
+        let slot_history = SlotHistory::new(data.as_slice()).unwrap();
+        assert_eq!(slot_history.next_slot(), next_slot);
+        assert_eq!(slot_history.newest(), MAX_ENTRIES + 99);
+        assert_eq!(slot_history.oldest(), 100);
+
+        assert_eq!(slot_history.check(MAX_ENTRIES + 99), SlotCheck::Found);
+        assert_eq!(slot_history.check(MAX_ENTRIES + 63), SlotCheck::Found);
+        assert_eq!(slot_history.check(150), SlotCheck::Found);
+        assert_eq!(slot_history.check(151), SlotCheck::NotFound);
+        assert_eq!(slot_history.check(MAX_ENTRIES + 100), SlotCheck::TooNew);
+        // Slot 64 is older than the history, but it is recorded in the same bit
+        // as `MAX_ENTRIES + 64`, which wraps around to it.
+        assert_eq!(slot_history.check(64), SlotCheck::TooOld);
+        assert_eq!(slot_history.check(MAX_ENTRIES + 64), SlotCheck::Found);
+        assert_eq!(slot_history.check(MAX_ENTRIES + 65), SlotCheck::NotFound);
+        assert_eq!(slot_history.check(99), SlotCheck::TooOld);
+
+        // Invalid data.
+        assert_eq!(
+            SlotHistory::new(&data[..SIZE - 1]).err(),
+            Some(ProgramError::AccountDataTooSmall)
+        );
+        let mut invalid = data.clone();
+        invalid[0] = 0;
+        assert_eq!(
+            SlotHistory::new(invalid.as_slice()).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_fetch() {
+        crate::sysvars::mock::reset();
+        assert_eq!(fetch_check(0).err(), Some(ProgramError::UnsupportedSysvar));
+
+        let data = build_slot_history(&[0, 5, 1_000], 1_001);
+        crate::sysvars::mock::set_sysvar(SLOT_HISTORY_ID, &data);
+
+        let slot_history = SlotHistory::fetch().unwrap();
+        assert_eq!(slot_history.check(5), SlotCheck::Found);
+
+        assert_eq!(fetch_next_slot().unwrap(), 1_001);
+        assert_eq!(fetch_check(0).unwrap(), SlotCheck::Found);
+        assert_eq!(fetch_check(5).unwrap(), SlotCheck::Found);
+        assert_eq!(fetch_check(6).unwrap(), SlotCheck::NotFound);
+        assert_eq!(fetch_check(1_000).unwrap(), SlotCheck::Found);
+        assert_eq!(fetch_check(1_001).unwrap(), SlotCheck::TooNew);
+    }
+}
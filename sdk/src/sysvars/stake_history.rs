@@ -0,0 +1,387 @@
+//! Efficient, zero-copy access to `StakeHistory` sysvar data.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use {
+    crate::{
+        account::{AccountView, Ref},
+        error::ProgramError,
+        hint::unlikely,
+        sysvars::{clock::Epoch, get_sysvar_unchecked, PartialSysvar, SysvarField},
+        Address,
+    },
+    core::{mem, ops::Deref, slice::from_raw_parts},
+};
+
+/// `SysvarStakeHistory1111111111111111111111111`
+pub const STAKE_HISTORY_ID: Address = Address::new_from_array([
+    6, 167, 213, 23, 25, 53, 132, 208, 254, 237, 155, 179, 67, 29, 19, 32, 107, 229, 68, 40, 27,
+    87, 184, 86, 108, 197, 55, 95, 244, 0, 0, 0,
+]);
+/// Sysvar data is:
+/// `len`    (8 bytes): little-endian entry count (`≤ 512`)
+/// `entries`(`len × 32 bytes`): consecutive `(u64 epoch, u64 effective, u64
+/// activating, u64 deactivating)` tuples, newest epoch first
+/// Size of the entry count field at the beginning of sysvar data.
+pub const NUM_ENTRIES_SIZE: usize = mem::size_of::<u64>();
+/// Size of a single stake history entry.
+pub const ENTRY_SIZE: usize = mem::size_of::<StakeHistoryEntry>();
+/// Maximum number of stake history entries that can be stored in the sysvar.
+pub const MAX_ENTRIES: usize = 512;
+/// Max size of the sysvar data in bytes.
+pub const MAX_SIZE: usize = NUM_ENTRIES_SIZE + MAX_ENTRIES * ENTRY_SIZE;
+
+/// A single entry in the `StakeHistory` sysvar, holding the stake state of
+/// the cluster at the end of an epoch.
+#[cfg_attr(feature = "copy", derive(Copy))]
+#[derive(Clone, Eq, Debug, PartialEq)]
+#[repr(C)]
+pub struct StakeHistoryEntry {
+    /// The epoch stored as little-endian bytes.
+    epoch_le: [u8; 8],
+    /// The effective stake stored as little-endian bytes.
+    effective_le: [u8; 8],
+    /// The activating stake stored as little-endian bytes.
+    activating_le: [u8; 8],
+    /// The deactivating stake stored as little-endian bytes.
+    deactivating_le: [u8; 8],
+}
+
+// Fail compilation if `StakeHistoryEntry` is not byte-aligned.
+const _: [(); 1] = [(); mem::align_of::<StakeHistoryEntry>()];
+
+impl StakeHistoryEntry {
+    /// Returns the epoch of the entry.
+    #[inline(always)]
+    pub fn epoch(&self) -> Epoch {
+        u64::from_le_bytes(self.epoch_le)
+    }
+
+    /// Returns the effective stake at the end of the epoch, in lamports.
+    #[inline(always)]
+    pub fn effective(&self) -> u64 {
+        u64::from_le_bytes(self.effective_le)
+    }
+
+    /// Returns the stake being activated at the end of the epoch, in
+    /// lamports.
+    #[inline(always)]
+    pub fn activating(&self) -> u64 {
+        u64::from_le_bytes(self.activating_le)
+    }
+
+    /// Returns the stake being deactivated at the end of the epoch, in
+    /// lamports.
+    #[inline(always)]
+    pub fn deactivating(&self) -> u64 {
+        u64::from_le_bytes(self.deactivating_le)
+    }
+}
+
+/// `StakeHistory` provides read-only, zero-copy access to `StakeHistory`
+/// sysvar bytes.
+#[derive(Debug)]
+pub struct StakeHistory<T: Deref<Target = [u8]>> {
+    data: T,
+}
+
+/// Get the number of entries from the sysvar data bytes.
+///
+/// # Safety
+///
+/// Caller must ensure that `data` has at least `NUM_ENTRIES_SIZE` bytes.
+#[inline(always)]
+unsafe fn get_entry_count(data: &[u8]) -> usize {
+    debug_assert!(data.len() >= NUM_ENTRIES_SIZE);
+    u64::from_le_bytes(*(data.as_ptr() as *const [u8; NUM_ENTRIES_SIZE])) as usize
+}
+
+impl<T: Deref<Target = [u8]>> StakeHistory<T> {
+    /// Creates a `StakeHistory` instance with validation of the entry count
+    /// and buffer size.
+    ///
+    /// This constructor validates that the buffer has at least enough bytes to
+    /// contain the declared number of entries. Does not validate that entries
+    /// are sorted in descending order.
+    #[inline(always)]
+    pub fn new(data: T) -> Result<Self, ProgramError> {
+        if data.len() < NUM_ENTRIES_SIZE {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+
+        // SAFETY: `data` is guaranteed to have at least `NUM_ENTRIES_SIZE` bytes.
+        let num_entries = unsafe { get_entry_count(data.as_ref()) };
+
+        if num_entries > MAX_ENTRIES {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // `num_entries` is guaranteed to be at most `MAX_ENTRIES`, so the
+        // multiplication cannot overflow.
+        if data.len() < NUM_ENTRIES_SIZE + num_entries * ENTRY_SIZE {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+
+        // SAFETY: `num_entries` is validated to be at most `MAX_ENTRIES`, and
+        // `data.len()` is validated to be of the expected size.
+        Ok(unsafe { Self::new_unchecked(data) })
+    }
+
+    /// Creates a `StakeHistory` instance without validation.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure:
+    /// 1. The underlying byte slice in `data` represents valid `StakeHistory`
+    ///    data (length prefix plus entries, where entries are sorted in
+    ///    descending order by epoch).
+    /// 2. The data slice has at least `NUM_ENTRIES_SIZE + (declared_entries *
+    ///    ENTRY_SIZE)` bytes.
+    #[inline(always)]
+    pub unsafe fn new_unchecked(data: T) -> Self {
+        StakeHistory { data }
+    }
+
+    /// Returns the number of `StakeHistoryEntry` items accessible.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        // SAFETY: `StakeHistory` invariants guarantee that `self.data` has at
+        // least `NUM_ENTRIES_SIZE` bytes.
+        unsafe { get_entry_count(self.data.as_ref()) }
+    }
+
+    /// Returns if the sysvar is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a `&[StakeHistoryEntry]` view into the underlying data.
+    #[inline(always)]
+    pub fn entries(&self) -> &[StakeHistoryEntry] {
+        // SAFETY: The slice begins `NUM_ENTRIES_SIZE` bytes into `self.data`, which
+        // is guaranteed by the constructor to have at least `len * ENTRY_SIZE`
+        // additional bytes. `StakeHistoryEntry` has alignment 1.
+        unsafe {
+            from_raw_parts(
+                self.data.as_ptr().add(NUM_ENTRIES_SIZE) as *const StakeHistoryEntry,
+                self.len(),
+            )
+        }
+    }
+
+    /// Gets a reference to the entry at `index` or `None` if out of bounds.
+    #[inline(always)]
+    pub fn get_entry(&self, index: usize) -> Option<&StakeHistoryEntry> {
+        self.entries().get(index)
+    }
+
+    /// Finds the entry for a specific epoch using binary search.
+    ///
+    /// Returns the entry if the epoch is found, or `None` if not found.
+    /// Assumes entries are sorted by epoch in descending order.
+    #[inline(always)]
+    pub fn get(&self, epoch: Epoch) -> Option<&StakeHistoryEntry> {
+        self.position(epoch).map(|index| &self.entries()[index])
+    }
+
+    /// Finds the position (index) of a specific epoch using binary search.
+    ///
+    /// Returns the index if the epoch is found, or `None` if not found.
+    /// Assumes entries are sorted by epoch in descending order.
+    #[inline(always)]
+    pub fn position(&self, epoch: Epoch) -> Option<usize> {
+        self.entries()
+            .binary_search_by(|probe_entry| probe_entry.epoch().cmp(&epoch).reverse())
+            .ok()
+    }
+}
+
+impl<'a, T: Deref<Target = [u8]>> IntoIterator for &'a StakeHistory<T> {
+    type Item = &'a StakeHistoryEntry;
+    type IntoIter = core::slice::Iter<'a, StakeHistoryEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries().iter()
+    }
+}
+
+impl<T: Deref<Target = [u8]>> PartialSysvar for StakeHistory<T> {
+    const ID: Address = STAKE_HISTORY_ID;
+
+    const DATA_LEN: usize = MAX_SIZE;
+}
+
+/// Returns the field of the `StakeHistory` entry at `index`, or `None` if
+/// `index` is not less than `MAX_ENTRIES`.
+///
+/// Fetching the field copies a single `StakeHistoryEntry` from the sysvar.
+#[inline(always)]
+pub const fn entry_field(
+    index: usize,
+) -> Option<SysvarField<StakeHistory<&'static [u8]>, StakeHistoryEntry>> {
+    if index >= MAX_ENTRIES {
+        return None;
+    }
+    // SAFETY: All bit patterns are valid for `StakeHistoryEntry`.
+    Some(unsafe { SysvarField::new(NUM_ENTRIES_SIZE + index * ENTRY_SIZE) })
+}
+
+impl<'a> StakeHistory<Ref<'a, [u8]>> {
+    /// Creates a `StakeHistory` instance by safely borrowing data from an
+    /// `AccountView`.
+    ///
+    /// # Errors
+    /// - `ProgramError::InvalidArgument` if the account key doesn't match the
+    ///   `StakeHistory` sysvar ID
+    /// - `ProgramError::AccountBorrowFailed` if the account data is already
+    ///   mutably borrowed
+    #[inline(always)]
+    pub fn from_account_view(account_view: &'a AccountView) -> Result<Self, ProgramError> {
+        if unlikely(account_view.address() != &STAKE_HISTORY_ID) {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Self::new(account_view.try_borrow()?)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl StakeHistory<Box<[u8]>> {
+    /// Fetches the `StakeHistory` sysvar data directly via syscall.
+    ///
+    /// This copies the full sysvar data (`MAX_SIZE` bytes).
+    #[inline(always)]
+    pub fn fetch() -> Result<Self, ProgramError> {
+        let mut sysvar_data = Box::<[u8]>::new_uninit_slice(MAX_SIZE);
+        // SAFETY: The allocation is valid for `MAX_SIZE` bytes.
+        unsafe {
+            get_sysvar_unchecked(
+                sysvar_data.as_mut_ptr() as *mut _,
+                &STAKE_HISTORY_ID,
+                0,
+                MAX_SIZE,
+            )?;
+        }
+        // SAFETY: The data was initialized by the syscall.
+        Self::new(unsafe { sysvar_data.assume_init() })
+    }
+}
+
+/// Fetches the `StakeHistory` entry for a specific epoch via syscall,
+/// without copying the whole sysvar data.
+///
+/// Since entries are stored for consecutive epochs, newest first, the entry
+/// offset is derived from the newest epoch in the sysvar. Only the sysvar
+/// header and the requested entry are copied.
+///
+/// Returns `None` if the sysvar does not hold an entry for `epoch`.
+#[inline]
+pub fn fetch_entry(epoch: Epoch) -> Result<Option<StakeHistoryEntry>, ProgramError> {
+    // Entry count followed by the newest epoch.
+    let mut header = [0u8; NUM_ENTRIES_SIZE + mem::size_of::<Epoch>()];
+    // SAFETY: `header` is valid for its length.
+    unsafe { get_sysvar_unchecked(header.as_mut_ptr(), &STAKE_HISTORY_ID, 0, header.len()) }?;
+
+    // SAFETY: `header` has at least `NUM_ENTRIES_SIZE` bytes.
+    let num_entries = unsafe { get_entry_count(&header) }.min(MAX_ENTRIES);
+    let newest_epoch = u64::from_le_bytes(header[NUM_ENTRIES_SIZE..].try_into().unwrap());
+
+    let field = match newest_epoch.checked_sub(epoch) {
+        Some(index) if index < num_entries as u64 => entry_field(index as usize),
+        _ => None,
+    };
+    let Some(field) = field else {
+        return Ok(None);
+    };
+
+    let entry = field.fetch()?;
+
+    // Entries are expected to be consecutive; guard against gaps.
+    if unlikely(entry.epoch() != epoch) {
+        return Ok(None);
+    }
+
+    Ok(Some(entry))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, alloc::vec::Vec};
+
+    /// Build `StakeHistory` sysvar bytes for consecutive epochs ending at
+    /// `newest_epoch`, with `count` entries.
+    fn build_stake_history(newest_epoch: u64, count: usize) -> Vec<u8> {
+        let mut data = Vec::with_capacity(MAX_SIZE);
+        data.extend_from_slice(&(count as u64).to_le_bytes());
+        for i in 0..count as u64 {
+            let epoch = newest_epoch - i;
+            data.extend_from_slice(&epoch.to_le_bytes());
+            data.extend_from_slice(&(epoch * 1_000).to_le_bytes());
+            data.extend_from_slice(&(epoch * 10).to_le_bytes());
+            data.extend_from_slice(&epoch.to_le_bytes());
+        }
+        data.resize(MAX_SIZE, 0);
+        data
+    }
+
+    #[test]
+    fn test_stake_history() {
+        let data = build_stake_history(600, MAX_ENTRIES);
+        let stake_history = StakeHistory::new(data.as_slice()).unwrap();
+
+        assert_eq!(stake_history.len(), MAX_ENTRIES);
+        assert_eq!(stake_history.get_entry(0).unwrap().epoch(), 600);
+        assert_eq!(stake_history.get_entry(511).unwrap().epoch(), 89);
+        assert!(stake_history.get_entry(512).is_none());
+
+        let entry = stake_history.get(300).unwrap();
+        assert_eq!(entry.epoch(), 300);
+        assert_eq!(entry.effective(), 300_000);
+        assert_eq!(entry.activating(), 3_000);
+        assert_eq!(entry.deactivating(), 300);
+        assert_eq!(stake_history.position(300), Some(300));
+
+        assert!(stake_history.get(601).is_none());
+        assert!(stake_history.get(88).is_none());
+        assert_eq!(stake_history.into_iter().count(), MAX_ENTRIES);
+
+        // Invalid data.
+        assert_eq!(
+            StakeHistory::new(&data[..4]).err(),
+            Some(ProgramError::AccountDataTooSmall)
+        );
+        assert_eq!(
+            StakeHistory::new(&data[..NUM_ENTRIES_SIZE + ENTRY_SIZE]).err(),
+            Some(ProgramError::AccountDataTooSmall)
+        );
+        let mut invalid = data.clone();
+        invalid[..NUM_ENTRIES_SIZE].copy_from_slice(&(MAX_ENTRIES as u64 + 1).to_le_bytes());
+        assert_eq!(
+            StakeHistory::new(invalid.as_slice()).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_fetch() {
+        crate::sysvars::mock::reset();
+        assert_eq!(fetch_entry(10).err(), Some(ProgramError::UnsupportedSysvar));
+
+        let data = build_stake_history(100, 50);
+        crate::sysvars::mock::set_sysvar(STAKE_HISTORY_ID, &data);
+
+        let stake_history = StakeHistory::fetch().unwrap();
+        assert_eq!(stake_history.len(), 50);
+        assert_eq!(stake_history.get(51).unwrap().effective(), 51_000);
+
+        let entry = fetch_entry(100).unwrap().unwrap();
+        assert_eq!(entry.epoch(), 100);
+        assert_eq!(entry.effective(), 100_000);
+        assert_eq!(&entry, stake_history.get(100).unwrap());
+
+        assert_eq!(fetch_entry(51).unwrap().unwrap().activating(), 510);
+        assert!(fetch_entry(50).unwrap().is_none());
+        assert!(fetch_entry(101).unwrap().is_none());
+    }
+}
@@ -0,0 +1,490 @@
+//! Helpers to run a program off-chain against SVM-serialized input.
+//!
+//! The [`ProgramInput`] type builds an input buffer following the loader
+//! serialization layout described in the [`entrypoint`](mod@crate::entrypoint)
+//! module, including duplicated accounts, the padding reserved for account
+//! resizing, the instruction data, the program id and the slice of account
+//! pointers. The buffer can be passed to
+//! [`process_program_input`],
+//! [`InstructionContext`] or the (deprecated) `deserialize` function, and the
+//! accounts state can be read back after the instruction is processed.
+//!
+//! ```ignore
+//! use pinocchio::test_utils::{ProgramInput, TestAccount};
+//!
+//! let mut input = ProgramInput::new(
+//!     &PROGRAM_ID,
+//!     &[
+//!         TestAccount::new(payer, SYSTEM_PROGRAM_ID, 1_000_000_000, &[])
+//!             .signer()
+//!             .writable()
+//!             .into(),
+//!         TestAccount::new(vault, PROGRAM_ID, 0, &[0; 16]).writable().into(),
+//!     ],
+//!     &[0, 1, 2, 3],
+//! );
+//!
+//! assert_eq!(input.process(process_instruction), SUCCESS);
+//! assert_eq!(input.lamports(1), 1_000_000);
+//! ```
+
+use {
+    crate::{
+        account::{AccountView, RuntimeAccount, MAX_PERMITTED_DATA_INCREASE},
+        entrypoint::{process_program_input, InstructionContext, NON_DUP_MARKER},
+        Address, ProgramResult, BPF_ALIGN_OF_U128,
+    },
+    alloc::{vec, vec::Vec},
+    core::{
+        mem::size_of,
+        slice::{from_raw_parts, from_raw_parts_mut},
+    },
+};
+
+/// Align a length to the BPF alignment of [`u128`].
+#[inline(always)]
+const fn align(len: usize) -> usize {
+    (len + (BPF_ALIGN_OF_U128 - 1)) & !(BPF_ALIGN_OF_U128 - 1)
+}
+
+/// An account to serialize into the program input.
+#[derive(Clone, Debug, Default)]
+pub struct TestAccount {
+    /// Address of the account.
+    pub address: Address,
+
+    /// Program that owns the account.
+    pub owner: Address,
+
+    /// Lamports held by the account.
+    pub lamports: u64,
+
+    /// Account data.
+    pub data: Vec<u8>,
+
+    /// Indicates whether the account is a signer.
+    pub is_signer: bool,
+
+    /// Indicates whether the account is writable.
+    pub is_writable: bool,
+
+    /// Indicates whether the account is executable.
+    pub executable: bool,
+}
+
+impl TestAccount {
+    /// Creates a new read-only, non-signer account.
+    pub fn new(address: Address, owner: Address, lamports: u64, data: &[u8]) -> Self {
+        Self {
+            address,
+            owner,
+            lamports,
+            data: data.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    /// Marks the account as a signer.
+    pub fn signer(mut self) -> Self {
+        self.is_signer = true;
+        self
+    }
+
+    /// Marks the account as writable.
+    pub fn writable(mut self) -> Self {
+        self.is_writable = true;
+        self
+    }
+
+    /// Marks the account as executable.
+    pub fn executable(mut self) -> Self {
+        self.executable = true;
+        self
+    }
+}
+
+/// An entry of the accounts serialized into the program input.
+#[derive(Clone, Debug)]
+pub enum InputAccount {
+    /// A non-duplicated account.
+    Account(TestAccount),
+
+    /// A duplicate of the account at the given index.
+    Duplicated(u8),
+}
+
+impl From<TestAccount> for InputAccount {
+    fn from(account: TestAccount) -> Self {
+        InputAccount::Account(account)
+    }
+}
+
+/// SVM-serialized program input.
+///
+/// The input is stored in an 8-bytes aligned buffer that lives as long as the
+/// `ProgramInput`. Pointers returned by [`Self::as_mut_ptr`] and
+/// [`Self::instruction_data_ptr`] are valid while the `ProgramInput` is not
+/// dropped.
+#[derive(Debug)]
+pub struct ProgramInput {
+    /// Buffer holding the serialized input.
+    buffer: Vec<u64>,
+
+    /// Offset of the instruction data in the buffer.
+    instruction_data_offset: usize,
+
+    /// Offset of each account in the buffer.
+    ///
+    /// The offset of a duplicated account is the offset of the original
+    /// account.
+    account_offsets: Vec<usize>,
+}
+
+impl ProgramInput {
+    /// Serializes the program input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a duplicated account does not reference a previous account.
+    pub fn new(program_id: &Address, accounts: &[InputAccount], instruction_data: &[u8]) -> Self {
+        // Number of accounts + instruction data length + program id.
+        let mut len = size_of::<u64>()
+            + size_of::<u64>()
+            + align(instruction_data.len() + size_of::<Address>())
+            + accounts.len() * size_of::<AccountView>();
+
+        for account in accounts {
+            len += match account {
+                InputAccount::Account(account) => {
+                    size_of::<RuntimeAccount>()
+                        + align(account.data.len() + MAX_PERMITTED_DATA_INCREASE)
+                        + size_of::<u64>()
+                }
+                InputAccount::Duplicated(_) => size_of::<u64>(),
+            };
+        }
+
+        let mut input = Self {
+            buffer: vec![0; len / size_of::<u64>()],
+            instruction_data_offset: 0,
+            account_offsets: Vec::with_capacity(accounts.len()),
+        };
+
+        input.write(&(accounts.len() as u64).to_le_bytes(), 0);
+        let mut offset = size_of::<u64>();
+
+        for (index, account) in accounts.iter().enumerate() {
+            match account {
+                InputAccount::Account(account) => {
+                    let runtime_account = RuntimeAccount {
+                        borrow_state: NON_DUP_MARKER,
+                        is_signer: account.is_signer as u8,
+                        is_writable: account.is_writable as u8,
+                        executable: account.executable as u8,
+                        // The entrypoint stores the original data length in the
+                        // padding when the `account-resize` feature is enabled.
+                        #[cfg(feature = "account-resize")]
+                        padding: (account.data.len() as u32).to_le_bytes(),
+                        #[cfg(not(feature = "account-resize"))]
+                        padding: [0; 4],
+                        address: Address::new_from_array(*account.address.as_array()),
+                        owner: Address::new_from_array(*account.owner.as_array()),
+                        lamports: account.lamports,
+                        data_len: account.data.len() as u64,
+                    };
+                    // SAFETY: The offset is aligned to 8 bytes and the buffer has
+                    // enough space for the account.
+                    unsafe {
+                        (input.buffer.as_mut_ptr().cast::<u8>().add(offset) as *mut RuntimeAccount)
+                            .write(runtime_account);
+                    }
+                    input.account_offsets.push(offset);
+
+                    offset += size_of::<RuntimeAccount>();
+                    input.write(&account.data, offset);
+                    // Account data, resize padding, alignment and rent epoch.
+                    offset += align(account.data.len() + MAX_PERMITTED_DATA_INCREASE);
+                    offset += size_of::<u64>();
+                }
+                InputAccount::Duplicated(original) => {
+                    assert!(
+                        (*original as usize) < index,
+                        "Duplicated account must reference a previous account"
+                    );
+                    input.write(&[*original], offset);
+                    input
+                        .account_offsets
+                        .push(input.account_offsets[*original as usize]);
+
+                    offset += size_of::<u64>();
+                }
+            }
+        }
+
+        input.write(&(instruction_data.len() as u64).to_le_bytes(), offset);
+        offset += size_of::<u64>();
+
+        input.instruction_data_offset = offset;
+        input.write(instruction_data, offset);
+        offset += instruction_data.len();
+
+        input.write(program_id.as_ref(), offset);
+        offset = align(offset + size_of::<Address>());
+
+        // Slice of account pointers.
+        for index in 0..accounts.len() {
+            let pointer = input
+                .as_mut_ptr()
+                .wrapping_add(input.account_offsets[index]);
+            input.write(&(pointer as usize).to_le_bytes(), offset);
+            offset += size_of::<AccountView>();
+        }
+
+        input
+    }
+
+    /// Returns a pointer to the start of the program input.
+    ///
+    /// This is the pointer passed as the first argument to the program
+    /// entrypoint.
+    #[inline(always)]
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.buffer.as_mut_ptr().cast()
+    }
+
+    /// Returns a pointer to the instruction data.
+    ///
+    /// This is the pointer passed as the second argument to the program
+    /// entrypoint.
+    #[inline(always)]
+    pub fn instruction_data_ptr(&mut self) -> *mut u8 {
+        self.as_mut_ptr().wrapping_add(self.instruction_data_offset)
+    }
+
+    /// Processes the input using
+    /// [`process_program_input`].
+    ///
+    /// Returns the value that the program entrypoint would return.
+    pub fn process<F>(&mut self, process_instruction: F) -> u64
+    where
+        F: FnOnce(&Address, &mut [AccountView], &[u8]) -> ProgramResult,
+    {
+        let program_input = self.as_mut_ptr();
+        let instruction_data = self.instruction_data_ptr();
+        // SAFETY: The input was serialized following the loader layout.
+        unsafe { process_program_input(program_input, instruction_data, process_instruction) }
+    }
+
+    /// Returns an [`InstructionContext`] for the input.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the returned context does not outlive the
+    /// `ProgramInput`.
+    #[inline(always)]
+    pub unsafe fn instruction_context(&mut self) -> InstructionContext {
+        InstructionContext::new_unchecked(self.as_mut_ptr())
+    }
+
+    /// Returns the (runtime) account at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline(always)]
+    pub fn account(&self, index: usize) -> &RuntimeAccount {
+        // SAFETY: The offset points to a serialized account.
+        unsafe {
+            &*(self
+                .buffer
+                .as_ptr()
+                .cast::<u8>()
+                .add(self.account_offsets[index]) as *const RuntimeAccount)
+        }
+    }
+
+    /// Returns the lamports of the account at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline(always)]
+    pub fn lamports(&self, index: usize) -> u64 {
+        self.account(index).lamports
+    }
+
+    /// Returns the owner of the account at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline(always)]
+    pub fn owner(&self, index: usize) -> &Address {
+        &self.account(index).owner
+    }
+
+    /// Returns the data of the account at the given index.
+    ///
+    /// The data length is read from the account, so it reflects any resize
+    /// performed by the program.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline(always)]
+    pub fn data(&self, index: usize) -> &[u8] {
+        let account = self.account(index);
+        // SAFETY: The account data is located after the account header and the
+        // length is bounded by the resize padding.
+        unsafe {
+            from_raw_parts(
+                (account as *const RuntimeAccount as *const u8).add(size_of::<RuntimeAccount>()),
+                account.data_len as usize,
+            )
+        }
+    }
+
+    /// Write `data` to the buffer at the specified offset.
+    fn write(&mut self, data: &[u8], offset: usize) {
+        let buffer = unsafe {
+            from_raw_parts_mut(
+                self.buffer.as_mut_ptr().cast::<u8>(),
+                self.buffer.len() * size_of::<u64>(),
+            )
+        };
+        buffer[offset..offset + data.len()].copy_from_slice(data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{entrypoint::MaybeAccount, error::ProgramError, SUCCESS},
+        core::mem::MaybeUninit,
+    };
+
+    const PROGRAM_ID: Address = Address::new_from_array([5u8; 32]);
+
+    const OWNER: Address = Address::new_from_array([7u8; 32]);
+
+    fn accounts() -> [InputAccount; 4] {
+        [
+            TestAccount::new(Address::new_from_array([1u8; 32]), OWNER, 100, &[])
+                .signer()
+                .writable()
+                .into(),
+            TestAccount::new(Address::new_from_array([2u8; 32]), OWNER, 0, &[1, 2, 3]).into(),
+            InputAccount::Duplicated(0),
+            TestAccount::new(Address::new_from_array([3u8; 32]), PROGRAM_ID, 1, &[4; 9])
+                .writable()
+                .into(),
+        ]
+    }
+
+    fn process_transfer(
+        program_id: &Address,
+        accounts: &mut [AccountView],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        assert_eq!(program_id, &PROGRAM_ID);
+        assert_eq!(instruction_data, &[9, 8, 7]);
+
+        let [from, data, duplicated, to] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        assert_eq!(from, duplicated);
+        assert!(from.is_signer());
+        assert_eq!(data.try_borrow()?.as_ref(), &[1, 2, 3]);
+
+        from.set_lamports(from.lamports() - 50);
+        to.set_lamports(to.lamports() + 50);
+        to.try_borrow_mut()?.copy_from_slice(&[6; 9]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_program_input() {
+        let mut input = ProgramInput::new(&PROGRAM_ID, &accounts(), &[9, 8, 7]);
+
+        assert_eq!(input.process(process_transfer), SUCCESS);
+
+        assert_eq!(input.lamports(0), 50);
+        assert_eq!(input.lamports(2), 50);
+        assert_eq!(input.lamports(3), 51);
+        assert_eq!(input.data(1), &[1, 2, 3]);
+        assert_eq!(input.data(3), &[6; 9]);
+        assert_eq!(input.owner(3), &PROGRAM_ID);
+    }
+
+    #[test]
+    fn test_instruction_context() {
+        let mut input = ProgramInput::new(&PROGRAM_ID, &accounts(), &[9, 8, 7]);
+        let mut context = unsafe { input.instruction_context() };
+
+        assert_eq!(context.remaining(), 4);
+
+        let MaybeAccount::Account(first) = context.next_account().unwrap() else {
+            panic!("expected account");
+        };
+        assert_eq!(first.lamports(), 100);
+        assert!(matches!(
+            context.next_account().unwrap(),
+            MaybeAccount::Account(_)
+        ));
+        assert!(matches!(
+            context.next_account().unwrap(),
+            MaybeAccount::Duplicated(0)
+        ));
+        let mut last = context.next_account().unwrap().assume_account();
+        last.set_lamports(2);
+
+        assert_eq!(context.instruction_data(), Ok(&[9u8, 8, 7][..]));
+        assert_eq!(context.program_id(), Ok(&PROGRAM_ID));
+        assert_eq!(input.lamports(3), 2);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deserialize() {
+        let mut input = ProgramInput::new(&PROGRAM_ID, &accounts(), &[9, 8, 7]);
+        let mut accounts = [const { MaybeUninit::<AccountView>::uninit() }; 4];
+
+        let (program_id, count, instruction_data) =
+            unsafe { crate::entrypoint::deserialize(input.as_mut_ptr(), &mut accounts) };
+
+        assert_eq!(program_id, &PROGRAM_ID);
+        assert_eq!(count, 4);
+        assert_eq!(instruction_data, &[9, 8, 7]);
+
+        let accounts = unsafe { &*(accounts.as_ptr() as *const [AccountView; 4]) };
+
+        assert_eq!(accounts[0], accounts[2]);
+        assert_eq!(accounts[3].data_len(), 9);
+    }
+
+    #[cfg(feature = "account-resize")]
+    #[test]
+    fn test_resize() {
+        use crate::Resize;
+
+        let mut input = ProgramInput::new(&PROGRAM_ID, &accounts(), &[9, 8, 7]);
+
+        assert_eq!(
+            input.process(|_, accounts, _| {
+                accounts[3].resize(9 + MAX_PERMITTED_DATA_INCREASE)?;
+                assert!(accounts[3]
+                    .resize(10 + MAX_PERMITTED_DATA_INCREASE)
+                    .is_err());
+                accounts[3].resize(20)
+            }),
+            SUCCESS
+        );
+
+        assert_eq!(input.data(3).len(), 20);
+        assert_eq!(&input.data(3)[..9], &[4; 9]);
+        assert_eq!(&input.data(3)[9..], &[0; 11]);
+    }
+}